@@ -1,5 +1,9 @@
 #![allow(clippy::len_without_is_empty)]
 
+#[cfg(test)]
+#[path = "tests/simulation.rs"]
+mod tests;
+
 use std::collections::BTreeMap;
 
 use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
@@ -0,0 +1,258 @@
+use std::collections::BTreeMap;
+
+use calimero_sdk::env::ext::{External, ProposalAction};
+use calimero_sdk::testing::Simulation;
+
+use super::KvStore;
+
+const ALICE: [u8; 32] = [1; 32];
+const BOB: [u8; 32] = [2; 32];
+
+fn setup(replicas: usize) -> Simulation<KvStore> {
+    let mut sim = Simulation::new(replicas);
+
+    sim.init(0, ALICE, KvStore::init);
+    sim.deliver_all();
+
+    sim
+}
+
+fn set(sim: &mut Simulation<KvStore>, replica: usize, key: &str, value: &str) {
+    sim.call(replica, ALICE, |app| {
+        app.set(key.to_owned(), value.to_owned())
+            .expect("set failed");
+    });
+}
+
+fn entries(sim: &mut Simulation<KvStore>, replica: usize) -> BTreeMap<String, String> {
+    sim.view(replica, |app| app.entries().expect("entries failed"))
+}
+
+#[test]
+fn init_reaches_every_replica() {
+    let sim = setup(3);
+
+    sim.assert_converged();
+}
+
+#[test]
+fn delivered_deltas_converge() {
+    let mut sim = setup(3);
+
+    set(&mut sim, 1, "a", "1");
+    set(&mut sim, 2, "b", "2");
+
+    assert_eq!(sim.messages().count(), 4);
+
+    sim.deliver_all();
+
+    sim.assert_converged();
+
+    for replica in 0..3 {
+        assert_eq!(
+            entries(&mut sim, replica),
+            BTreeMap::from([
+                ("a".to_owned(), "1".to_owned()),
+                ("b".to_owned(), "2".to_owned()),
+            ])
+        );
+    }
+}
+
+#[test]
+fn undelivered_writes_stay_on_their_replica() {
+    let mut sim = setup(2);
+
+    set(&mut sim, 0, "key", "value");
+
+    // each replica has storage of its own, rather than the one shared by
+    // everything storage does natively outside a simulation
+    assert_eq!(
+        entries(&mut sim, 0),
+        BTreeMap::from([("key".to_owned(), "value".to_owned())])
+    );
+    assert_eq!(entries(&mut sim, 1), BTreeMap::new());
+}
+
+#[test]
+fn reordered_concurrent_writes_converge() {
+    let mut sim = setup(2);
+
+    set(&mut sim, 0, "key", "first");
+    set(&mut sim, 1, "key", "second");
+
+    let messages = sim
+        .messages()
+        .map(|(id, message)| (id, message.from, message.to))
+        .collect::<Vec<_>>();
+
+    // as a node would, fall back on a sync whenever a delta doesn't converge
+    for (id, from, to) in messages.into_iter().rev() {
+        if !sim.deliver(id) {
+            sim.sync(to, from);
+        }
+    }
+
+    sim.assert_converged();
+
+    assert_eq!(
+        entries(&mut sim, 0).get("key").map(String::as_str),
+        Some("second")
+    );
+}
+
+#[test]
+fn duplicated_deltas_are_idempotent() {
+    let mut sim = setup(3);
+
+    set(&mut sim, 0, "a", "1");
+
+    let ids = sim.messages().map(|(id, _)| id).collect::<Vec<_>>();
+
+    for id in ids {
+        let _ignored = sim.duplicate(id);
+    }
+
+    sim.deliver_all();
+
+    sim.assert_converged();
+}
+
+#[test]
+fn dropped_deltas_are_recovered_by_sync() {
+    let mut sim = setup(3);
+
+    set(&mut sim, 0, "a", "1");
+
+    let (dropped, _) = sim
+        .messages()
+        .find(|(_, message)| message.to == 2)
+        .expect("no message to replica 2");
+
+    let _ignored = sim.discard(dropped);
+
+    sim.deliver_all();
+
+    assert!(
+        !sim.is_converged(),
+        "replica 2 should have missed the delta"
+    );
+
+    sim.sync(2, 0);
+
+    sim.assert_converged();
+
+    assert_eq!(entries(&mut sim, 2).get("a").map(String::as_str), Some("1"));
+}
+
+#[test]
+fn partitions_heal_through_sync() {
+    let mut sim = setup(3);
+
+    sim.partition(&[&[0, 1], &[2]]);
+
+    set(&mut sim, 0, "a", "1");
+    set(&mut sim, 2, "b", "2");
+
+    assert!(
+        sim.messages()
+            .all(|(_, message)| message.to != 2 && message.from != 2),
+        "messages should not cross the partition"
+    );
+
+    sim.deliver_all();
+
+    assert!(!sim.is_converged(), "partitions should have diverged");
+
+    sim.heal();
+    sim.sync(2, 0);
+    sim.sync(1, 2);
+
+    sim.assert_converged();
+
+    assert_eq!(entries(&mut sim, 1).len(), 2);
+}
+
+#[test]
+fn events_and_logs_are_recorded_per_replica() {
+    let mut sim = setup(2);
+
+    set(&mut sim, 1, "a", "1");
+
+    let replica = sim.replica(1);
+
+    assert_eq!(
+        replica
+            .events()
+            .iter()
+            .map(|event| event.kind.as_str())
+            .collect::<Vec<_>>(),
+        ["Inserted"]
+    );
+    assert_eq!(replica.logs(), [r#"Setting key: "a" to value: "1""#]);
+
+    assert!(
+        sim.replica(0).events().is_empty(),
+        "replica 0 emitted events"
+    );
+}
+
+#[test]
+fn executors_are_distinguished() {
+    let mut sim = setup(1);
+
+    let executor = sim.call(0, BOB, |_| calimero_sdk::env::executor_id());
+
+    assert_eq!(executor, BOB);
+}
+
+#[test]
+fn proposals_are_visible_in_the_proxy() {
+    let mut sim = setup(2);
+
+    let proposal_id = sim.call(0, ALICE, |_| {
+        External
+            .propose()
+            .set_context_value(b"key".to_vec().into(), b"value".to_vec().into())
+            .send()
+    });
+
+    sim.approve(BOB, proposal_id.0);
+
+    let (proposal, approvers) = sim.view(1, |_| {
+        (
+            External.proposal(proposal_id),
+            External.approvers(proposal_id),
+        )
+    });
+
+    let proposal = proposal.expect("proposal is not pending");
+
+    assert_eq!(proposal.id, proposal_id);
+    assert_eq!(proposal.author_id, ALICE);
+    assert_eq!(
+        proposal.actions,
+        [ProposalAction::SetContextValue {
+            key: b"key".to_vec().into(),
+            value: b"value".to_vec().into(),
+        }]
+    );
+    assert_eq!(approvers, [ALICE, BOB]);
+}
+
+#[test]
+fn context_values_are_visible_in_the_proxy() {
+    let mut sim = setup(1);
+
+    sim.set_context_value(b"key".to_vec(), b"value".to_vec());
+
+    let (value, missing) = sim.view(0, |_| {
+        (
+            External.context_value(b"key"),
+            External.context_value(b"missing"),
+        )
+    });
+
+    assert_eq!(value.as_deref(), Some(&b"value"[..]));
+    assert_eq!(missing, None);
+}
@@ -1,5 +1,9 @@
 #![allow(clippy::len_without_is_empty)]
 
+#[cfg(test)]
+#[path = "tests/simulation.rs"]
+mod tests;
+
 use std::result::Result;
 
 use calimero_sdk::app;
@@ -24,13 +28,32 @@ impl VisitedCities {
     }
 
     pub fn add_person(&mut self, person: String) -> Result<bool, Error> {
-        Ok(self.visited.insert(person, UnorderedSet::new())?.is_some())
+        let existed = self.visited.contains(&person)?;
+
+        let _cities = self.visited.get_or_insert_nested(person)?;
+
+        Ok(existed)
+    }
+
+    pub fn remove_person(&mut self, person: String) -> Result<bool, Error> {
+        Ok(self.visited.remove(&person)?.is_some())
     }
 
     pub fn add_visited_city(&mut self, person: String, city: String) -> Result<bool, Error> {
         Ok(self.visited.get(&person)?.unwrap().insert(city)?)
     }
 
+    pub fn get_visited_cities(&self, person: String) -> Result<Vec<String>, Error> {
+        let Some(cities) = self.visited.get(&person)? else {
+            return Ok(vec![]);
+        };
+
+        let mut cities: Vec<_> = cities.entries()?.collect();
+        cities.sort();
+
+        Ok(cities)
+    }
+
     pub fn get_person_with_most_cities_visited(&self) -> Result<String, Error> {
         let mut max = 0;
         let mut person = String::new();
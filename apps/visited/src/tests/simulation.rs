@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use calimero_sdk::testing::Simulation;
+
+use super::VisitedCities;
+
+const ALICE: [u8; 32] = [1; 32];
+
+fn setup(replicas: usize) -> Simulation<VisitedCities> {
+    let mut sim = Simulation::new(replicas);
+
+    sim.init(0, ALICE, VisitedCities::init);
+    sim.deliver_all();
+
+    sim
+}
+
+fn add_person(sim: &mut Simulation<VisitedCities>, replica: usize, person: &str) {
+    sim.call(replica, ALICE, |app| {
+        let _existed = app
+            .add_person(person.to_owned())
+            .expect("add_person failed");
+    });
+}
+
+fn add_city(sim: &mut Simulation<VisitedCities>, replica: usize, person: &str, city: &str) {
+    sim.call(replica, ALICE, |app| {
+        let _added = app
+            .add_visited_city(person.to_owned(), city.to_owned())
+            .expect("add_visited_city failed");
+    });
+}
+
+fn cities(sim: &mut Simulation<VisitedCities>, replica: usize, person: &str) -> Vec<String> {
+    sim.view(replica, |app| {
+        app.get_visited_cities(person.to_owned())
+            .expect("get_visited_cities failed")
+    })
+}
+
+/// Delivers every message, newest first, falling back on a sync whenever a
+/// delta doesn't converge, as a node would.
+fn deliver_in_reverse(sim: &mut Simulation<VisitedCities>) {
+    let messages = sim
+        .messages()
+        .map(|(id, message)| (id, message.from, message.to))
+        .collect::<Vec<_>>();
+
+    for (id, from, to) in messages.into_iter().rev() {
+        if !sim.deliver(id) {
+            sim.sync(to, from);
+        }
+    }
+}
+
+#[test]
+fn concurrent_cities_for_the_same_person_merge() {
+    let mut sim = setup(2);
+
+    add_person(&mut sim, 0, "alice");
+    add_person(&mut sim, 1, "alice");
+    sim.deliver_all();
+
+    add_city(&mut sim, 0, "alice", "paris");
+    add_city(&mut sim, 1, "alice", "rome");
+    sim.deliver_all();
+
+    sim.assert_converged();
+
+    for replica in 0..2 {
+        assert_eq!(cities(&mut sim, replica, "alice"), ["paris", "rome"]);
+    }
+}
+
+#[test]
+fn removed_person_stays_removed_despite_concurrent_cities() {
+    let mut sim = setup(2);
+
+    add_person(&mut sim, 0, "alice");
+    add_city(&mut sim, 0, "alice", "paris");
+    sim.deliver_all();
+
+    // neither replica hears of the other's change until both are made
+    add_city(&mut sim, 1, "alice", "rome");
+    sim.call(0, ALICE, |app| {
+        assert!(app
+            .remove_person("alice".to_owned())
+            .expect("remove_person failed"));
+    });
+
+    deliver_in_reverse(&mut sim);
+
+    sim.assert_converged();
+
+    for replica in 0..2 {
+        assert!(cities(&mut sim, replica, "alice").is_empty());
+    }
+}
+
+#[test]
+fn removed_person_stays_removed_through_sync() {
+    let mut sim = setup(2);
+
+    add_person(&mut sim, 0, "alice");
+    add_city(&mut sim, 0, "alice", "paris");
+    sim.deliver_all();
+
+    sim.partition(&[&[0], &[1]]);
+
+    sim.call(0, ALICE, |app| {
+        assert!(app
+            .remove_person("alice".to_owned())
+            .expect("remove_person failed"));
+    });
+
+    // a city added after the removal still goes along with the person
+    sim.advance_time(Duration::from_secs(1));
+    add_city(&mut sim, 1, "alice", "rome");
+
+    sim.heal();
+    sim.sync(1, 0);
+    sim.sync(0, 1);
+
+    sim.assert_converged();
+
+    for replica in 0..2 {
+        assert!(cities(&mut sim, replica, "alice").is_empty());
+    }
+}
@@ -0,0 +1,365 @@
+//! Generates typed Rust clients for Calimero applications from their ABI.
+//!
+//! The generated client wraps a `calimero_client::Client`, with one async
+//! method per application method and a typed stream per event type. It is
+//! meant to be run from a build script:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), calimero_client_codegen::CodegenError> {
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("kv_store.rs");
+//!
+//! calimero_client_codegen::Generator::from_wasm_file("KvStore", "res/kv_store.wasm")?
+//!     .write(out)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! and then `include!`d into the crate using it, which must depend on
+//! `calimero-client`.
+
+#[cfg(test)]
+#[path = "tests/generate.rs"]
+mod tests;
+
+use std::fs::{read, write};
+use std::io::Error as IoError;
+use std::path::Path;
+
+use calimero_primitives::abi::{Abi, AbiError, AbiEvent, AbiMethod, AbiModifier};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse2, parse_str, Error as SynError, File, Type};
+use thiserror::Error as ThisError;
+
+use crate::schema::{ident, Types};
+
+mod schema;
+
+#[derive(Debug, ThisError)]
+#[non_exhaustive]
+pub enum CodegenError {
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Abi(#[from] AbiError),
+    #[error("the module has no embedded ABI")]
+    MissingAbi,
+    #[error("invalid type `{0}`: {1}")]
+    InvalidType(String, SynError),
+    #[error("failed to generate valid code: {0}")]
+    InvalidOutput(SynError),
+}
+
+/// Generates a typed client for an application from its ABI.
+#[derive(Debug)]
+pub struct Generator {
+    name: String,
+    abi: Abi,
+    types: Types,
+}
+
+impl Generator {
+    /// Creates a generator for a client struct named `name`.
+    #[must_use]
+    pub fn new(name: &str, abi: Abi) -> Self {
+        Self {
+            name: name.to_owned(),
+            abi,
+            types: Types::default(),
+        }
+    }
+
+    /// Creates a generator from the ABI embedded in a wasm module.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the module is malformed, or has no ABI.
+    pub fn from_wasm(name: &str, wasm: &[u8]) -> Result<Self, CodegenError> {
+        let abi = Abi::from_wasm(wasm)?.ok_or(CodegenError::MissingAbi)?;
+
+        Ok(Self::new(name, abi))
+    }
+
+    /// Creates a generator from the ABI embedded in a wasm file.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be read, is malformed, or has no ABI.
+    pub fn from_wasm_file(name: &str, path: impl AsRef<Path>) -> Result<Self, CodegenError> {
+        Self::from_wasm(name, &read(path)?)
+    }
+
+    /// Maps an application type, as named in the ABI, to a Rust type.
+    ///
+    /// Application types that aren't mapped are exposed as JSON values.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `ty` isn't a valid Rust type.
+    pub fn map_type(mut self, title: &str, ty: &str) -> Result<Self, CodegenError> {
+        let ty =
+            parse_str::<Type>(ty).map_err(|err| CodegenError::InvalidType(ty.to_owned(), err))?;
+
+        self.types.map(title.to_owned(), ty);
+
+        Ok(self)
+    }
+
+    /// Generates the source of the client.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the ABI describes items that don't make valid Rust code.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let file = parse2::<File>(self.tokens()).map_err(CodegenError::InvalidOutput)?;
+
+        Ok(prettyplease::unparse(&file))
+    }
+
+    /// Generates the source of the client into a file.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the source can't be generated, or the file can't be written.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), CodegenError> {
+        write(path, self.generate()?)?;
+
+        Ok(())
+    }
+
+    fn tokens(&self) -> TokenStream {
+        let name = ident(&self.name);
+        let doc = format!(" A typed client for the `{}` application.", self.name);
+
+        let methods =
+            self.abi
+                .methods
+                .iter()
+                .filter(|method| {
+                    !method.modifiers.iter().any(|modifier| {
+                        matches!(modifier, AbiModifier::Init | AbiModifier::Migrate)
+                    })
+                })
+                .map(|method| self.method(method));
+
+        let (streams, events): (Vec<_>, Vec<_>) = self
+            .abi
+            .events
+            .iter()
+            .map(|event| self.event(event))
+            .unzip();
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Clone, Debug)]
+            pub struct #name {
+                client: ::calimero_client::Client,
+                context_id: ::calimero_client::calimero_primitives::context::ContextId,
+                executor: ::calimero_client::calimero_primitives::identity::PublicKey,
+            }
+
+            impl #name {
+                /// Creates a client calling methods on the given context, as
+                /// the given executor.
+                #[must_use]
+                pub const fn new(
+                    client: ::calimero_client::Client,
+                    context_id: ::calimero_client::calimero_primitives::context::ContextId,
+                    executor: ::calimero_client::calimero_primitives::identity::PublicKey,
+                ) -> Self {
+                    Self {
+                        client,
+                        context_id,
+                        executor,
+                    }
+                }
+
+                #(#methods)*
+
+                #(#streams)*
+            }
+
+            #(#events)*
+        }
+    }
+
+    fn method(&self, method: &AbiMethod) -> TokenStream {
+        let name = ident(&method.name);
+        let method_name = &method.name;
+
+        let doc = if method.mutates {
+            format!(" Calls the `{method_name}` method, which may change the state.")
+        } else {
+            format!(" Calls the `{method_name}` method.")
+        };
+
+        let lifetime = quote! { 'a };
+        let elided = quote! {};
+
+        let arg_names = method
+            .arguments
+            .iter()
+            .map(|arg| ident(&arg.name))
+            .collect::<Vec<_>>();
+        let arg_types = method
+            .arguments
+            .iter()
+            .map(|arg| self.types.borrowed(&arg.schema, &elided));
+        let field_types = method
+            .arguments
+            .iter()
+            .map(|arg| self.types.borrowed(&arg.schema, &lifetime));
+        let arg_keys = method.arguments.iter().map(|arg| &arg.name);
+
+        let args = if method.arguments.is_empty() {
+            quote! { ::calimero_client::serde_json::Map::new() }
+        } else {
+            quote! {{
+                #[derive(::calimero_client::serde::Serialize)]
+                #[serde(crate = "::calimero_client::serde")]
+                struct Args<'a> {
+                    #(
+                        #[serde(rename = #arg_keys)]
+                        #arg_names: #field_types,
+                    )*
+                }
+
+                Args { #(#arg_names),* }
+            }}
+        };
+
+        let returns = method
+            .returns
+            .as_ref()
+            .map_or_else(|| quote! { () }, |schema| self.types.owned(schema));
+        let error = method.error.as_ref().map_or_else(
+            || quote! { ::calimero_client::NoError },
+            |schema| self.types.owned(schema),
+        );
+
+        quote! {
+            #[doc = #doc]
+            pub async fn #name(
+                &self
+                #(, #arg_names: #arg_types)*
+            ) -> ::core::result::Result<#returns, ::calimero_client::MethodError<#error>> {
+                let args = #args;
+
+                self.client
+                    .execute(self.context_id, #method_name, &args, self.executor)
+                    .await
+            }
+        }
+    }
+
+    fn event(&self, event: &AbiEvent) -> (TokenStream, TokenStream) {
+        let name = ident(&event.name);
+        let stream = format_ident!("{}s", snake_case(&event.name));
+
+        let stream_doc = format!(" Subscribes to the `{}` events of the context.", event.name);
+
+        let mut variants = vec![];
+        let mut arms = vec![];
+        let mut structs = vec![];
+
+        for variant in &event.variants {
+            let kind = &variant.kind;
+            let variant_name = ident(kind);
+
+            let Some(schema) = &variant.data else {
+                variants.push(quote! { #variant_name });
+                arms.push(quote! { #kind => ::core::result::Result::Ok(Self::#variant_name) });
+                continue;
+            };
+
+            let ty = self.types.fields(schema).map_or_else(
+                || self.types.owned(schema),
+                |fields| {
+                    let struct_name = format_ident!("{}{}", name, variant_name);
+                    let (keys, types): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+                    let field_names = keys.iter().map(|key| ident(key));
+
+                    structs.push(quote! {
+                        #[derive(Clone, Debug, ::calimero_client::serde::Deserialize)]
+                        #[serde(crate = "::calimero_client::serde")]
+                        pub struct #struct_name {
+                            #(
+                                #[serde(rename = #keys)]
+                                pub #field_names: #types,
+                            )*
+                        }
+                    });
+
+                    quote! { #struct_name }
+                },
+            );
+
+            variants.push(quote! { #variant_name(#ty) });
+            arms.push(quote! {
+                #kind => ::core::result::Result::Ok(
+                    Self::#variant_name(::calimero_client::serde_json::from_slice(data)?)
+                )
+            });
+        }
+
+        let stream = quote! {
+            #[doc = #stream_doc]
+            pub async fn #stream(
+                &self,
+            ) -> ::core::result::Result<
+                impl ::calimero_client::futures_util::Stream<
+                    Item = ::core::result::Result<#name, ::calimero_client::ClientError>,
+                >,
+                ::calimero_client::ClientError,
+            > {
+                let subscription = self.client.subscribe(vec![self.context_id]).await?;
+
+                Ok(subscription.events::<#name>())
+            }
+        };
+
+        let event = quote! {
+            #[derive(Clone, Debug)]
+            pub enum #name {
+                #(#variants,)*
+            }
+
+            #(#structs)*
+
+            impl ::calimero_client::AppEvent for #name {
+                #[allow(unused_variables)]
+                fn decode(
+                    kind: &str,
+                    data: &[u8],
+                ) -> ::core::result::Result<Self, ::calimero_client::ClientError> {
+                    match kind {
+                        #(#arms,)*
+                        _ => ::core::result::Result::Err(
+                            ::calimero_client::ClientError::UnknownEvent(kind.to_owned()),
+                        ),
+                    }
+                }
+            }
+        };
+
+        (stream, event)
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+
+    for (idx, ch) in name.char_indices() {
+        if ch.is_uppercase() {
+            if idx != 0 {
+                out.push('_');
+            }
+
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+
+    out
+}
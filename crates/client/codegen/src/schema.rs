@@ -0,0 +1,138 @@
+#[cfg(test)]
+#[path = "tests/schema.rs"]
+mod tests;
+
+use std::collections::BTreeMap;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+use serde_json::{Map, Value};
+use syn::Type;
+
+/// Maps the JSON Schemas of an ABI to Rust types.
+///
+/// Schemas that only carry a `title` name an application type, which is left
+/// as an opaque JSON value unless it has been mapped explicitly.
+#[derive(Debug, Default)]
+pub struct Types {
+    mapped: BTreeMap<String, Type>,
+}
+
+impl Types {
+    pub fn map(&mut self, title: String, ty: Type) {
+        let _ignored = self.mapped.insert(title, ty);
+    }
+
+    /// The owned type described by a schema.
+    pub fn owned(&self, schema: &Value) -> TokenStream {
+        let Some(schema) = schema.as_object() else {
+            return quote! { ::calimero_client::serde_json::Value };
+        };
+
+        if let Some(inner) = optional(schema) {
+            let inner = self.owned(inner);
+
+            return quote! { ::core::option::Option<#inner> };
+        }
+
+        if let Some(title) = schema.get("title").and_then(Value::as_str) {
+            return self.mapped.get(title).map_or_else(
+                || quote! { ::calimero_client::serde_json::Value },
+                ToTokens::to_token_stream,
+            );
+        }
+
+        match schema.get("type").and_then(Value::as_str) {
+            Some("null") => quote! { () },
+            Some("boolean") => quote! { bool },
+            Some("integer") if schema.get("minimum").is_some() => quote! { u64 },
+            Some("integer") => quote! { i64 },
+            Some("number") => quote! { f64 },
+            Some("string") => quote! { ::std::string::String },
+            Some("array") => {
+                if let Some(items) = schema.get("prefixItems").and_then(Value::as_array) {
+                    let items = items.iter().map(|item| self.owned(item));
+
+                    return quote! { (#(#items,)*) };
+                }
+
+                let item = schema
+                    .get("items")
+                    .map_or_else(|| self.owned(&Value::Null), |item| self.owned(item));
+
+                quote! { ::std::vec::Vec<#item> }
+            }
+            Some("object") => schema.get("additionalProperties").map_or_else(
+                || quote! { ::calimero_client::serde_json::Value },
+                |value| {
+                    let value = self.owned(value);
+
+                    quote! { ::std::collections::BTreeMap<::std::string::String, #value> }
+                },
+            ),
+            _ => quote! { ::calimero_client::serde_json::Value },
+        }
+    }
+
+    /// The borrowed form of the type described by a schema, as taken by
+    /// generated methods, with the given (possibly empty) lifetime.
+    pub fn borrowed(&self, schema: &Value, lifetime: &TokenStream) -> TokenStream {
+        let ty = schema.get("type").and_then(Value::as_str);
+
+        if schema.get("title").is_none() && optional_of(schema).is_none() {
+            match ty {
+                Some("string") => return quote! { &#lifetime str },
+                Some("array") if schema.get("prefixItems").is_none() => {
+                    let item = schema
+                        .get("items")
+                        .map_or_else(|| self.owned(&Value::Null), |item| self.owned(item));
+
+                    return quote! { &#lifetime [#item] };
+                }
+                _ => {}
+            }
+        }
+
+        let owned = self.owned(schema);
+
+        quote! { &#lifetime #owned }
+    }
+
+    /// The fields of an object schema, if it describes a struct.
+    pub fn fields<'a>(&self, schema: &'a Value) -> Option<Vec<(&'a str, TokenStream)>> {
+        let properties = schema.get("properties")?.as_object()?;
+
+        Some(
+            properties
+                .iter()
+                .map(|(name, schema)| (name.as_str(), self.owned(schema)))
+                .collect(),
+        )
+    }
+}
+
+/// Turns a name from the ABI into a Rust identifier.
+pub fn ident(name: &str) -> Ident {
+    let mut sanitized = name
+        .chars()
+        .map(|ch| if ch.is_alphanumeric() { ch } else { '_' })
+        .collect::<String>();
+
+    if sanitized.is_empty() || sanitized.starts_with(|ch: char| ch.is_numeric()) {
+        sanitized.insert(0, '_');
+    }
+
+    syn::parse_str::<Ident>(&sanitized)
+        .unwrap_or_else(|_| Ident::new_raw(&sanitized, Span::call_site()))
+}
+
+fn optional(schema: &Map<String, Value>) -> Option<&Value> {
+    match schema.get("anyOf")?.as_array()?.as_slice() {
+        [inner, null] if null.get("type").and_then(Value::as_str) == Some("null") => Some(inner),
+        _ => None,
+    }
+}
+
+fn optional_of(schema: &Value) -> Option<&Value> {
+    optional(schema.as_object()?)
+}
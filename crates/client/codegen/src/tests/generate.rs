@@ -0,0 +1,122 @@
+use serde_json::{from_value as from_json_value, json};
+
+use super::*;
+
+fn abi() -> Abi {
+    from_json_value(json!({
+        "methods": [
+            {
+                "name": "init",
+                "arguments": [],
+                "modifiers": ["init"]
+            },
+            {
+                "name": "set",
+                "arguments": [
+                    { "name": "key", "schema": { "type": "string" } },
+                    { "name": "value", "schema": { "title": "Entry" } }
+                ],
+                "mutates": true
+            },
+            {
+                "name": "get",
+                "arguments": [{ "name": "key", "schema": { "type": "string" } }],
+                "returns": { "anyOf": [{ "title": "Entry" }, { "type": "null" }] },
+                "error": { "type": "string" }
+            },
+            {
+                "name": "len",
+                "arguments": [],
+                "returns": { "type": "integer", "minimum": 0 }
+            }
+        ],
+        "events": [
+            {
+                "name": "KvEvent",
+                "variants": [
+                    {
+                        "kind": "Inserted",
+                        "data": {
+                            "type": "object",
+                            "properties": { "key": { "type": "string" } },
+                            "required": ["key"]
+                        }
+                    },
+                    { "kind": "Removed", "data": { "type": "string" } },
+                    { "kind": "Cleared" }
+                ]
+            }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_generate() {
+    let generator = Generator::new("KvStore", abi())
+        .map_type("Entry", "crate::Entry")
+        .unwrap();
+
+    assert!(generator.generate().is_ok());
+
+    // token spacing depends on how tokens were joined, so compare without it
+    let squash = |tokens: TokenStream| tokens.to_string().replace(' ', "");
+
+    let source = squash(generator.tokens());
+    let contains = |snippet: TokenStream| source.contains(&squash(snippet));
+
+    assert!(contains(quote! { pub struct KvStore }));
+    assert!(!contains(quote! { pub async fn init }));
+    assert!(contains(quote! {
+        pub async fn set(&self, key: &str, value: &crate::Entry)
+            -> ::core::result::Result<(), ::calimero_client::MethodError<::calimero_client::NoError>>
+    }));
+    assert!(contains(quote! {
+        pub async fn get(&self, key: &str)
+            -> ::core::result::Result<
+                ::core::option::Option<crate::Entry>,
+                ::calimero_client::MethodError<::std::string::String>
+            >
+    }));
+    assert!(contains(quote! {
+        pub async fn len(&self)
+            -> ::core::result::Result<u64, ::calimero_client::MethodError<::calimero_client::NoError>>
+    }));
+    assert!(contains(quote! {
+        self.client.execute(self.context_id, "set", &args, self.executor)
+    }));
+    assert!(contains(quote! { pub async fn kv_events }));
+    assert!(contains(quote! {
+        pub enum KvEvent {
+            Inserted(KvEventInserted),
+            Removed(::std::string::String),
+            Cleared,
+        }
+    }));
+    assert!(contains(quote! { pub struct KvEventInserted }));
+    assert!(contains(quote! {
+        "Cleared" => ::core::result::Result::Ok(Self::Cleared)
+    }));
+}
+
+#[test]
+fn test_generate_invalid_type() {
+    assert!(matches!(
+        Generator::new("KvStore", abi()).map_type("Entry", "not a type"),
+        Err(CodegenError::InvalidType(..))
+    ));
+}
+
+#[test]
+fn test_from_wasm_without_abi() {
+    assert!(matches!(
+        Generator::from_wasm("KvStore", b"\0asm\x01\0\0\0"),
+        Err(CodegenError::MissingAbi)
+    ));
+}
+
+#[test]
+fn test_snake_case() {
+    assert_eq!(snake_case("Event"), "event");
+    assert_eq!(snake_case("KvEvent"), "kv_event");
+}
@@ -0,0 +1,104 @@
+use serde_json::json;
+use syn::parse_quote;
+
+use super::*;
+
+fn owned(types: &Types, schema: &Value) -> String {
+    types.owned(schema).to_string()
+}
+
+#[test]
+fn test_owned() {
+    let types = Types::default();
+
+    assert_eq!(owned(&types, &json!({ "type": "boolean" })), "bool");
+    assert_eq!(
+        owned(&types, &json!({ "type": "integer", "minimum": 0_u64 })),
+        "u64"
+    );
+    assert_eq!(owned(&types, &json!({ "type": "integer" })), "i64");
+    assert_eq!(
+        owned(
+            &types,
+            &json!({ "type": "array", "items": { "type": "string" } })
+        ),
+        quote! { ::std::vec::Vec<::std::string::String> }.to_string()
+    );
+    assert_eq!(
+        owned(
+            &types,
+            &json!({ "anyOf": [{ "type": "number" }, { "type": "null" }] })
+        ),
+        quote! { ::core::option::Option<f64> }.to_string()
+    );
+    assert_eq!(
+        owned(
+            &types,
+            &json!({
+                "type": "array",
+                "prefixItems": [{ "type": "string" }, { "type": "boolean" }],
+                "items": false
+            })
+        ),
+        quote! { (::std::string::String, bool,) }.to_string()
+    );
+    assert_eq!(
+        owned(
+            &types,
+            &json!({ "type": "object", "additionalProperties": { "type": "null" } })
+        ),
+        quote! { ::std::collections::BTreeMap<::std::string::String, ()> }.to_string()
+    );
+}
+
+#[test]
+fn test_owned_titled() {
+    let mut types = Types::default();
+
+    let schema = json!({ "title": "Entry" });
+
+    assert_eq!(
+        owned(&types, &schema),
+        quote! { ::calimero_client::serde_json::Value }.to_string()
+    );
+
+    types.map("Entry".to_owned(), parse_quote!(crate::Entry));
+
+    assert_eq!(owned(&types, &schema), quote! { crate::Entry }.to_string());
+}
+
+#[test]
+fn test_borrowed() {
+    let types = Types::default();
+    let lifetime = quote! { 'a };
+
+    assert_eq!(
+        types
+            .borrowed(&json!({ "type": "string" }), &lifetime)
+            .to_string(),
+        quote! { &'a str }.to_string()
+    );
+    assert_eq!(
+        types
+            .borrowed(
+                &json!({ "type": "array", "items": { "type": "integer", "minimum": 0_u64 } }),
+                &quote! {}
+            )
+            .to_string(),
+        quote! { &[u64] }.to_string()
+    );
+    assert_eq!(
+        types
+            .borrowed(&json!({ "type": "boolean" }), &quote! {})
+            .to_string(),
+        quote! { &bool }.to_string()
+    );
+}
+
+#[test]
+fn test_ident() {
+    assert_eq!(ident("get_value").to_string(), "get_value");
+    assert_eq!(ident("type").to_string(), "r#type");
+    assert_eq!(ident("1st").to_string(), "_1st");
+    assert_eq!(ident("with-dash").to_string(), "with_dash");
+}
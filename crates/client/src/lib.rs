@@ -0,0 +1,244 @@
+//! A client for the JSON-RPC and WebSocket APIs of a Calimero node.
+//!
+//! [`Client::execute`] calls application methods with typed arguments and
+//! results, and [`Client::subscribe`] streams the events of a set of contexts.
+//! Typed clients for individual applications are generated on top of this by
+//! `calimero-client-codegen`.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use calimero_primitives::context::ContextId;
+use calimero_primitives::identity::PublicKey;
+use calimero_server_primitives::jsonrpc::{
+    ExecuteError, ExecuteRequest, ExecuteResponse, Request, RequestId, RequestPayload, Response,
+    ResponseBody, ResponseBodyError, Version,
+};
+use calimero_server_primitives::ws::ResponseBodyError as WsResponseBodyError;
+use chrono::Utc;
+use libp2p::identity::{Keypair, SigningError};
+use reqwest::Client as HttpClient;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{from_value as from_json_value, to_value as to_json_value, Value};
+use thiserror::Error as ThisError;
+use tokio_tungstenite::tungstenite::Error as WsError;
+use url::{ParseError, Url};
+
+pub use {calimero_primitives, futures_util, serde, serde_json};
+
+pub mod ws;
+
+pub use ws::{AppEvent, Subscription};
+
+/// How requests to the node are authenticated.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+#[expect(variant_size_differences, reason = "Only held once per client")]
+pub enum Auth {
+    /// No authentication, for nodes that don't require it.
+    #[default]
+    None,
+    /// A JWT issued by the node's admin API.
+    Token(String),
+    /// The node's own identity, for the development endpoint.
+    Keypair(Keypair),
+}
+
+#[derive(Debug, ThisError)]
+#[non_exhaustive]
+pub enum ClientError {
+    #[error("invalid url: {0}")]
+    Url(#[from] ParseError),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("server responded with {status_code}: {message}")]
+    Api { status_code: u16, message: String },
+    #[error("failed to sign request: {0}")]
+    Signing(#[from] SigningError),
+    #[error("codec error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Rpc(ResponseBodyError),
+    #[error(transparent)]
+    Execute(Box<ExecuteError>),
+    #[error(transparent)]
+    WebSocket(Box<WsError>),
+    #[error("subscription error: {0:?}")]
+    Subscription(WsResponseBodyError),
+    #[error("unknown event kind: {0}")]
+    UnknownEvent(String),
+}
+
+impl From<WsError> for ClientError {
+    fn from(err: WsError) -> Self {
+        Self::WebSocket(Box::new(err))
+    }
+}
+
+/// The error of an application method call.
+#[derive(Debug, ThisError)]
+#[non_exhaustive]
+pub enum MethodError<E> {
+    /// The error returned by the method itself.
+    #[error("application error: {0:?}")]
+    Application(E),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+}
+
+impl<E> From<serde_json::Error> for MethodError<E> {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Client(err.into())
+    }
+}
+
+/// The error type of methods that can't fail.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[expect(clippy::exhaustive_enums, reason = "This will never have variants")]
+pub enum NoError {}
+
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: HttpClient,
+    url: Url,
+    auth: Auth,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Client {
+    /// Creates a client for the node whose server is listening at `url`.
+    #[must_use]
+    pub fn new(url: Url) -> Self {
+        Self {
+            http: HttpClient::new(),
+            url,
+            auth: Auth::None,
+            next_id: Arc::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    #[must_use]
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Sends a JSON-RPC request, and returns the result on success.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request can't be sent, or the server responds with an
+    /// error.
+    pub async fn request(&self, payload: RequestPayload) -> Result<Value, ClientError> {
+        let path = match self.auth {
+            Auth::Keypair(_) => "jsonrpc/dev",
+            Auth::None | Auth::Token(_) => "jsonrpc",
+        };
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        let request = Request::new(Version::TwoPointZero, Some(RequestId::Number(id)), payload);
+
+        let mut builder = self.http.post(self.url.join(path)?).json(&request);
+
+        match &self.auth {
+            Auth::None => {}
+            Auth::Token(token) => builder = builder.bearer_auth(token),
+            Auth::Keypair(keypair) => {
+                let timestamp = Utc::now().timestamp().to_string();
+                let signature = keypair.sign(timestamp.as_bytes())?;
+
+                builder = builder
+                    .header("X-Signature", bs58::encode(signature).into_string())
+                    .header("X-Timestamp", timestamp);
+            }
+        }
+
+        let response = builder.send().await?;
+
+        if !response.status().is_success() {
+            return Err(ClientError::Api {
+                status_code: response.status().as_u16(),
+                message: response.text().await?,
+            });
+        }
+
+        let response = response.json::<Response>().await?;
+
+        match response.body {
+            ResponseBody::Result(result) => Ok(result.0),
+            ResponseBody::Error(err) => Err(ClientError::Rpc(err)),
+        }
+    }
+
+    /// Calls an application method as `executor`, in the given context.
+    ///
+    /// `args` must serialize to an object keyed by argument name.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`MethodError::Application`] if the method itself returns an
+    /// error, and [`MethodError::Client`] if the call couldn't be completed.
+    pub async fn execute<A, T, E>(
+        &self,
+        context_id: ContextId,
+        method: &str,
+        args: &A,
+        executor: PublicKey,
+    ) -> Result<T, MethodError<E>>
+    where
+        A: Serialize + ?Sized,
+        T: DeserializeOwned,
+        E: DeserializeOwned,
+    {
+        let payload = RequestPayload::Execute(ExecuteRequest::new(
+            context_id,
+            method.to_owned(),
+            to_json_value(args)?,
+            executor,
+            None,
+            None,
+        ));
+
+        let result = match self.request(payload).await {
+            Ok(result) => result,
+            Err(ClientError::Rpc(ResponseBodyError::HandlerError(err))) => {
+                let err = from_json_value::<ExecuteError>(err)?;
+
+                // errors that aren't of the expected type are passed on as
+                // they are, rather than lost to a decoding error
+                if let ExecuteError::ApplicationError(value) = &err {
+                    if let Ok(err) = E::deserialize(value) {
+                        return Err(MethodError::Application(err));
+                    }
+                }
+
+                return Err(ClientError::Execute(Box::new(err)).into());
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let response = from_json_value::<ExecuteResponse>(result)?;
+
+        Ok(from_json_value(response.output.unwrap_or_default())?)
+    }
+
+    /// Subscribes to the events of the given contexts.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the connection can't be established, or the node rejects the
+    /// subscription.
+    pub async fn subscribe(
+        &self,
+        context_ids: Vec<ContextId>,
+    ) -> Result<Subscription, ClientError> {
+        Subscription::connect(&self.url, &self.auth, context_ids).await
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::VecDeque;
+
+use calimero_primitives::context::ContextId;
+use calimero_primitives::events::{ContextEvent, ContextEventPayload, ExecutionEvent};
+use calimero_server_primitives::ws::{
+    EventFilter, Request, RequestPayload, Response, ResponseBody, SubscribeRequest,
+};
+use futures_util::stream::unfold;
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde_json::{
+    from_str as from_json_str, from_value as from_json_value, to_string as to_json_string,
+};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use url::Url;
+
+use crate::{Auth, ClientError};
+
+/// An application event type, decoded from the events a method emits.
+pub trait AppEvent: Sized {
+    /// Decodes an event from its kind and JSON-encoded data.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ClientError::UnknownEvent`] if the kind isn't one of this
+    /// type's, or if the data doesn't match it.
+    fn decode(kind: &str, data: &[u8]) -> Result<Self, ClientError>;
+}
+
+/// A subscription to the events of a set of contexts.
+#[derive(Debug)]
+pub struct Subscription {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl Subscription {
+    pub(crate) async fn connect(
+        url: &Url,
+        auth: &Auth,
+        context_ids: Vec<ContextId>,
+    ) -> Result<Self, ClientError> {
+        let mut url = url.join("ws")?;
+
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+
+        // both schemes are special, so this can't fail
+        let _ignored = url.set_scheme(scheme);
+
+        let mut request = url.as_str().into_client_request()?;
+
+        // the development endpoint has no websocket counterpart, so a keypair
+        // connects as an unauthenticated client would
+        if let Auth::Token(token) = auth {
+            let value = HeaderValue::from_str(&format!("Bearer {token}")).map_err(WsError::from)?;
+
+            let _ignored = request.headers_mut().insert(AUTHORIZATION, value);
+        }
+
+        let (mut socket, _) = connect_async(request).await?;
+
+        let request = Request {
+            id: Some(0),
+            payload: RequestPayload::Subscribe(SubscribeRequest {
+                context_ids,
+                filter: EventFilter::default(),
+                resume_token: None,
+            }),
+        };
+
+        socket
+            .send(Message::Text(to_json_string(&request)?))
+            .await?;
+
+        Ok(Self { socket })
+    }
+
+    /// Waits for the next event, returning `None` once the connection closes.
+    pub async fn next(&mut self) -> Option<Result<ContextEvent, ClientError>> {
+        loop {
+            let message = match self.socket.next().await? {
+                Ok(message) => message,
+                Err(err) => return Some(Err(err.into())),
+            };
+
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Close(_) => return None,
+                Message::Binary(_) | Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {
+                    continue
+                }
+            };
+
+            let response = match from_json_str::<Response>(&text) {
+                Ok(response) => response,
+                Err(err) => return Some(Err(err.into())),
+            };
+
+            let value = match response.body {
+                // events are the only messages the node sends unprompted
+                ResponseBody::Result(_) if response.id.is_some() => continue,
+                ResponseBody::Result(value) => value,
+                ResponseBody::Error(err) => return Some(Err(ClientError::Subscription(err))),
+            };
+
+            return Some(from_json_value(value).map_err(Into::into));
+        }
+    }
+
+    /// Turns the subscription into a stream of context events.
+    pub fn into_stream(self) -> impl Stream<Item = Result<ContextEvent, ClientError>> {
+        unfold(self, |mut subscription| async move {
+            let item = subscription.next().await?;
+
+            Some((item, subscription))
+        })
+    }
+
+    /// Turns the subscription into a stream of the application events emitted
+    /// in the subscribed contexts.
+    pub fn events<E: AppEvent>(self) -> impl Stream<Item = Result<E, ClientError>> {
+        unfold(
+            (self, VecDeque::<ExecutionEvent>::new()),
+            |(mut subscription, mut pending)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        let item = E::decode(&event.kind, &event.data);
+
+                        return Some((item, (subscription, pending)));
+                    }
+
+                    match subscription.next().await? {
+                        Ok(event) => {
+                            if let ContextEventPayload::ExecutionEvent(payload) = event.payload {
+                                pending.extend(payload.events);
+                            }
+                        }
+                        Err(err) => return Some((Err(err), (subscription, pending))),
+                    }
+                }
+            },
+        )
+    }
+}
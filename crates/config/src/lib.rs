@@ -5,6 +5,7 @@ use calimero_context::config::ContextConfig;
 use calimero_network::config::{BootstrapConfig, DiscoveryConfig, SwarmConfig};
 use calimero_server::admin::service::AdminConfig;
 use calimero_server::jsonrpc::JsonRpcConfig;
+use calimero_server::metrics::MetricsConfig;
 use calimero_server::ws::WsConfig;
 use camino::{Utf8Path, Utf8PathBuf};
 use eyre::{Result as EyreResult, WrapErr};
@@ -32,6 +33,9 @@ pub struct ConfigFile {
     pub blobstore: BlobStoreConfig,
 
     pub context: ContextConfig,
+
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
@@ -40,6 +44,22 @@ pub struct SyncConfig {
     pub timeout: Duration,
     #[serde(rename = "interval_ms", with = "serde_duration")]
     pub interval: Duration,
+    /// How often to poll the proxy contract of each context for changes to
+    /// its proposals, zero disables polling.
+    #[serde(default, rename = "proposals_interval_ms", with = "serde_duration")]
+    pub proposals_interval: Duration,
+    /// How long an entity received before its parent is held for, waiting
+    /// for the parent to arrive, before being discarded.
+    #[serde(
+        default = "default_orphan_expiry",
+        rename = "orphan_expiry_ms",
+        with = "serde_duration"
+    )]
+    pub orphan_expiry: Duration,
+}
+
+const fn default_orphan_expiry() -> Duration {
+    Duration::from_secs(60 * 60)
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -86,6 +106,9 @@ pub struct ServerConfig {
 
     #[serde(default)]
     pub websocket: Option<WsConfig>,
+
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
 }
 
 impl ServerConfig {
@@ -95,12 +118,14 @@ impl ServerConfig {
         admin: Option<AdminConfig>,
         jsonrpc: Option<JsonRpcConfig>,
         websocket: Option<WsConfig>,
+        metrics: Option<MetricsConfig>,
     ) -> Self {
         Self {
             listen,
             admin,
             jsonrpc,
             websocket,
+            metrics,
         }
     }
 }
@@ -131,6 +156,30 @@ impl BlobStoreConfig {
     }
 }
 
+/// Where the spans of the node are exported to, if anywhere.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct TelemetryConfig {
+    /// The OTLP (gRPC) endpoint spans are exported to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otlp_endpoint: Option<String>,
+
+    /// The file spans are appended to as JSON lines, relative to the node's
+    /// home.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<Utf8PathBuf>,
+}
+
+impl TelemetryConfig {
+    #[must_use]
+    pub const fn new(otlp_endpoint: Option<String>, file: Option<Utf8PathBuf>) -> Self {
+        Self {
+            otlp_endpoint,
+            file,
+        }
+    }
+}
+
 impl ConfigFile {
     #[must_use]
     pub const fn new(
@@ -140,6 +189,7 @@ impl ConfigFile {
         datastore: DataStoreConfig,
         blobstore: BlobStoreConfig,
         context: ContextConfig,
+        telemetry: TelemetryConfig,
     ) -> Self {
         Self {
             identity,
@@ -148,6 +198,7 @@ impl ConfigFile {
             datastore,
             blobstore,
             context,
+            telemetry,
         }
     }
 
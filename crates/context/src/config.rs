@@ -7,4 +7,18 @@ use serde::{Deserialize, Serialize};
 pub struct ContextConfig {
     #[serde(rename = "config")]
     pub client: ClientConfig,
+
+    /// The number of past root hashes to retain per context for historical
+    /// reads, zero disables retention.
+    #[serde(default)]
+    pub retained_roots: usize,
+
+    /// The number of seconds the outcome of an execution made under an
+    /// idempotency key is replayed to retries for, zero disables replays.
+    #[serde(default = "default_idempotency_ttl_secs")]
+    pub idempotency_ttl_secs: u64,
+}
+
+const fn default_idempotency_ttl_secs() -> u64 {
+    24 * 60 * 60
 }
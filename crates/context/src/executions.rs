@@ -0,0 +1,119 @@
+#[cfg(test)]
+#[path = "tests/executions.rs"]
+mod tests;
+
+use borsh::to_vec;
+use calimero_primitives::context::ContextId;
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::PublicKey;
+use calimero_store::key::ContextExecution as ContextExecutionKey;
+use calimero_store::types::ContextExecution as ContextExecutionValue;
+use calimero_store::Store;
+use eyre::Result as EyreResult;
+
+use crate::StateWrites;
+
+/// What an execution made under an idempotency key finds recorded for it.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Replay {
+    /// Nothing, or nothing that hasn't expired, so the call is to be executed.
+    Absent,
+    /// The outcome of the same call, returned in place of executing it again.
+    Recorded(ContextExecutionValue),
+    /// The key was used for another call.
+    Reused,
+}
+
+/// Looks up the execution the executor made in the context under
+/// `idempotency_key`, with `request` identifying the call being made.
+pub fn find(
+    store: &Store,
+    context_id: ContextId,
+    executor: PublicKey,
+    idempotency_key: &str,
+    request: Hash,
+    now: u64,
+) -> EyreResult<Replay> {
+    let handle = store.handle();
+
+    let Some(execution) = handle.get(&key(context_id, executor, idempotency_key))? else {
+        return Ok(Replay::Absent);
+    };
+
+    if execution.expires_at <= now {
+        return Ok(Replay::Absent);
+    }
+
+    if execution.request != *request {
+        return Ok(Replay::Reused);
+    }
+
+    Ok(Replay::Recorded(execution))
+}
+
+/// Records the outcome of an execution the executor made in the context under
+/// `idempotency_key`, for retries of it to receive in its place for `ttl`
+/// nanoseconds, dropping the executor's records that have since expired.
+#[expect(clippy::too_many_arguments, reason = "Not worth a dedicated type")]
+pub fn record(
+    store: &Store,
+    writes: &mut impl StateWrites,
+    context_id: ContextId,
+    executor: PublicKey,
+    idempotency_key: &str,
+    request: Hash,
+    returns: Option<Box<[u8]>>,
+    logs: Vec<String>,
+    now: u64,
+    ttl: u64,
+) -> EyreResult<()> {
+    delete_expired(store, writes, context_id, executor, now)?;
+
+    writes.put(
+        key(context_id, executor, idempotency_key),
+        to_vec(&ContextExecutionValue::new(
+            *request,
+            now.saturating_add(ttl),
+            returns,
+            logs,
+        ))?,
+    )?;
+
+    Ok(())
+}
+
+fn delete_expired(
+    store: &Store,
+    writes: &mut impl StateWrites,
+    context_id: ContextId,
+    executor: PublicKey,
+    now: u64,
+) -> EyreResult<()> {
+    let handle = store.handle();
+
+    let mut iter = handle.iter::<ContextExecutionKey>()?;
+
+    let first = iter
+        .seek(ContextExecutionKey::new(context_id, executor, [0; 32]))
+        .transpose()
+        .map(|k| (k, iter.read()));
+
+    for (k, v) in first.into_iter().chain(iter.entries()) {
+        let (k, v) = (k?, v?);
+
+        if k.context_id() != context_id || k.executor() != executor {
+            break;
+        }
+
+        if v.expires_at <= now {
+            writes.delete(k)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn key(context_id: ContextId, executor: PublicKey, idempotency_key: &str) -> ContextExecutionKey {
+    ContextExecutionKey::new(context_id, executor, *Hash::new(idempotency_key.as_bytes()))
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+#[path = "tests/history.rs"]
+mod tests;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use borsh::to_vec;
+use calimero_primitives::context::ContextId;
+use calimero_primitives::hash::Hash;
+use calimero_store::key::{
+    ContextHistory as ContextHistoryKey, ContextMeta as ContextMetaKey,
+    ContextState as ContextStateKey, ContextStateHistory as ContextStateHistoryKey,
+};
+use calimero_store::types::ContextStateHistory as ContextStateHistoryValue;
+use calimero_store::Store;
+use eyre::Result as EyreResult;
+
+use crate::StateWrites;
+
+/// Records the values that the entries changed by a transition away from
+/// `root_hash` had beforehand, retaining up to `retained_roots` roots, so
+/// that the state can later be read as it was at each.
+pub fn record(
+    store: &Store,
+    writes: &mut impl StateWrites,
+    retained_roots: usize,
+    context_id: ContextId,
+    root_hash: Hash,
+    originals: BTreeMap<[u8; 32], Option<Box<[u8]>>>,
+) -> EyreResult<()> {
+    if retained_roots == 0 {
+        return Ok(());
+    }
+
+    let handle = store.handle();
+
+    let key = ContextHistoryKey::new(context_id);
+
+    let mut history = handle.get(&key)?.unwrap_or_default();
+
+    // the state has returned to a root that is still retained, the records
+    // leading up to it would be ambiguous, so retention restarts from here
+    if let Some(index) = history.roots.iter().position(|root| *root == *root_hash) {
+        for root in history.roots.drain(..=index) {
+            forget(store, writes, context_id, root)?;
+        }
+    }
+
+    for (state_key, value) in originals {
+        writes.put(
+            ContextStateHistoryKey::new(context_id, *root_hash, state_key),
+            to_vec(&ContextStateHistoryValue::new(value))?,
+        )?;
+    }
+
+    history.roots.push(*root_hash);
+
+    let excess = history.roots.len().saturating_sub(retained_roots);
+
+    for root in history.roots.drain(..excess) {
+        forget(store, writes, context_id, root)?;
+    }
+
+    writes.put(key, to_vec(&history)?)?;
+
+    Ok(())
+}
+
+/// Returns the retained roots to resolve reads through in order to read the
+/// state as it was at `root_hash`, or `None` if it is not retained.
+pub fn roots(
+    store: &Store,
+    context_id: &ContextId,
+    root_hash: Hash,
+) -> EyreResult<Option<Vec<[u8; 32]>>> {
+    let handle = store.handle();
+
+    let Some(context_meta) = handle.get(&ContextMetaKey::new(*context_id))? else {
+        return Ok(None);
+    };
+
+    if context_meta.root_hash == *root_hash {
+        return Ok(Some(vec![]));
+    }
+
+    let history = handle
+        .get(&ContextHistoryKey::new(*context_id))?
+        .unwrap_or_default();
+
+    Ok(history
+        .roots
+        .iter()
+        .position(|root| *root == *root_hash)
+        .map(|index| history.roots[index..].to_vec()))
+}
+
+/// Returns the entries that differ between two retained root hashes, along
+/// with their values at each, or `None` if either is not retained.
+#[expect(clippy::type_complexity, reason = "Not worth a dedicated type")]
+pub fn diff(
+    store: &Store,
+    context_id: &ContextId,
+    from: Hash,
+    to: Hash,
+) -> EyreResult<Option<Vec<([u8; 32], Option<Box<[u8]>>, Option<Box<[u8]>>)>>> {
+    let (Some(from_roots), Some(to_roots)) = (
+        roots(store, context_id, from)?,
+        roots(store, context_id, to)?,
+    ) else {
+        return Ok(None);
+    };
+
+    // the later roots are a suffix of the earlier ones, so the transitions
+    // between the two roots are whatever the earlier one has in addition
+    let (earlier, later) = if from_roots.len() >= to_roots.len() {
+        (&from_roots, &to_roots)
+    } else {
+        (&to_roots, &from_roots)
+    };
+
+    let mut state_keys = BTreeSet::new();
+
+    for root_hash in &earlier[..earlier.len().saturating_sub(later.len())] {
+        state_keys.extend(
+            records(store, *context_id, *root_hash)?
+                .into_iter()
+                .map(|(state_key, _)| state_key),
+        );
+    }
+
+    let mut changes = vec![];
+
+    for state_key in state_keys {
+        let old = state_at(store, *context_id, &from_roots, state_key)?;
+        let new = state_at(store, *context_id, &to_roots, state_key)?;
+
+        if old != new {
+            changes.push((state_key, old, new));
+        }
+    }
+
+    Ok(Some(changes))
+}
+
+/// Returns the value of an entry as it was at the earliest of `roots`, as
+/// returned by [`roots`], or as it is now when there are none.
+pub fn state_at(
+    store: &Store,
+    context_id: ContextId,
+    roots: &[[u8; 32]],
+    state_key: [u8; 32],
+) -> EyreResult<Option<Box<[u8]>>> {
+    let handle = store.handle();
+
+    for root_hash in roots {
+        if let Some(record) = handle.get(&ContextStateHistoryKey::new(
+            context_id, *root_hash, state_key,
+        ))? {
+            return Ok(record.value);
+        }
+    }
+
+    Ok(handle
+        .get(&ContextStateKey::new(context_id, state_key))?
+        .map(|state| state.value.into_boxed()))
+}
+
+#[expect(clippy::type_complexity, reason = "Not worth a dedicated type")]
+fn records(
+    store: &Store,
+    context_id: ContextId,
+    root_hash: [u8; 32],
+) -> EyreResult<Vec<([u8; 32], Option<Box<[u8]>>)>> {
+    let handle = store.handle();
+
+    let mut iter = handle.iter::<ContextStateHistoryKey>()?;
+
+    let first = iter
+        .seek(ContextStateHistoryKey::new(context_id, root_hash, [0; 32]))
+        .transpose()
+        .map(|k| (k, iter.read()));
+
+    let mut records = vec![];
+
+    for (k, v) in first.into_iter().chain(iter.entries()) {
+        let (k, v) = (k?, v?);
+
+        if k.context_id() != context_id || k.root_hash() != root_hash {
+            break;
+        }
+
+        records.push((k.state_key(), v.value));
+    }
+
+    Ok(records)
+}
+
+fn forget(
+    store: &Store,
+    writes: &mut impl StateWrites,
+    context_id: ContextId,
+    root_hash: [u8; 32],
+) -> EyreResult<()> {
+    for (state_key, _) in records(store, context_id, root_hash)? {
+        writes.delete(ContextStateHistoryKey::new(
+            context_id, root_hash, state_key,
+        ))?;
+    }
+
+    Ok(())
+}
@@ -1,9 +1,11 @@
 #![expect(clippy::unwrap_in_result, reason = "Repr transmute")]
 
 use core::error::Error;
-use std::collections::HashSet;
+use core::time::Duration;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::Error as IoError;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use calimero_blobstore::{Blob, BlobManager, Size};
 use calimero_context_config::client::config::ClientConfig;
@@ -14,12 +16,14 @@ use calimero_context_config::client::{AnyTransport, Client as ExternalClient};
 use calimero_context_config::repr::{Repr, ReprBytes, ReprTransmute};
 use calimero_context_config::types::{
     Application as ApplicationConfig, ApplicationMetadata as ApplicationMetadataConfig,
-    ApplicationSource as ApplicationSourceConfig, ContextIdentity, ContextStorageEntry, ProposalId,
+    ApplicationSource as ApplicationSourceConfig, Capability, ContextIdentity, ContextStorageEntry,
+    ProposalId,
 };
 use calimero_context_config::{Proposal, ProposalAction, ProposalWithApprovals};
 use calimero_network::client::NetworkClient;
 use calimero_network::types::IdentTopic;
 use calimero_node_primitives::{ExecutionRequest, ServerSender};
+use calimero_primitives::abi::Abi;
 use calimero_primitives::application::{Application, ApplicationId, ApplicationSource};
 use calimero_primitives::blobs::BlobId;
 use calimero_primitives::context::{
@@ -27,15 +31,20 @@ use calimero_primitives::context::{
 };
 use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::{PrivateKey, PublicKey};
+use calimero_storage::store::Key as StorageKey;
 use calimero_store::key::{
-    ApplicationMeta as ApplicationMetaKey, BlobMeta as BlobMetaKey,
-    ContextConfig as ContextConfigKey, ContextIdentity as ContextIdentityKey,
-    ContextMeta as ContextMetaKey, ContextState as ContextStateKey, FromKeyParts, Key,
+    ApplicationMeta as ApplicationMetaKey, AsKeyParts, BlobMeta as BlobMetaKey,
+    ContextConfig as ContextConfigKey, ContextHistory as ContextHistoryKey,
+    ContextIdentity as ContextIdentityKey, ContextMeta as ContextMetaKey,
+    ContextPrivateState as ContextPrivateStateKey, ContextState as ContextStateKey,
+    ContextStateHistory as ContextStateHistoryKey, FromKeyParts, Key,
 };
 use calimero_store::layer::{ReadLayer, WriteLayer};
+use calimero_store::slice::Slice;
 use calimero_store::types::{
     ApplicationMeta as ApplicationMetaValue, ContextConfig as ContextConfigValue,
     ContextIdentity as ContextIdentityValue, ContextMeta as ContextMetaValue,
+    ContextState as ContextStateValue,
 };
 use calimero_store::Store;
 use camino::Utf8PathBuf;
@@ -48,11 +57,32 @@ use reqwest::{Client as ReqClient, Url};
 use tokio::fs::File;
 use tokio::sync::{oneshot, RwLock};
 use tokio_util::compat::TokioAsyncReadCompatExt;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub mod config;
+pub mod executions;
+pub mod history;
 
 use config::ContextConfig;
+use executions::Replay;
+
+/// Where the records kept along with a context's state are written when it
+/// changes, for them to be committed together with the change.
+pub trait StateWrites {
+    fn put<K: AsKeyParts>(&mut self, key: K, value: Vec<u8>) -> EyreResult<()>;
+    fn delete<K: AsKeyParts>(&mut self, key: K) -> EyreResult<()>;
+}
+
+// written straight away, outside of any change to the state
+impl StateWrites for Store {
+    fn put<K: AsKeyParts>(&mut self, key: K, value: Vec<u8>) -> EyreResult<()> {
+        WriteLayer::put(self, &key, value.into())
+    }
+
+    fn delete<K: AsKeyParts>(&mut self, key: K) -> EyreResult<()> {
+        WriteLayer::delete(self, &key)
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct ContextManager {
@@ -62,12 +92,16 @@ pub struct ContextManager {
     blob_manager: BlobManager,
     network_client: NetworkClient,
     server_sender: ServerSender,
+    retained_roots: usize,
+    idempotency_ttl: Duration,
     state: Arc<RwLock<State>>,
 }
 
 #[derive(Debug, Default)]
 struct State {
     pending_catchup: HashSet<ContextId>,
+    // applications are immutable once installed, and so are their ABIs
+    abis: HashMap<ApplicationId, Option<Arc<Abi>>>,
 }
 
 impl ContextManager {
@@ -88,6 +122,8 @@ impl ContextManager {
             blob_manager,
             network_client,
             server_sender,
+            retained_roots: config.retained_roots,
+            idempotency_ttl: Duration::from_secs(config.idempotency_ttl_secs),
             state: Arc::default(),
         };
 
@@ -211,13 +247,18 @@ impl ContextManager {
         let this = self.clone();
         let finalizer = async move {
             this.server_sender
-                .send(ExecutionRequest::new(
-                    context.id,
-                    "init".to_owned(),
-                    initialization_params,
-                    identity_secret.public_key(),
-                    tx,
-                ))
+                .send(
+                    ExecutionRequest::new(
+                        context.id,
+                        "init".to_owned(),
+                        initialization_params,
+                        identity_secret.public_key(),
+                        None,
+                        None,
+                        tx,
+                    )
+                    .into(),
+                )
                 .await?;
 
             if let Some(return_value) = rx.await??.returns? {
@@ -327,8 +368,16 @@ impl ContextManager {
     pub fn save_context(&self, context: &Context) -> EyreResult<()> {
         let mut handle = self.store.handle();
 
+        let key = ContextMetaKey::new(context.id);
+
+        if let Some(meta) = handle.get(&key)? {
+            if meta.application.application_id() != context.application_id {
+                self.record_revision(context.id, &meta)?;
+            }
+        }
+
         handle.put(
-            &ContextMetaKey::new(context.id),
+            &key,
             &ContextMetaValue::new(
                 ApplicationMetaKey::new(context.application_id),
                 context.root_hash.into(),
@@ -338,6 +387,132 @@ impl ContextManager {
         Ok(())
     }
 
+    // the node records the revision of the state on its first execution, so
+    // if the application changes before then, the state is recorded as having
+    // been written by the outgoing one, for the next execution to migrate from
+    fn record_revision(&self, context_id: ContextId, meta: &ContextMetaValue) -> EyreResult<()> {
+        if meta.root_hash == [0; 32] {
+            return Ok(());
+        }
+
+        let mut handle = self.store.handle();
+
+        let key = ContextStateKey::new(context_id, StorageKey::Migration.to_bytes());
+
+        if !handle.has(&key)? {
+            handle.put(
+                &key,
+                &ContextStateValue::from(Slice::from(meta.application.application_id().to_vec())),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the values that the entries changed by a transition away from
+    /// `root_hash` had beforehand, so that the state can later be read as it
+    /// was at that root hash.
+    pub fn record_state_history(
+        &self,
+        writes: &mut impl StateWrites,
+        context_id: ContextId,
+        root_hash: Hash,
+        originals: BTreeMap<[u8; 32], Option<Box<[u8]>>>,
+    ) -> EyreResult<()> {
+        history::record(
+            &self.store,
+            writes,
+            self.retained_roots,
+            context_id,
+            root_hash,
+            originals,
+        )
+    }
+
+    /// Returns the retained roots to resolve reads through in order to read
+    /// the state as it was at `root_hash`, or `None` if it is not retained.
+    pub fn state_history(
+        &self,
+        context_id: &ContextId,
+        root_hash: Hash,
+    ) -> EyreResult<Option<Vec<[u8; 32]>>> {
+        history::roots(&self.store, context_id, root_hash)
+    }
+
+    /// Returns the entries that differ between two retained root hashes, along
+    /// with their values at each, or `None` if either is not retained.
+    #[expect(clippy::type_complexity, reason = "Not worth a dedicated type")]
+    pub fn state_diff(
+        &self,
+        context_id: &ContextId,
+        from: Hash,
+        to: Hash,
+    ) -> EyreResult<Option<Vec<([u8; 32], Option<Box<[u8]>>, Option<Box<[u8]>>)>>> {
+        history::diff(&self.store, context_id, from, to)
+    }
+
+    /// Returns the value of an entry as it was at the root `history` was
+    /// returned for by [`Self::state_history`].
+    pub fn get_state_at(
+        &self,
+        context_id: ContextId,
+        history: &[[u8; 32]],
+        state_key: [u8; 32],
+    ) -> EyreResult<Option<Box<[u8]>>> {
+        history::state_at(&self.store, context_id, history, state_key)
+    }
+
+    /// Looks up the execution the executor made in the context under
+    /// `idempotency_key`, with `request` identifying the call being made, so
+    /// that the key isn't reused for another one.
+    pub fn find_execution(
+        &self,
+        context_id: ContextId,
+        executor: PublicKey,
+        idempotency_key: &str,
+        request: Hash,
+    ) -> EyreResult<Replay> {
+        executions::find(
+            &self.store,
+            context_id,
+            executor,
+            idempotency_key,
+            request,
+            now(),
+        )
+    }
+
+    /// Records the outcome of an execution the executor made in the context
+    /// under `idempotency_key`, for retries of it to receive in its place.
+    #[expect(clippy::too_many_arguments, reason = "Not worth a dedicated type")]
+    pub fn record_execution(
+        &self,
+        writes: &mut impl StateWrites,
+        context_id: ContextId,
+        executor: PublicKey,
+        idempotency_key: &str,
+        request: Hash,
+        returns: Option<Box<[u8]>>,
+        logs: Vec<String>,
+    ) -> EyreResult<()> {
+        if self.idempotency_ttl.is_zero() {
+            return Ok(());
+        }
+
+        executions::record(
+            &self.store,
+            writes,
+            context_id,
+            executor,
+            idempotency_key,
+            request,
+            returns,
+            logs,
+            now(),
+            u64::try_from(self.idempotency_ttl.as_nanos()).unwrap_or(u64::MAX),
+        )
+    }
+
     pub async fn join_context(
         &self,
         identity_secret: PrivateKey,
@@ -668,6 +843,10 @@ impl ContextManager {
 
         self.delete_context_scoped::<ContextIdentityKey, 32>(context_id, [0; 32], None)?;
         self.delete_context_scoped::<ContextStateKey, 32>(context_id, [0; 32], None)?;
+        self.delete_context_scoped::<ContextPrivateStateKey, 32>(context_id, [0; 32], None)?;
+
+        handle.delete(&ContextHistoryKey::new(*context_id))?;
+        self.delete_context_scoped::<ContextStateHistoryKey, 64>(context_id, [0; 64], None)?;
 
         self.unsubscribe(context_id).await?;
 
@@ -897,6 +1076,34 @@ impl ContextManager {
         Ok(value.private_key.is_some())
     }
 
+    /// Fetches the capabilities an identity holds in the context config.
+    pub async fn get_capabilities(
+        &self,
+        context_id: ContextId,
+        public_key: PublicKey,
+    ) -> EyreResult<Vec<Capability>> {
+        let handle = self.store.handle();
+
+        let Some(context_config) = handle.get(&ContextConfigKey::new(context_id))? else {
+            bail!("Context not found");
+        };
+
+        let privileges = self
+            .config_client
+            .query::<ContextConfigEnv>(
+                context_config.protocol.as_ref().into(),
+                context_config.network.as_ref().into(),
+                context_config.contract.as_ref().into(),
+            )
+            .privileges(
+                context_id.rt().expect("infallible conversion"),
+                &[public_key.rt().expect("infallible conversion")],
+            )
+            .await?;
+
+        Ok(privileges.into_values().flatten().collect())
+    }
+
     pub fn get_contexts(&self, start: Option<ContextId>) -> EyreResult<Vec<Context>> {
         let handle = self.store.handle();
 
@@ -986,6 +1193,8 @@ impl ContextManager {
             .send(requester_secret, nonce)
             .await?;
 
+        self.record_revision(context_id, &context_meta)?;
+
         context_meta.application = ApplicationMetaKey::new(application_id);
 
         handle.put(&key, &context_meta)?;
@@ -1227,6 +1436,42 @@ impl ContextManager {
         Ok(Some(buf))
     }
 
+    /// Gets the ABI embedded in an installed application, reading it from the
+    /// blob only the first time.
+    ///
+    /// An ABI that can't be decoded is treated as absent, since it describes
+    /// the application rather than being needed to run it.
+    pub async fn get_application_abi(
+        &self,
+        application_id: &ApplicationId,
+    ) -> EyreResult<Option<Arc<Abi>>> {
+        if let Some(abi) = self.state.read().await.abis.get(application_id) {
+            return Ok(abi.clone());
+        }
+
+        let Some(blob) = self.load_application_blob(application_id).await? else {
+            return Ok(None);
+        };
+
+        let abi = match Abi::from_wasm(&blob) {
+            Ok(abi) => abi.map(Arc::new),
+            Err(err) => {
+                warn!(%application_id, %err, "Failed to decode the application ABI");
+
+                None
+            }
+        };
+
+        let _ignored = self
+            .state
+            .write()
+            .await
+            .abis
+            .insert(*application_id, abi.clone());
+
+        Ok(abi)
+    }
+
     pub fn get_blob(&self, blob_id: BlobId) -> EyreResult<Option<Blob>> {
         let Some(stream) = self.blob_manager.get(blob_id)? else {
             return Ok(None);
@@ -1347,6 +1592,16 @@ impl ContextManager {
         context_id: ContextId,
         proposal_id: ProposalId,
     ) -> EyreResult<Proposal> {
+        self.find_proposal(context_id, proposal_id)
+            .await?
+            .ok_or_eyre("no proposal found with the specified ID")
+    }
+
+    pub async fn find_proposal(
+        &self,
+        context_id: ContextId,
+        proposal_id: ProposalId,
+    ) -> EyreResult<Option<Proposal>> {
         let handle = self.store.handle();
 
         let Some(context_config) = handle.get(&ContextConfigKey::new(context_id))? else {
@@ -1363,7 +1618,7 @@ impl ContextManager {
             .proposal(proposal_id)
             .await?;
 
-        response.ok_or_eyre("no proposal found with the specified ID")
+        Ok(response)
     }
 
     pub async fn get_number_of_active_proposals(&self, context_id: ContextId) -> EyreResult<u16> {
@@ -1488,3 +1743,12 @@ impl ContextManager {
         Ok(proxy_contract)
     }
 }
+
+// nanoseconds since the unix epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX)
+        })
+}
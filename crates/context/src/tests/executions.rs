@@ -0,0 +1,132 @@
+use calimero_store::config::StoreConfig;
+use calimero_store::db::RocksDB;
+use tempdir::TempDir;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+const EXECUTOR: [u8; 32] = [2; 32];
+const TTL: u64 = 100;
+
+fn open() -> (TempDir, Store) {
+    let dir = TempDir::new("_calimero_context_executions").unwrap();
+
+    let config = StoreConfig::new(dir.path().to_owned().try_into().unwrap());
+
+    let store = Store::open::<RocksDB>(&config).unwrap();
+
+    (dir, store)
+}
+
+fn record_at(store: &Store, idempotency_key: &str, call: &[u8], now: u64) {
+    record(
+        store,
+        &mut store.clone(),
+        CONTEXT_ID.into(),
+        EXECUTOR.into(),
+        idempotency_key,
+        Hash::new(call),
+        Some(b"returned".to_vec().into_boxed_slice()),
+        vec!["logged".to_owned()],
+        now,
+        TTL,
+    )
+    .unwrap();
+}
+
+fn find_at(store: &Store, idempotency_key: &str, call: &[u8], now: u64) -> Replay {
+    find(
+        store,
+        CONTEXT_ID.into(),
+        EXECUTOR.into(),
+        idempotency_key,
+        Hash::new(call),
+        now,
+    )
+    .unwrap()
+}
+
+#[test]
+fn retries_replay_the_recorded_outcome() {
+    let (_dir, store) = open();
+
+    record_at(&store, "transfer-1", b"transfer", 0);
+
+    let Replay::Recorded(execution) = find_at(&store, "transfer-1", b"transfer", 1) else {
+        panic!("expected the recorded execution");
+    };
+
+    assert_eq!(execution.returns.as_deref(), Some(&b"returned"[..]));
+    assert_eq!(execution.logs, ["logged"]);
+}
+
+#[test]
+fn unused_keys_find_nothing() {
+    let (_dir, store) = open();
+
+    record_at(&store, "transfer-1", b"transfer", 0);
+
+    assert!(matches!(
+        find_at(&store, "transfer-2", b"transfer", 1),
+        Replay::Absent
+    ));
+}
+
+#[test]
+fn keys_are_not_reused_for_other_calls() {
+    let (_dir, store) = open();
+
+    record_at(&store, "transfer-1", b"transfer", 0);
+
+    assert!(matches!(
+        find_at(&store, "transfer-1", b"transfer with other args", 1),
+        Replay::Reused
+    ));
+    assert!(matches!(
+        find_at(&store, "transfer-1", b"another method", 1),
+        Replay::Reused
+    ));
+}
+
+#[test]
+fn records_expire_after_their_ttl() {
+    let (_dir, store) = open();
+
+    record_at(&store, "transfer-1", b"transfer", 0);
+
+    assert!(matches!(
+        find_at(&store, "transfer-1", b"transfer", TTL - 1),
+        Replay::Recorded(_)
+    ));
+    assert!(matches!(
+        find_at(&store, "transfer-1", b"transfer", TTL),
+        Replay::Absent
+    ));
+
+    // once expired, the key may be used for another call
+    assert!(matches!(
+        find_at(&store, "transfer-1", b"another method", TTL),
+        Replay::Absent
+    ));
+}
+
+#[test]
+fn expired_records_are_dropped_when_recording() {
+    let (_dir, store) = open();
+
+    record_at(&store, "transfer-1", b"transfer", 0);
+    record_at(&store, "transfer-2", b"transfer", TTL / 2);
+    record_at(&store, "transfer-3", b"transfer", TTL);
+
+    let handle = store.handle();
+
+    let has = |idempotency_key| {
+        handle
+            .has(&key(CONTEXT_ID.into(), EXECUTOR.into(), idempotency_key))
+            .unwrap()
+    };
+
+    assert!(!has("transfer-1"));
+    assert!(has("transfer-2"));
+    assert!(has("transfer-3"));
+}
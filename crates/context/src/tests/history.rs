@@ -0,0 +1,245 @@
+use calimero_store::config::StoreConfig;
+use calimero_store::db::RocksDB;
+use calimero_store::key::ApplicationMeta as ApplicationMetaKey;
+use calimero_store::slice::Slice;
+use calimero_store::types::{ContextMeta as ContextMetaValue, ContextState as ContextStateValue};
+use tempdir::TempDir;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+const KEY_A: [u8; 32] = [10; 32];
+const KEY_B: [u8; 32] = [11; 32];
+
+fn open(root_hash: [u8; 32]) -> (TempDir, Store) {
+    let dir = TempDir::new("_calimero_context_history").unwrap();
+
+    let config = StoreConfig::new(dir.path().to_owned().try_into().unwrap());
+
+    let store = Store::open::<RocksDB>(&config).unwrap();
+
+    set_root(&store, root_hash);
+
+    (dir, store)
+}
+
+fn set_root(store: &Store, root_hash: [u8; 32]) {
+    store
+        .handle()
+        .put(
+            &ContextMetaKey::new(CONTEXT_ID.into()),
+            &ContextMetaValue::new(ApplicationMetaKey::new([0; 32].into()), root_hash),
+        )
+        .unwrap();
+}
+
+// applies the changes, recording what they replaced, as executions do
+fn transition(
+    store: &Store,
+    retained_roots: usize,
+    from: [u8; 32],
+    to: [u8; 32],
+    changes: &[([u8; 32], Option<&[u8]>)],
+) {
+    let mut handle = store.handle();
+
+    let mut originals = BTreeMap::new();
+
+    for (state_key, value) in changes {
+        let key = ContextStateKey::new(CONTEXT_ID.into(), *state_key);
+
+        let original = handle
+            .get(&key)
+            .unwrap()
+            .map(|state| state.value.into_boxed());
+
+        let _ignored = originals.insert(*state_key, original);
+
+        match value {
+            Some(value) => handle
+                .put(&key, &ContextStateValue::from(Slice::from(value.to_vec())))
+                .unwrap(),
+            None => handle.delete(&key).unwrap(),
+        }
+    }
+
+    record(
+        store,
+        &mut store.clone(),
+        retained_roots,
+        CONTEXT_ID.into(),
+        from.into(),
+        originals,
+    )
+    .unwrap();
+
+    set_root(store, to);
+}
+
+fn read(store: &Store, root_hash: [u8; 32], state_key: [u8; 32]) -> Option<Vec<u8>> {
+    let roots = roots(store, &CONTEXT_ID.into(), root_hash.into())
+        .unwrap()
+        .expect("root hash not retained");
+
+    state_at(store, CONTEXT_ID.into(), &roots, state_key)
+        .unwrap()
+        .map(Vec::from)
+}
+
+fn retained(store: &Store, root_hash: [u8; 32]) -> bool {
+    roots(store, &CONTEXT_ID.into(), root_hash.into())
+        .unwrap()
+        .is_some()
+}
+
+#[test]
+fn state_is_read_as_it_was_at_retained_roots() {
+    let (_dir, store) = open([1; 32]);
+
+    transition(&store, 8, [0; 32], [1; 32], &[(KEY_A, Some(b"a1"))]);
+    transition(&store, 8, [1; 32], [2; 32], &[(KEY_A, Some(b"a2"))]);
+    transition(
+        &store,
+        8,
+        [2; 32],
+        [3; 32],
+        &[(KEY_A, None), (KEY_B, Some(b"b3"))],
+    );
+
+    assert_eq!(read(&store, [1; 32], KEY_A).as_deref(), Some(&b"a1"[..]));
+    assert_eq!(read(&store, [1; 32], KEY_B), None);
+
+    assert_eq!(read(&store, [2; 32], KEY_A).as_deref(), Some(&b"a2"[..]));
+    assert_eq!(read(&store, [2; 32], KEY_B), None);
+
+    assert_eq!(read(&store, [3; 32], KEY_A), None);
+    assert_eq!(read(&store, [3; 32], KEY_B).as_deref(), Some(&b"b3"[..]));
+
+    assert_eq!(
+        roots(&store, &CONTEXT_ID.into(), [3; 32].into()).unwrap(),
+        Some(vec![])
+    );
+
+    assert!(!retained(&store, [4; 32]));
+}
+
+#[test]
+fn diff_lists_the_entries_changed_between_roots() {
+    let (_dir, store) = open([1; 32]);
+
+    transition(&store, 8, [0; 32], [1; 32], &[(KEY_A, Some(b"a1"))]);
+    transition(&store, 8, [1; 32], [2; 32], &[(KEY_A, Some(b"a2"))]);
+    transition(
+        &store,
+        8,
+        [2; 32],
+        [3; 32],
+        &[(KEY_A, Some(b"a1")), (KEY_B, Some(b"b3"))],
+    );
+
+    let diff_between = |from: [u8; 32], to: [u8; 32]| {
+        diff(&store, &CONTEXT_ID.into(), from.into(), to.into())
+            .unwrap()
+            .expect("root hash not retained")
+            .into_iter()
+            .map(|(state_key, old, new)| (state_key, old.map(Vec::from), new.map(Vec::from)))
+            .collect::<Vec<_>>()
+    };
+
+    // reverted in between, so only the new entry differs
+    assert_eq!(
+        diff_between([1; 32], [3; 32]),
+        [(KEY_B, None, Some(b"b3".to_vec()))]
+    );
+
+    assert_eq!(
+        diff_between([3; 32], [2; 32]),
+        [
+            (KEY_A, Some(b"a1".to_vec()), Some(b"a2".to_vec())),
+            (KEY_B, Some(b"b3".to_vec()), None),
+        ]
+    );
+
+    assert!(diff_between([2; 32], [2; 32]).is_empty());
+
+    assert!(
+        diff(&store, &CONTEXT_ID.into(), [1; 32].into(), [4; 32].into())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn roots_beyond_the_retention_are_evicted() {
+    let (_dir, store) = open([1; 32]);
+
+    transition(&store, 2, [0; 32], [1; 32], &[(KEY_A, Some(b"a1"))]);
+    transition(&store, 2, [1; 32], [2; 32], &[(KEY_A, Some(b"a2"))]);
+    transition(&store, 2, [2; 32], [3; 32], &[(KEY_A, Some(b"a3"))]);
+    transition(&store, 2, [3; 32], [4; 32], &[(KEY_A, Some(b"a4"))]);
+
+    assert!(!retained(&store, [1; 32]));
+    assert!(retained(&store, [2; 32]));
+    assert!(retained(&store, [3; 32]));
+
+    assert_eq!(read(&store, [2; 32], KEY_A).as_deref(), Some(&b"a2"[..]));
+
+    // the records of evicted roots are gone along with them
+    assert!(records(&store, CONTEXT_ID.into(), [0; 32])
+        .unwrap()
+        .is_empty());
+    assert!(records(&store, CONTEXT_ID.into(), [1; 32])
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        records(&store, CONTEXT_ID.into(), [2; 32]).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn returning_to_a_retained_root_restarts_retention() {
+    let (_dir, store) = open([1; 32]);
+
+    transition(&store, 8, [0; 32], [1; 32], &[(KEY_A, Some(b"a1"))]);
+    transition(&store, 8, [1; 32], [2; 32], &[(KEY_A, Some(b"a2"))]);
+    transition(&store, 8, [2; 32], [1; 32], &[(KEY_A, Some(b"a1"))]);
+
+    assert!(retained(&store, [0; 32]));
+
+    // moving on from the root returned to drops the roots leading up to it
+    transition(&store, 8, [1; 32], [3; 32], &[(KEY_A, Some(b"a3"))]);
+
+    assert!(!retained(&store, [0; 32]));
+    assert!(retained(&store, [1; 32]));
+    assert!(retained(&store, [2; 32]));
+
+    assert!(records(&store, CONTEXT_ID.into(), [0; 32])
+        .unwrap()
+        .is_empty());
+
+    assert_eq!(read(&store, [1; 32], KEY_A).as_deref(), Some(&b"a1"[..]));
+    assert_eq!(read(&store, [2; 32], KEY_A).as_deref(), Some(&b"a2"[..]));
+    assert_eq!(read(&store, [3; 32], KEY_A).as_deref(), Some(&b"a3"[..]));
+}
+
+#[test]
+fn nothing_is_retained_when_retention_is_disabled() {
+    let (_dir, store) = open([1; 32]);
+
+    transition(&store, 0, [0; 32], [1; 32], &[(KEY_A, Some(b"a1"))]);
+    transition(&store, 0, [1; 32], [2; 32], &[(KEY_A, Some(b"a2"))]);
+
+    assert!(!retained(&store, [1; 32]));
+    assert!(retained(&store, [2; 32]));
+
+    assert!(records(&store, CONTEXT_ID.into(), [1; 32])
+        .unwrap()
+        .is_empty());
+
+    assert!(!store
+        .handle()
+        .has(&ContextHistoryKey::new(CONTEXT_ID.into()))
+        .unwrap());
+}
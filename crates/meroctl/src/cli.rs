@@ -11,6 +11,7 @@ use thiserror::Error as ThisError;
 use crate::defaults;
 use crate::output::{Format, Output, Report};
 
+mod api_key;
 mod app;
 mod bootstrap;
 mod call;
@@ -18,6 +19,7 @@ mod context;
 mod identity;
 mod proxy;
 
+use api_key::ApiKeyCommand;
 use app::AppCommand;
 use call::CallCommand;
 use context::ContextCommand;
@@ -56,6 +58,7 @@ pub enum SubCommands {
     Proxy(ProxyCommand),
     Call(CallCommand),
     Bootstrap(BootstrapCommand),
+    ApiKey(ApiKeyCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -106,6 +109,7 @@ impl RootCommand {
             SubCommands::Proxy(proxy) => proxy.run(&environment).await,
             SubCommands::Call(call) => call.run(&environment).await,
             SubCommands::Bootstrap(call) => call.run(&environment).await,
+            SubCommands::ApiKey(api_key) => api_key.run(&environment).await,
         };
 
         if let Err(err) = result {
@@ -0,0 +1,82 @@
+use calimero_primitives::identity::ApiKey;
+use clap::{Parser, Subcommand};
+use const_format::concatcp;
+use eyre::Result as EyreResult;
+
+use crate::cli::api_key::create::CreateCommand;
+use crate::cli::api_key::list::ListCommand;
+use crate::cli::api_key::revoke::RevokeCommand;
+use crate::cli::Environment;
+use crate::output::Report;
+
+mod create;
+mod list;
+mod revoke;
+
+pub const EXAMPLES: &str = r"
+  # Create a key for a CI job that may only read from a context
+  $ meroctl -- --node-name node1 api-key create --name ci --role application --context-id <CONTEXT_ID> --read-only --expires-in-days 90
+
+  # List all keys
+  $ meroctl -- --node-name node1 api-key ls
+
+  # Revoke a key
+  $ meroctl -- --node-name node1 api-key revoke <KEY_ID>
+";
+
+#[derive(Debug, Parser)]
+#[command(about = "Command for managing API keys")]
+#[command(after_help = concatcp!(
+    "Examples:",
+    EXAMPLES
+))]
+pub struct ApiKeyCommand {
+    #[command(subcommand)]
+    pub subcommand: ApiKeySubCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ApiKeySubCommands {
+    Create(CreateCommand),
+    #[command(alias = "ls")]
+    List(ListCommand),
+    Revoke(RevokeCommand),
+}
+
+impl Report for ApiKey {
+    fn report(&self) {
+        println!("id: {}", self.id);
+        println!("name: {}", self.name);
+        println!("role: {:?}", self.scopes.role);
+        if let Some(context_ids) = &self.scopes.context_ids {
+            println!("contexts:");
+            for context_id in context_ids {
+                println!("  {context_id}");
+            }
+        }
+        if let Some(methods) = &self.scopes.methods {
+            println!("methods:");
+            for method in methods {
+                println!("  {method}");
+            }
+        }
+        println!("readOnly: {}", self.scopes.read_only);
+        println!("createdAt: {}", self.created_at);
+        if let Some(expires_at) = self.expires_at {
+            println!("expiresAt: {expires_at}");
+        }
+        if let Some(last_used_at) = self.last_used_at {
+            println!("lastUsedAt: {last_used_at}");
+        }
+    }
+}
+
+impl ApiKeyCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        match self.subcommand {
+            ApiKeySubCommands::Create(create) => create.run(environment).await,
+            ApiKeySubCommands::List(list) => list.run(environment).await,
+            ApiKeySubCommands::Revoke(revoke) => revoke.run(environment).await,
+        }
+    }
+}
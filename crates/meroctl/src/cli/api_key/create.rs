@@ -0,0 +1,104 @@
+use calimero_primitives::context::ContextId;
+use calimero_primitives::identity::{Role, Scopes};
+use calimero_server_primitives::admin::{CreateApiKeyRequest, CreateApiKeyResponse};
+use chrono::{Duration, Utc};
+use clap::{Parser, ValueEnum};
+use eyre::{OptionExt, Report as EyreReport, Result as EyreResult};
+use reqwest::Client;
+
+use crate::cli::Environment;
+use crate::common::{do_request, fetch_multiaddr, load_config, multiaddr_to_url, RequestType};
+use crate::output::Report;
+
+#[derive(Debug, Parser)]
+#[command(about = "Create an API key, printing the secret it's used with")]
+pub struct CreateCommand {
+    #[arg(long, help = "A name to tell the key apart by")]
+    pub name: String,
+
+    #[arg(long, value_enum, help = "The APIs the key may use")]
+    pub role: RoleArg,
+
+    #[arg(
+        long = "context-id",
+        value_name = "CONTEXT_ID",
+        help = "A context the key may interact with, any of them unless given"
+    )]
+    pub context_ids: Vec<ContextId>,
+
+    #[arg(
+        long = "method",
+        value_name = "PATTERN",
+        help = "A method the key may call, where `*` matches anything, any of them unless given"
+    )]
+    pub methods: Vec<String>,
+
+    #[arg(long, help = "Keep the key from modifying anything")]
+    pub read_only: bool,
+
+    #[arg(long, value_name = "DAYS", help = "Expire the key after some days")]
+    pub expires_in_days: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum RoleArg {
+    Admin,
+    Application,
+}
+
+impl From<RoleArg> for Role {
+    fn from(role: RoleArg) -> Self {
+        match role {
+            RoleArg::Admin => Self::Admin,
+            RoleArg::Application => Self::Application,
+        }
+    }
+}
+
+impl Report for CreateApiKeyResponse {
+    fn report(&self) {
+        self.data.api_key.report();
+        println!("secret: {}", self.data.secret);
+        println!("The secret won't be shown again, store it somewhere safe.");
+    }
+}
+
+impl CreateCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        let config = load_config(&environment.args.home, &environment.args.node_name)?;
+
+        let expires_at = self
+            .expires_in_days
+            .map(|days| {
+                let expires_at = Utc::now()
+                    .checked_add_signed(Duration::days(days.into()))
+                    .ok_or_eyre("expiry out of range")?;
+
+                #[expect(clippy::cast_sign_loss, reason = "Essentially infallible")]
+                let expires_at = expires_at.timestamp_millis() as u64;
+
+                Ok::<_, EyreReport>(expires_at)
+            })
+            .transpose()?;
+
+        let scopes = Scopes::new(
+            self.role.into(),
+            (!self.context_ids.is_empty()).then_some(self.context_ids),
+            (!self.methods.is_empty()).then_some(self.methods),
+            self.read_only,
+        );
+
+        let response: CreateApiKeyResponse = do_request(
+            &Client::new(),
+            multiaddr_to_url(fetch_multiaddr(&config)?, "admin-api/dev/api-keys")?,
+            Some(CreateApiKeyRequest::new(self.name, scopes, expires_at)),
+            &config.identity,
+            RequestType::Post,
+        )
+        .await?;
+
+        environment.output.write(&response);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use calimero_server_primitives::admin::ListApiKeysResponse;
+use clap::Parser;
+use eyre::Result as EyreResult;
+use reqwest::Client;
+
+use crate::cli::Environment;
+use crate::common::{do_request, fetch_multiaddr, load_config, multiaddr_to_url, RequestType};
+use crate::output::Report;
+
+#[derive(Debug, Parser)]
+#[command(about = "List API keys")]
+pub struct ListCommand;
+
+impl Report for ListApiKeysResponse {
+    fn report(&self) {
+        for api_key in &self.data.api_keys {
+            api_key.report();
+        }
+    }
+}
+
+impl ListCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        let config = load_config(&environment.args.home, &environment.args.node_name)?;
+
+        let response: ListApiKeysResponse = do_request(
+            &Client::new(),
+            multiaddr_to_url(fetch_multiaddr(&config)?, "admin-api/dev/api-keys")?,
+            None::<()>,
+            &config.identity,
+            RequestType::Get,
+        )
+        .await?;
+
+        environment.output.write(&response);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use calimero_primitives::hash::Hash;
+use calimero_server_primitives::admin::RevokeApiKeyResponse;
+use clap::Parser;
+use eyre::Result as EyreResult;
+use reqwest::Client;
+
+use crate::cli::Environment;
+use crate::common::{do_request, fetch_multiaddr, load_config, multiaddr_to_url, RequestType};
+use crate::output::Report;
+
+#[derive(Debug, Parser)]
+#[command(about = "Revoke an API key")]
+pub struct RevokeCommand {
+    #[arg(value_name = "KEY_ID", help = "The id of the key to revoke")]
+    pub id: Hash,
+}
+
+impl Report for RevokeApiKeyResponse {
+    fn report(&self) {
+        println!("is_revoked: {}", self.data.is_revoked);
+    }
+}
+
+impl RevokeCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        let config = load_config(&environment.args.home, &environment.args.node_name)?;
+
+        let url = multiaddr_to_url(
+            fetch_multiaddr(&config)?,
+            &format!("admin-api/dev/api-keys/{}", self.id),
+        )?;
+
+        let response: RevokeApiKeyResponse = do_request(
+            &Client::new(),
+            url,
+            None::<()>,
+            &config.identity,
+            RequestType::Delete,
+        )
+        .await?;
+
+        environment.output.write(&response);
+
+        Ok(())
+    }
+}
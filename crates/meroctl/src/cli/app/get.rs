@@ -1,4 +1,4 @@
-use calimero_server_primitives::admin::GetApplicationResponse;
+use calimero_server_primitives::admin::GetApplicationDetailsResponse;
 use clap::{Parser, ValueEnum};
 use eyre::Result as EyreResult;
 use reqwest::Client;
@@ -19,11 +19,30 @@ pub enum GetValues {
     Details,
 }
 
-impl Report for GetApplicationResponse {
+impl Report for GetApplicationDetailsResponse {
     fn report(&self) {
-        match self.data.application {
-            Some(ref application) => application.report(),
-            None => println!("No application found"),
+        self.data.application.report();
+
+        let Some(abi) = &self.data.abi else {
+            return;
+        };
+
+        println!("methods:");
+        for method in &abi.methods {
+            let arguments = method
+                .arguments
+                .iter()
+                .map(|argument| argument.name.as_str())
+                .collect::<Vec<_>>();
+
+            println!("  {}({})", method.name, arguments.join(", "));
+        }
+
+        println!("events:");
+        for event in &abi.events {
+            for variant in &event.variants {
+                println!("  {}::{}", event.name, variant.kind);
+            }
         }
     }
 }
@@ -37,7 +56,7 @@ impl GetCommand {
             &format!("admin-api/dev/applications/{}", self.app_id),
         )?;
 
-        let response: GetApplicationResponse = do_request(
+        let response: GetApplicationDetailsResponse = do_request(
             &Client::new(),
             url,
             None::<()>,
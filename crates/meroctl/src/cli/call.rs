@@ -1,4 +1,5 @@
 use calimero_primitives::context::ContextId;
+use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::PublicKey;
 use calimero_server_primitives::jsonrpc::{
     ExecuteRequest, Request, RequestId, RequestPayload, Response, ResponseBody, Version,
@@ -37,6 +38,20 @@ pub struct CallCommand {
     #[arg(long = "as", help = "Public key of the executor")]
     pub executor: PublicKey,
 
+    #[arg(
+        long,
+        value_name = "ROOT_HASH",
+        help = "Execute as a view against the state at a retained root hash"
+    )]
+    pub at: Option<Hash>,
+
+    #[arg(
+        long,
+        value_name = "KEY",
+        help = "Execute at most once for this key, retries return the first result"
+    )]
+    pub idempotency_key: Option<String>,
+
     #[arg(
         long,
         default_value = "dontcare",
@@ -95,6 +110,8 @@ impl CallCommand {
             self.method,
             self.args.unwrap_or(json!({})),
             self.executor,
+            self.at,
+            self.idempotency_key,
         ));
 
         let request = Request::new(
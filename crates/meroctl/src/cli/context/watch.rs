@@ -1,5 +1,8 @@
 use calimero_primitives::context::ContextId;
-use calimero_server_primitives::ws::{Request, RequestPayload, Response, SubscribeRequest};
+use calimero_primitives::identity::PublicKey;
+use calimero_server_primitives::ws::{
+    EventFilter, Request, RequestPayload, Response, ResumeToken, SubscribeRequest,
+};
 use clap::Parser;
 use eyre::Result as EyreResult;
 use futures_util::{SinkExt, StreamExt};
@@ -16,11 +19,30 @@ pub struct WatchCommand {
     /// ContextId to stream events from
     #[arg(value_name = "CONTEXT_ID", help = "ContextId to stream events from")]
     pub context_id: ContextId,
+
+    /// Payload types to stream, such as StateMutation or ExecutionEvent
+    #[arg(long = "type", value_name = "TYPE")]
+    pub types: Vec<String>,
+
+    /// Kinds of application event to stream
+    #[arg(long = "kind", value_name = "KIND")]
+    pub kinds: Vec<String>,
+
+    /// Executors whose events to stream
+    #[arg(long = "executor", value_name = "PUBLIC_KEY")]
+    pub executors: Vec<PublicKey>,
+
+    /// Replays the retained events logged after this resume token first
+    #[arg(long, value_name = "RESUME_TOKEN")]
+    pub resume_token: Option<ResumeToken>,
 }
 
 impl Report for Response {
     fn report(&self) {
         println!("id: {:?}", self.id);
+        if let Some(resume_token) = self.resume_token {
+            println!("resume token: {resume_token}");
+        }
         println!("payload: {:?}", self.body);
     }
 }
@@ -40,8 +62,16 @@ impl WatchCommand {
         let (ws_stream, _) = connect_async(url.as_str()).await?;
         let (mut write, mut read) = ws_stream.split();
 
+        let filter = EventFilter::new(
+            (!self.types.is_empty()).then_some(self.types),
+            (!self.kinds.is_empty()).then_some(self.kinds),
+            (!self.executors.is_empty()).then_some(self.executors),
+        );
+
         let subscribe_request = RequestPayload::Subscribe(SubscribeRequest {
             context_ids: vec![self.context_id],
+            filter,
+            resume_token: self.resume_token,
         });
         let request = Request {
             id: None,
@@ -1,3 +1,4 @@
+use calimero_config::{ConfigFile, TelemetryConfig};
 use camino::Utf8PathBuf;
 use clap::{Parser, Subcommand};
 use const_format::concatcp;
@@ -69,6 +70,28 @@ pub struct RootArgs {
 }
 
 impl RootCommand {
+    /// The telemetry of the node when running it, with the file resolved
+    /// against its home.
+    pub fn telemetry(&self) -> EyreResult<Option<TelemetryConfig>> {
+        if !matches!(self.action, SubCommands::Run(_)) {
+            return Ok(None);
+        }
+
+        let path = self.args.home.join(&self.args.node_name);
+
+        // a missing config is reported when the node is run
+        if !ConfigFile::exists(&path) {
+            return Ok(None);
+        }
+
+        let telemetry = ConfigFile::load(&path)?.telemetry;
+
+        Ok(Some(TelemetryConfig::new(
+            telemetry.otlp_endpoint,
+            telemetry.file.map(|file| path.join(file)),
+        )))
+    }
+
     pub async fn run(self) -> EyreResult<()> {
         match self.action {
             SubCommands::Config(config) => config.run(&self.args),
@@ -5,7 +5,7 @@ use std::fs::{create_dir, create_dir_all};
 
 use calimero_config::{
     BlobStoreConfig, ConfigFile, DataStoreConfig as StoreConfigFile, NetworkConfig, ServerConfig,
-    SyncConfig,
+    SyncConfig, TelemetryConfig,
 };
 use calimero_context::config::ContextConfig;
 use calimero_context_config::client::config::{
@@ -20,10 +20,12 @@ use calimero_network::config::{
 };
 use calimero_server::admin::service::AdminConfig;
 use calimero_server::jsonrpc::JsonRpcConfig;
+use calimero_server::metrics::MetricsConfig;
 use calimero_server::ws::WsConfig;
 use calimero_store::config::StoreConfig;
 use calimero_store::db::RocksDB;
 use calimero_store::Store;
+use camino::Utf8PathBuf;
 use clap::{Parser, ValueEnum};
 use ed25519_consensus::SigningKey as IcpSigningKey;
 use eyre::{bail, Result as EyreResult, WrapErr};
@@ -104,6 +106,14 @@ pub struct InitCommand {
     #[clap(long, default_value = "3")]
     pub relay_registrations_limit: usize,
 
+    /// OTLP (gRPC) endpoint to export spans to
+    #[clap(long, value_name = "URL")]
+    pub otlp_endpoint: Option<String>,
+
+    /// File to append spans to as JSON lines, relative to the node's home
+    #[clap(long, value_name = "PATH")]
+    pub trace_file: Option<Utf8PathBuf>,
+
     /// Force initialization even if the directory already exists
     #[clap(long)]
     pub force: bool,
@@ -321,17 +331,23 @@ impl InitCommand {
                     Some(AdminConfig::new(true)),
                     Some(JsonRpcConfig::new(true)),
                     Some(WsConfig::new(true)),
+                    Some(MetricsConfig::new(false)),
                 ),
             ),
             SyncConfig {
                 timeout: Duration::from_secs(30),
                 interval: Duration::from_secs(30),
+                proposals_interval: Duration::from_secs(10),
+                orphan_expiry: Duration::from_secs(60 * 60),
             },
             StoreConfigFile::new("data".into()),
             BlobStoreConfig::new("blobs".into()),
             ContextConfig {
                 client: client_config,
+                retained_roots: 0,
+                idempotency_ttl_secs: 24 * 60 * 60,
             },
+            TelemetryConfig::new(self.otlp_endpoint, self.trace_file),
         );
 
         config.save(&path)?;
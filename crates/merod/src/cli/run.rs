@@ -36,6 +36,8 @@ impl RunCommand {
             SyncConfig {
                 timeout: config.sync.timeout,
                 interval: config.sync.interval,
+                proposals_interval: config.sync.proposals_interval,
+                orphan_expiry: config.sync.orphan_expiry,
             },
             StoreConfig::new(path.join(config.datastore.path)),
             BlobStoreConfig::new(path.join(config.blobstore.path)),
@@ -46,6 +48,7 @@ impl RunCommand {
                 config.network.server.admin,
                 config.network.server.jsonrpc,
                 config.network.server.websocket,
+                config.network.server.metrics,
             ),
         ))
         .await
@@ -1,5 +1,6 @@
 use std::env::var;
 
+use calimero_config::TelemetryConfig;
 use clap::Parser;
 use eyre::Result as EyreResult;
 use tracing_subscriber::fmt::layer;
@@ -7,24 +8,31 @@ use tracing_subscriber::prelude::*;
 use tracing_subscriber::{registry, EnvFilter};
 
 use crate::cli::RootCommand;
+use crate::telemetry::Telemetry;
 
 mod cli;
 mod defaults;
+mod telemetry;
 
 #[tokio::main]
 async fn main() -> EyreResult<()> {
-    setup()?;
-
     let command = RootCommand::parse();
 
+    let _telemetry = setup(command.telemetry()?.as_ref())?;
+
     command.run().await
 }
 
-fn setup() -> EyreResult<()> {
+fn setup(telemetry: Option<&TelemetryConfig>) -> EyreResult<Telemetry> {
+    let telemetry = Telemetry::new(telemetry)?;
+
     registry()
         .with(EnvFilter::builder().parse(format!("info,{}", var("RUST_LOG").unwrap_or_default()))?)
         .with(layer())
+        .with(telemetry.layer())
         .init();
 
-    color_eyre::install()
+    color_eyre::install()?;
+
+    Ok(telemetry)
 }
@@ -0,0 +1,181 @@
+#[cfg(test)]
+#[path = "tests/telemetry.rs"]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use calimero_config::TelemetryConfig;
+use camino::Utf8Path;
+use eyre::{Result as EyreResult, WrapErr};
+use futures_util::future::{ready, BoxFuture};
+use opentelemetry::trace::{TraceError, TracerProvider as _};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::{SpanExporter as OtlpExporter, WithExportConfig};
+use opentelemetry_sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::runtime::Tokio;
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use serde::Serialize;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Exports the spans of the node where configured, flushing whatever is
+/// pending when dropped.
+#[derive(Debug)]
+pub struct Telemetry {
+    provider: Option<TracerProvider>,
+}
+
+impl Telemetry {
+    pub fn new(config: Option<&TelemetryConfig>) -> EyreResult<Self> {
+        // peers can continue our traces even when we don't export any
+        global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let Some(config) = config else {
+            return Ok(Self { provider: None });
+        };
+
+        if config.otlp_endpoint.is_none() && config.file.is_none() {
+            return Ok(Self { provider: None });
+        }
+
+        let mut builder = TracerProvider::builder();
+
+        if let Some(endpoint) = &config.otlp_endpoint {
+            let exporter = OtlpExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint.clone())
+                .build()?;
+
+            builder = builder.with_batch_exporter(exporter, Tokio);
+        }
+
+        if let Some(path) = &config.file {
+            builder = builder.with_batch_exporter(JsonFileExporter::open(path)?, Tokio);
+        }
+
+        Ok(Self {
+            provider: Some(builder.build()),
+        })
+    }
+
+    pub fn layer<S>(&self) -> Option<OpenTelemetryLayer<S, Tracer>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        self.provider
+            .as_ref()
+            .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("merod")))
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            if let Err(err) = provider.shutdown() {
+                eprintln!("failed to flush spans: {err}");
+            }
+        }
+    }
+}
+
+/// Appends finished spans to a file, one JSON object per line, for when
+/// there's no collector to send them to.
+#[derive(Debug)]
+struct JsonFileExporter {
+    file: BufWriter<File>,
+}
+
+impl JsonFileExporter {
+    fn open(path: &Utf8Path) -> EyreResult<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .wrap_err_with(|| format!("failed to open trace file {path:?}"))?;
+
+        Ok(Self {
+            file: BufWriter::new(file),
+        })
+    }
+
+    fn write(&mut self, batch: &[SpanData]) -> EyreResult<()> {
+        for span in batch {
+            serde_json::to_writer(&mut self.file, &JsonSpan::from(span))?;
+            self.file.write_all(b"\n")?;
+        }
+
+        self.file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl SpanExporter for JsonFileExporter {
+    fn export(&mut self, batch: Vec<SpanData>) -> BoxFuture<'static, ExportResult> {
+        let result = self
+            .write(&batch)
+            .map_err(|err| TraceError::Other(err.into()));
+
+        Box::pin(ready(result))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonSpan<'a> {
+    trace_id: String,
+    span_id: String,
+    parent_span_id: String,
+    name: &'a str,
+    start_time_unix_nano: u128,
+    end_time_unix_nano: u128,
+    attributes: BTreeMap<&'a str, String>,
+    events: Vec<JsonEvent<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonEvent<'a> {
+    name: &'a str,
+    time_unix_nano: u128,
+    attributes: BTreeMap<&'a str, String>,
+}
+
+impl<'a> From<&'a SpanData> for JsonSpan<'a> {
+    fn from(span: &'a SpanData) -> Self {
+        Self {
+            trace_id: span.span_context.trace_id().to_string(),
+            span_id: span.span_context.span_id().to_string(),
+            parent_span_id: span.parent_span_id.to_string(),
+            name: &span.name,
+            start_time_unix_nano: unix_nanos(span.start_time),
+            end_time_unix_nano: unix_nanos(span.end_time),
+            attributes: attributes(&span.attributes),
+            events: span
+                .events
+                .events
+                .iter()
+                .map(|event| JsonEvent {
+                    name: &event.name,
+                    time_unix_nano: unix_nanos(event.timestamp),
+                    attributes: attributes(&event.attributes),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn attributes(attributes: &[KeyValue]) -> BTreeMap<&str, String> {
+    attributes
+        .iter()
+        .map(|attribute| (attribute.key.as_str(), attribute.value.to_string()))
+        .collect()
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos())
+}
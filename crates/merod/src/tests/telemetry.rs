@@ -0,0 +1,76 @@
+use std::fs::read_to_string;
+
+use camino::Utf8PathBuf;
+use opentelemetry::trace::{Span as _, TraceContextExt, Tracer as _, TracerProvider as _};
+use serde_json::{from_str as from_json_str, json, Value};
+use tempdir::TempDir;
+
+use super::*;
+
+// records a span with a child, which ends first, through an exporter writing to the file
+fn record(path: &Utf8Path) {
+    let provider = TracerProvider::builder()
+        .with_simple_exporter(JsonFileExporter::open(path).unwrap())
+        .build();
+
+    let tracer = provider.tracer("test");
+
+    tracer.in_span("parent", |cx| {
+        cx.span().set_attribute(KeyValue::new("context_id", "abc"));
+
+        let mut child = tracer.start_with_context("child", &cx);
+
+        child.add_event("applied", vec![KeyValue::new("count", 2_i64)]);
+        child.end();
+    });
+
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_json_file_exporter() {
+    let dir = TempDir::new("_calimero_merod_telemetry").unwrap();
+
+    let path = Utf8PathBuf::try_from(dir.path().join("traces.jsonl")).unwrap();
+
+    record(&path);
+
+    // spans of later runs are appended to those of earlier ones
+    record(&path);
+
+    let spans: Vec<Value> = read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| from_json_str(line).unwrap())
+        .collect();
+
+    assert_eq!(spans.len(), 4);
+
+    let (child, parent) = (&spans[0], &spans[1]);
+
+    assert_eq!(child["name"], "child");
+    assert_eq!(parent["name"], "parent");
+
+    assert_eq!(child["trace_id"], parent["trace_id"]);
+    assert_eq!(child["parent_span_id"], parent["span_id"]);
+    assert_eq!(parent["parent_span_id"], "0000000000000000");
+
+    assert_eq!(parent["attributes"], json!({ "context_id": "abc" }));
+    assert_eq!(
+        child["events"],
+        json!([{
+            "name": "applied",
+            "time_unix_nano": child["events"][0]["time_unix_nano"],
+            "attributes": { "count": "2" },
+        }])
+    );
+
+    let time = |span: &Value, field: &str| span[field].as_u64().unwrap();
+
+    assert!(time(parent, "start_time_unix_nano") > 0);
+    assert!(time(parent, "start_time_unix_nano") <= time(child, "start_time_unix_nano"));
+    assert!(time(child, "end_time_unix_nano") <= time(parent, "end_time_unix_nano"));
+
+    assert_eq!(spans[2]["name"], "child");
+    assert_ne!(spans[2]["trace_id"], child["trace_id"]);
+}
@@ -5,6 +5,7 @@
 
 use eyre::eyre;
 use libp2p::core::ConnectedPoint;
+use libp2p::metrics::Recorder;
 use multiaddr::Protocol;
 use tracing::error;
 
@@ -33,15 +34,32 @@ impl EventLoop {
     // TODO: Consider splitting this long function into multiple parts.
     #[expect(clippy::too_many_lines, reason = "TODO: Will be refactored")]
     pub(super) async fn handle_swarm_event(&mut self, event: SwarmEvent<BehaviourEvent>) {
+        self.metrics.record(&event);
+
         #[expect(clippy::wildcard_enum_match_arm, reason = "This is reasonable here")]
         match event {
             SwarmEvent::Behaviour(event) => match event {
-                BehaviourEvent::Dcutr(event) => EventHandler::handle(self, event).await,
-                BehaviourEvent::Gossipsub(event) => EventHandler::handle(self, event).await,
-                BehaviourEvent::Identify(event) => EventHandler::handle(self, event).await,
-                BehaviourEvent::Kad(event) => EventHandler::handle(self, event).await,
+                BehaviourEvent::Dcutr(event) => {
+                    self.metrics.record(&event);
+                    EventHandler::handle(self, event).await;
+                }
+                BehaviourEvent::Gossipsub(event) => {
+                    self.metrics.record(&event);
+                    EventHandler::handle(self, event).await;
+                }
+                BehaviourEvent::Identify(event) => {
+                    self.metrics.record(&event);
+                    EventHandler::handle(self, event).await;
+                }
+                BehaviourEvent::Kad(event) => {
+                    self.metrics.record(&event);
+                    EventHandler::handle(self, event).await;
+                }
                 BehaviourEvent::Mdns(event) => EventHandler::handle(self, event).await,
-                BehaviourEvent::Ping(event) => EventHandler::handle(self, event).await,
+                BehaviourEvent::Ping(event) => {
+                    self.metrics.record(&event);
+                    EventHandler::handle(self, event).await;
+                }
                 BehaviourEvent::Relay(event) => EventHandler::handle(self, event).await,
                 BehaviourEvent::Rendezvous(event) => EventHandler::handle(self, event).await,
                 BehaviourEvent::Stream(()) => {}
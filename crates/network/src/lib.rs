@@ -13,13 +13,14 @@ use libp2p::dcutr::Behaviour as DcutrBehaviour;
 use libp2p::futures::prelude::*;
 use libp2p::gossipsub::{
     Behaviour as GossipsubBehaviour, Config as GossipsubConfig, IdentTopic, MessageAuthenticity,
-    MessageId, TopicHash,
+    MessageId, MetricsConfig as GossipsubMetricsConfig, TopicHash,
 };
 use libp2p::identify::{Behaviour as IdentifyBehaviour, Config as IdentifyConfig};
 use libp2p::kad::store::MemoryStore;
 use libp2p::kad::{Behaviour as KadBehaviour, Config as KadConfig, Mode, QueryId};
 use libp2p::mdns::tokio::Behaviour as MdnsTokioBehaviour;
 use libp2p::mdns::{Behaviour as MdnsBehaviour, Config as MdnsConfig};
+use libp2p::metrics::Metrics;
 use libp2p::noise::Config as NoiseConfig;
 use libp2p::ping::Behaviour as PingBehaviour;
 use libp2p::relay::client::Behaviour as RelayBehaviour;
@@ -32,6 +33,7 @@ use libp2p::yamux::Config as YamuxConfig;
 use libp2p::{PeerId, StreamProtocol, SwarmBuilder};
 use libp2p_stream::{Behaviour as StreamBehaviour, IncomingStreams};
 use multiaddr::{Multiaddr, Protocol};
+use prometheus_client::registry::Registry;
 use stream::Stream;
 use tokio::sync::{mpsc, oneshot};
 use tokio::time::{interval, Duration};
@@ -64,12 +66,15 @@ struct Behaviour {
     stream: StreamBehaviour,
 }
 
+/// Starts the network, registering the swarm's metrics, and those of the
+/// gossipsub mesh of every topic, with the given registry.
 pub async fn run(
     config: &NetworkConfig,
+    registry: &mut Registry,
 ) -> EyreResult<(NetworkClient, mpsc::Receiver<NetworkEvent>)> {
     let peer_id = config.identity.public().to_peer_id();
 
-    let (client, event_receiver, event_loop) = init(peer_id, config)?;
+    let (client, event_receiver, event_loop) = init(peer_id, config, registry)?;
 
     drop(spawn(event_loop.run()));
 
@@ -85,6 +90,7 @@ pub async fn run(
 fn init(
     peer_id: PeerId,
     config: &NetworkConfig,
+    registry: &mut Registry,
 ) -> EyreResult<(NetworkClient, mpsc::Receiver<NetworkEvent>, EventLoop)> {
     let bootstrap_peers = {
         let mut peers = vec![];
@@ -139,9 +145,11 @@ fn init(
 
                 kad
             },
-            gossipsub: GossipsubBehaviour::new(
+            gossipsub: GossipsubBehaviour::new_with_metrics(
                 MessageAuthenticity::Signed(key.clone()),
                 GossipsubConfig::default(),
+                registry.sub_registry_with_prefix("gossipsub"),
+                GossipsubMetricsConfig::default(),
             )
             .expect("Valid gossipsub config."),
             ping: PingBehaviour::default(),
@@ -173,12 +181,15 @@ fn init(
 
     let discovery = Discovery::new(&config.discovery.rendezvous, &config.discovery.relay);
 
+    let metrics = Metrics::new(registry);
+
     let event_loop = EventLoop::new(
         swarm,
         incoming_streams,
         command_receiver,
         event_sender,
         discovery,
+        metrics,
     );
 
     Ok((client, event_receiver, event_loop))
@@ -190,6 +201,7 @@ pub(crate) struct EventLoop {
     command_receiver: mpsc::Receiver<Command>,
     event_sender: mpsc::Sender<NetworkEvent>,
     discovery: Discovery,
+    metrics: Metrics,
     pending_dial: HashMap<PeerId, oneshot::Sender<EyreResult<Option<()>>>>,
     pending_bootstrap: HashMap<QueryId, oneshot::Sender<EyreResult<Option<()>>>>,
     pending_start_providing: HashMap<QueryId, oneshot::Sender<()>>,
@@ -207,6 +219,7 @@ impl EventLoop {
         command_receiver: mpsc::Receiver<Command>,
         event_sender: mpsc::Sender<NetworkEvent>,
         discovery: Discovery,
+        metrics: Metrics,
     ) -> Self {
         Self {
             swarm,
@@ -214,6 +227,7 @@ impl EventLoop {
             command_receiver,
             event_sender,
             discovery,
+            metrics,
             pending_dial: HashMap::default(),
             pending_bootstrap: HashMap::default(),
             pending_start_providing: HashMap::default(),
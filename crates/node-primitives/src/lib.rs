@@ -1,10 +1,12 @@
 use calimero_primitives::application::ApplicationId;
 use calimero_primitives::context::ContextId;
+use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::PublicKey;
 use calimero_runtime::logic::Outcome;
 use serde::{Deserialize, Serialize};
 use thiserror::Error as ThisError;
 use tokio::sync::{mpsc, oneshot};
+use tracing::Span;
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -13,16 +15,25 @@ pub struct ExecutionRequest {
     pub method: String,
     pub payload: Vec<u8>,
     pub executor_public_key: PublicKey,
+    pub root_hash: Option<Hash>,
+    pub idempotency_key: Option<String>,
+    /// Whether the call is rejected should it change the state, for clients
+    /// that may only read it.
+    pub read_only: bool,
     pub outcome_sender: oneshot::Sender<Result<Outcome, CallError>>,
+    /// The span the request was made in, which its handling continues.
+    pub span: Span,
 }
 
 impl ExecutionRequest {
     #[must_use]
-    pub const fn new(
+    pub fn new(
         context_id: ContextId,
         method: String,
         payload: Vec<u8>,
         executor_public_key: PublicKey,
+        root_hash: Option<Hash>,
+        idempotency_key: Option<String>,
         outcome_sender: oneshot::Sender<Result<Outcome, CallError>>,
     ) -> Self {
         Self {
@@ -30,12 +41,75 @@ impl ExecutionRequest {
             method,
             payload,
             executor_public_key,
+            root_hash,
+            idempotency_key,
+            read_only: false,
             outcome_sender,
+            span: Span::current(),
         }
     }
+
+    #[must_use]
+    pub const fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+}
+
+/// A request to execute several calls on a context as one, so that either
+/// all of them are applied, or none of them are.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct MultiExecutionRequest {
+    pub context_id: ContextId,
+    /// The methods to call and their payloads, in the order they're executed.
+    pub calls: Vec<(String, Vec<u8>)>,
+    pub executor_public_key: PublicKey,
+    /// Receives the outcome of each call executed, which ends at the first
+    /// that failed, if any.
+    pub outcome_sender: oneshot::Sender<Result<Vec<Outcome>, CallError>>,
+    /// The span the request was made in, which its handling continues.
+    pub span: Span,
+}
+
+impl MultiExecutionRequest {
+    #[must_use]
+    pub fn new(
+        context_id: ContextId,
+        calls: Vec<(String, Vec<u8>)>,
+        executor_public_key: PublicKey,
+        outcome_sender: oneshot::Sender<Result<Vec<Outcome>, CallError>>,
+    ) -> Self {
+        Self {
+            context_id,
+            calls,
+            executor_public_key,
+            outcome_sender,
+            span: Span::current(),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ServerRequest {
+    Execute(ExecutionRequest),
+    MultiExecute(MultiExecutionRequest),
+}
+
+impl From<ExecutionRequest> for ServerRequest {
+    fn from(request: ExecutionRequest) -> Self {
+        Self::Execute(request)
+    }
+}
+
+impl From<MultiExecutionRequest> for ServerRequest {
+    fn from(request: MultiExecutionRequest) -> Self {
+        Self::MultiExecute(request)
+    }
 }
 
-pub type ServerSender = mpsc::Sender<ExecutionRequest>;
+pub type ServerSender = mpsc::Sender<ServerRequest>;
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, ThisError)]
 #[serde(tag = "type", content = "data")]
@@ -52,6 +126,14 @@ pub enum CallError {
     Uninitialized,
     #[error("application not installed: '{application_id}'")]
     ApplicationNotInstalled { application_id: ApplicationId },
+    #[error("root hash not retained: '{root_hash}'")]
+    RootHashNotRetained { root_hash: Hash },
+    #[error("idempotency key already used for a different call")]
+    IdempotencyKeyReused,
+    #[error("the call changes the state, which the client is not permitted to do")]
+    ReadOnly,
+    #[error("no calls to execute")]
+    NoCalls,
     #[error("internal error")]
     InternalError,
 }
@@ -40,6 +40,8 @@ impl CallCommand {
                 &self.method,
                 serde_json::to_vec(&self.args.unwrap_or(json!({})))?,
                 self.executor,
+                None,
+                None,
             )
             .await;
 
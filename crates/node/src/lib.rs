@@ -7,30 +7,42 @@
 use core::future::{pending, Future};
 use core::pin::Pin;
 use core::str;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
 use std::time::Duration;
 
-use borsh::{from_slice, to_vec};
+use borsh::from_slice;
 use calimero_blobstore::config::BlobStoreConfig;
 use calimero_blobstore::{BlobManager, FileSystem};
 use calimero_context::config::ContextConfig;
+use calimero_context::executions::Replay;
 use calimero_context::ContextManager;
-use calimero_context_config::repr::ReprTransmute;
+use calimero_context_config::repr::{ReprBytes, ReprTransmute};
+use calimero_context_config::types::{Capability, ProposalId};
 use calimero_context_config::ProposalAction;
 use calimero_crypto::{Nonce, SharedKey, NONCE_LEN};
 use calimero_network::client::NetworkClient;
 use calimero_network::config::NetworkConfig;
 use calimero_network::types::{NetworkEvent, PeerId};
-use calimero_node_primitives::{CallError, ExecutionRequest};
+use calimero_node_primitives::{CallError, ServerRequest};
+use calimero_primitives::application::ApplicationId;
 use calimero_primitives::context::{Context, ContextId};
 use calimero_primitives::events::{
-    ContextEvent, ContextEventPayload, ExecutionEvent, ExecutionEventPayload, NodeEvent,
-    StateMutationPayload,
+    ContextEvent, ContextEventPayload, EntitiesChangedPayload, EntityChange, EntityChangeKind,
+    ExecutionEvent, ExecutionEventPayload, NodeEvent, StateMutationPayload,
 };
 use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::PublicKey;
+use calimero_runtime::errors::{FunctionCallError, MethodResolutionError};
 use calimero_runtime::logic::{Outcome, VMContext, VMLimits};
+use calimero_runtime::store::Storage;
 use calimero_runtime::Constraint;
 use calimero_server::config::ServerConfig;
+use calimero_storage::store::Key as StorageKey;
+use calimero_storage::sync::{
+    changes as entity_changes, combine as combine_artifacts, ChangeKind,
+    EntityChange as StorageEntityChange,
+};
 use calimero_store::config::StoreConfig;
 use calimero_store::db::RocksDB;
 use calimero_store::key::ContextMeta as ContextMetaKey;
@@ -39,21 +51,27 @@ use camino::Utf8PathBuf;
 use eyre::{bail, eyre, OptionExt, Result as EyreResult};
 use libp2p::gossipsub::{IdentTopic, Message, TopicHash};
 use libp2p::identity::Keypair;
+use prometheus_client::registry::Registry;
 use rand::{thread_rng, Rng};
 use tokio::io::{stdin, AsyncBufReadExt, BufReader};
 use tokio::select;
 use tokio::sync::{broadcast, mpsc};
-use tokio::time::{interval_at, Instant};
-use tracing::{debug, error, info, warn};
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant, Interval};
+use tracing::{debug, error, info, info_span, instrument, warn, Instrument};
 
 pub mod interactive_cli;
+mod metrics;
+pub mod proposals;
 pub mod runtime_compat;
 pub mod sync;
 pub mod types;
 
-use runtime_compat::RuntimeCompatStore;
+use metrics::{Metrics, StorageMetrics, STORAGE_INTERVAL};
+use proposals::TrackedProposal;
+use runtime_compat::{PrivateCompatStore, ProxyCompat, RuntimeCompatStore};
 use sync::SyncConfig;
-use types::BroadcastMessage;
+use types::{BroadcastMessage, TraceContext};
 
 type BoxedFuture<T> = Pin<Box<dyn Future<Output = T>>>;
 
@@ -102,6 +120,13 @@ pub struct Node {
     ctx_manager: ContextManager,
     network_client: NetworkClient,
     node_events: broadcast::Sender<NodeEvent>,
+    metrics: Metrics,
+    // the proposals pending in the proxy of each context, as last polled
+    proposals: BTreeMap<ContextId, BTreeMap<ProposalId, TrackedProposal>>,
+    // the proposals our identities have asked the proxy to delete
+    deleted_proposals: BTreeSet<ProposalId>,
+    // the poll fetching the proposals from the proxies, while in progress
+    proposal_poll: Option<JoinHandle<()>>,
 }
 
 pub async fn start(config: NodeConfig) -> EyreResult<()> {
@@ -111,14 +136,30 @@ pub async fn start(config: NodeConfig) -> EyreResult<()> {
 
     let (node_events, _) = broadcast::channel(32);
 
-    let (network_client, mut network_events) = calimero_network::run(&config.network).await?;
+    let mut registry = Registry::with_prefix("calimero");
+
+    let (network_client, mut network_events) = calimero_network::run(
+        &config.network,
+        registry.sub_registry_with_prefix("network"),
+    )
+    .await?;
 
     let store = Store::open::<RocksDB>(&config.datastore)?;
 
     let blob_manager = BlobManager::new(store.clone(), FileSystem::new(&config.blobstore).await?);
 
+    let metrics = Metrics::new(registry.sub_registry_with_prefix("node"));
+
+    let storage_metrics = StorageMetrics::new(
+        registry.sub_registry_with_prefix("storage"),
+        store.clone(),
+        blob_manager.clone(),
+    );
+
     let (server_sender, mut server_receiver) = mpsc::channel(32);
 
+    let (proposals_sender, mut proposals_receiver) = mpsc::channel(32);
+
     let ctx_manager = ContextManager::start(
         &config.context,
         store.clone(),
@@ -135,6 +176,7 @@ pub async fn start(config: NodeConfig) -> EyreResult<()> {
         ctx_manager.clone(),
         node_events.clone(),
         store.clone(),
+        Arc::new(registry),
     )) as BoxedFuture<EyreResult<()>>;
 
     let mut stdin = BufReader::new(stdin()).lines();
@@ -157,7 +199,19 @@ pub async fn start(config: NodeConfig) -> EyreResult<()> {
         config.sync.interval,
     );
 
-    let mut node = Node::new(config.sync, network_client, node_events, ctx_manager, store);
+    let mut proposals_interval_tick = (!config.sync.proposals_interval.is_zero())
+        .then(|| interval_at(Instant::now(), config.sync.proposals_interval));
+
+    let mut storage_metrics_tick = interval_at(Instant::now(), STORAGE_INTERVAL);
+
+    let mut node = Node::new(
+        config.sync,
+        network_client,
+        node_events,
+        ctx_manager,
+        store,
+        metrics,
+    );
 
     #[expect(clippy::redundant_pub_crate, reason = "Tokio code")]
     loop {
@@ -182,6 +236,9 @@ pub async fn start(config: NodeConfig) -> EyreResult<()> {
             }
             Some(request) = server_receiver.recv() => node.handle_server_request(request).await,
             _ = catchup_interval_tick.tick() => node.perform_interval_sync().await,
+            _ = tick(proposals_interval_tick.as_mut()) => node.perform_proposal_poll(&proposals_sender),
+            Some(polled) = proposals_receiver.recv() => node.handle_polled_proposals(polled).await,
+            _ = storage_metrics_tick.tick() => storage_metrics.refresh().await,
         }
     }
 
@@ -190,12 +247,13 @@ pub async fn start(config: NodeConfig) -> EyreResult<()> {
 
 impl Node {
     #[must_use]
-    pub const fn new(
+    pub(crate) const fn new(
         sync_config: SyncConfig,
         network_client: NetworkClient,
         node_events: broadcast::Sender<NodeEvent>,
         ctx_manager: ContextManager,
         store: Store,
+        metrics: Metrics,
     ) -> Self {
         Self {
             sync_config,
@@ -203,6 +261,10 @@ impl Node {
             ctx_manager,
             network_client,
             node_events,
+            metrics,
+            proposals: BTreeMap::new(),
+            deleted_proposals: BTreeSet::new(),
+            proposal_poll: None,
         }
     }
 
@@ -297,26 +359,49 @@ impl Node {
             return Ok(());
         };
 
-        let message = from_slice::<BroadcastMessage<'static>>(&message.data)?;
+        let message = BroadcastMessage::from_bytes(&message.data)?;
 
         match message {
             BroadcastMessage::StateDelta {
                 context_id,
                 author_id,
                 root_hash,
+                application_id,
                 artifact,
                 nonce,
+                trace,
             } => {
+                // continues the trace of the call that produced the delta
+                let span = info_span!(
+                    "handle_state_delta",
+                    %context_id,
+                    author = %author_id,
+                    peer = %source,
+                );
+
+                trace.attach(&span);
+
                 self.handle_state_delta(
                     source,
                     context_id,
                     author_id,
                     root_hash,
+                    application_id,
                     artifact.into_owned(),
                     nonce,
                 )
+                .instrument(span)
                 .await?;
             }
+            BroadcastMessage::ProposalDeleted {
+                context_id,
+                proposal_id,
+            } => {
+                self.record_deleted_proposal(
+                    context_id,
+                    proposal_id.rt().expect("infallible conversion"),
+                );
+            }
         }
 
         Ok(())
@@ -328,6 +413,7 @@ impl Node {
         context_id: ContextId,
         author_id: PublicKey,
         root_hash: Hash,
+        application_id: Option<ApplicationId>,
         artifact: Vec<u8>,
         nonce: [u8; NONCE_LEN],
     ) -> EyreResult<()> {
@@ -340,6 +426,25 @@ impl Node {
             return Ok(());
         }
 
+        // peers that predate the versioned layout don't say which revision
+        // the delta was produced by, so it's applied as it used to be
+        if let Some(application_id) = application_id.filter(|id| *id != context.application_id) {
+            context = self.ctx_manager.sync_context_config(context_id).await?;
+
+            // deltas produced by another application revision may not be
+            // compatible with our state, the interval sync will reconcile once
+            // both sides agree on the application
+            if application_id != context.application_id {
+                debug!(
+                    %context_id,
+                    our_application_id=%context.application_id,
+                    their_application_id=%application_id,
+                    "Received state delta for a different application revision, ignoring.."
+                );
+                return Ok(());
+            }
+        }
+
         let Some(sender_key) = self.ctx_manager.get_sender_key(&context_id, &author_id)? else {
             return self.initiate_sync(context_id, source).await;
         };
@@ -351,7 +456,13 @@ impl Node {
             .ok_or_eyre("failed to decrypt message")?;
 
         let Some(outcome) = self
-            .execute(&mut context, "__calimero_sync_next", artifact, author_id)
+            .execute(
+                &mut context,
+                "__calimero_sync_next",
+                artifact,
+                author_id,
+                None,
+            )
             .await?
         else {
             bail!("application not installed");
@@ -366,10 +477,11 @@ impl Node {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(context_id = %context.id, author = %executor_public_key))]
     async fn send_state_delta(
         &self,
         context: &Context,
-        outcome: &Outcome,
+        artifact: &[u8],
         executor_public_key: PublicKey,
     ) -> EyreResult<()> {
         if self
@@ -387,16 +499,19 @@ impl Node {
             let nonce = thread_rng().gen::<Nonce>();
 
             let artifact_encrypted = shared_key
-                .encrypt(outcome.artifact.clone(), nonce)
+                .encrypt(artifact.to_vec(), nonce)
                 .ok_or_eyre("encryption failed")?;
 
-            let message = to_vec(&BroadcastMessage::StateDelta {
+            let message = BroadcastMessage::StateDelta {
                 context_id: context.id,
                 author_id: executor_public_key,
                 root_hash: context.root_hash,
+                application_id: Some(context.application_id),
                 artifact: artifact_encrypted.as_slice().into(),
                 nonce,
-            })?;
+                trace: TraceContext::current(),
+            }
+            .to_bytes()?;
 
             let _ignored = self
                 .network_client
@@ -407,29 +522,96 @@ impl Node {
         Ok(())
     }
 
-    pub async fn handle_server_request(&mut self, request: ExecutionRequest) {
-        let result = self
-            .handle_call(
-                request.context_id,
-                &request.method,
-                request.payload,
-                request.executor_public_key,
-            )
-            .await;
+    async fn send_proposal_deletion(
+        &self,
+        context_id: ContextId,
+        proposal_id: ProposalId,
+    ) -> EyreResult<()> {
+        if self
+            .network_client
+            .mesh_peer_count(TopicHash::from_raw(context_id))
+            .await
+            != 0
+        {
+            let message = BroadcastMessage::ProposalDeleted {
+                context_id,
+                proposal_id: Hash::from(proposal_id.as_bytes()),
+            }
+            .to_bytes()?;
 
-        if let Err(err) = request.outcome_sender.send(result) {
-            error!(?err, "failed to respond to client request");
+            let _ignored = self
+                .network_client
+                .publish(TopicHash::from_raw(context_id), message)
+                .await?;
         }
+
+        Ok(())
     }
 
-    async fn handle_call(
-        &mut self,
+    pub async fn handle_server_request(&mut self, request: ServerRequest) {
+        let sent = match request {
+            ServerRequest::Execute(request) => {
+                let span = info_span!(
+                    parent: &request.span,
+                    "handle_call",
+                    context_id = %request.context_id,
+                    method = %request.method,
+                    author = %request.executor_public_key,
+                );
+
+                let result = self
+                    .handle_call(
+                        request.context_id,
+                        &request.method,
+                        request.payload,
+                        request.executor_public_key,
+                        request.root_hash,
+                        request.idempotency_key,
+                        request.read_only,
+                    )
+                    .instrument(span)
+                    .await;
+
+                request.outcome_sender.send(result).is_ok()
+            }
+            ServerRequest::MultiExecute(request) => {
+                let span = info_span!(
+                    parent: &request.span,
+                    "handle_multi_call",
+                    context_id = %request.context_id,
+                    calls = request.calls.len(),
+                    author = %request.executor_public_key,
+                );
+
+                let result = self
+                    .handle_multi_call(
+                        request.context_id,
+                        request.calls,
+                        request.executor_public_key,
+                    )
+                    .instrument(span)
+                    .await;
+
+                request.outcome_sender.send(result).is_ok()
+            }
+            _ => {
+                error!(?request, "Unhandled client request");
+                return;
+            }
+        };
+
+        if !sent {
+            error!("failed to respond to client request");
+        }
+    }
+
+    // the context, provided the executor is one of our identities in it
+    fn call_context(
+        &self,
         context_id: ContextId,
-        method: &str,
-        payload: Vec<u8>,
         executor_public_key: PublicKey,
-    ) -> Result<Outcome, CallError> {
-        let Ok(Some(mut context)) = self.ctx_manager.get_context(&context_id) else {
+    ) -> Result<Context, CallError> {
+        let Ok(Some(context)) = self.ctx_manager.get_context(&context_id) else {
             return Err(CallError::ContextNotFound);
         };
 
@@ -448,8 +630,112 @@ impl Node {
             });
         }
 
+        Ok(context)
+    }
+
+    #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
+    async fn handle_call(
+        &mut self,
+        context_id: ContextId,
+        method: &str,
+        payload: Vec<u8>,
+        executor_public_key: PublicKey,
+        root_hash: Option<Hash>,
+        idempotency_key: Option<String>,
+        read_only: bool,
+    ) -> Result<Outcome, CallError> {
+        let mut context = self.call_context(context_id, executor_public_key)?;
+
+        // calls that may only read are made against the current root, which,
+        // as for any past one, never has its changes committed
+        let root_hash = root_hash.or_else(|| read_only.then_some(context.root_hash));
+
+        if let Some(root_hash) = root_hash {
+            let history = self
+                .ctx_manager
+                .state_history(&context_id, root_hash)
+                .map_err(|e| {
+                    error!(%e, "Failed to read state history.");
+                    CallError::InternalError
+                })?
+                .ok_or(CallError::RootHashNotRetained { root_hash })?;
+
+            // the state at a past root may have been written by an earlier
+            // revision of the application, which is the one it's read with
+            let revision = self
+                .ctx_manager
+                .get_state_at(context_id, &history, StorageKey::Migration.to_bytes())
+                .map_err(|e| {
+                    error!(%e, "Failed to read state history.");
+                    CallError::InternalError
+                })?;
+
+            if let Some(revision) =
+                revision.and_then(|revision| <[u8; 32]>::try_from(&*revision).ok())
+            {
+                context.application_id = revision.into();
+            }
+
+            let outcome_option = self
+                .execute_at(&context, history, method, payload, executor_public_key)
+                .await
+                .map_err(|e| {
+                    error!(%e, "Failed to execute historical query call.");
+                    CallError::InternalError
+                })?;
+
+            let outcome = outcome_option.ok_or(CallError::ApplicationNotInstalled {
+                application_id: context.application_id,
+            })?;
+
+            if read_only && outcome.root_hash.is_some() {
+                return Err(CallError::ReadOnly);
+            }
+
+            return Ok(outcome);
+        }
+
+        let idempotency = match idempotency_key {
+            Some(idempotency_key) => {
+                let request = Hash::hash_borsh(&(method, &payload)).map_err(|e| {
+                    error!(%e, "Failed to hash the request.");
+                    CallError::InternalError
+                })?;
+
+                let replay = self
+                    .ctx_manager
+                    .find_execution(context_id, executor_public_key, &idempotency_key, request)
+                    .map_err(|e| {
+                        error!(%e, "Failed to read recorded execution.");
+                        CallError::InternalError
+                    })?;
+
+                match replay {
+                    Replay::Recorded(execution) => {
+                        return Ok(Outcome::returning(
+                            execution.returns.map(Vec::from),
+                            execution.logs,
+                        ));
+                    }
+                    Replay::Reused => return Err(CallError::IdempotencyKeyReused),
+                    _ => {}
+                }
+
+                Some((idempotency_key, request))
+            }
+            None => None,
+        };
+
         let outcome_option = self
-            .execute(&mut context, method, payload.clone(), executor_public_key)
+            .execute(
+                &mut context,
+                method,
+                payload,
+                executor_public_key,
+                idempotency
+                    .as_ref()
+                    .map(|(idempotency_key, request)| (idempotency_key.as_str(), *request)),
+            )
             .await
             .map_err(|e| {
                 error!(%e, "Failed to execute query call.");
@@ -466,6 +752,80 @@ impl Node {
             return Ok(outcome);
         }
 
+        self.submit_proposals(context_id, &outcome, executor_public_key)
+            .await?;
+
+        if !outcome.artifact.is_empty() {
+            if let Err(err) = self
+                .send_state_delta(&context, &outcome.artifact, executor_public_key)
+                .await
+            {
+                error!(%err, "Failed to send state delta.");
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    async fn handle_multi_call(
+        &mut self,
+        context_id: ContextId,
+        calls: Vec<(String, Vec<u8>)>,
+        executor_public_key: PublicKey,
+    ) -> Result<Vec<Outcome>, CallError> {
+        if calls.is_empty() {
+            return Err(CallError::NoCalls);
+        }
+
+        let mut context = self.call_context(context_id, executor_public_key)?;
+
+        let outcomes_option = self
+            .execute_many(&mut context, calls, executor_public_key, None)
+            .await
+            .map_err(|e| {
+                error!(%e, "Failed to execute multi call.");
+                CallError::InternalError
+            })?;
+
+        let Some(outcomes) = outcomes_option else {
+            return Err(CallError::ApplicationNotInstalled {
+                application_id: context.application_id,
+            });
+        };
+
+        if outcomes.iter().any(|outcome| outcome.returns.is_err()) {
+            return Ok(outcomes);
+        }
+
+        for outcome in &outcomes {
+            self.submit_proposals(context_id, outcome, executor_public_key)
+                .await?;
+        }
+
+        let artifact = combine_artifacts(outcomes.iter().map(|outcome| &*outcome.artifact))
+            .map_err(|e| {
+                error!(%e, "Failed to combine artifacts.");
+                CallError::InternalError
+            })?;
+
+        if !artifact.is_empty() {
+            if let Err(err) = self
+                .send_state_delta(&context, &artifact, executor_public_key)
+                .await
+            {
+                error!(%err, "Failed to send state delta.");
+            }
+        }
+
+        Ok(outcomes)
+    }
+
+    async fn submit_proposals(
+        &mut self,
+        context_id: ContextId,
+        outcome: &Outcome,
+        executor_public_key: PublicKey,
+    ) -> Result<(), CallError> {
         for (proposal_id, actions) in &outcome.proposals {
             let actions: Vec<ProposalAction> = from_slice(actions).map_err(|e| {
                 error!(%e, "Failed to deserialize proposal actions.");
@@ -486,6 +846,21 @@ impl Node {
                     error!(%e, "Failed to create proposal {:?}", proposal_id);
                     CallError::InternalError
                 })?;
+
+            // the proxy deletes the target straight away, leaving no trace of
+            // why it's gone for the proposal polling to find
+            let deleted = actions.iter().find_map(|action| match action {
+                ProposalAction::DeleteProposal { proposal_id } => Some(**proposal_id),
+                _ => None,
+            });
+
+            if let Some(deleted) = deleted {
+                self.record_deleted_proposal(context_id, deleted);
+
+                if let Err(err) = self.send_proposal_deletion(context_id, deleted).await {
+                    warn!(%context_id, %err, "Failed to announce deleted proposal");
+                }
+            }
         }
 
         for proposal_id in &outcome.approvals {
@@ -500,16 +875,7 @@ impl Node {
                 })?;
         }
 
-        if !outcome.artifact.is_empty() {
-            if let Err(err) = self
-                .send_state_delta(&context, &outcome, executor_public_key)
-                .await
-            {
-                error!(%err, "Failed to send state delta.");
-            }
-        }
-
-        Ok(outcome)
+        Ok(())
     }
 
     async fn execute(
@@ -518,7 +884,31 @@ impl Node {
         method: &str,
         payload: Vec<u8>,
         executor_public_key: PublicKey,
+        idempotency: Option<(&str, Hash)>,
     ) -> EyreResult<Option<Outcome>> {
+        let outcomes = self
+            .execute_many(
+                context,
+                vec![(method.to_owned(), payload)],
+                executor_public_key,
+                idempotency,
+            )
+            .await?;
+
+        Ok(outcomes.and_then(|mut outcomes| outcomes.pop()))
+    }
+
+    // executes the calls in turn within the same transaction, which is only
+    // committed if all of them succeed, the outcomes end at the first failure,
+    // the outcome of the last is recorded under the idempotency key, if any,
+    // in that same transaction
+    async fn execute_many(
+        &self,
+        context: &mut Context,
+        calls: Vec<(String, Vec<u8>)>,
+        executor_public_key: PublicKey,
+        idempotency: Option<(&str, Hash)>,
+    ) -> EyreResult<Option<Vec<Outcome>>> {
         let Some(blob) = self
             .ctx_manager
             .load_application_blob(&context.application_id)
@@ -531,52 +921,394 @@ impl Node {
 
         let mut storage = RuntimeCompatStore::new(&mut store, context.id);
 
-        let outcome = calimero_runtime::run(
+        let mut private_store = self.store.clone();
+
+        let mut private_storage = PrivateCompatStore::new(&mut private_store, context.id);
+
+        // deltas from other members are applied over this node's own migration,
+        // which is left for the interval sync to reconcile, as there is no call
+        // of its own to carry it
+        let replicated = !calls
+            .iter()
+            .any(|(method, _)| method == "__calimero_sync_next");
+
+        let migration = self.migrate(
             &blob,
-            &method,
-            VMContext::new(payload, *context.id, *executor_public_key),
+            context,
             &mut storage,
-            &get_runtime_limits()?,
+            &mut private_storage,
+            executor_public_key,
         )?;
 
-        if outcome.returns.is_ok() {
-            if let Some(root_hash) = outcome.root_hash {
-                if outcome.artifact.is_empty() && method != "__calimero_sync_next" {
-                    eyre::bail!("context state changed, but no actions were generated, discarding execution outcome to mitigate potential state inconsistency");
-                }
+        let mut outcomes = Vec::with_capacity(calls.len());
 
-                context.root_hash = root_hash.into();
+        for (method, payload) in calls {
+            let capabilities = self
+                .capabilities(context, &method, executor_public_key)
+                .await?;
+
+            let limits = get_runtime_limits()?;
+
+            let started = Instant::now();
+
+            let outcome = info_span!("run", %method).in_scope(|| {
+                calimero_runtime::run(
+                    &blob,
+                    &method,
+                    VMContext::new(payload, *context.id, *executor_public_key)
+                        .with_capabilities(capabilities)
+                        .with_orphan_expiry(self.sync_config.orphan_expiry),
+                    &mut storage,
+                    &mut private_storage,
+                    &mut ProxyCompat::new(&self.ctx_manager, context.id),
+                    &limits,
+                )
+            })?;
+
+            self.metrics.record_execution(
+                context.application_id,
+                started.elapsed(),
+                outcome.returns.is_ok(),
+            );
 
-                drop(self.node_events.send(NodeEvent::Context(ContextEvent::new(
-                    context.id,
-                    ContextEventPayload::StateMutation(StateMutationPayload::new(
-                        context.root_hash,
-                    )),
-                ))));
+            if outcome.returns.is_err() {
+                outcomes.push(outcome);
 
-                self.ctx_manager.save_context(context)?;
+                return Ok(Some(outcomes));
             }
 
-            if !storage.is_empty() {
-                storage.commit()?;
+            if method == "init" {
+                let _ignored = storage.set(
+                    StorageKey::Migration.to_bytes().to_vec(),
+                    context.application_id.to_vec(),
+                );
             }
 
+            // remote deltas carry their actions in the payload rather than the outcome
+            if outcome.root_hash.is_some()
+                && outcome.artifact.is_empty()
+                && method != "__calimero_sync_next"
+            {
+                eyre::bail!("context state changed, but no actions were generated, discarding execution outcome to mitigate potential state inconsistency");
+            }
+
+            outcomes.push(outcome);
+        }
+
+        // every member migrates on its own, so the migration rides along with
+        // the first call's delta, for the members to converge on its result
+        if let Some(migration) = migration.as_ref().filter(|_| replicated) {
+            if let Some(first) = outcomes.first_mut() {
+                first.artifact = combine_artifacts(
+                    [&*migration.artifact, &*first.artifact]
+                        .into_iter()
+                        .filter(|artifact| !artifact.is_empty()),
+                )?;
+            }
+        }
+
+        let previous_root_hash = context.root_hash;
+
+        let root_hash = outcomes
+            .iter()
+            .rev()
+            .find_map(|outcome| outcome.root_hash)
+            .or_else(|| migration.as_ref().and_then(|outcome| outcome.root_hash));
+
+        if let Some(root_hash) = root_hash {
+            context.root_hash = root_hash.into();
+
             drop(
-                self.node_events.send(NodeEvent::Context(ContextEvent::new(
-                    context.id,
-                    ContextEventPayload::ExecutionEvent(ExecutionEventPayload::new(
-                        outcome
-                            .events
-                            .iter()
-                            .map(|e| ExecutionEvent::new(e.kind.clone(), e.data.clone()))
-                            .collect(),
-                    )),
-                ))),
+                self.node_events.send(NodeEvent::Context(
+                    ContextEvent::new(
+                        context.id,
+                        ContextEventPayload::StateMutation(StateMutationPayload::new(
+                            context.root_hash,
+                        )),
+                    )
+                    .with_executor(executor_public_key),
+                )),
             );
+
+            // only what storage actually applied, rather than everything it
+            // was sent, some of which may have been held back or superseded
+            let changes = migration
+                .iter()
+                .chain(&outcomes)
+                .map(|outcome| entity_changes(&outcome.changes))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|changes| changes.into_iter().flatten().collect::<Vec<_>>());
+
+            match changes {
+                Ok(changes) if !changes.is_empty() => {
+                    drop(
+                        self.node_events.send(NodeEvent::Context(
+                            ContextEvent::new(
+                                context.id,
+                                ContextEventPayload::EntitiesChanged(EntitiesChangedPayload::new(
+                                    changes.into_iter().map(to_entity_change).collect(),
+                                )),
+                            )
+                            .with_executor(executor_public_key),
+                        )),
+                    );
+                }
+                Ok(_) => {}
+                Err(err) => warn!(%err, "Failed to decode entity changes"),
+            }
+
+            self.ctx_manager.save_context(context)?;
+        }
+
+        let originals = storage.take_originals();
+
+        if context.root_hash != previous_root_hash {
+            self.ctx_manager.record_state_history(
+                &mut storage,
+                context.id,
+                previous_root_hash,
+                originals,
+            )?;
+        }
+
+        if let (Some((idempotency_key, request)), Some(outcome)) = (idempotency, outcomes.last()) {
+            self.ctx_manager.record_execution(
+                &mut storage,
+                context.id,
+                executor_public_key,
+                idempotency_key,
+                request,
+                outcome
+                    .returns
+                    .as_ref()
+                    .ok()
+                    .and_then(|returns| returns.as_deref().map(Box::from)),
+                outcome.logs.clone(),
+            )?;
+        }
+
+        if !storage.is_empty() {
+            storage.commit()?;
         }
 
+        if !private_storage.is_empty() {
+            private_storage.commit()?;
+        }
+
+        for outcome in &outcomes {
+            drop(
+                self.node_events.send(NodeEvent::Context(
+                    ContextEvent::new(
+                        context.id,
+                        ContextEventPayload::ExecutionEvent(ExecutionEventPayload::new(
+                            outcome
+                                .events
+                                .iter()
+                                .map(|e| ExecutionEvent::new(e.kind.clone(), e.data.clone()))
+                                .collect(),
+                        )),
+                    )
+                    .with_executor(executor_public_key),
+                )),
+            );
+        }
+
+        Ok(Some(outcomes))
+    }
+
+    // brings the state up to the context's application revision ahead of an
+    // execution, within the same transaction, so it applies exactly once
+    fn migrate(
+        &self,
+        blob: &[u8],
+        context: &Context,
+        storage: &mut RuntimeCompatStore<'_, '_>,
+        private_storage: &mut PrivateCompatStore<'_, '_>,
+        executor_public_key: PublicKey,
+    ) -> EyreResult<Option<Outcome>> {
+        let key = StorageKey::Migration.to_bytes().to_vec();
+
+        let revision = context.application_id.to_vec();
+
+        // until the context is initialised there's no state to migrate, and
+        // the state it's initialised with is of the current revision, whatever
+        // the calls that come first
+        if *context.root_hash == [0; 32] {
+            let _ignored = storage.set(key, revision);
+
+            return Ok(None);
+        }
+
+        let Some(marker) = storage.get(&key) else {
+            // the revision is recorded on the first execution, and the context
+            // manager records the outgoing one should the application change
+            // before that, so only contexts that predate migrations get here
+            // with state, and they adopt their current revision
+            let _ignored = storage.set(key, revision);
+
+            return Ok(None);
+        };
+
+        if marker == revision {
+            return Ok(None);
+        }
+
+        debug!(
+            context_id=%context.id,
+            application_id=%context.application_id,
+            "Migrating context state to new application revision"
+        );
+
+        let outcome = calimero_runtime::run(
+            blob,
+            "migrate",
+            VMContext::new(vec![], *context.id, *executor_public_key)
+                .with_orphan_expiry(self.sync_config.orphan_expiry),
+            storage,
+            private_storage,
+            &mut ProxyCompat::new(&self.ctx_manager, context.id),
+            &get_runtime_limits()?,
+        )?;
+
+        match &outcome.returns {
+            Ok(_) => {}
+            // the application declares no migration, so its state layout is unchanged
+            Err(FunctionCallError::MethodResolutionError(
+                MethodResolutionError::MethodNotFound { .. },
+            )) => {}
+            Err(err) => bail!(
+                "failed to migrate context '{}' to application '{}': {}",
+                context.id,
+                context.application_id,
+                err
+            ),
+        }
+
+        let _ignored = storage.set(key, revision);
+
         Ok(Some(outcome))
     }
+
+    // executes against the state as it was at a past root hash, discarding any changes
+    async fn execute_at(
+        &self,
+        context: &Context,
+        history: Vec<[u8; 32]>,
+        method: &str,
+        payload: Vec<u8>,
+        executor_public_key: PublicKey,
+    ) -> EyreResult<Option<Outcome>> {
+        let Some(blob) = self
+            .ctx_manager
+            .load_application_blob(&context.application_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut store = self.store.clone();
+
+        let mut storage = RuntimeCompatStore::historical(&mut store, context.id, history);
+
+        let mut private_store = self.store.clone();
+
+        let mut private_storage = PrivateCompatStore::new(&mut private_store, context.id);
+
+        let capabilities = self
+            .capabilities(context, method, executor_public_key)
+            .await?;
+
+        let outcome = calimero_runtime::run(
+            &blob,
+            &method,
+            VMContext::new(payload, *context.id, *executor_public_key)
+                .with_capabilities(capabilities)
+                .with_orphan_expiry(self.sync_config.orphan_expiry),
+            &mut storage,
+            &mut private_storage,
+            &mut ProxyCompat::new(&self.ctx_manager, context.id),
+            &get_runtime_limits()?,
+        )?;
+
+        Ok(Some(outcome))
+    }
+
+    // the capabilities the executor holds in the context config, fetched only
+    // when the guards of the method, or those of the fields it may change, need
+    // them
+    async fn capabilities(
+        &self,
+        context: &Context,
+        method: &str,
+        executor_public_key: PublicKey,
+    ) -> EyreResult<Vec<u32>> {
+        let abi = self
+            .ctx_manager
+            .get_application_abi(&context.application_id)
+            .await?;
+
+        let guarded = abi.is_some_and(|abi| {
+            let abi_method = abi.method(method);
+
+            // the guards on the fields are evaluated for every change to the
+            // state, including those applied from other members
+            let changes_state = method == "__calimero_sync_next"
+                || abi_method.is_some_and(|abi_method| abi_method.mutates);
+
+            abi_method.is_some_and(|abi_method| abi_method.capabilities().next().is_some())
+                || (changes_state
+                    && abi
+                        .fields
+                        .iter()
+                        .any(|field| field.capabilities().next().is_some()))
+        });
+
+        if !guarded {
+            return Ok(vec![]);
+        }
+
+        let capabilities = self
+            .ctx_manager
+            .get_capabilities(context.id, executor_public_key)
+            .await?;
+
+        // the discriminants known to `calimero_sdk::env::Capability`
+        Ok(capabilities
+            .into_iter()
+            .map(|capability| match capability {
+                Capability::ManageApplication => 0,
+                Capability::ManageMembers => 1,
+                Capability::Proxy => 2,
+            })
+            .collect())
+    }
+}
+
+// polling is disabled with a zero interval, in which case this never ticks
+async fn tick(interval: Option<&mut Interval>) -> Instant {
+    match interval {
+        Some(interval) => interval.tick().await,
+        None => pending().await,
+    }
+}
+
+fn to_entity_change(change: StorageEntityChange) -> EntityChange {
+    let kind = match change.kind {
+        ChangeKind::Added => EntityChangeKind::Added,
+        ChangeKind::Updated => EntityChangeKind::Updated,
+        ChangeKind::Deleted => EntityChangeKind::Deleted,
+    };
+
+    EntityChange::new(
+        (*change.id.as_bytes()).into(),
+        kind,
+        change.collection.map(|id| (*id.as_bytes()).into()),
+        change
+            .path
+            .into_iter()
+            .map(|id| (*id.as_bytes()).into())
+            .collect(),
+    )
 }
 
 // TODO: move this into the config
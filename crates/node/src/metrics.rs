@@ -0,0 +1,209 @@
+#[cfg(test)]
+#[path = "tests/metrics.rs"]
+mod tests;
+
+use std::time::Duration;
+
+use calimero_blobstore::BlobManager;
+use calimero_primitives::application::ApplicationId;
+use calimero_store::Store;
+use eyre::Result as EyreResult;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::Registry;
+use tracing::error;
+
+// how often the storage metrics are refreshed
+pub(crate) const STORAGE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, EncodeLabelSet)]
+struct ApplicationLabels {
+    application_id: String,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, EncodeLabelSet)]
+struct ColumnLabels {
+    column: String,
+    property: String,
+}
+
+fn duration_histogram() -> Histogram {
+    // from 1ms up to about 30s
+    Histogram::new(exponential_buckets(0.001, 2.0, 16))
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Metrics {
+    sync_attempts: Counter,
+    sync_successes: Counter,
+    sync_failures: Counter,
+    sync_duration: Histogram,
+    executions: Family<ApplicationLabels, Counter>,
+    execution_failures: Family<ApplicationLabels, Counter>,
+    execution_duration: Family<ApplicationLabels, Histogram>,
+}
+
+impl Metrics {
+    pub(crate) fn new(registry: &mut Registry) -> Self {
+        let metrics = Self {
+            sync_attempts: Counter::default(),
+            sync_successes: Counter::default(),
+            sync_failures: Counter::default(),
+            sync_duration: duration_histogram(),
+            executions: Family::default(),
+            execution_failures: Family::default(),
+            execution_duration: Family::new_with_constructor(duration_histogram),
+        };
+
+        let sync = registry.sub_registry_with_prefix("sync");
+
+        sync.register(
+            "attempts",
+            "Syncs initiated with a peer",
+            metrics.sync_attempts.clone(),
+        );
+        sync.register(
+            "successes",
+            "Syncs that completed",
+            metrics.sync_successes.clone(),
+        );
+        sync.register(
+            "failures",
+            "Syncs that failed",
+            metrics.sync_failures.clone(),
+        );
+        sync.register(
+            "duration_seconds",
+            "Time taken by syncs, whether they completed or not",
+            metrics.sync_duration.clone(),
+        );
+
+        let execution = registry.sub_registry_with_prefix("execution");
+
+        execution.register(
+            "calls",
+            "Methods executed, per application",
+            metrics.executions.clone(),
+        );
+        execution.register(
+            "failures",
+            "Methods executed that failed, per application",
+            metrics.execution_failures.clone(),
+        );
+        execution.register(
+            "duration_seconds",
+            "Time taken by methods executed, per application",
+            metrics.execution_duration.clone(),
+        );
+
+        metrics
+    }
+
+    pub(crate) fn record_sync(&self, duration: Duration, succeeded: bool) {
+        let _ = self.sync_attempts.inc();
+
+        if succeeded {
+            let _ = self.sync_successes.inc();
+        } else {
+            let _ = self.sync_failures.inc();
+        }
+
+        self.sync_duration.observe(duration.as_secs_f64());
+    }
+
+    pub(crate) fn record_execution(
+        &self,
+        application_id: ApplicationId,
+        duration: Duration,
+        succeeded: bool,
+    ) {
+        let labels = ApplicationLabels {
+            application_id: application_id.to_string(),
+        };
+
+        let _ = self.executions.get_or_create(&labels).inc();
+
+        if !succeeded {
+            let _ = self.execution_failures.get_or_create(&labels).inc();
+        }
+
+        self.execution_duration
+            .get_or_create(&labels)
+            .observe(duration.as_secs_f64());
+    }
+}
+
+/// Periodically samples the statistics of the datastore and the size of the
+/// blobstore, which aren't tracked as they change.
+#[derive(Debug)]
+pub(crate) struct StorageMetrics {
+    store: Store,
+    blob_manager: BlobManager,
+    columns: Family<ColumnLabels, Gauge>,
+    blobs: Gauge,
+    blob_bytes: Gauge,
+}
+
+impl StorageMetrics {
+    pub(crate) fn new(registry: &mut Registry, store: Store, blob_manager: BlobManager) -> Self {
+        let metrics = Self {
+            store,
+            blob_manager,
+            columns: Family::default(),
+            blobs: Gauge::default(),
+            blob_bytes: Gauge::default(),
+        };
+
+        registry.register(
+            "datastore",
+            "Statistics of each column of the datastore, by property",
+            metrics.columns.clone(),
+        );
+
+        let blobstore = registry.sub_registry_with_prefix("blobstore");
+
+        blobstore.register(
+            "chunks",
+            "Chunks kept in the blobstore",
+            metrics.blobs.clone(),
+        );
+        blobstore.register(
+            "bytes",
+            "Total size of the chunks kept in the blobstore",
+            metrics.blob_bytes.clone(),
+        );
+
+        metrics
+    }
+
+    pub(crate) async fn refresh(&self) {
+        if let Err(err) = self.try_refresh().await {
+            error!(%err, "Failed to refresh storage metrics");
+        }
+    }
+
+    async fn try_refresh(&self) -> EyreResult<()> {
+        for (column, property, value) in self.store.stats()? {
+            let labels = ColumnLabels {
+                column: column.as_ref().to_owned(),
+                property: property.to_owned(),
+            };
+
+            let _ = self.columns.get_or_create(&labels).set(saturate(value));
+        }
+
+        let (blobs, blob_bytes) = self.blob_manager.usage().await?;
+
+        let _ = self.blobs.set(saturate(blobs));
+        let _ = self.blob_bytes.set(saturate(blob_bytes));
+
+        Ok(())
+    }
+}
+
+fn saturate(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
@@ -0,0 +1,328 @@
+#[cfg(test)]
+#[path = "tests/proposals.rs"]
+mod tests;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use calimero_context::ContextManager;
+use calimero_context_config::repr::ReprBytes;
+use calimero_context_config::types::{ContextIdentity, ProposalId, SignerId};
+use calimero_context_config::Proposal;
+use calimero_primitives::context::ContextId;
+use calimero_primitives::events::{
+    ApprovalAddedPayload, ContextEvent, ContextEventPayload, NodeEvent, ProposalCreatedPayload,
+    ProposalDeletedPayload, ProposalExecutedPayload,
+};
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::PublicKey;
+use eyre::Result as EyreResult;
+use serde_json::{to_value as to_json_value, to_vec as to_json_vec, Map, Value};
+use tokio::spawn;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::{debug, error, warn};
+
+use crate::Node;
+
+/// The number of proposals fetched from the proxy per query.
+const PROPOSALS_PAGE_SIZE: usize = 50;
+
+/// The method an application can declare to be notified of proposal events.
+///
+/// It must take `&mut self` and a single argument, which is given the event.
+pub const PROPOSAL_CALLBACK: &str = "on_proposal";
+
+// a proposal pending in the proxy, as last observed
+#[derive(Debug)]
+pub struct TrackedProposal {
+    author_id: SignerId,
+    approvers: BTreeSet<ContextIdentity>,
+}
+
+// the proposals pending in the proxy of a context, with their approvers, as
+// fetched by a poll
+#[derive(Debug)]
+pub struct PolledProposals {
+    context_id: ContextId,
+    proposals: BTreeMap<ProposalId, (Proposal, Vec<ContextIdentity>)>,
+}
+
+impl Node {
+    // fetches the proposals of every context off the main loop, as each takes
+    // a number of chain queries, handing them back through `sender` to be
+    // compared with those last observed
+    pub fn perform_proposal_poll(&mut self, sender: &mpsc::Sender<PolledProposals>) {
+        if self
+            .proposal_poll
+            .as_ref()
+            .is_some_and(|poll| !poll.is_finished())
+        {
+            debug!("Previous proposal poll still in progress, skipping..");
+            return;
+        }
+
+        let context_ids = match self.ctx_manager.get_contexts_ids(None) {
+            Ok(context_ids) => context_ids,
+            Err(err) => {
+                error!(%err, "Failed to list contexts for proposal polling");
+                return;
+            }
+        };
+
+        let ctx_manager = self.ctx_manager.clone();
+        let interval = self.sync_config.proposals_interval;
+        let sender = sender.clone();
+
+        self.proposal_poll = Some(spawn(async move {
+            for context_id in context_ids {
+                match timeout(interval, fetch_proposals(&ctx_manager, context_id)).await {
+                    Ok(Ok(proposals)) => {
+                        let polled = PolledProposals {
+                            context_id,
+                            proposals,
+                        };
+
+                        if sender.send(polled).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(Err(err)) => warn!(%context_id, %err, "Failed to poll proposals"),
+                    Err(_) => error!(%context_id, "Timeout while polling proposals"),
+                }
+            }
+        }));
+    }
+
+    pub async fn handle_polled_proposals(&mut self, polled: PolledProposals) {
+        let context_id = polled.context_id;
+
+        if let Err(err) = self.compare_proposals(polled).await {
+            warn!(%context_id, %err, "Failed to compare polled proposals");
+        }
+    }
+
+    async fn compare_proposals(&mut self, polled: PolledProposals) -> EyreResult<()> {
+        let PolledProposals {
+            context_id,
+            proposals: current,
+        } = polled;
+
+        let Some(tracked) = self.proposals.get(&context_id) else {
+            // the first observation only establishes what's already pending
+            debug!(%context_id, proposals=%current.len(), "Started tracking proposals");
+
+            let _ignored = self.proposals.insert(context_id, track(current));
+
+            return Ok(());
+        };
+
+        let events = proposal_events(tracked, &current, &mut self.deleted_proposals);
+
+        let _ignored = self.proposals.insert(context_id, track(current));
+
+        for (author_id, event) in events {
+            drop(self.node_events.send(NodeEvent::Context(ContextEvent::new(
+                context_id,
+                event.clone(),
+            ))));
+
+            if let Err(err) = self
+                .notify_proposal_event(context_id, author_id, &event)
+                .await
+            {
+                warn!(%context_id, %err, "Failed to notify application of proposal event");
+            }
+        }
+
+        Ok(())
+    }
+
+    // the proxy keeps no record of why a proposal left it, but only its author
+    // can delete it, which is done through the node holding that identity, so
+    // that node tells the others, kept only while the proposal is tracked
+    pub fn record_deleted_proposal(&mut self, context_id: ContextId, proposal_id: ProposalId) {
+        if self
+            .proposals
+            .get(&context_id)
+            .is_some_and(|tracked| tracked.contains_key(&proposal_id))
+        {
+            let _ignored = self.deleted_proposals.insert(proposal_id);
+        }
+    }
+
+    // invokes the application's callback, if it declares one, as the author of
+    // the proposal, so it's only invoked on the author's node, and the changes
+    // it makes reach the other members like those of any other call
+    async fn notify_proposal_event(
+        &mut self,
+        context_id: ContextId,
+        author_id: SignerId,
+        event: &ContextEventPayload,
+    ) -> EyreResult<()> {
+        let executor = PublicKey::from(author_id.as_bytes());
+
+        if !self
+            .ctx_manager
+            .context_has_owned_identity(context_id, executor)?
+        {
+            return Ok(());
+        }
+
+        let Some(context) = self.ctx_manager.get_context(&context_id)? else {
+            return Ok(());
+        };
+
+        let Some(abi) = self
+            .ctx_manager
+            .get_application_abi(&context.application_id)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let Some(argument) = abi
+            .method(PROPOSAL_CALLBACK)
+            .filter(|method| method.mutates)
+            .and_then(|method| match &*method.arguments {
+                [argument] => Some(argument.name.clone()),
+                _ => None,
+            })
+        else {
+            return Ok(());
+        };
+
+        let mut input = Map::new();
+
+        drop(input.insert(argument, to_json_value(event)?));
+
+        let outcome = self
+            .handle_call(
+                context_id,
+                PROPOSAL_CALLBACK,
+                to_json_vec(&Value::Object(input))?,
+                executor,
+                None,
+                None,
+            )
+            .await?;
+
+        if let Err(err) = outcome.returns {
+            warn!(%context_id, %err, "Proposal callback failed");
+        }
+
+        Ok(())
+    }
+}
+
+// the events for how the proposals pending in the proxy changed since they
+// were last observed, with the authors of the proposals they're about
+fn proposal_events(
+    tracked: &BTreeMap<ProposalId, TrackedProposal>,
+    current: &BTreeMap<ProposalId, (Proposal, Vec<ContextIdentity>)>,
+    deleted_proposals: &mut BTreeSet<ProposalId>,
+) -> Vec<(SignerId, ContextEventPayload)> {
+    let mut events = vec![];
+
+    for (proposal_id, (proposal, approvers)) in current {
+        let known = tracked.get(proposal_id);
+
+        if known.is_none() {
+            events.push((
+                *proposal.author_id,
+                ContextEventPayload::ProposalCreated(ProposalCreatedPayload::new(
+                    Hash::from(proposal_id.as_bytes()),
+                    PublicKey::from(proposal.author_id.as_bytes()),
+                )),
+            ));
+        }
+
+        let mut approvals = known.map_or(0, |known| known.approvers.len());
+
+        for approver in approvers {
+            if known.is_some_and(|known| known.approvers.contains(approver)) {
+                continue;
+            }
+
+            approvals = approvals.saturating_add(1);
+
+            events.push((
+                *proposal.author_id,
+                ContextEventPayload::ApprovalAdded(ApprovalAddedPayload::new(
+                    Hash::from(proposal_id.as_bytes()),
+                    PublicKey::from(approver.as_bytes()),
+                    approvals,
+                )),
+            ));
+        }
+    }
+
+    for (proposal_id, proposal) in tracked {
+        if current.contains_key(proposal_id) {
+            continue;
+        }
+
+        let deleted = deleted_proposals.remove(proposal_id);
+
+        let proposal_id = Hash::from(proposal_id.as_bytes());
+
+        events.push((
+            proposal.author_id,
+            if deleted {
+                ContextEventPayload::ProposalDeleted(ProposalDeletedPayload::new(proposal_id))
+            } else {
+                ContextEventPayload::ProposalExecuted(ProposalExecutedPayload::new(proposal_id))
+            },
+        ));
+    }
+
+    events
+}
+
+fn track(
+    proposals: BTreeMap<ProposalId, (Proposal, Vec<ContextIdentity>)>,
+) -> BTreeMap<ProposalId, TrackedProposal> {
+    proposals
+        .into_iter()
+        .map(|(proposal_id, (proposal, approvers))| {
+            let proposal = TrackedProposal {
+                author_id: *proposal.author_id,
+                approvers: approvers.into_iter().collect(),
+            };
+
+            (proposal_id, proposal)
+        })
+        .collect()
+}
+
+async fn fetch_proposals(
+    ctx_manager: &ContextManager,
+    context_id: ContextId,
+) -> EyreResult<BTreeMap<ProposalId, (Proposal, Vec<ContextIdentity>)>> {
+    let mut proposals = vec![];
+
+    loop {
+        let page = ctx_manager
+            .get_proposals(context_id, proposals.len(), PROPOSALS_PAGE_SIZE)
+            .await?;
+
+        let exhausted = page.len() < PROPOSALS_PAGE_SIZE;
+
+        proposals.extend(page);
+
+        if exhausted {
+            break;
+        }
+    }
+
+    let mut current = BTreeMap::new();
+
+    for proposal in proposals {
+        let approvers = ctx_manager
+            .get_proposal_approvers(context_id, *proposal.id)
+            .await?;
+
+        let _ignored = current.insert(*proposal.id, (proposal, approvers));
+    }
+
+    Ok(current)
+}
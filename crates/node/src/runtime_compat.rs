@@ -1,14 +1,30 @@
+#[cfg(test)]
+#[path = "tests/runtime_compat.rs"]
+mod tests;
+
+use core::any::Any;
 use core::cell::RefCell;
-use core::mem::transmute;
+use core::mem::{take, transmute};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use borsh::{from_slice, to_vec};
+use calimero_context::{ContextManager, StateWrites};
+use calimero_context_config::repr::ReprTransmute;
 use calimero_primitives::context::ContextId;
+use calimero_runtime::proxy::{Proxy, ProxyQuery};
 use calimero_runtime::store::{Key, Storage, Value};
-use calimero_store::key::ContextState as ContextStateKey;
+use calimero_store::key::{
+    AsKeyParts, ContextPrivateState as ContextPrivateStateKey, ContextState as ContextStateKey,
+    ContextStateHistory as ContextStateHistoryKey,
+};
 use calimero_store::layer::temporal::Temporal;
 use calimero_store::layer::{LayerExt, ReadLayer, WriteLayer};
+use calimero_store::types::ContextStateHistory as ContextStateHistoryValue;
 use calimero_store::Store;
 use eyre::Result as EyreResult;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::task::block_in_place;
 
 #[derive(Debug)]
 pub struct RuntimeCompatStore<'this, 'entry> {
@@ -16,6 +32,12 @@ pub struct RuntimeCompatStore<'this, 'entry> {
     inner: Temporal<'this, 'entry, Store>,
     // todo! unideal, will revisit the shape of WriteLayer to own keys (since they are now fixed-sized)
     keys: RefCell<Vec<Arc<ContextStateKey>>>,
+    // values of the entries written, as they were before this execution
+    originals: BTreeMap<[u8; 32], Option<Box<[u8]>>>,
+    // retained roots to resolve reads through, when reading at a past root hash
+    history: Option<Vec<[u8; 32]>>,
+    // keys of the records written along with the state, same as `keys`
+    records: RefCell<Vec<Box<dyn Any>>>,
 }
 
 impl<'this, 'entry> RuntimeCompatStore<'this, 'entry> {
@@ -24,6 +46,20 @@ impl<'this, 'entry> RuntimeCompatStore<'this, 'entry> {
             context_id,
             inner: store.temporal(),
             keys: RefCell::default(),
+            originals: BTreeMap::new(),
+            history: None,
+            records: RefCell::default(),
+        }
+    }
+
+    pub fn historical(
+        store: &'this mut Store,
+        context_id: ContextId,
+        history: Vec<[u8; 32]>,
+    ) -> Self {
+        Self {
+            history: Some(history),
+            ..Self::new(store, context_id)
         }
     }
 
@@ -46,6 +82,49 @@ impl<'this, 'entry> RuntimeCompatStore<'this, 'entry> {
         }
     }
 
+    fn record_key<K: AsKeyParts>(&self, key: K) -> &'entry K {
+        let mut records = self.records.borrow_mut();
+
+        records.push(Box::new(key));
+
+        let key = records
+            .last()
+            .and_then(|key| key.downcast_ref::<K>())
+            .expect("key was just pushed");
+
+        // safety: TemporalStore lives as long as Self, so the reference will hold
+        unsafe { transmute::<&K, &'entry K>(key) }
+    }
+
+    fn read(&self, key: &ContextStateKey) -> Option<Box<[u8]>> {
+        if let Some(history) = &self.history {
+            let state_key = key.state_key();
+
+            // entries written during this execution are read back as written
+            if !self.originals.contains_key(&state_key) {
+                let record = history.iter().find_map(|root_hash| {
+                    let key = ContextStateHistoryKey::new(self.context_id, *root_hash, state_key);
+
+                    let slice = self.inner.get(&key).ok()??;
+
+                    from_slice::<ContextStateHistoryValue>(&slice).ok()
+                });
+
+                if let Some(record) = record {
+                    return record.value;
+                }
+            }
+        }
+
+        let slice = self.inner.get(key).ok()??;
+
+        Some(slice.into_boxed())
+    }
+
+    pub fn take_originals(&mut self) -> BTreeMap<[u8; 32], Option<Box<[u8]>>> {
+        take(&mut self.originals)
+    }
+
     pub fn commit(self) -> EyreResult<()> {
         self.inner.commit()
     }
@@ -56,6 +135,116 @@ impl<'this, 'entry> RuntimeCompatStore<'this, 'entry> {
 }
 
 impl Storage for RuntimeCompatStore<'_, '_> {
+    fn get(&self, key: &Key) -> Option<Vec<u8>> {
+        let key = self.state_key(key)?;
+
+        self.read(key).map(Vec::from)
+    }
+
+    fn remove(&mut self, key: &Key) -> Option<Vec<u8>> {
+        let key = self.state_key(key)?;
+
+        let old = self.read(key);
+
+        let _ignored = self
+            .originals
+            .entry(key.state_key())
+            .or_insert_with(|| old.clone());
+
+        self.inner.delete(key).ok()?;
+
+        old.map(Vec::from)
+    }
+
+    fn set(&mut self, key: Key, value: Value) -> Option<Value> {
+        let key = self.state_key(&key)?;
+
+        let old = self.read(key);
+
+        let _ignored = self
+            .originals
+            .entry(key.state_key())
+            .or_insert_with(|| old.clone());
+
+        self.inner.put(key, value.into()).ok()?;
+
+        old.map(Vec::from)
+    }
+
+    fn has(&self, key: &Key) -> bool {
+        let Some(key) = self.state_key(key) else {
+            return false;
+        };
+
+        self.read(key).is_some()
+    }
+}
+
+// the records kept along with the state are committed in the same transaction
+impl StateWrites for RuntimeCompatStore<'_, '_> {
+    fn put<K: AsKeyParts>(&mut self, key: K, value: Vec<u8>) -> EyreResult<()> {
+        let key = self.record_key(key);
+
+        self.inner.put(key, value.into())
+    }
+
+    fn delete<K: AsKeyParts>(&mut self, key: K) -> EyreResult<()> {
+        let key = self.record_key(key);
+
+        self.inner.delete(key)
+    }
+}
+
+// node-local storage, kept apart from the replicated state
+#[derive(Debug)]
+pub struct PrivateCompatStore<'this, 'entry> {
+    context_id: ContextId,
+    inner: Temporal<'this, 'entry, Store>,
+    // todo! same as RuntimeCompatStore, revisit along with the shape of WriteLayer
+    keys: RefCell<Vec<Arc<ContextPrivateStateKey>>>,
+}
+
+impl<'this, 'entry> PrivateCompatStore<'this, 'entry> {
+    pub fn new(store: &'this mut Store, context_id: ContextId) -> Self {
+        Self {
+            context_id,
+            inner: store.temporal(),
+            keys: RefCell::default(),
+        }
+    }
+
+    fn state_key(&self, key: &[u8]) -> Option<&'entry ContextPrivateStateKey> {
+        let mut state_key = [0; 32];
+
+        (key.len() <= state_key.len()).then_some(())?;
+
+        state_key[..key.len()].copy_from_slice(key);
+
+        let mut keys = self.keys.borrow_mut();
+
+        keys.push(Arc::new(ContextPrivateStateKey::new(
+            self.context_id,
+            state_key,
+        )));
+
+        // safety: TemporalStore lives as long as Self, so the reference will hold
+        unsafe {
+            transmute::<Option<&ContextPrivateStateKey>, Option<&'entry ContextPrivateStateKey>>(
+                keys.last().map(|x| &**x),
+            )
+        }
+    }
+
+    pub fn commit(self) -> EyreResult<()> {
+        self.inner.commit()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl Storage for PrivateCompatStore<'_, '_> {
     fn get(&self, key: &Key) -> Option<Vec<u8>> {
         let key = self.state_key(key)?;
 
@@ -84,9 +273,8 @@ impl Storage for RuntimeCompatStore<'_, '_> {
 
         let old = self
             .inner
-            .has(key)
-            .ok()?
-            .then(|| self.inner.get(key).ok().flatten())
+            .get(key)
+            .ok()
             .flatten()
             .map(|slice| slice.into_boxed().into_vec());
 
@@ -103,3 +291,74 @@ impl Storage for RuntimeCompatStore<'_, '_> {
         self.inner.has(key).unwrap_or(false)
     }
 }
+
+// resolves the queries of an execution against the proxy contract of the context
+//
+// executions are synchronous, so each query blocks the worker thread running
+// the execution until the chain responds, which needs a multi-threaded tokio
+// runtime (`rt-multi-thread`) for the rest of the node to carry on meanwhile
+#[derive(Debug)]
+pub struct ProxyCompat<'a> {
+    ctx_manager: &'a ContextManager,
+    context_id: ContextId,
+}
+
+impl<'a> ProxyCompat<'a> {
+    pub const fn new(ctx_manager: &'a ContextManager, context_id: ContextId) -> Self {
+        Self {
+            ctx_manager,
+            context_id,
+        }
+    }
+
+    async fn resolve(&self, query: &ProxyQuery) -> EyreResult<Option<Vec<u8>>> {
+        match query {
+            ProxyQuery::Proposal(proposal_id) => {
+                let proposal = self
+                    .ctx_manager
+                    .find_proposal(
+                        self.context_id,
+                        proposal_id.rt().expect("infallible conversion"),
+                    )
+                    .await?;
+
+                Ok(proposal.map(|proposal| to_vec(&proposal)).transpose()?)
+            }
+            ProxyQuery::Approvers(proposal_id) => {
+                let approvers = self
+                    .ctx_manager
+                    .get_proposal_approvers(
+                        self.context_id,
+                        proposal_id.rt().expect("infallible conversion"),
+                    )
+                    .await?;
+
+                Ok(Some(to_vec(&approvers)?))
+            }
+            ProxyQuery::ContextValue(key) => {
+                let value = self
+                    .ctx_manager
+                    .get_context_value(self.context_id, key.clone())
+                    .await?;
+
+                // the proxy doesn't distinguish unset values from empty ones
+                Ok((!value.is_empty()).then_some(value))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Proxy for ProxyCompat<'_> {
+    fn query(&mut self, query: &ProxyQuery) -> Result<Option<Vec<u8>>, String> {
+        let handle = Handle::try_current().map_err(|err| err.to_string())?;
+
+        // `block_in_place` would panic on a current-thread runtime, which
+        // couldn't make progress on the query while blocked on it anyway
+        if handle.runtime_flavor() != RuntimeFlavor::MultiThread {
+            return Err("proxy queries require a multi-threaded runtime".to_owned());
+        }
+
+        block_in_place(|| handle.block_on(self.resolve(query))).map_err(|err| err.to_string())
+    }
+}
@@ -3,14 +3,15 @@ use std::time::Duration;
 use calimero_crypto::{Nonce, SharedKey};
 use calimero_network::stream::{Message, Stream};
 use calimero_primitives::context::ContextId;
+use calimero_primitives::identity::PublicKey;
 use eyre::{bail, eyre, OptionExt, Result as EyreResult};
 use futures_util::{SinkExt, StreamExt};
 use libp2p::gossipsub::TopicHash;
 use libp2p::PeerId;
 use rand::seq::{IteratorRandom, SliceRandom};
 use rand::thread_rng;
-use tokio::time::timeout;
-use tracing::{debug, error};
+use tokio::time::{timeout, Instant};
+use tracing::{debug, error, info_span, Instrument};
 
 use crate::types::{InitPayload, StreamMessage};
 use crate::Node;
@@ -23,6 +24,10 @@ mod state;
 pub struct SyncConfig {
     pub timeout: Duration,
     pub interval: Duration,
+    /// How often to poll for changes to proposals, zero disables polling.
+    pub proposals_interval: Duration,
+    /// How long entities received before their parents are held for.
+    pub orphan_expiry: Duration,
 }
 
 async fn send(
@@ -30,7 +35,7 @@ async fn send(
     message: &StreamMessage<'_>,
     shared_key: Option<(SharedKey, Nonce)>,
 ) -> EyreResult<()> {
-    let base_data = borsh::to_vec(message)?;
+    let base_data = message.to_bytes()?;
 
     let data = match shared_key {
         Some((key, nonce)) => key
@@ -69,7 +74,7 @@ async fn recv(
         None => message_data,
     };
 
-    let decoded = borsh::from_slice::<StreamMessage<'static>>(&data)?;
+    let decoded = StreamMessage::from_bytes(&data)?;
 
     Ok(Some(decoded))
 }
@@ -110,6 +115,23 @@ impl Node {
         &self,
         context_id: ContextId,
         chosen_peer: PeerId,
+    ) -> EyreResult<()> {
+        let started = Instant::now();
+
+        let result = self
+            .internal_initiate_sync(context_id, chosen_peer)
+            .instrument(info_span!("initiate_sync", %context_id, peer = %chosen_peer))
+            .await;
+
+        self.metrics.record_sync(started.elapsed(), result.is_ok());
+
+        result
+    }
+
+    async fn internal_initiate_sync(
+        &self,
+        context_id: ContextId,
+        chosen_peer: PeerId,
     ) -> EyreResult<()> {
         let mut context = self.ctx_manager.sync_context_config(context_id).await?;
 
@@ -164,19 +186,39 @@ impl Node {
             return Ok(None);
         };
 
-        let (context_id, their_identity, payload, nonce) = match message {
+        let (context_id, their_identity, payload, nonce, trace) = match message {
             StreamMessage::Init {
                 context_id,
                 party_id,
                 payload,
                 next_nonce,
-                ..
-            } => (context_id, party_id, payload, next_nonce),
+                trace,
+            } => (context_id, party_id, payload, next_nonce, trace),
             unexpected @ (StreamMessage::Message { .. } | StreamMessage::OpaqueError) => {
                 bail!("expected initialization handshake, got {:?}", unexpected)
             }
         };
 
+        // continues the trace of the peer that opened the stream
+        let span = info_span!("handle_sync", %context_id, party = %their_identity);
+
+        trace.attach(&span);
+
+        self.handle_stream_init(context_id, their_identity, payload, nonce, stream)
+            .instrument(span)
+            .await?;
+
+        Ok(Some(()))
+    }
+
+    async fn handle_stream_init(
+        &self,
+        context_id: ContextId,
+        their_identity: PublicKey,
+        payload: InitPayload,
+        nonce: Nonce,
+        stream: &mut Stream,
+    ) -> EyreResult<()> {
         let Some(mut context) = self.ctx_manager.get_context(&context_id)? else {
             bail!("context not found: {}", context_id);
         };
@@ -247,7 +289,7 @@ impl Node {
             }
         };
 
-        Ok(Some(()))
+        Ok(())
     }
 
     pub async fn perform_interval_sync(&self) {
@@ -11,7 +11,7 @@ use tokio::sync::mpsc;
 use tracing::{debug, warn};
 
 use super::{recv, send, Sequencer};
-use crate::types::{InitPayload, MessagePayload, StreamMessage};
+use crate::types::{InitPayload, MessagePayload, StreamMessage, TraceContext};
 use crate::Node;
 
 impl Node {
@@ -39,6 +39,7 @@ impl Node {
                 party_id: our_identity,
                 payload: InitPayload::BlobShare { blob_id },
                 next_nonce: our_nonce,
+                trace: TraceContext::current(),
             },
             None,
         )
@@ -186,6 +187,7 @@ impl Node {
                 party_id: our_identity,
                 payload: InitPayload::BlobShare { blob_id },
                 next_nonce: our_nonce,
+                trace: TraceContext::current(),
             },
             None,
         )
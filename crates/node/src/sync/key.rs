@@ -7,7 +7,7 @@ use rand::{thread_rng, Rng};
 use tracing::debug;
 
 use crate::sync::{recv, send, Sequencer};
-use crate::types::{InitPayload, MessagePayload, StreamMessage};
+use crate::types::{InitPayload, MessagePayload, StreamMessage, TraceContext};
 use crate::Node;
 
 impl Node {
@@ -32,6 +32,7 @@ impl Node {
                 party_id: our_identity,
                 payload: InitPayload::KeyShare,
                 next_nonce: our_nonce,
+                trace: TraceContext::current(),
             },
             None,
         )
@@ -89,6 +90,7 @@ impl Node {
                 party_id: our_identity,
                 payload: InitPayload::KeyShare,
                 next_nonce: our_nonce,
+                trace: TraceContext::current(),
             },
             None,
         )
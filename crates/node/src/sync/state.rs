@@ -11,7 +11,7 @@ use rand::{thread_rng, Rng};
 use tracing::debug;
 
 use crate::sync::{recv, send, Sequencer};
-use crate::types::{InitPayload, MessagePayload, StreamMessage};
+use crate::types::{InitPayload, MessagePayload, StreamMessage, TraceContext};
 use crate::Node;
 
 impl Node {
@@ -41,6 +41,7 @@ impl Node {
                     application_id: context.application_id,
                 },
                 next_nonce: our_nonce,
+                trace: TraceContext::current(),
             },
             None,
         )
@@ -219,6 +220,7 @@ impl Node {
                     application_id: context.application_id,
                 },
                 next_nonce: our_nonce,
+                trace: TraceContext::current(),
             },
             None,
         )
@@ -306,12 +308,15 @@ impl Node {
                 break;
             }
 
+            // the changes the peer sends are held to the guards of the state
+            // for their identity, not ours
             let outcome = self
                 .execute(
                     context,
                     "__calimero_sync_next",
                     artifact.into_owned(),
-                    our_identity,
+                    their_identity,
+                    None,
                 )
                 .await?
                 .ok_or_eyre("the application was not found??")?;
@@ -0,0 +1,64 @@
+use prometheus_client::encoding::text::encode;
+
+use super::*;
+
+fn encoded(registry: &Registry) -> String {
+    let mut text = String::new();
+
+    encode(&mut text, registry).unwrap();
+
+    text
+}
+
+#[test]
+fn test_metrics_record_syncs() {
+    let mut registry = Registry::default();
+
+    let metrics = Metrics::new(&mut registry);
+
+    metrics.record_sync(Duration::from_millis(500), true);
+    metrics.record_sync(Duration::from_secs(2), false);
+
+    let text = encoded(&registry);
+
+    for line in [
+        "sync_attempts_total 2",
+        "sync_successes_total 1",
+        "sync_failures_total 1",
+        "sync_duration_seconds_sum 2.5",
+        "sync_duration_seconds_count 2",
+    ] {
+        assert!(text.lines().any(|l| l == line), "{line:?} not in {text}");
+    }
+}
+
+#[test]
+fn test_metrics_record_executions_per_application() {
+    let mut registry = Registry::default();
+
+    let metrics = Metrics::new(&mut registry);
+
+    let first = ApplicationId::from([1; 32]);
+    let second = ApplicationId::from([2; 32]);
+
+    metrics.record_execution(first, Duration::from_millis(500), true);
+    metrics.record_execution(first, Duration::from_secs(2), false);
+    metrics.record_execution(second, Duration::from_millis(500), true);
+
+    let text = encoded(&registry);
+
+    for line in [
+        format!("execution_calls_total{{application_id=\"{first}\"}} 2"),
+        format!("execution_failures_total{{application_id=\"{first}\"}} 1"),
+        format!("execution_duration_seconds_sum{{application_id=\"{first}\"}} 2.5"),
+        format!("execution_calls_total{{application_id=\"{second}\"}} 1"),
+        format!("execution_duration_seconds_count{{application_id=\"{second}\"}} 1"),
+    ] {
+        assert!(text.lines().any(|l| l == line), "{line:?} not in {text}");
+    }
+
+    // only the applications that failed are counted among the failures
+    assert!(!text.contains(&format!(
+        "execution_failures_total{{application_id=\"{second}\"}}"
+    )));
+}
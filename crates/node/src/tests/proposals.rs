@@ -0,0 +1,119 @@
+use calimero_context_config::repr::{Repr, ReprTransmute};
+
+use super::*;
+
+fn proposal_id(byte: u8) -> ProposalId {
+    [byte; 32].rt().unwrap()
+}
+
+fn identity(byte: u8) -> ContextIdentity {
+    [byte; 32].rt().unwrap()
+}
+
+fn pending(id: u8, author: u8, approvers: &[u8]) -> (ProposalId, (Proposal, Vec<ContextIdentity>)) {
+    let proposal = Proposal {
+        id: Repr::new(proposal_id(id)),
+        author_id: Repr::new([author; 32].rt().unwrap()),
+        actions: vec![],
+    };
+
+    let approvers = approvers.iter().copied().map(identity).collect();
+
+    (proposal_id(id), (proposal, approvers))
+}
+
+#[test]
+fn new_proposals_are_reported_with_their_approvals() {
+    let tracked = BTreeMap::new();
+    let current = BTreeMap::from([pending(1, 10, &[11])]);
+
+    let events = proposal_events(&tracked, &current, &mut BTreeSet::new());
+
+    let [(author, created), (_, approved)] = &events[..] else {
+        panic!("unexpected events: {events:?}");
+    };
+
+    assert_eq!(author.as_bytes(), [10; 32]);
+
+    let ContextEventPayload::ProposalCreated(created) = created else {
+        panic!("unexpected event: {created:?}");
+    };
+
+    assert_eq!(*created.proposal_id, [1; 32]);
+    assert_eq!(*created.author_id, [10; 32]);
+
+    let ContextEventPayload::ApprovalAdded(approved) = approved else {
+        panic!("unexpected event: {approved:?}");
+    };
+
+    assert_eq!(*approved.approver_id, [11; 32]);
+    assert_eq!(approved.approvals, 1);
+}
+
+#[test]
+fn only_new_approvals_are_reported() {
+    let tracked = track(BTreeMap::from([pending(1, 10, &[11])]));
+    let current = BTreeMap::from([pending(1, 10, &[11, 12])]);
+
+    let events = proposal_events(&tracked, &current, &mut BTreeSet::new());
+
+    let [(_, ContextEventPayload::ApprovalAdded(approved))] = &events[..] else {
+        panic!("unexpected events: {events:?}");
+    };
+
+    assert_eq!(*approved.proposal_id, [1; 32]);
+    assert_eq!(*approved.approver_id, [12; 32]);
+    assert_eq!(approved.approvals, 2);
+}
+
+#[test]
+fn unchanged_proposals_are_not_reported() {
+    let tracked = track(BTreeMap::from([pending(1, 10, &[11])]));
+    let current = BTreeMap::from([pending(1, 10, &[11])]);
+
+    let events = proposal_events(&tracked, &current, &mut BTreeSet::new());
+
+    assert!(events.is_empty(), "unexpected events: {events:?}");
+}
+
+#[test]
+fn removed_proposals_are_reported_as_executed() {
+    let tracked = track(BTreeMap::from([pending(1, 10, &[11])]));
+    let current = BTreeMap::new();
+
+    let events = proposal_events(&tracked, &current, &mut BTreeSet::new());
+
+    let [(author, ContextEventPayload::ProposalExecuted(executed))] = &events[..] else {
+        panic!("unexpected events: {events:?}");
+    };
+
+    assert_eq!(author.as_bytes(), [10; 32]);
+    assert_eq!(*executed.proposal_id, [1; 32]);
+}
+
+#[test]
+fn removed_proposals_known_to_be_deleted_are_reported_as_deleted() {
+    let tracked = track(BTreeMap::from([pending(1, 10, &[]), pending(2, 10, &[])]));
+    let current = BTreeMap::new();
+
+    let mut deleted = BTreeSet::from([proposal_id(2)]);
+
+    let events = proposal_events(&tracked, &current, &mut deleted);
+
+    let [(_, executed), (_, removed)] = &events[..] else {
+        panic!("unexpected events: {events:?}");
+    };
+
+    let ContextEventPayload::ProposalExecuted(executed) = executed else {
+        panic!("unexpected event: {executed:?}");
+    };
+
+    let ContextEventPayload::ProposalDeleted(removed) = removed else {
+        panic!("unexpected event: {removed:?}");
+    };
+
+    assert_eq!(*executed.proposal_id, [1; 32]);
+    assert_eq!(*removed.proposal_id, [2; 32]);
+
+    assert!(deleted.is_empty());
+}
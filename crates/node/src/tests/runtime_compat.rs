@@ -0,0 +1,222 @@
+use calimero_store::config::StoreConfig;
+use calimero_store::db::RocksDB;
+use tempdir::TempDir;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+fn open() -> (TempDir, Store) {
+    let dir = TempDir::new("_calimero_node_private").unwrap();
+
+    let config = StoreConfig::new(dir.path().to_owned().try_into().unwrap());
+
+    let store = Store::open::<RocksDB>(&config).unwrap();
+
+    (dir, store)
+}
+
+fn key(bytes: &[u8]) -> Key {
+    bytes.to_vec()
+}
+
+#[test]
+fn private_writes_are_kept_apart_from_state() {
+    let (_dir, mut store) = open();
+
+    let mut private = PrivateCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    assert_eq!(private.set(key(b"secret"), b"hunter2".to_vec()), None);
+    assert!(!private.is_empty());
+
+    private.commit().unwrap();
+
+    let mut state_key = [0; 32];
+    state_key[..6].copy_from_slice(b"secret");
+
+    let handle = store.handle();
+
+    let value = handle
+        .get(&ContextPrivateStateKey::new(CONTEXT_ID.into(), state_key))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(value.value.as_ref(), b"hunter2");
+
+    assert!(!handle
+        .has(&ContextStateKey::new(CONTEXT_ID.into(), state_key))
+        .unwrap());
+
+    let storage = RuntimeCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    assert_eq!(storage.get(&key(b"secret")), None);
+}
+
+#[test]
+fn private_writes_round_trip() {
+    let (_dir, mut store) = open();
+
+    let mut private = PrivateCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    assert!(!private.has(&key(b"secret")));
+    assert_eq!(private.set(key(b"secret"), b"hunter2".to_vec()), None);
+    assert_eq!(
+        private.set(key(b"secret"), b"hunter3".to_vec()),
+        Some(b"hunter2".to_vec())
+    );
+
+    private.commit().unwrap();
+
+    let mut private = PrivateCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    assert!(private.has(&key(b"secret")));
+    assert_eq!(private.get(&key(b"secret")), Some(b"hunter3".to_vec()));
+    assert_eq!(private.remove(&key(b"secret")), Some(b"hunter3".to_vec()));
+    assert_eq!(private.get(&key(b"secret")), None);
+
+    private.commit().unwrap();
+
+    let private = PrivateCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    assert!(!private.has(&key(b"secret")));
+}
+
+#[test]
+fn private_writes_are_scoped_to_their_context() {
+    let (_dir, mut store) = open();
+
+    let mut private = PrivateCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    drop(private.set(key(b"secret"), b"hunter2".to_vec()));
+
+    private.commit().unwrap();
+
+    let other = PrivateCompatStore::new(&mut store, [2; 32].into());
+
+    assert_eq!(other.get(&key(b"secret")), None);
+}
+
+#[test]
+fn uncommitted_private_writes_are_discarded() {
+    let (_dir, mut store) = open();
+
+    let mut private = PrivateCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    drop(private.set(key(b"secret"), b"hunter2".to_vec()));
+
+    drop(private);
+
+    let private = PrivateCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    assert_eq!(private.get(&key(b"secret")), None);
+}
+
+#[test]
+fn long_private_keys_are_refused() {
+    let (_dir, mut store) = open();
+
+    let mut private = PrivateCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    assert_eq!(private.set(vec![0; 33], b"hunter2".to_vec()), None);
+    assert!(!private.has(&vec![0; 33]));
+    assert!(private.is_empty());
+}
+
+fn state_key(bytes: &[u8]) -> [u8; 32] {
+    let mut state_key = [0; 32];
+    state_key[..bytes.len()].copy_from_slice(bytes);
+    state_key
+}
+
+fn record_history(store: &mut Store, root_hash: [u8; 32], bytes: &[u8], value: Option<&[u8]>) {
+    StateWrites::put(
+        store,
+        ContextStateHistoryKey::new(CONTEXT_ID.into(), root_hash, state_key(bytes)),
+        to_vec(&ContextStateHistoryValue::new(value.map(Box::from))).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn historical_reads_resolve_through_the_records() {
+    let (_dir, mut store) = open();
+
+    let mut storage = RuntimeCompatStore::new(&mut store, CONTEXT_ID.into());
+
+    drop(storage.set(key(b"changed"), b"now".to_vec()));
+    drop(storage.set(key(b"created"), b"now".to_vec()));
+    drop(storage.set(key(b"unchanged"), b"always".to_vec()));
+
+    storage.commit().unwrap();
+
+    // root 2 changed an entry and root 3 created one, root 3 being the latest
+    record_history(&mut store, [2; 32], b"changed", Some(b"then"));
+    record_history(&mut store, [2; 32], b"created", None);
+    record_history(&mut store, [3; 32], b"created", Some(b"later"));
+
+    let at_2 =
+        RuntimeCompatStore::historical(&mut store, CONTEXT_ID.into(), vec![[2; 32], [3; 32]]);
+
+    assert_eq!(at_2.get(&key(b"changed")), Some(b"then".to_vec()));
+    assert_eq!(at_2.get(&key(b"created")), None);
+    assert!(!at_2.has(&key(b"created")));
+    assert_eq!(at_2.get(&key(b"unchanged")), Some(b"always".to_vec()));
+
+    drop(at_2);
+
+    let at_3 = RuntimeCompatStore::historical(&mut store, CONTEXT_ID.into(), vec![[3; 32]]);
+
+    assert_eq!(at_3.get(&key(b"changed")), Some(b"now".to_vec()));
+    assert_eq!(at_3.get(&key(b"created")), Some(b"later".to_vec()));
+}
+
+#[test]
+fn historical_writes_read_back_as_written() {
+    let (_dir, mut store) = open();
+
+    record_history(&mut store, [2; 32], b"changed", Some(b"then"));
+
+    let mut storage = RuntimeCompatStore::historical(&mut store, CONTEXT_ID.into(), vec![[2; 32]]);
+
+    assert_eq!(
+        storage.set(key(b"changed"), b"written".to_vec()),
+        Some(b"then".to_vec())
+    );
+    assert_eq!(storage.get(&key(b"changed")), Some(b"written".to_vec()));
+
+    assert_eq!(storage.remove(&key(b"changed")), Some(b"written".to_vec()));
+    assert_eq!(storage.get(&key(b"changed")), None);
+}
+
+#[test]
+fn records_are_committed_along_with_the_state() {
+    let (_dir, mut store) = open();
+
+    let record = ContextStateHistoryKey::new(CONTEXT_ID.into(), [2; 32], state_key(b"changed"));
+    let state = ContextStateKey::new(CONTEXT_ID.into(), state_key(b"changed"));
+
+    for commit in [false, true] {
+        let mut storage = RuntimeCompatStore::new(&mut store, CONTEXT_ID.into());
+
+        drop(storage.set(key(b"changed"), b"now".to_vec()));
+
+        StateWrites::put(
+            &mut storage,
+            record,
+            to_vec(&ContextStateHistoryValue::new(None)).unwrap(),
+        )
+        .unwrap();
+
+        if commit {
+            storage.commit().unwrap();
+        } else {
+            drop(storage);
+        }
+
+        let handle = store.handle();
+
+        // either both are written or neither is
+        assert_eq!(handle.has(&record).unwrap(), commit);
+        assert_eq!(handle.has(&state).unwrap(), commit);
+    }
+}
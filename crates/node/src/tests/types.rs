@@ -0,0 +1,227 @@
+use borsh::to_vec;
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::TracerProvider;
+use tracing::info_span;
+use tracing::subscriber::with_default;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+use super::*;
+
+fn state_delta(trace: TraceContext) -> BroadcastMessage<'static> {
+    BroadcastMessage::StateDelta {
+        context_id: [1; 32].into(),
+        author_id: [2; 32].into(),
+        root_hash: [3; 32].into(),
+        application_id: Some([4; 32].into()),
+        artifact: vec![5; 8].into(),
+        nonce: [6; NONCE_LEN],
+        trace,
+    }
+}
+
+#[test]
+fn test_broadcast_message_round_trip() {
+    let trace = TraceContext(HashMap::from([(
+        "traceparent".to_owned(),
+        "00-1".to_owned(),
+    )]));
+
+    let data = state_delta(trace).to_bytes().unwrap();
+
+    assert_eq!(data[..2], [ENVELOPE, VERSION]);
+
+    let BroadcastMessage::StateDelta {
+        context_id,
+        application_id,
+        artifact,
+        trace,
+        ..
+    } = BroadcastMessage::from_bytes(&data).unwrap()
+    else {
+        panic!("expected a state delta");
+    };
+
+    assert_eq!(context_id, [1; 32].into());
+    assert_eq!(application_id, Some([4; 32].into()));
+    assert_eq!(*artifact, [5; 8]);
+    assert_eq!(trace.0["traceparent"], "00-1");
+
+    let data = BroadcastMessage::ProposalDeleted {
+        context_id: [1; 32].into(),
+        proposal_id: [7; 32].into(),
+    }
+    .to_bytes()
+    .unwrap();
+
+    assert!(matches!(
+        BroadcastMessage::from_bytes(&data).unwrap(),
+        BroadcastMessage::ProposalDeleted { proposal_id, .. } if proposal_id == [7; 32].into()
+    ));
+}
+
+#[test]
+fn test_broadcast_message_of_the_initial_layout() {
+    let data = to_vec(&LegacyBroadcastMessage::StateDelta {
+        context_id: [1; 32].into(),
+        author_id: [2; 32].into(),
+        root_hash: [3; 32].into(),
+        artifact: vec![5; 8].into(),
+        nonce: [6; NONCE_LEN],
+    })
+    .unwrap();
+
+    let BroadcastMessage::StateDelta {
+        author_id,
+        root_hash,
+        application_id,
+        artifact,
+        nonce,
+        trace,
+        ..
+    } = BroadcastMessage::from_bytes(&data).unwrap()
+    else {
+        panic!("expected a state delta");
+    };
+
+    assert_eq!(author_id, [2; 32].into());
+    assert_eq!(root_hash, [3; 32].into());
+    assert_eq!(application_id, None);
+    assert_eq!(*artifact, [5; 8]);
+    assert_eq!(nonce, [6; NONCE_LEN]);
+    assert!(trace.0.is_empty());
+}
+
+#[test]
+fn test_stream_message_of_either_layout() {
+    let data = StreamMessage::Init {
+        context_id: [1; 32].into(),
+        party_id: [2; 32].into(),
+        payload: InitPayload::KeyShare,
+        next_nonce: [3; NONCE_LEN],
+        trace: TraceContext(HashMap::from([(
+            "traceparent".to_owned(),
+            "00-1".to_owned(),
+        )])),
+    }
+    .to_bytes()
+    .unwrap();
+
+    assert!(matches!(
+        StreamMessage::from_bytes(&data).unwrap(),
+        StreamMessage::Init { trace, .. } if trace.0.len() == 1
+    ));
+
+    let data = to_vec(&LegacyStreamMessage::Init {
+        context_id: [1; 32].into(),
+        party_id: [2; 32].into(),
+        payload: InitPayload::BlobShare {
+            blob_id: [4; 32].into(),
+        },
+        next_nonce: [3; NONCE_LEN],
+    })
+    .unwrap();
+
+    assert!(matches!(
+        StreamMessage::from_bytes(&data).unwrap(),
+        StreamMessage::Init {
+            payload: InitPayload::BlobShare { blob_id },
+            next_nonce,
+            trace,
+            ..
+        } if blob_id == [4; 32].into() && next_nonce == [3; NONCE_LEN] && trace.0.is_empty()
+    ));
+
+    let data = to_vec(&LegacyStreamMessage::Message {
+        sequence_id: 5,
+        payload: MessagePayload::BlobShare {
+            chunk: vec![6; 4].into(),
+        },
+        next_nonce: [3; NONCE_LEN],
+    })
+    .unwrap();
+
+    assert!(matches!(
+        StreamMessage::from_bytes(&data).unwrap(),
+        StreamMessage::Message {
+            sequence_id: 5,
+            payload: MessagePayload::BlobShare { chunk },
+            ..
+        } if *chunk == [6; 4]
+    ));
+
+    let data = to_vec(&LegacyStreamMessage::OpaqueError).unwrap();
+
+    assert!(matches!(
+        StreamMessage::from_bytes(&data).unwrap(),
+        StreamMessage::OpaqueError
+    ));
+}
+
+#[test]
+fn test_messages_of_unknown_versions_are_rejected() {
+    let mut data = StreamMessage::OpaqueError.to_bytes().unwrap();
+
+    data[1] = VERSION + 1;
+
+    assert!(StreamMessage::from_bytes(&data).is_err());
+
+    let mut data = state_delta(TraceContext::default()).to_bytes().unwrap();
+
+    data[1] = VERSION + 1;
+
+    assert!(BroadcastMessage::from_bytes(&data).is_err());
+}
+
+#[test]
+fn test_trace_context_continues_the_trace() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let provider = TracerProvider::builder().build();
+
+    let subscriber = Registry::default()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+    with_default(subscriber, || {
+        // outside of any span, there's no trace to continue
+        assert!(TraceContext::current().0.is_empty());
+
+        let sender = info_span!("sender");
+
+        let trace = sender.in_scope(TraceContext::current);
+
+        assert!(trace.0.contains_key("traceparent"));
+
+        // as the trace would arrive at the peer
+        let trace = BroadcastMessage::from_bytes(&state_delta(trace).to_bytes().unwrap())
+            .map(|message| match message {
+                BroadcastMessage::StateDelta { trace, .. } => trace,
+                BroadcastMessage::ProposalDeleted { .. } => unreachable!(),
+            })
+            .unwrap();
+
+        let receiver = info_span!("receiver");
+
+        trace.attach(&receiver);
+
+        let sent = sender.context();
+        let received = receiver.context();
+
+        let sent = sent.span().span_context().clone();
+        let received = received.span().span_context().clone();
+
+        assert!(sent.is_valid());
+        assert_eq!(received.trace_id(), sent.trace_id());
+        assert_ne!(received.span_id(), sent.span_id());
+
+        // a span without a trace of its own to continue starts a new one
+        let unrelated = info_span!("unrelated");
+
+        TraceContext::default().attach(&unrelated);
+
+        let unrelated = unrelated.context();
+
+        assert_ne!(unrelated.span().span_context().trace_id(), sent.trace_id());
+    });
+}
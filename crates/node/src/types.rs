@@ -1,14 +1,47 @@
 #![expect(single_use_lifetimes, reason = "borsh shenanigans")]
 
+#[cfg(test)]
+#[path = "tests/types.rs"]
+mod tests;
+
 use std::borrow::Cow;
+use std::collections::HashMap;
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{from_slice, BorshDeserialize, BorshSerialize};
 use calimero_crypto::{Nonce, NONCE_LEN};
 use calimero_primitives::application::ApplicationId;
 use calimero_primitives::blobs::BlobId;
 use calimero_primitives::context::ContextId;
 use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::{PrivateKey, PublicKey};
+use eyre::{bail, Result as EyreResult};
+use opentelemetry::global;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+// no message of the initial layout, which carried no version, starts with this
+const ENVELOPE: u8 = u8::MAX;
+
+/// The version of the layout messages are sent in, following the envelope
+/// marker, to be bumped whenever the layout changes.
+const VERSION: u8 = 1;
+
+fn encode<T: BorshSerialize>(message: &T) -> EyreResult<Vec<u8>> {
+    let mut data = vec![ENVELOPE, VERSION];
+
+    message.serialize(&mut data)?;
+
+    Ok(data)
+}
+
+// returns the message, unless it's in the initial layout
+fn decode<T: BorshDeserialize>(data: &[u8]) -> EyreResult<Option<T>> {
+    match data {
+        [ENVELOPE, VERSION, message @ ..] => Ok(Some(from_slice(message)?)),
+        [ENVELOPE, version, ..] => bail!("unsupported message version {version}"),
+        _ => Ok(None),
+    }
+}
 
 #[derive(Debug, BorshSerialize, BorshDeserialize)]
 #[non_exhaustive]
@@ -18,18 +51,59 @@ pub enum BroadcastMessage<'a> {
         context_id: ContextId,
         author_id: PublicKey,
         root_hash: Hash,
+        /// Missing from deltas of peers that predate the versioned layout.
+        application_id: Option<ApplicationId>,
         artifact: Cow<'a, [u8]>,
         nonce: [u8; NONCE_LEN],
+        trace: TraceContext,
+    },
+    /// The author of a proposal deleted it, which the proxy keeps no record of.
+    ProposalDeleted {
+        context_id: ContextId,
+        proposal_id: Hash,
     },
 }
 
+impl BroadcastMessage<'_> {
+    pub fn to_bytes(&self) -> EyreResult<Vec<u8>> {
+        encode(self)
+    }
+
+    /// Decodes a message in either the versioned layout or the initial one.
+    pub fn from_bytes(data: &[u8]) -> EyreResult<BroadcastMessage<'static>> {
+        if let Some(message) = decode(data)? {
+            return Ok(message);
+        }
+
+        let LegacyBroadcastMessage::StateDelta {
+            context_id,
+            author_id,
+            root_hash,
+            artifact,
+            nonce,
+        } = from_slice::<LegacyBroadcastMessage<'static>>(data)?;
+
+        Ok(BroadcastMessage::StateDelta {
+            context_id,
+            author_id,
+            root_hash,
+            application_id: None,
+            artifact,
+            nonce,
+            trace: TraceContext::default(),
+        })
+    }
+}
+
 #[derive(Debug, BorshSerialize, BorshDeserialize)]
+#[expect(clippy::large_enum_variant, reason = "Of no consequence here")]
 pub enum StreamMessage<'a> {
     Init {
         context_id: ContextId,
         party_id: PublicKey,
         payload: InitPayload,
         next_nonce: Nonce,
+        trace: TraceContext,
     },
     Message {
         sequence_id: usize,
@@ -40,6 +114,46 @@ pub enum StreamMessage<'a> {
     OpaqueError,
 }
 
+impl StreamMessage<'_> {
+    pub fn to_bytes(&self) -> EyreResult<Vec<u8>> {
+        encode(self)
+    }
+
+    /// Decodes a message in either the versioned layout or the initial one.
+    pub fn from_bytes(data: &[u8]) -> EyreResult<StreamMessage<'static>> {
+        if let Some(message) = decode(data)? {
+            return Ok(message);
+        }
+
+        let message = match from_slice::<LegacyStreamMessage<'static>>(data)? {
+            LegacyStreamMessage::Init {
+                context_id,
+                party_id,
+                payload,
+                next_nonce,
+            } => StreamMessage::Init {
+                context_id,
+                party_id,
+                payload,
+                next_nonce,
+                trace: TraceContext::default(),
+            },
+            LegacyStreamMessage::Message {
+                sequence_id,
+                payload,
+                next_nonce,
+            } => StreamMessage::Message {
+                sequence_id,
+                payload,
+                next_nonce,
+            },
+            LegacyStreamMessage::OpaqueError => StreamMessage::OpaqueError,
+        };
+
+        Ok(message)
+    }
+}
+
 #[derive(Copy, Clone, Debug, BorshSerialize, BorshDeserialize)]
 pub enum InitPayload {
     BlobShare {
@@ -59,3 +173,58 @@ pub enum MessagePayload<'a> {
     BlobShare { chunk: Cow<'a, [u8]> },
     KeyShare { sender_key: PrivateKey },
 }
+
+/// The trace a message was sent in, so that the peer handling it continues
+/// the same trace.
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize)]
+pub struct TraceContext(HashMap<String, String>);
+
+impl TraceContext {
+    #[must_use]
+    pub fn current() -> Self {
+        let mut carrier = HashMap::new();
+
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&Span::current().context(), &mut carrier);
+        });
+
+        Self(carrier)
+    }
+
+    /// Makes the span a continuation of the trace, if there was one.
+    pub fn attach(&self, span: &Span) {
+        let parent = global::get_text_map_propagator(|propagator| propagator.extract(&self.0));
+
+        span.set_parent(parent);
+    }
+}
+
+/// A [`BroadcastMessage`] of a peer that predates the versioned layout.
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
+enum LegacyBroadcastMessage<'a> {
+    StateDelta {
+        context_id: ContextId,
+        author_id: PublicKey,
+        root_hash: Hash,
+        artifact: Cow<'a, [u8]>,
+        nonce: [u8; NONCE_LEN],
+    },
+}
+
+/// A [`StreamMessage`] of a peer that predates the versioned layout.
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
+#[expect(clippy::large_enum_variant, reason = "Of no consequence here")]
+enum LegacyStreamMessage<'a> {
+    Init {
+        context_id: ContextId,
+        party_id: PublicKey,
+        payload: InitPayload,
+        next_nonce: Nonce,
+    },
+    Message {
+        sequence_id: usize,
+        payload: MessagePayload<'a>,
+        next_nonce: Nonce,
+    },
+    OpaqueError,
+}
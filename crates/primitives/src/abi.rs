@@ -0,0 +1,313 @@
+#[cfg(test)]
+#[path = "tests/abi.rs"]
+mod tests;
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{from_slice as from_json_slice, Error as JsonError, Value};
+use thiserror::Error as ThisError;
+
+/// The custom wasm section that `calimero_sdk` macros write the ABI into.
+///
+/// Each macro invocation contributes its own fragments, one JSON-encoded
+/// [`AbiEntry`] per line, and the linker concatenates them.
+pub const ABI_SECTION: &str = "calimero_abi";
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Abi {
+    pub methods: Vec<AbiMethod>,
+    pub events: Vec<AbiEvent>,
+    /// JSON Schemas of the types declared with `#[app::schema]`, by name,
+    /// which the other schemas refer to as `#/types/<name>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub types: BTreeMap<String, Value>,
+    /// The fields of the state declared with `#[app::guard]`, which the
+    /// guards are enforced on for every change, wherever it comes from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<AbiField>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AbiMethod {
+    pub name: String,
+    pub arguments: Vec<AbiArgument>,
+    /// JSON Schema of the returned value, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub returns: Option<Value>,
+    /// JSON Schema of the error, if the method returns a `Result`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+    /// Whether the method takes `&mut self`, and so may change the state.
+    #[serde(default)]
+    pub mutates: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<AbiModifier>,
+    /// The guards a caller must pass, all of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guards: Vec<AbiGuard>,
+}
+
+impl AbiMethod {
+    /// The capabilities the guards require the caller to hold.
+    pub fn capabilities(&self) -> impl Iterator<Item = AbiCapability> + '_ {
+        self.guards.iter().filter_map(|guard| match guard {
+            AbiGuard::Capability(capability) => Some(*capability),
+            AbiGuard::State(_) => None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AbiField {
+    pub name: String,
+    /// The guards an executor must pass to change the field, all of them.
+    pub guards: Vec<AbiGuard>,
+}
+
+impl AbiField {
+    /// The capabilities the guards require the executor to hold.
+    pub fn capabilities(&self) -> impl Iterator<Item = AbiCapability> + '_ {
+        self.guards.iter().filter_map(|guard| match guard {
+            AbiGuard::Capability(capability) => Some(*capability),
+            AbiGuard::State(_) => None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AbiArgument {
+    pub name: String,
+    /// JSON Schema of the argument.
+    pub schema: Value,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum AbiModifier {
+    Init,
+    Destroy,
+    Migrate,
+}
+
+/// A condition on calling a method, declared with `#[app::guard]`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum AbiGuard {
+    /// The caller must hold the capability in the context config.
+    Capability(AbiCapability),
+    /// The caller must pass the named check against the state.
+    State(String),
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum AbiCapability {
+    ManageApplication,
+    ManageMembers,
+    Proxy,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AbiEvent {
+    pub name: String,
+    pub variants: Vec<AbiEventVariant>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AbiEventVariant {
+    /// The `kind` the event is emitted with.
+    pub kind: String,
+    /// JSON Schema of the event data, if the variant carries any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AbiType {
+    pub name: String,
+    /// JSON Schema of the type, as it is serialised to JSON.
+    pub schema: Value,
+}
+
+/// A single fragment of the ABI section.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum AbiEntry {
+    Method(AbiMethod),
+    Event(AbiEvent),
+    Type(AbiType),
+    Field(AbiField),
+}
+
+#[derive(Debug, ThisError)]
+#[non_exhaustive]
+pub enum AbiError {
+    #[error("invalid wasm module")]
+    InvalidModule,
+    #[error("invalid ABI entry: {0}")]
+    InvalidEntry(#[from] JsonError),
+}
+
+impl Abi {
+    /// Extracts the ABI embedded in a wasm module, if it has one.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the module is malformed, or the ABI section can't be decoded.
+    pub fn from_wasm(wasm: &[u8]) -> Result<Option<Self>, AbiError> {
+        let mut abi = None;
+
+        for section in custom_sections(wasm, ABI_SECTION)? {
+            let abi = abi.get_or_insert_with(Self::default);
+
+            for line in section.split(|&byte| byte == b'\n') {
+                if line.is_empty() {
+                    continue;
+                }
+
+                match from_json_slice(line)? {
+                    AbiEntry::Method(method) => abi.methods.push(method),
+                    AbiEntry::Event(event) => abi.events.push(event),
+                    AbiEntry::Type(ty) => drop(abi.types.insert(ty.name, ty.schema)),
+                    AbiEntry::Field(field) => abi.fields.push(field),
+                }
+            }
+        }
+
+        if let Some(abi) = &mut abi {
+            abi.resolve_types();
+        }
+
+        Ok(abi)
+    }
+
+    // types used without being declared are referred to all the same, so
+    // those references are replaced by their title, as the schema is unknown
+    fn resolve_types(&mut self) {
+        let types = self.types.keys().cloned().collect::<Vec<_>>();
+
+        let schemas = self
+            .methods
+            .iter_mut()
+            .flat_map(|method| {
+                method
+                    .arguments
+                    .iter_mut()
+                    .map(|argument| &mut argument.schema)
+                    .chain(&mut method.returns)
+                    .chain(&mut method.error)
+            })
+            .chain(
+                self.events
+                    .iter_mut()
+                    .flat_map(|event| &mut event.variants)
+                    .filter_map(|variant| variant.data.as_mut()),
+            )
+            .chain(self.types.values_mut());
+
+        for schema in schemas {
+            resolve_type(schema, &types);
+        }
+    }
+
+    #[must_use]
+    pub fn method(&self, name: &str) -> Option<&AbiMethod> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+}
+
+fn resolve_type(schema: &mut Value, types: &[String]) {
+    match schema {
+        Value::Object(object) => {
+            let undeclared = object
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix("#/types/"))
+                .filter(|name| !types.iter().any(|ty| ty == name))
+                .map(str::to_owned);
+
+            if let Some(name) = undeclared {
+                drop(object.remove("$ref"));
+                drop(object.insert("title".to_owned(), Value::String(name)));
+            }
+
+            for value in object.values_mut() {
+                resolve_type(value, types);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                resolve_type(value, types);
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+}
+
+fn custom_sections<'a>(wasm: &'a [u8], name: &str) -> Result<Vec<&'a [u8]>, AbiError> {
+    let mut rest = wasm
+        .strip_prefix(b"\0asm\x01\0\0\0")
+        .ok_or(AbiError::InvalidModule)?;
+
+    let mut sections = vec![];
+
+    while let Some((&id, tail)) = rest.split_first() {
+        let (size, tail) = read_leb128(tail)?;
+
+        let (contents, tail) = tail.split_at_checked(size).ok_or(AbiError::InvalidModule)?;
+
+        rest = tail;
+
+        // custom sections are the only ones with id 0
+        if id != 0 {
+            continue;
+        }
+
+        let (len, contents) = read_leb128(contents)?;
+
+        let (section_name, data) = contents
+            .split_at_checked(len)
+            .ok_or(AbiError::InvalidModule)?;
+
+        if section_name == name.as_bytes() {
+            sections.push(data);
+        }
+    }
+
+    Ok(sections)
+}
+
+fn read_leb128(bytes: &[u8]) -> Result<(usize, &[u8]), AbiError> {
+    let mut value = 0_usize;
+
+    for (idx, &byte) in bytes.iter().enumerate().take(5) {
+        value |= usize::from(byte & 0x7f) << (idx.wrapping_mul(7));
+
+        if byte & 0x80 == 0 {
+            let rest = bytes.get(idx.wrapping_add(1)..).unwrap_or_default();
+
+            return Ok((value, rest));
+        }
+    }
+
+    Err(AbiError::InvalidModule)
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::context::ContextId;
 use crate::hash::Hash;
+use crate::identity::PublicKey;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -15,6 +16,9 @@ pub enum NodeEvent {
 #[non_exhaustive]
 pub struct ContextEvent {
     pub context_id: ContextId,
+    /// The identity the execution that produced the event was made as.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executor_id: Option<PublicKey>,
     #[serde(flatten)]
     pub payload: ContextEventPayload,
 }
@@ -24,18 +28,45 @@ impl ContextEvent {
     pub const fn new(context_id: ContextId, payload: ContextEventPayload) -> Self {
         Self {
             context_id,
+            executor_id: None,
             payload,
         }
     }
+
+    #[must_use]
+    pub const fn with_executor(mut self, executor_id: PublicKey) -> Self {
+        self.executor_id = Some(executor_id);
+        self
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(tag = "type", content = "data", rename_all = "PascalCase")]
 #[non_exhaustive]
-#[expect(variant_size_differences, reason = "fine for now")]
 pub enum ContextEventPayload {
     StateMutation(StateMutationPayload),
     ExecutionEvent(ExecutionEventPayload),
+    EntitiesChanged(EntitiesChangedPayload),
+    ProposalCreated(ProposalCreatedPayload),
+    ApprovalAdded(ApprovalAddedPayload),
+    ProposalExecuted(ProposalExecutedPayload),
+    ProposalDeleted(ProposalDeletedPayload),
+}
+
+impl ContextEventPayload {
+    /// The name of the payload's type, as it's tagged when serialized.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::StateMutation(_) => "StateMutation",
+            Self::ExecutionEvent(_) => "ExecutionEvent",
+            Self::EntitiesChanged(_) => "EntitiesChanged",
+            Self::ProposalCreated(_) => "ProposalCreated",
+            Self::ApprovalAdded(_) => "ApprovalAdded",
+            Self::ProposalExecuted(_) => "ProposalExecuted",
+            Self::ProposalDeleted(_) => "ProposalDeleted",
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -79,3 +110,124 @@ impl ExecutionEventPayload {
         Self { events }
     }
 }
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "These are the only kinds of change"
+)]
+pub enum EntityChangeKind {
+    Added,
+    Updated,
+    Deleted,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EntityChange {
+    pub id: Hash,
+    pub kind: EntityChangeKind,
+    pub collection: Option<Hash>,
+    // the ancestors of the entity, from the root down to its collection
+    pub path: Vec<Hash>,
+}
+
+impl EntityChange {
+    #[must_use]
+    pub const fn new(
+        id: Hash,
+        kind: EntityChangeKind,
+        collection: Option<Hash>,
+        path: Vec<Hash>,
+    ) -> Self {
+        Self {
+            id,
+            kind,
+            collection,
+            path,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EntitiesChangedPayload {
+    pub changes: Vec<EntityChange>,
+}
+
+impl EntitiesChangedPayload {
+    #[must_use]
+    pub const fn new(changes: Vec<EntityChange>) -> Self {
+        Self { changes }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ProposalCreatedPayload {
+    pub proposal_id: Hash,
+    pub author_id: PublicKey,
+}
+
+impl ProposalCreatedPayload {
+    #[must_use]
+    pub const fn new(proposal_id: Hash, author_id: PublicKey) -> Self {
+        Self {
+            proposal_id,
+            author_id,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ApprovalAddedPayload {
+    pub proposal_id: Hash,
+    pub approver_id: PublicKey,
+    /// The number of approvals the proposal has gathered so far.
+    pub approvals: usize,
+}
+
+impl ApprovalAddedPayload {
+    #[must_use]
+    pub const fn new(proposal_id: Hash, approver_id: PublicKey, approvals: usize) -> Self {
+        Self {
+            proposal_id,
+            approver_id,
+            approvals,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ProposalExecutedPayload {
+    pub proposal_id: Hash,
+}
+
+impl ProposalExecutedPayload {
+    #[must_use]
+    pub const fn new(proposal_id: Hash) -> Self {
+        Self { proposal_id }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ProposalDeletedPayload {
+    pub proposal_id: Hash,
+}
+
+impl ProposalDeletedPayload {
+    #[must_use]
+    pub const fn new(proposal_id: Hash) -> Self {
+        Self { proposal_id }
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(test)]
+#[path = "tests/identity.rs"]
+mod tests;
+
 use core::fmt;
 use core::ops::Deref;
 use core::str::FromStr;
@@ -198,11 +202,14 @@ pub struct ClientKey {
     pub signing_key: String,
     pub created_at: u64,
     pub context_id: Option<ContextId>,
+    /// What the key, and the tokens issued to it, may be used for.
+    #[serde(default)]
+    pub scopes: Scopes,
 }
 
 impl ClientKey {
     #[must_use]
-    pub const fn new(
+    pub fn new(
         wallet_type: WalletType,
         signing_key: String,
         created_at: u64,
@@ -213,8 +220,169 @@ impl ClientKey {
             signing_key,
             created_at,
             context_id,
+            scopes: Scopes::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_scopes(mut self, scopes: Scopes) -> Self {
+        self.scopes = scopes;
+        self
+    }
+}
+
+/// A long-lived key a service authenticates with in place of a wallet.
+///
+/// Only the hash of its secret is kept, which doubles as its id.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ApiKey {
+    pub id: Hash,
+    pub name: String,
+    pub scopes: Scopes,
+    pub created_at: u64,
+    /// When the key stops being accepted, never when absent.
+    pub expires_at: Option<u64>,
+    pub last_used_at: Option<u64>,
+}
+
+impl ApiKey {
+    #[must_use]
+    pub const fn new(
+        id: Hash,
+        name: String,
+        scopes: Scopes,
+        created_at: u64,
+        expires_at: Option<u64>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            scopes,
+            created_at,
+            expires_at,
+            last_used_at: None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// What a client may do. Unless narrowed down, it may do anything, as keys
+/// registered before scopes existed could.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Scopes {
+    pub role: Role,
+    /// The contexts that may be interacted with, any of them when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_ids: Option<Vec<ContextId>>,
+    /// Patterns of the methods that may be called, any of them when absent,
+    /// where `*` matches any run of characters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub methods: Option<Vec<String>>,
+    /// Whether nothing may be modified, calls that would change the state
+    /// being rejected, as are admin routes that modify anything.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl Default for Scopes {
+    fn default() -> Self {
+        Self {
+            role: Role::Admin,
+            context_ids: None,
+            methods: None,
+            read_only: false,
+        }
+    }
+}
+
+impl Scopes {
+    #[must_use]
+    pub const fn new(
+        role: Role,
+        context_ids: Option<Vec<ContextId>>,
+        methods: Option<Vec<String>>,
+        read_only: bool,
+    ) -> Self {
+        Self {
+            role,
+            context_ids,
+            methods,
+            read_only,
         }
     }
+
+    #[must_use]
+    pub fn permits_context(&self, context_id: &ContextId) -> bool {
+        self.context_ids
+            .as_ref()
+            .map_or(true, |context_ids| context_ids.contains(context_id))
+    }
+
+    #[must_use]
+    pub fn permits_method(&self, method: &str) -> bool {
+        self.methods.as_ref().map_or(true, |patterns| {
+            patterns
+                .iter()
+                .any(|pattern| matches_pattern(pattern, method))
+        })
+    }
+
+    /// Whether the method may be called on the context, which, when the
+    /// scopes are read-only, is only as long as it leaves the state as it is.
+    #[must_use]
+    pub fn permits_call(&self, context_id: &ContextId, method: &str) -> bool {
+        self.permits_context(context_id) && self.permits_method(method)
+    }
+
+    /// Whether the admin API may be used, where `modifies` is whether the
+    /// route modifies anything.
+    #[must_use]
+    pub fn permits_admin(&self, modifies: bool) -> bool {
+        self.role == Role::Admin && !(modifies && self.read_only)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Role {
+    /// The admin API, besides everything an application may do.
+    Admin,
+    /// Only the JSON-RPC and WebSocket APIs applications are used through.
+    Application,
+}
+
+fn matches_pattern(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+
+    let Some(mut rest) = parts.next().and_then(|prefix| value.strip_prefix(prefix)) else {
+        return false;
+    };
+
+    let parts = parts.collect::<Vec<_>>();
+
+    let Some((suffix, middle)) = parts.split_last() else {
+        // without a wildcard, the pattern is matched exactly
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        let Some((_, after)) = rest.split_once(part) else {
+            return false;
+        };
+
+        rest = after;
+    }
+
+    rest.ends_with(suffix)
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
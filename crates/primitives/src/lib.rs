@@ -1,3 +1,4 @@
+pub mod abi;
 pub mod application;
 pub mod blobs;
 pub mod common;
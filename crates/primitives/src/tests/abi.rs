@@ -0,0 +1,173 @@
+use serde_json::json;
+
+use super::*;
+
+fn leb128(mut value: usize, buf: &mut Vec<u8>) {
+    loop {
+        let byte = u8::try_from(value & 0x7f).unwrap();
+        value >>= 7_u32;
+
+        if value == 0 {
+            buf.push(byte);
+            break;
+        }
+
+        buf.push(byte | 0x80);
+    }
+}
+
+fn module(sections: &[(u8, &[u8])]) -> Vec<u8> {
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+
+    for &(id, contents) in sections {
+        wasm.push(id);
+        leb128(contents.len(), &mut wasm);
+        wasm.extend_from_slice(contents);
+    }
+
+    wasm
+}
+
+fn custom(name: &str, data: &str) -> Vec<u8> {
+    let mut section = vec![];
+    leb128(name.len(), &mut section);
+    section.extend_from_slice(name.as_bytes());
+    section.extend_from_slice(data.as_bytes());
+    section
+}
+
+#[test]
+fn test_abi_from_wasm() {
+    let logic = custom(
+        ABI_SECTION,
+        concat!(
+            r#"{"method":{"name":"set","arguments":[{"name":"key","schema":{"type":"string"}}],"mutates":true}}"#,
+            "\n",
+            r#"{"method":{"name":"init","arguments":[],"modifiers":["init"]}}"#,
+            "\n",
+            r#"{"method":{"name":"grant","arguments":[],"guards":[{"capability":"manageMembers"},{"state":"Self::is_admin"}],"mutates":true}}"#,
+            "\n",
+        ),
+    );
+    let events = custom(
+        ABI_SECTION,
+        concat!(
+            r#"{"event":{"name":"Event","variants":[{"kind":"Inserted","data":{"type":"string"}}]}}"#,
+            "\n",
+        ),
+    );
+    let other = custom("name", "ignored");
+
+    let wasm = module(&[(0, &logic), (1, &[0]), (0, &other), (0, &events)]);
+
+    let abi = Abi::from_wasm(&wasm).unwrap().unwrap();
+
+    assert_eq!(abi.methods.len(), 3);
+    assert_eq!(abi.events.len(), 1);
+
+    let set = abi.method("set").unwrap();
+    assert!(set.mutates);
+    assert_eq!(set.arguments[0].name, "key");
+    assert_eq!(set.arguments[0].schema, json!({ "type": "string" }));
+
+    let init = abi.method("init").unwrap();
+    assert_eq!(init.modifiers, vec![AbiModifier::Init]);
+    assert!(!init.mutates);
+    assert!(init.guards.is_empty());
+
+    let grant = abi.method("grant").unwrap();
+    assert_eq!(
+        grant.guards,
+        vec![
+            AbiGuard::Capability(AbiCapability::ManageMembers),
+            AbiGuard::State("Self::is_admin".to_owned())
+        ]
+    );
+    assert_eq!(
+        grant.capabilities().collect::<Vec<_>>(),
+        vec![AbiCapability::ManageMembers]
+    );
+
+    assert_eq!(abi.events[0].variants[0].kind, "Inserted");
+}
+
+#[test]
+fn test_abi_from_wasm_types() {
+    let logic = custom(
+        ABI_SECTION,
+        concat!(
+            r##"{"method":{"name":"get","arguments":[{"name":"id","schema":{"$ref":"#/types/Id"}}],"returns":{"anyOf":[{"$ref":"#/types/Item"},{"type":"null"}]}}}"##,
+            "\n",
+            r##"{"type":{"name":"Item","schema":{"type":"object","properties":{"id":{"$ref":"#/types/Id"}},"required":["id"]}}}"##,
+            "\n",
+        ),
+    );
+
+    let abi = Abi::from_wasm(&module(&[(0, &logic)])).unwrap().unwrap();
+
+    let get = abi.method("get").unwrap();
+    assert_eq!(get.arguments[0].schema, json!({ "title": "Id" }));
+    assert_eq!(
+        get.returns,
+        Some(json!({ "anyOf": [{ "$ref": "#/types/Item" }, { "type": "null" }] }))
+    );
+
+    assert_eq!(abi.types.len(), 1);
+    assert_eq!(
+        abi.types["Item"],
+        json!({
+            "type": "object",
+            "properties": { "id": { "title": "Id" } },
+            "required": ["id"]
+        })
+    );
+}
+
+#[test]
+fn test_abi_from_wasm_fields() {
+    let state = custom(
+        ABI_SECTION,
+        concat!(
+            r#"{"field":{"name":"members","guards":[{"capability":"manageMembers"},{"state":"Self::is_admin"}]}}"#,
+            "\n",
+        ),
+    );
+
+    let abi = Abi::from_wasm(&module(&[(0, &state)])).unwrap().unwrap();
+
+    assert_eq!(abi.fields.len(), 1);
+    assert_eq!(abi.fields[0].name, "members");
+    assert_eq!(
+        abi.fields[0].guards,
+        vec![
+            AbiGuard::Capability(AbiCapability::ManageMembers),
+            AbiGuard::State("Self::is_admin".to_owned()),
+        ]
+    );
+    assert_eq!(
+        abi.fields[0].capabilities().collect::<Vec<_>>(),
+        vec![AbiCapability::ManageMembers]
+    );
+}
+
+#[test]
+fn test_abi_from_wasm_without_section() {
+    let wasm = module(&[(1, &[0])]);
+
+    assert!(Abi::from_wasm(&wasm).unwrap().is_none());
+}
+
+#[test]
+fn test_abi_from_wasm_invalid() {
+    assert!(matches!(
+        Abi::from_wasm(b"not wasm"),
+        Err(AbiError::InvalidModule)
+    ));
+
+    let wasm = module(&[(0, &custom(ABI_SECTION, "{\n"))]);
+
+    assert!(matches!(
+        Abi::from_wasm(&wasm),
+        Err(AbiError::InvalidEntry(_))
+    ));
+}
@@ -0,0 +1,100 @@
+use serde_json::{from_value as from_json_value, json};
+
+use super::*;
+
+#[test]
+fn test_matches_pattern() {
+    assert!(matches_pattern("get", "get"));
+    assert!(!matches_pattern("get", "get_all"));
+    assert!(!matches_pattern("get", "forget"));
+
+    assert!(matches_pattern("*", ""));
+    assert!(matches_pattern("*", "anything"));
+
+    assert!(matches_pattern("get_*", "get_"));
+    assert!(matches_pattern("get_*", "get_posts"));
+    assert!(!matches_pattern("get_*", "set_posts"));
+
+    assert!(matches_pattern("*_posts", "get_posts"));
+    assert!(!matches_pattern("*_posts", "get_post"));
+
+    assert!(matches_pattern("get_*_by_*", "get_posts_by_author"));
+    assert!(!matches_pattern("get_*_by_*", "get_posts"));
+
+    assert!(matches_pattern("a*a", "aa"));
+    assert!(!matches_pattern("a*a", "a"));
+}
+
+#[test]
+fn test_default_scopes_permit_everything() {
+    let scopes = Scopes::default();
+
+    let context_id = ContextId::from([1; 32]);
+
+    assert!(scopes.permits_call(&context_id, "set"));
+    assert!(scopes.permits_admin(true));
+}
+
+#[test]
+fn test_scopes_narrowed() {
+    let allowed = ContextId::from([1; 32]);
+    let other = ContextId::from([2; 32]);
+
+    let scopes = Scopes::new(
+        Role::Application,
+        Some(vec![allowed]),
+        Some(vec!["get_*".to_owned()]),
+        true,
+    );
+
+    assert!(scopes.permits_call(&allowed, "get_posts"));
+    assert!(!scopes.permits_call(&allowed, "create_post"));
+    assert!(!scopes.permits_call(&other, "get_posts"));
+
+    assert!(!scopes.permits_admin(false));
+}
+
+#[test]
+fn test_read_only_admin() {
+    let scopes = Scopes::new(Role::Admin, None, None, true);
+
+    assert!(scopes.permits_admin(false));
+    assert!(!scopes.permits_admin(true));
+}
+
+#[test]
+fn test_client_key_without_scopes() {
+    let client_key: ClientKey = from_json_value(json!({
+        "wallet": { "type": "ETH", "chainId": 1_u64 },
+        "signingKey": "key",
+        "createdAt": 0_u64,
+        "contextId": null,
+    }))
+    .unwrap();
+
+    assert_eq!(client_key.scopes, Scopes::default());
+}
+
+#[test]
+fn test_api_key_expiry() {
+    let api_key = ApiKey::new(
+        Hash::new(b"secret"),
+        "ci".to_owned(),
+        Scopes::default(),
+        0,
+        None,
+    );
+
+    assert!(!api_key.is_expired(u64::MAX));
+
+    let api_key = ApiKey::new(
+        Hash::new(b"secret"),
+        "ci".to_owned(),
+        Scopes::default(),
+        0,
+        Some(10),
+    );
+
+    assert!(!api_key.is_expired(9));
+    assert!(api_key.is_expired(10));
+}
@@ -7,6 +7,7 @@ use std::io::Read;
 use std::path::Path;
 
 use calimero_runtime::logic::{VMContext, VMLimits};
+use calimero_runtime::proxy::NoProxy;
 use calimero_runtime::store::InMemoryStorage;
 use calimero_runtime::{run, Constraint};
 use eyre::Result as EyreResult;
@@ -52,6 +53,7 @@ fn main() -> EyreResult<()> {
     let file = File::open(path)?.bytes().collect::<Result<Vec<u8>, _>>()?;
 
     let mut storage = InMemoryStorage::default();
+    let mut private_storage = InMemoryStorage::default();
 
     let limits = VMLimits {
         max_memory_pages: 1 << 10, // 1 KiB
@@ -89,7 +91,15 @@ fn main() -> EyreResult<()> {
             [0; 32],
         );
 
-        let outcome = run(&file, name, cx, &mut storage, &limits)?;
+        let outcome = run(
+            &file,
+            name,
+            cx,
+            &mut storage,
+            &mut private_storage,
+            &mut NoProxy,
+            &limits,
+        )?;
 
         // dbg!(&outcome);
 
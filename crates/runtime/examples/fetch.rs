@@ -6,6 +6,7 @@ use std::io::Read;
 use std::path::Path;
 
 use calimero_runtime::logic::{VMContext, VMLimits};
+use calimero_runtime::proxy::NoProxy;
 use calimero_runtime::store::InMemoryStorage;
 use calimero_runtime::{run, Constraint};
 use eyre::Result as EyreResult;
@@ -27,6 +28,7 @@ fn main() -> EyreResult<()> {
     let file = File::open(path)?.bytes().collect::<Result<Vec<u8>, _>>()?;
 
     let mut storage = InMemoryStorage::default();
+    let mut private_storage = InMemoryStorage::default();
 
     let limits = VMLimits {
         max_memory_pages: 1 << 10, // 1 KiB
@@ -51,7 +53,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let get_outcome = run(&file, "view_account", cx, &mut storage, &limits)?;
+    let get_outcome = run(
+        &file,
+        "view_account",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     let returns = String::from_utf8(get_outcome.returns.unwrap().unwrap()).unwrap();
     println!("{returns}");
 
@@ -7,6 +7,7 @@ use std::io::Read;
 use std::path::Path;
 
 use calimero_runtime::logic::{VMContext, VMLimits};
+use calimero_runtime::proxy::NoProxy;
 use calimero_runtime::store::InMemoryStorage;
 use calimero_runtime::{run, Constraint};
 use eyre::Result as EyreResult;
@@ -66,6 +67,7 @@ fn main() -> EyreResult<()> {
     let file = File::open(path)?.bytes().collect::<Result<Vec<u8>, _>>()?;
 
     let mut storage = InMemoryStorage::default();
+    let mut private_storage = InMemoryStorage::default();
 
     let limits = VMLimits {
         max_memory_pages: 1 << 10, // 1 KiB
@@ -95,7 +97,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let create_keypair_outcome = run(&file, "create_keypair", cx, &mut storage, &limits)?;
+    let create_keypair_outcome = run(
+        &file,
+        "create_keypair",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&create_keypair_outcome);
 
     let joe_keypair = from_json_slice::<KeyComponents>(
@@ -119,7 +129,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let create_keypair_outcome = run(&file, "create_keypair", cx, &mut storage, &limits)?;
+    let create_keypair_outcome = run(
+        &file,
+        "create_keypair",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&create_keypair_outcome);
 
     let melissa_keypair = from_json_slice::<KeyComponents>(
@@ -142,7 +160,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let join_outcome = run(&file, "join", cx, &mut storage, &limits)?;
+    let join_outcome = run(
+        &file,
+        "join",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&join_outcome);
 
     let joe_idx =
@@ -162,7 +188,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let join_outcome = run(&file, "join", cx, &mut storage, &limits)?;
+    let join_outcome = run(
+        &file,
+        "join",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&join_outcome);
 
     let melissa_idx =
@@ -178,7 +212,15 @@ fn main() -> EyreResult<()> {
     println!("{}", "--".repeat(20).dimmed());
 
     let cx = VMContext::new(vec![], [0; 32], [0; 32]);
-    let state_outcome = run(&file, "state", cx, &mut storage, &limits)?;
+    let state_outcome = run(
+        &file,
+        "state",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&state_outcome);
 
     let game_state = from_json_slice::<[Option<(String, State)>; 2]>(
@@ -203,7 +245,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let prepare_outcome = run(&file, "prepare", cx, &mut storage, &limits)?;
+    let prepare_outcome = run(
+        &file,
+        "prepare",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&prepare_outcome);
 
     let (joe_commitment, joe_signature) = from_json_slice::<(String, String)>(
@@ -228,7 +278,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let prepare_outcome = run(&file, "prepare", cx, &mut storage, &limits)?;
+    let prepare_outcome = run(
+        &file,
+        "prepare",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&prepare_outcome);
 
     let (melissa_commitment, melissa_signature) = from_json_slice::<(String, String)>(
@@ -250,7 +308,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let commit_outcome = run(&file, "commit", cx, &mut storage, &limits)?;
+    let commit_outcome = run(
+        &file,
+        "commit",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&commit_outcome);
 
     from_json_slice::<()>(&commit_outcome.returns?.expect("Expected a return value"))?;
@@ -268,7 +334,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let commit_outcome = run(&file, "commit", cx, &mut storage, &limits)?;
+    let commit_outcome = run(
+        &file,
+        "commit",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&commit_outcome);
 
     from_json_slice::<()>(&commit_outcome.returns?.expect("Expected a return value"))?;
@@ -285,7 +359,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let reveal_outcome = run(&file, "reveal", cx, &mut storage, &limits)?;
+    let reveal_outcome = run(
+        &file,
+        "reveal",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&reveal_outcome);
 
     from_json_slice::<()>(&reveal_outcome.returns?.expect("Expected a return value"))?;
@@ -302,7 +384,15 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let reveal_outcome = run(&file, "reveal", cx, &mut storage, &limits)?;
+    let reveal_outcome = run(
+        &file,
+        "reveal",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&reveal_outcome);
 
     from_json_slice::<()>(&reveal_outcome.returns?.expect("Expected a return value"))?;
@@ -315,7 +405,15 @@ fn main() -> EyreResult<()> {
     println!("{}", "--".repeat(20).dimmed());
 
     let cx = VMContext::new(vec![], [0; 32], [0; 32]);
-    let state_outcome = run(&file, "state", cx, &mut storage, &limits)?;
+    let state_outcome = run(
+        &file,
+        "state",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&state_outcome);
 
     let game_state = from_json_slice::<[Option<(String, State)>; 2]>(
@@ -343,13 +441,29 @@ fn main() -> EyreResult<()> {
         [0; 32],
         [0; 32],
     );
-    let reset_outcome = run(&file, "reset", cx, &mut storage, &limits)?;
+    let reset_outcome = run(
+        &file,
+        "reset",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&reset_outcome);
 
     from_json_slice::<()>(&reset_outcome.returns?.expect("Expected a return value"))?;
 
     let cx = VMContext::new(vec![], [0; 32], [0; 32]);
-    let state_outcome = run(&file, "state", cx, &mut storage, &limits)?;
+    let state_outcome = run(
+        &file,
+        "state",
+        cx,
+        &mut storage,
+        &mut private_storage,
+        &mut NoProxy,
+        &limits,
+    )?;
     dbg!(&state_outcome);
 
     let game_state = from_json_slice::<[Option<(String, State)>; 2]>(
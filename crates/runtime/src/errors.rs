@@ -47,6 +47,8 @@ pub enum FunctionCallError {
     HostError(HostError),
     #[error("the method call returned an error: {0:?}")]
     ExecutionError(Vec<u8>),
+    #[error("the executor is not authorized to call this method")]
+    Unauthorized,
 }
 
 #[derive(Debug, Serialize, ThisError)]
@@ -102,6 +104,10 @@ pub enum HostError {
     EventKindSizeOverflow,
     #[error("event data size overflow")]
     EventDataSizeOverflow,
+    #[error("the executor is not authorized to call this method")]
+    Unauthorized,
+    #[error("proxy query failed: {message}")]
+    ProxyQueryFailed { message: String },
 }
 
 #[derive(Copy, Clone, Debug, Serialize)]
@@ -3,12 +3,14 @@ use wasmer::{Engine, Instance, Module, NativeEngineExt, Store};
 use crate::errors::{FunctionCallError, VMRuntimeError};
 use crate::logic::{Outcome, VMContext, VMLimits, VMLogic, VMLogicError};
 use crate::memory::WasmerTunables;
+use crate::proxy::Proxy;
 use crate::store::Storage;
 
 mod constraint;
 pub mod errors;
 pub mod logic;
 mod memory;
+pub mod proxy;
 pub mod store;
 
 pub use constraint::Constraint;
@@ -20,6 +22,8 @@ pub fn run(
     method_name: &str,
     context: VMContext,
     storage: &mut dyn Storage,
+    private_storage: &mut dyn Storage,
+    proxy: &mut dyn Proxy,
     limits: &VMLimits,
 ) -> RuntimeResult<Outcome> {
     // todo! calculate storage key for cached precompiled
@@ -30,7 +34,7 @@ pub fn run(
 
     let mut store = Store::new(engine);
 
-    let mut logic = VMLogic::new(storage, context, limits);
+    let mut logic = VMLogic::new(storage, private_storage, proxy, context, limits);
 
     // todo! apply a prepare step
     // todo! - parse the wasm blob, validate and apply transformations
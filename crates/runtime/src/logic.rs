@@ -1,7 +1,12 @@
 #![allow(single_use_lifetimes, unused_lifetimes, reason = "False positive")]
 #![allow(clippy::mem_forget, reason = "Safe for now")]
 
+#[cfg(test)]
+#[path = "tests/logic.rs"]
+mod tests;
+
 use core::num::NonZeroU64;
+use core::time::Duration;
 use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::vec;
@@ -13,6 +18,7 @@ use serde::Serialize;
 
 use crate::constraint::{Constrained, MaxU64};
 use crate::errors::{FunctionCallError, HostError, Location, PanicContext};
+use crate::proxy::{Proxy, ProxyQuery};
 use crate::store::Storage;
 
 mod errors;
@@ -30,6 +36,10 @@ pub struct VMContext {
     pub input: Vec<u8>,
     pub context_id: [u8; 32],
     pub executor_public_key: [u8; 32],
+    /// The context config capabilities held by the executor, by discriminant.
+    pub capabilities: Vec<u32>,
+    /// How long storage should hold entities whose parents have not arrived.
+    pub orphan_expiry: Option<Duration>,
 }
 
 impl VMContext {
@@ -39,8 +49,22 @@ impl VMContext {
             input,
             context_id,
             executor_public_key,
+            capabilities: Vec::new(),
+            orphan_expiry: None,
         }
     }
+
+    #[must_use]
+    pub fn with_capabilities(mut self, capabilities: Vec<u32>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_orphan_expiry(mut self, orphan_expiry: Duration) -> Self {
+        self.orphan_expiry = Some(orphan_expiry);
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -66,6 +90,10 @@ pub struct VMLimits {
 #[derive(Debug)]
 pub struct VMLogic<'a> {
     storage: &'a mut dyn Storage,
+    private_storage: &'a mut dyn Storage,
+    proxy: &'a mut dyn Proxy,
+    // proxy queries resolved during this execution
+    queries: BTreeMap<ProxyQuery, Option<Vec<u8>>>,
     memory: Option<wasmer::Memory>,
     context: VMContext,
     limits: &'a VMLimits,
@@ -75,14 +103,24 @@ pub struct VMLogic<'a> {
     events: Vec<Event>,
     root_hash: Option<[u8; 32]>,
     artifact: Vec<u8>,
+    changes: Vec<u8>,
     proposals: BTreeMap<[u8; 32], Vec<u8>>,
     approvals: Vec<[u8; 32]>,
 }
 
 impl<'a> VMLogic<'a> {
-    pub fn new(storage: &'a mut dyn Storage, context: VMContext, limits: &'a VMLimits) -> Self {
+    pub fn new(
+        storage: &'a mut dyn Storage,
+        private_storage: &'a mut dyn Storage,
+        proxy: &'a mut dyn Proxy,
+        context: VMContext,
+        limits: &'a VMLimits,
+    ) -> Self {
         VMLogic {
             storage,
+            private_storage,
+            proxy,
+            queries: BTreeMap::new(),
             memory: None,
             context,
             limits,
@@ -92,6 +130,7 @@ impl<'a> VMLogic<'a> {
             events: vec![],
             root_hash: None,
             artifact: vec![],
+            changes: vec![],
             proposals: BTreeMap::new(),
             approvals: vec![],
         }
@@ -123,6 +162,8 @@ pub struct Outcome {
     pub events: Vec<Event>,
     pub root_hash: Option<[u8; 32]>,
     pub artifact: Vec<u8>,
+    // the changes made to entities, as described by the storage
+    pub changes: Vec<u8>,
     pub proposals: BTreeMap<[u8; 32], Vec<u8>>,
     //list of ids for approved proposals
     pub approvals: Vec<[u8; 32]>,
@@ -130,6 +171,24 @@ pub struct Outcome {
     // current storage usage of the app
 }
 
+impl Outcome {
+    /// An outcome that only returns, with nothing else to apply, such as one
+    /// recorded from an earlier execution.
+    #[must_use]
+    pub const fn returning(returns: Option<Vec<u8>>, logs: Vec<String>) -> Self {
+        Self {
+            returns: Ok(returns),
+            logs,
+            events: vec![],
+            root_hash: None,
+            artifact: vec![],
+            changes: vec![],
+            proposals: BTreeMap::new(),
+            approvals: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[non_exhaustive]
 pub struct Event {
@@ -154,6 +213,7 @@ impl VMLogic<'_> {
             events: self.events,
             root_hash: self.root_hash,
             artifact: self.artifact,
+            changes: self.changes,
             proposals: self.proposals,
             approvals: self.approvals,
         }
@@ -268,6 +328,16 @@ impl VMHostFunctions<'_> {
         })
     }
 
+    pub fn has_capability(&self, capability: u32) -> VMLogicResult<u32> {
+        let logic = self.borrow_logic();
+
+        Ok(logic.context.capabilities.contains(&capability).into())
+    }
+
+    pub fn unauthorized(&self) -> VMLogicResult<()> {
+        Err(HostError::Unauthorized.into())
+    }
+
     pub fn input(&mut self, register_id: u64) -> VMLogicResult<()> {
         self.with_logic_mut(|logic| {
             logic
@@ -363,6 +433,24 @@ impl VMHostFunctions<'_> {
         Ok(())
     }
 
+    /// Records the changes made to entities during the execution.
+    ///
+    /// Like the artifact, these are opaque to the runtime, and are handed to
+    /// the node so that it can tell subscribers which entities changed.
+    ///
+    /// # Parameters
+    ///
+    /// * `changes_ptr` - Pointer to the start of the changes in WASM memory.
+    /// * `changes_len` - Length of the changes.
+    ///
+    pub fn commit_changes(&mut self, changes_ptr: u64, changes_len: u64) -> VMLogicResult<()> {
+        let changes = self.read_guest_memory(changes_ptr, changes_len)?;
+
+        self.with_logic_mut(|logic| logic.changes = changes);
+
+        Ok(())
+    }
+
     pub fn storage_read(
         &mut self,
         key_ptr: u64,
@@ -444,6 +532,87 @@ impl VMHostFunctions<'_> {
         Ok(0)
     }
 
+    pub fn private_storage_read(
+        &mut self,
+        key_ptr: u64,
+        key_len: u64,
+        register_id: u64,
+    ) -> VMLogicResult<u32> {
+        let logic = self.borrow_logic();
+
+        if key_len > logic.limits.max_storage_key_size.get() {
+            return Err(HostError::KeyLengthOverflow.into());
+        }
+
+        let key = self.read_guest_memory(key_ptr, key_len)?;
+
+        if let Some(value) = logic.private_storage.get(&key) {
+            self.with_logic_mut(|logic| logic.registers.set(logic.limits, register_id, value))?;
+
+            return Ok(1);
+        }
+
+        Ok(0)
+    }
+
+    pub fn private_storage_remove(
+        &mut self,
+        key_ptr: u64,
+        key_len: u64,
+        register_id: u64,
+    ) -> VMLogicResult<u32> {
+        let logic = self.borrow_logic();
+
+        if key_len > logic.limits.max_storage_key_size.get() {
+            return Err(HostError::KeyLengthOverflow.into());
+        }
+
+        let key = self.read_guest_memory(key_ptr, key_len)?;
+
+        if let Some(value) = logic.private_storage.get(&key) {
+            self.with_logic_mut(|logic| {
+                drop(logic.private_storage.remove(&key));
+                logic.registers.set(logic.limits, register_id, value)
+            })?;
+
+            return Ok(1);
+        }
+
+        Ok(0)
+    }
+
+    pub fn private_storage_write(
+        &mut self,
+        key_ptr: u64,
+        key_len: u64,
+        value_ptr: u64,
+        value_len: u64,
+        register_id: u64,
+    ) -> VMLogicResult<u32> {
+        let logic = self.borrow_logic();
+
+        if key_len > logic.limits.max_storage_key_size.get() {
+            return Err(HostError::KeyLengthOverflow.into());
+        }
+
+        if value_len > logic.limits.max_storage_value_size.get() {
+            return Err(HostError::ValueLengthOverflow.into());
+        }
+
+        let key = self.read_guest_memory(key_ptr, key_len)?;
+        let value = self.read_guest_memory(value_ptr, value_len)?;
+
+        let evicted = self.with_logic_mut(|logic| logic.private_storage.set(key, value));
+
+        if let Some(evicted) = evicted {
+            self.with_logic_mut(|logic| logic.registers.set(logic.limits, register_id, evicted))?;
+
+            return Ok(1);
+        };
+
+        Ok(0)
+    }
+
     #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
     pub fn fetch(
         &mut self,
@@ -533,6 +702,27 @@ impl VMHostFunctions<'_> {
         Ok(())
     }
 
+    /// Gets the period after which storage discards orphaned entities.
+    ///
+    /// The period is written as a nanosecond count, if the node has configured
+    /// one, otherwise the guest is told to use its own default.
+    ///
+    pub fn orphan_expiry(&mut self, ptr: u64, len: u64) -> VMLogicResult<u32> {
+        if len != 8 {
+            return Err(HostError::InvalidMemoryAccess.into());
+        }
+
+        let Some(expiry) = self.borrow_logic().context.orphan_expiry else {
+            return Ok(0);
+        };
+
+        let nanos = u64::try_from(expiry.as_nanos()).unwrap_or(u64::MAX);
+
+        self.borrow_memory().write(ptr, &nanos.to_le_bytes())?;
+
+        Ok(1)
+    }
+
     /// Call the contract's `send_proposal()` function through the bridge.
     ///
     /// The proposal actions are obtained as raw data and pushed onto a list of
@@ -580,4 +770,67 @@ impl VMHostFunctions<'_> {
 
         Ok(())
     }
+
+    pub fn proxy_proposal(
+        &mut self,
+        id_ptr: u64,
+        id_len: u64,
+        register_id: u64,
+    ) -> VMLogicResult<u32> {
+        let proposal_id = self.read_guest_memory_sized::<32>(id_ptr, id_len)?;
+
+        self.query_proxy(ProxyQuery::Proposal(proposal_id), register_id)
+    }
+
+    pub fn proxy_approvers(
+        &mut self,
+        id_ptr: u64,
+        id_len: u64,
+        register_id: u64,
+    ) -> VMLogicResult<u32> {
+        let proposal_id = self.read_guest_memory_sized::<32>(id_ptr, id_len)?;
+
+        self.query_proxy(ProxyQuery::Approvers(proposal_id), register_id)
+    }
+
+    pub fn proxy_context_value(
+        &mut self,
+        key_ptr: u64,
+        key_len: u64,
+        register_id: u64,
+    ) -> VMLogicResult<u32> {
+        if key_len > self.borrow_logic().limits.max_storage_key_size.get() {
+            return Err(HostError::KeyLengthOverflow.into());
+        }
+
+        let key = self.read_guest_memory(key_ptr, key_len)?;
+
+        self.query_proxy(ProxyQuery::ContextValue(key), register_id)
+    }
+
+    // resolves each query against the proxy at most once per execution
+    fn query_proxy(&mut self, query: ProxyQuery, register_id: u64) -> VMLogicResult<u32> {
+        let value = self.with_logic_mut(|logic| {
+            if let Some(value) = logic.queries.get(&query) {
+                return Ok(value.clone());
+            }
+
+            let value = logic
+                .proxy
+                .query(&query)
+                .map_err(|message| HostError::ProxyQueryFailed { message })?;
+
+            drop(logic.queries.insert(query, value.clone()));
+
+            Ok::<_, VMLogicError>(value)
+        })?;
+
+        let Some(value) = value else {
+            return Ok(0);
+        };
+
+        self.with_logic_mut(|logic| logic.registers.set(logic.limits, register_id, value))?;
+
+        Ok(1)
+    }
 }
@@ -31,6 +31,7 @@ impl TryFrom<VMLogicError> for FunctionCallError {
             //     context: PanicContext::Host,
             //     message,
             // }) => Err(VMRuntimeError::HostError(err)),
+            VMLogicError::HostError(HostError::Unauthorized) => Ok(Self::Unauthorized),
             VMLogicError::HostError(err) => Ok(Self::HostError(err)),
         }
     }
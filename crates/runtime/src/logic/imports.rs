@@ -36,6 +36,8 @@ impl VMLogic<'_> {
 
             fn context_id(register_id: u64);
             fn executor_id(register_id: u64);
+            fn has_capability(capability: u32) -> u32;
+            fn unauthorized();
 
             fn input(register_id: u64);
             fn value_return(tag: u64, value_ptr: u64, value_len: u64);
@@ -43,6 +45,7 @@ impl VMLogic<'_> {
             fn emit(kind_ptr: u64, kind_len: u64, data_ptr: u64, data_len: u64);
 
             fn commit(root_hash_ptr: u64, root_hash_len: u64, artifact_ptr: u64, artifact_len: u64);
+            fn commit_changes(changes_ptr: u64, changes_len: u64);
 
             fn storage_write(
                 key_ptr: u64,
@@ -54,6 +57,16 @@ impl VMLogic<'_> {
             fn storage_read(key_ptr: u64, key_len: u64, register_id: u64) -> u32;
             fn storage_remove(key_ptr: u64, key_len: u64, register_id: u64) -> u32;
 
+            fn private_storage_write(
+                key_ptr: u64,
+                key_len: u64,
+                value_ptr: u64,
+                value_len: u64,
+                register_id: u64,
+            ) -> u32;
+            fn private_storage_read(key_ptr: u64, key_len: u64, register_id: u64) -> u32;
+            fn private_storage_remove(key_ptr: u64, key_len: u64, register_id: u64) -> u32;
+
             fn fetch(
                 url_ptr: u64,
                 url_len: u64,
@@ -68,9 +81,14 @@ impl VMLogic<'_> {
 
             fn random_bytes(ptr: u64, len: u64);
             fn time_now(ptr: u64, len: u64);
+            fn orphan_expiry(ptr: u64, len: u64) -> u32;
 
             fn send_proposal(actions_ptr: u64, actions_len: u64, id_ptr: u64, id_len: u64);
             fn approve_proposal(approval_ptr: u64, approval_len: u64);
+
+            fn proxy_proposal(id_ptr: u64, id_len: u64, register_id: u64) -> u32;
+            fn proxy_approvers(id_ptr: u64, id_len: u64, register_id: u64) -> u32;
+            fn proxy_context_value(key_ptr: u64, key_len: u64, register_id: u64) -> u32;
         }
     }
 }
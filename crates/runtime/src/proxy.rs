@@ -0,0 +1,29 @@
+use core::fmt::Debug;
+
+/// A read-only query against the proxy contract of the context.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum ProxyQuery {
+    /// The proposal with the given ID, borsh-encoded.
+    Proposal([u8; 32]),
+    /// The identities that approved the proposal with the given ID,
+    /// borsh-encoded.
+    Approvers([u8; 32]),
+    /// The value set under the given key by a `SetContextValue` action.
+    ContextValue(Vec<u8>),
+}
+
+pub trait Proxy: Debug {
+    /// Resolves the query, returning `None` when there is nothing under it.
+    fn query(&mut self, query: &ProxyQuery) -> Result<Option<Vec<u8>>, String>;
+}
+
+/// A proxy with nothing in it, for executions outside of a context.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoProxy;
+
+impl Proxy for NoProxy {
+    fn query(&mut self, _query: &ProxyQuery) -> Result<Option<Vec<u8>>, String> {
+        Ok(None)
+    }
+}
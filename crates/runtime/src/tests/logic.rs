@@ -0,0 +1,125 @@
+use super::*;
+use crate::constraint::Constraint;
+use crate::proxy::NoProxy;
+use crate::run;
+use crate::store::{InMemoryStorage, Storage};
+
+/// A module keeping the value "hunter2" under the key "secret" in private
+/// storage.
+const MODULE: &str = r#"
+(module
+  (import "env" "private_storage_write"
+    (func $private_storage_write (param i64 i64 i64 i64 i64) (result i32)))
+  (import "env" "private_storage_read"
+    (func $private_storage_read (param i64 i64 i64) (result i32)))
+  (import "env" "private_storage_remove"
+    (func $private_storage_remove (param i64 i64 i64) (result i32)))
+  (import "env" "read_register" (func $read_register (param i64 i64 i64) (result i32)))
+  (import "env" "value_return" (func $value_return (param i64 i64 i64)))
+
+  (memory (export "memory") 1)
+
+  (data (i32.const 0) "secret")
+  (data (i32.const 16) "hunter2")
+
+  (func (export "write")
+    (drop (call $private_storage_write
+      (i64.const 0) (i64.const 6) (i64.const 16) (i64.const 7) (i64.const 0))))
+
+  (func (export "read")
+    (if (i32.eqz (call $private_storage_read (i64.const 0) (i64.const 6) (i64.const 0)))
+      (then (return)))
+    (drop (call $read_register (i64.const 0) (i64.const 32) (i64.const 7)))
+    (call $value_return (i64.const 0) (i64.const 32) (i64.const 7)))
+
+  (func (export "remove")
+    (drop (call $private_storage_remove (i64.const 0) (i64.const 6) (i64.const 0)))))
+"#;
+
+fn limits() -> VMLimits {
+    VMLimits {
+        max_memory_pages: 1 << 10,
+        max_stack_size: 200 << 10,
+        max_registers: 100,
+        max_register_size: (100 << 20).validate().unwrap(),
+        max_registers_capacity: 1 << 30,
+        max_logs: 100,
+        max_log_size: 16 << 10,
+        max_events: 100,
+        max_event_kind_size: 100,
+        max_event_data_size: 16 << 10,
+        max_storage_key_size: (1 << 20).try_into().unwrap(),
+        max_storage_value_size: (10 << 20).try_into().unwrap(),
+    }
+}
+
+fn execute(
+    method: &str,
+    storage: &mut InMemoryStorage,
+    private_storage: &mut InMemoryStorage,
+) -> Outcome {
+    run(
+        MODULE.as_bytes(),
+        method,
+        VMContext::new(vec![], [0; 32], [0; 32]),
+        storage,
+        private_storage,
+        &mut NoProxy,
+        &limits(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn private_storage_is_kept_apart() {
+    let mut storage = InMemoryStorage::default();
+    let mut private_storage = InMemoryStorage::default();
+
+    let outcome = execute("write", &mut storage, &mut private_storage);
+
+    assert!(outcome.returns.is_ok());
+    assert_eq!(outcome.root_hash, None);
+    assert!(outcome.artifact.is_empty());
+
+    assert_eq!(
+        private_storage.get(&b"secret".to_vec()),
+        Some(b"hunter2".to_vec())
+    );
+    assert!(!storage.has(&b"secret".to_vec()));
+}
+
+#[test]
+fn private_storage_round_trips() {
+    let mut storage = InMemoryStorage::default();
+    let mut private_storage = InMemoryStorage::default();
+
+    let outcome = execute("read", &mut storage, &mut private_storage);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+
+    drop(execute("write", &mut storage, &mut private_storage));
+
+    let outcome = execute("read", &mut storage, &mut private_storage);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"hunter2".to_vec()));
+
+    drop(execute("remove", &mut storage, &mut private_storage));
+
+    assert!(!private_storage.has(&b"secret".to_vec()));
+
+    let outcome = execute("read", &mut storage, &mut private_storage);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+}
+
+#[test]
+fn storage_is_not_private() {
+    let mut storage = InMemoryStorage::default();
+    let mut private_storage = InMemoryStorage::default();
+
+    drop(storage.set(b"secret".to_vec(), b"hunter2".to_vec()));
+
+    let outcome = execute("read", &mut storage, &mut private_storage);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+}
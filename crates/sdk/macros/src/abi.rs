@@ -0,0 +1,252 @@
+#[cfg(test)]
+#[path = "tests/abi.rs"]
+mod tests;
+
+use core::fmt::Write;
+
+use proc_macro2::{Literal, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{Fields, GenericArgument, PathArguments, Type, TypePath};
+
+use crate::errors::Pretty;
+use crate::items::StructOrEnumItem;
+
+// keep in sync with `calimero_primitives::abi::ABI_SECTION`
+const SECTION: &str = "calimero_abi";
+
+/// Embeds ABI fragments into the custom wasm section, one per line.
+///
+/// Every macro invocation emits its own static, and the linker concatenates
+/// them all into a single section.
+pub fn section(fragments: &[String]) -> TokenStream {
+    if fragments.is_empty() {
+        return quote! {};
+    }
+
+    let mut data = fragments.join("\n");
+    data.push('\n');
+
+    let len = data.len();
+    let bytes = Literal::byte_string(data.as_bytes());
+
+    quote! {
+        #[cfg(target_arch = "wasm32")]
+        const _: () = {
+            #[link_section = #SECTION]
+            #[used]
+            static ABI: [u8; #len] = *#bytes;
+        };
+    }
+}
+
+pub fn string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len().saturating_add(2));
+
+    out.push('"');
+
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ignored = write!(out, "\\u{:04x}", u32::from(ch));
+            }
+            ch => out.push(ch),
+        }
+    }
+
+    out.push('"');
+
+    out
+}
+
+/// Derives a JSON Schema for a type from its syntax alone.
+///
+/// Well-known primitives and containers are described structurally, and other
+/// types refer to the schema declared for them with `#[app::schema]`, by name.
+/// Generic types are left open and titled with their Rust type.
+#[expect(
+    clippy::wildcard_enum_match_arm,
+    reason = "anything else is described by its title"
+)]
+pub fn schema(ty: &Type) -> String {
+    match ty {
+        Type::Reference(ty) => schema(&ty.elem),
+        Type::Paren(ty) => schema(&ty.elem),
+        Type::Group(ty) => schema(&ty.elem),
+        Type::Slice(ty) => array(&ty.elem),
+        Type::Array(ty) => array(&ty.elem),
+        Type::Tuple(ty) if ty.elems.is_empty() => r#"{"type":"null"}"#.to_owned(),
+        Type::Tuple(ty) => tuple(ty.elems.iter()),
+        Type::Path(path) if path.qself.is_none() => path_schema(path, ty),
+        _ => titled(ty),
+    }
+}
+
+/// Splits a return type into the schemas of its value and error, if any.
+#[expect(
+    clippy::wildcard_enum_match_arm,
+    reason = "anything else is a plain value"
+)]
+pub fn returns(ty: &Type) -> (Option<String>, Option<String>) {
+    match ty {
+        Type::Tuple(ty) if ty.elems.is_empty() => (None, None),
+        Type::Path(path) if path.qself.is_none() => {
+            let Some(segment) = path.path.segments.last() else {
+                return (Some(schema(ty)), None);
+            };
+
+            match (segment.ident == "Result", &*type_args(&segment.arguments)) {
+                (true, [ok, err]) => (returns(ok).0, Some(schema(err))),
+                // `calimero_sdk::Result`, whose error serializes as a string
+                (true, [ok]) => (returns(ok).0, Some(r#"{"type":"string"}"#.to_owned())),
+                _ => (Some(schema(ty)), None),
+            }
+        }
+        _ => (Some(schema(ty)), None),
+    }
+}
+
+/// Describes a type declared with `#[app::schema]`, as serde serializes it by
+/// default, with enums externally tagged.
+pub fn item(item: &StructOrEnumItem) -> String {
+    let (name, schema) = match item {
+        StructOrEnumItem::Struct(item) => (
+            item.ident.to_string(),
+            fields(&item.fields).unwrap_or_else(|| r#"{"type":"null"}"#.to_owned()),
+        ),
+        StructOrEnumItem::Enum(item) => {
+            let variants = item
+                .variants
+                .iter()
+                .map(|variant| {
+                    let kind = string(&variant.ident.to_string());
+
+                    fields(&variant.fields).map_or_else(
+                        || format!(r#"{{"const":{kind}}}"#),
+                        |data| {
+                            format!(
+                                r#"{{"type":"object","properties":{{{kind}:{data}}},"required":[{kind}],"additionalProperties":false}}"#
+                            )
+                        },
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            (
+                item.ident.to_string(),
+                format!(r#"{{"oneOf":[{}]}}"#, variants.join(",")),
+            )
+        }
+    };
+
+    format!(
+        r#"{{"type":{{"name":{},"schema":{schema}}}}}"#,
+        string(&name)
+    )
+}
+
+/// Derives a JSON Schema for the fields of a struct or enum variant, if any.
+pub fn fields(fields: &Fields) -> Option<String> {
+    match fields {
+        Fields::Unit => None,
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            fields.unnamed.first().map(|field| schema(&field.ty))
+        }
+        Fields::Unnamed(fields) => Some(tuple(fields.unnamed.iter().map(|field| &field.ty))),
+        Fields::Named(fields) => {
+            let names = fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .map(|ident| string(&ident.to_string()))
+                .collect::<Vec<_>>();
+
+            let properties = fields
+                .named
+                .iter()
+                .zip(&names)
+                .map(|(field, name)| format!("{name}:{}", schema(&field.ty)))
+                .collect::<Vec<_>>();
+
+            Some(format!(
+                r#"{{"type":"object","properties":{{{}}},"required":[{}]}}"#,
+                properties.join(","),
+                names.join(",")
+            ))
+        }
+    }
+}
+
+fn path_schema(path: &TypePath, ty: &Type) -> String {
+    let Some(segment) = path.path.segments.last() else {
+        return titled(ty);
+    };
+
+    let name = segment.ident.to_string();
+
+    match (name.as_str(), &*type_args(&segment.arguments)) {
+        ("bool", []) => r#"{"type":"boolean"}"#.to_owned(),
+        ("u8" | "u16" | "u32" | "u64" | "u128" | "usize", []) => {
+            r#"{"type":"integer","minimum":0}"#.to_owned()
+        }
+        ("i8" | "i16" | "i32" | "i64" | "i128" | "isize", []) => r#"{"type":"integer"}"#.to_owned(),
+        ("f32" | "f64", []) => r#"{"type":"number"}"#.to_owned(),
+        ("String" | "str" | "char", []) => r#"{"type":"string"}"#.to_owned(),
+        ("Vec" | "VecDeque" | "BTreeSet" | "HashSet", [item]) => array(item),
+        ("Option", [inner]) => format!(r#"{{"anyOf":[{},{{"type":"null"}}]}}"#, schema(inner)),
+        ("Box" | "Rc" | "Arc" | "Cow", [inner]) => schema(inner),
+        ("BTreeMap" | "HashMap", [_, value]) => format!(
+            r#"{{"type":"object","additionalProperties":{}}}"#,
+            schema(value)
+        ),
+        (_, []) if segment.arguments.is_none() => {
+            format!(r#"{{"$ref":{}}}"#, string(&format!("#/types/{name}")))
+        }
+        _ => titled(ty),
+    }
+}
+
+fn type_args(arguments: &PathArguments) -> Vec<&Type> {
+    let PathArguments::AngleBracketed(arguments) = arguments else {
+        return vec![];
+    };
+
+    arguments
+        .args
+        .iter()
+        .filter_map(|arg| {
+            #[expect(clippy::wildcard_enum_match_arm, reason = "only types are relevant")]
+            match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn array(item: &Type) -> String {
+    format!(r#"{{"type":"array","items":{}}}"#, schema(item))
+}
+
+fn tuple<'a, I: Iterator<Item = &'a Type>>(items: I) -> String {
+    let items = items.map(schema).collect::<Vec<_>>();
+
+    format!(
+        r#"{{"type":"array","prefixItems":[{}],"items":false}}"#,
+        items.join(",")
+    )
+}
+
+fn titled(ty: &Type) -> String {
+    let mut title = String::new();
+
+    if write!(title, "{}", Pretty::Type(ty)).is_err() {
+        title = ty.to_token_stream().to_string();
+    }
+
+    format!(r#"{{"title":{}}}"#, string(&title))
+}
@@ -86,6 +86,26 @@ pub enum ParseError<'a> {
     InitMethodWithoutInitAttribute,
     #[error("method annotated with `#[app::init]` must be named `init`")]
     AppInitMethodNotNamedInit,
+    #[error("a migration, by definition, has no `self` to reference")]
+    NoSelfReceiverAtMigrate,
+    #[error("a migration method, by definition, has to be public")]
+    NoPrivateMigrate,
+    #[error("a migration method cannot take arguments")]
+    NoArgsAtMigrate,
+    #[error("method named `migrate` must be annotated with `#[app::migrate]`")]
+    MigrateMethodWithoutMigrateAttribute,
+    #[error("method annotated with `#[app::migrate]` must be named `migrate`")]
+    AppMigrateMethodNotNamedMigrate,
+    #[error("expected `capability = ..`, or a path to a guard function")]
+    InvalidGuard,
+    #[error("only public methods can be guarded")]
+    NoPrivateGuard,
+    #[error("an initializer cannot be guarded, as there is no state to guard yet")]
+    NoGuardAtInit,
+    #[error("a migration cannot be guarded, as it is applied by the node")]
+    NoGuardAtMigrate,
+    #[error("a guard on the state needs a `self` receiver to check against")]
+    NoSelfReceiverAtGuard,
 }
 
 impl AsRef<Self> for ParseError<'_> {
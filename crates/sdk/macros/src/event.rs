@@ -2,6 +2,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{parse_quote, Error as SynError, GenericParam, Generics, Ident, Visibility};
 
+use crate::abi;
 use crate::errors::{Errors, ParseError};
 use crate::items::StructOrEnumItem;
 use crate::reserved::{idents, lifetimes};
@@ -31,7 +32,36 @@ impl ToTokens for EventImpl<'_> {
 
         let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+        let variants = match orig {
+            StructOrEnumItem::Struct(item) => {
+                vec![(item.ident.to_string(), abi::fields(&item.fields))]
+            }
+            StructOrEnumItem::Enum(item) => item
+                .variants
+                .iter()
+                .map(|variant| (variant.ident.to_string(), abi::fields(&variant.fields)))
+                .collect(),
+        };
+
+        let variants = variants
+            .into_iter()
+            .map(|(kind, data)| {
+                data.map_or_else(
+                    || format!(r#"{{"kind":{}}}"#, abi::string(&kind)),
+                    |data| format!(r#"{{"kind":{},"data":{data}}}"#, abi::string(&kind)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let abi = abi::section(&[format!(
+            r#"{{"event":{{"name":{},"variants":[{}]}}}}"#,
+            abi::string(&ident.to_string()),
+            variants.join(",")
+        )]);
+
         quote! {
+            #abi
+
             #[derive(::calimero_sdk::serde::Serialize)]
             #[serde(crate = "::calimero_sdk::serde")]
             #[serde(tag = "kind", content = "data")]
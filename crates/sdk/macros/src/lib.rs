@@ -13,6 +13,7 @@ use crate::items::{Empty, StructOrEnumItem};
 use crate::logic::{LogicImpl, LogicImplInput};
 use crate::state::{StateArgs, StateImpl, StateImplInput};
 
+mod abi;
 mod errors;
 mod event;
 mod items;
@@ -63,6 +64,18 @@ pub fn init(_args: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
 
+#[proc_macro_attribute]
+pub fn migrate(_args: TokenStream, input: TokenStream) -> TokenStream {
+    // this is a no-op, the attribute is just a marker
+    input
+}
+
+#[proc_macro_attribute]
+pub fn guard(_args: TokenStream, input: TokenStream) -> TokenStream {
+    // this is a no-op, the attribute is just a marker
+    input
+}
+
 #[proc_macro_attribute]
 pub fn destroy(_args: TokenStream, input: TokenStream) -> TokenStream {
     // this is a no-op, the attribute is just a marker
@@ -81,6 +94,14 @@ pub fn event(args: TokenStream, input: TokenStream) -> TokenStream {
     tokens.into()
 }
 
+#[proc_macro_attribute]
+pub fn schema(args: TokenStream, input: TokenStream) -> TokenStream {
+    let _args = parse_macro_input!({ input } => args as Empty);
+    let item = parse_macro_input!(input as StructOrEnumItem);
+    let abi = abi::section(&[abi::item(&item)]);
+    quote!(#abi #item).into()
+}
+
 #[proc_macro]
 pub fn emit(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as Expr);
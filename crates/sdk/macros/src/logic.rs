@@ -1,7 +1,8 @@
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{parse2, Error as SynError, GenericParam, ImplItem, ItemImpl, Path};
+use syn::{parse2, Error as SynError, GenericParam, Ident, ImplItem, ItemImpl, Path};
 
+use crate::abi;
 use crate::errors::{Errors, ParseError};
 use crate::logic::method::{LogicMethod, LogicMethodImplInput, PublicLogicMethod};
 use crate::logic::utils::typed_path;
@@ -10,6 +11,7 @@ use crate::reserved::{idents, lifetimes};
 use crate::sanitizer::{Action, Case, Sanitizer};
 
 mod arg;
+pub mod guard;
 mod method;
 mod ty;
 mod utils;
@@ -25,15 +27,61 @@ impl ToTokens for LogicImpl<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let LogicImpl { orig, methods, .. } = self;
 
+        let abi = abi::section(
+            &methods
+                .iter()
+                .map(PublicLogicMethod::abi)
+                .collect::<Vec<_>>(),
+        );
+
+        let guard = guard(orig, methods);
+
         quote! {
             #orig
 
             #(#methods)*
+
+            #guard
+
+            #abi
         }
         .to_tokens(tokens);
     }
 }
 
+/// Implements the guards declared on the methods, which are checked whenever
+/// they are called.
+fn guard(orig: &ItemImpl, methods: &[PublicLogicMethod<'_>]) -> TokenStream {
+    let (impl_generics, _, where_clause) = orig.generics.split_for_impl();
+    let self_ty = &orig.self_ty;
+
+    let app = Ident::new("app", proc_macro2::Span::call_site());
+
+    let arms = methods
+        .iter()
+        .filter(|method| !method.guards().is_empty())
+        .map(|method| {
+            let name = method.name().to_string();
+            let checks = method.guards().iter().map(|guard| guard.check(&app));
+
+            quote! {
+                #name => #(#checks)&&*,
+            }
+        });
+
+    quote! {
+        impl #impl_generics ::calimero_sdk::state::AppGuard for #self_ty #where_clause {
+            #[allow(unused_variables, reason = "Not every method has guards")]
+            fn authorize(#app: Option<&Self>, method: &str) -> bool {
+                match method {
+                    #(#arms)*
+                    _ => true,
+                }
+            }
+        }
+    }
+}
+
 pub struct LogicImplInput<'a> {
     pub item: &'a ItemImpl,
 }
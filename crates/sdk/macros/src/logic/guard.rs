@@ -0,0 +1,81 @@
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{Error as SynError, Ident, Path, Result as SynResult, Token};
+
+use crate::abi;
+use crate::errors::ParseError;
+
+/// A condition on calling a method, declared with `#[app::guard(..)]`.
+pub enum Guard {
+    /// `#[app::guard(capability = ManageMembers)]`, requiring the executor to
+    /// hold the capability in the context config.
+    Capability(Ident),
+    /// `#[app::guard(Self::is_admin)]`, calling a `fn(&Self, &[u8; 32]) -> bool`
+    /// with the state and the executor.
+    State(Path),
+}
+
+impl Parse for Guard {
+    fn parse(input: ParseStream<'_>) -> SynResult<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let key = input.parse::<Ident>()?;
+
+            if key != "capability" {
+                return Err(SynError::new_spanned(key, ParseError::InvalidGuard));
+            }
+
+            let _eq = input.parse::<Token![=]>()?;
+
+            return Ok(Self::Capability(input.parse()?));
+        }
+
+        input
+            .parse()
+            .map(Self::State)
+            .map_err(|err| SynError::new(err.span(), ParseError::InvalidGuard))
+    }
+}
+
+impl Guard {
+    /// Describes the guard as an ABI fragment.
+    pub fn abi(&self) -> String {
+        match self {
+            Self::Capability(name) => {
+                let name = name.to_string();
+
+                let mut chars = name.chars();
+
+                let name = chars
+                    .next()
+                    .map(|first| first.to_lowercase().chain(chars).collect::<String>())
+                    .unwrap_or_default();
+
+                format!(r#"{{"capability":{}}}"#, abi::string(&name))
+            }
+            Self::State(path) => {
+                let path = path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+
+                format!(r#"{{"state":{}}}"#, abi::string(&path))
+            }
+        }
+    }
+
+    /// Evaluates the guard, given an `app` of type `Option<&Self>`.
+    pub fn check(&self, app: &Ident) -> TokenStream {
+        match self {
+            Self::Capability(name) => quote_spanned! {name.span()=>
+                ::calimero_sdk::env::has_capability(::calimero_sdk::env::Capability::#name)
+            },
+            Self::State(path) => quote_spanned! {path.span()=>
+                #app.is_some_and(|app| #path(app, &::calimero_sdk::env::executor_id()))
+            },
+        }
+    }
+}
@@ -1,10 +1,18 @@
+#[cfg(test)]
+#[path = "../tests/logic/method.rs"]
+mod tests;
+
+use core::fmt::Write;
+
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::{Error as SynError, GenericParam, Ident, ImplItemFn, Path, ReturnType, Visibility};
 
+use crate::abi;
 use crate::errors::{Errors, ParseError};
 use crate::logic::arg::{LogicArg, LogicArgInput, LogicArgTyped, SelfType};
+use crate::logic::guard::Guard;
 use crate::logic::ty::{LogicTy, LogicTyInput};
 use crate::reserved::{idents, lifetimes};
 
@@ -15,6 +23,8 @@ pub enum LogicMethod<'a> {
 
 pub enum Modifer {
     Init,
+    Destroy,
+    Migrate,
 }
 
 pub struct PublicLogicMethod<'a> {
@@ -28,6 +38,82 @@ pub struct PublicLogicMethod<'a> {
     has_refs: bool,
 
     modifiers: Vec<Modifer>,
+    guards: Vec<Guard>,
+}
+
+impl PublicLogicMethod<'_> {
+    pub const fn name(&self) -> &Ident {
+        self.name
+    }
+
+    pub fn guards(&self) -> &[Guard] {
+        &self.guards
+    }
+
+    /// Describes the method as an ABI fragment.
+    pub fn abi(&self) -> String {
+        let arguments = self
+            .args
+            .iter()
+            .map(|arg| {
+                format!(
+                    r#"{{"name":{},"schema":{}}}"#,
+                    abi::string(&arg.ident.to_string()),
+                    abi::schema(&arg.ty.ty)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut fragment = format!(
+            r#"{{"method":{{"name":{},"arguments":[{}]"#,
+            abi::string(&self.name.to_string()),
+            arguments.join(",")
+        );
+
+        // initializers and migrations return the state, not a value
+        let returns_state = self
+            .modifiers
+            .iter()
+            .any(|modifier| matches!(modifier, Modifer::Init | Modifer::Migrate));
+
+        if let (Some(ret), false) = (&self.ret, returns_state) {
+            let (returns, error) = abi::returns(&ret.ty);
+
+            if let Some(returns) = returns {
+                let _ignored = write!(fragment, r#","returns":{returns}"#);
+            }
+
+            if let Some(error) = error {
+                let _ignored = write!(fragment, r#","error":{error}"#);
+            }
+        }
+
+        if !self.guards.is_empty() {
+            let guards = self.guards.iter().map(Guard::abi).collect::<Vec<_>>();
+
+            let _ignored = write!(fragment, r#","guards":[{}]"#, guards.join(","));
+        }
+
+        let mutates = matches!(self.self_type, Some(SelfType::Mutable(_)));
+
+        let modifiers = self
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                Modifer::Init => r#""init""#,
+                Modifer::Destroy => r#""destroy""#,
+                Modifer::Migrate => r#""migrate""#,
+            })
+            .collect::<Vec<_>>();
+
+        let _ignored = write!(
+            fragment,
+            r#","mutates":{mutates},"modifiers":[{}]}}}}"#,
+            modifiers.join(",")
+        );
+
+        fragment
+    }
 }
 
 impl ToTokens for LogicMethod<'_> {
@@ -55,6 +141,10 @@ impl ToTokens for PublicLogicMethod<'_> {
             .iter()
             .any(|modifier| matches!(modifier, Modifer::Init));
 
+        let migrate_method = modifiers
+            .iter()
+            .any(|modifier| matches!(modifier, Modifer::Migrate));
+
         let input = if args.is_empty() {
             quote! {}
         } else {
@@ -101,11 +191,20 @@ impl ToTokens for PublicLogicMethod<'_> {
                         SelfType::Mutable(ty) => (Some(quote! {mut}), ty),
                         SelfType::Owned(ty) | SelfType::Immutable(ty) => (None, ty),
                     };
+                    // the guards on the fields of the state hold for every
+                    // change, whichever method makes it
+                    let state_guard = mutability.is_some().then(|| {
+                        quote_spanned! {ty.span()=>
+                            app.guard();
+                        }
+                    });
                     quote_spanned! {ty.span()=>
                         let Some(#mutability app) = ::calimero_storage::collections::Root::<#self_>::fetch()
                         else {
                             ::calimero_sdk::env::panic_str("Failed to find or read app state")
                         };
+
+                        #state_guard
                     }
                 },
                 quote_spanned! {name.span()=>
@@ -119,6 +218,10 @@ impl ToTokens for PublicLogicMethod<'_> {
                             ::calimero_sdk::env::panic_str("Cannot initialize over already existing state.")
                         };
 
+                        let app =
+                    }
+                } else if migrate_method {
+                    quote_spanned! {name.span()=>
                         let app =
                     }
                 } else {
@@ -130,7 +233,7 @@ impl ToTokens for PublicLogicMethod<'_> {
             ),
         };
 
-        if let (Some(ret), false) = (&self.ret, init_method) {
+        if let (Some(ret), false) = (&self.ret, init_method || migrate_method) {
             call = quote_spanned! {ret.ty.span()=>
                 let output = #call;
                 let output = {
@@ -150,7 +253,25 @@ impl ToTokens for PublicLogicMethod<'_> {
             }
         }
 
-        let state_finalizer = match (&self.self_type, init_method) {
+        let guard = if self.guards.is_empty() {
+            quote! {}
+        } else {
+            let app = if self.self_type.is_some() {
+                quote! { Some(&*app) }
+            } else {
+                quote! { None }
+            };
+
+            let method = name.to_string();
+
+            quote_spanned! {name.span()=>
+                if !<#self_ as ::calimero_sdk::state::AppGuard>::authorize(#app, #method) {
+                    ::calimero_sdk::env::unauthorized()
+                }
+            }
+        };
+
+        let state_finalizer = match (&self.self_type, init_method || migrate_method) {
             (Some(SelfType::Mutable(_)), _) | (_, true) => quote! {
                 app.commit();
             },
@@ -178,6 +299,12 @@ impl ToTokens for PublicLogicMethod<'_> {
                     type Return = #ret;
                 }
             }
+        } else if migrate_method {
+            call = quote_spanned! {name.span()=>
+                ::calimero_storage::collections::Root::<#self_>::migrate(|| #call)
+            };
+
+            quote! {}
         } else {
             quote! {}
         };
@@ -194,6 +321,8 @@ impl ToTokens for PublicLogicMethod<'_> {
 
                 #def
 
+                #guard
+
                 #call;
 
                 #state_finalizer
@@ -222,27 +351,60 @@ impl<'a, 'b> TryFrom<LogicMethodImplInput<'a, 'b>> for LogicMethod<'a> {
 
         let mut modifiers = vec![];
         let mut is_init = false;
+        let mut is_migrate = false;
+        let mut guards = vec![];
+        let mut guard_attr = None;
 
         for attr in &input.item.attrs {
-            if attr.path().segments.len() == 2
-                && attr.path().segments[0].ident == "app"
-                && attr.path().segments[1].ident == "init"
-            {
-                modifiers.push(Modifer::Init);
-                is_init = true;
+            if attr.path().segments.len() == 2 && attr.path().segments[0].ident == "app" {
+                if attr.path().segments[1].ident == "init" {
+                    modifiers.push(Modifer::Init);
+                    is_init = true;
+                } else if attr.path().segments[1].ident == "destroy" {
+                    modifiers.push(Modifer::Destroy);
+                } else if attr.path().segments[1].ident == "migrate" {
+                    modifiers.push(Modifer::Migrate);
+                    is_migrate = true;
+                } else if attr.path().segments[1].ident == "guard" {
+                    match attr.parse_args::<Guard>() {
+                        Ok(guard) => guards.push(guard),
+                        Err(err) => errors.subsume(err),
+                    }
+                    let _ignored = guard_attr.get_or_insert(attr);
+                }
             }
         }
 
-        match (&input.item.vis, is_init) {
-            (Visibility::Public(_), _) => {}
-            (_, true) => {
+        match (&input.item.vis, is_init, is_migrate) {
+            (Visibility::Public(_), _, _) => {}
+            (_, true, _) => {
                 errors.subsume(SynError::new_spanned(
                     &input.item.vis,
                     ParseError::NoPrivateInit,
                 ));
             }
-            (_, false) => {
-                return Ok(Self::Private);
+            (_, _, true) => {
+                errors.subsume(SynError::new_spanned(
+                    &input.item.vis,
+                    ParseError::NoPrivateMigrate,
+                ));
+            }
+            (_, false, false) => {
+                let Some(attr) = guard_attr else {
+                    return Ok(Self::Private);
+                };
+
+                errors.subsume(SynError::new_spanned(attr, ParseError::NoPrivateGuard));
+            }
+        }
+
+        if let Some(attr) = guard_attr {
+            if is_init {
+                errors.subsume(SynError::new_spanned(attr, ParseError::NoGuardAtInit));
+            }
+
+            if is_migrate {
+                errors.subsume(SynError::new_spanned(attr, ParseError::NoGuardAtMigrate));
             }
         }
 
@@ -314,6 +476,42 @@ impl<'a, 'b> TryFrom<LogicMethodImplInput<'a, 'b>> for LogicMethod<'a> {
             _ => {}
         }
 
+        match (is_migrate, &self_type) {
+            (true, Some(self_type)) => errors.subsume(SynError::new_spanned(
+                match self_type {
+                    SelfType::Owned(ty) | SelfType::Mutable(ty) | SelfType::Immutable(ty) => ty,
+                },
+                ParseError::NoSelfReceiverAtMigrate,
+            )),
+            (true, None) if name != "migrate" => errors.subsume(SynError::new_spanned(
+                name,
+                ParseError::AppMigrateMethodNotNamedMigrate,
+            )),
+            (false, _) if name == "migrate" => errors.subsume(SynError::new_spanned(
+                name,
+                ParseError::MigrateMethodWithoutMigrateAttribute,
+            )),
+            _ => {}
+        }
+
+        if self_type.is_none() {
+            for guard in &guards {
+                if let Guard::State(path) = guard {
+                    errors.subsume(SynError::new_spanned(
+                        path,
+                        ParseError::NoSelfReceiverAtGuard,
+                    ));
+                }
+            }
+        }
+
+        if is_migrate && !args.is_empty() {
+            errors.subsume(SynError::new_spanned(
+                &input.item.sig.inputs,
+                ParseError::NoArgsAtMigrate,
+            ));
+        }
+
         let mut ret = None;
         if let ReturnType::Type(_, ret_type) = &input.item.sig.output {
             match LogicTy::try_from(LogicTyInput {
@@ -335,6 +533,7 @@ impl<'a, 'b> TryFrom<LogicMethodImplInput<'a, 'b>> for LogicMethod<'a> {
             ret,
             has_refs,
             modifiers,
+            guards,
         }))
     }
 }
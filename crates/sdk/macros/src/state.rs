@@ -1,31 +1,66 @@
+#[cfg(test)]
+#[path = "tests/state.rs"]
+mod tests;
+
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::{
-    parse2, BoundLifetimes, Error as SynError, GenericParam, Generics, Ident, Lifetime,
-    LifetimeParam, Result as SynResult, Token, Type,
+    parse2, Attribute, BoundLifetimes, Error as SynError, GenericParam, Generics, Ident, Lifetime,
+    LifetimeParam, Member, Path, Result as SynResult, Token, Type,
 };
 
+use crate::abi;
 use crate::errors::{Errors, ParseError, Pretty};
 use crate::items::StructOrEnumItem;
+use crate::logic::guard::Guard;
 use crate::macros::infallible;
 use crate::reserved::idents;
 use crate::sanitizer::{Action, Case, Func, Sanitizer};
 
-#[derive(Clone, Copy)]
 pub struct StateImpl<'a> {
     ident: &'a Ident,
     generics: &'a Generics,
     emits: &'a Option<MaybeBoundEvent>,
+    derives_merge: bool,
+    fields: Vec<GuardedField>,
     orig: &'a StructOrEnumItem,
 }
 
+/// A field of the state declared with `#[app::guard(..)]`, which only those
+/// executors that pass all of its guards may change.
+struct GuardedField {
+    member: Member,
+    guards: Vec<Guard>,
+}
+
+impl GuardedField {
+    /// Describes the field as an ABI fragment.
+    fn abi(&self) -> String {
+        let name = match &self.member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        };
+
+        let guards = self.guards.iter().map(Guard::abi).collect::<Vec<_>>();
+
+        format!(
+            r#"{{"field":{{"name":{},"guards":[{}]}}}}"#,
+            abi::string(&name),
+            guards.join(",")
+        )
+    }
+}
+
 impl ToTokens for StateImpl<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let StateImpl {
             ident,
             generics,
             emits,
+            derives_merge,
+            ref fields,
             orig,
         } = *self;
 
@@ -44,13 +79,62 @@ impl ToTokens for StateImpl<'_> {
             };
         }
 
+        // States without their own merge strategy use last-write-wins
+        let merge = (!derives_merge).then(|| {
+            quote! {
+                impl #impl_generics ::calimero_storage::merge::Merge for #ident #ty_generics #where_clause {}
+            }
+        });
+
+        let item = strip_guards(orig);
+
+        let guard = guard(ident, generics, fields);
+
+        let abi = abi::section(&fields.iter().map(GuardedField::abi).collect::<Vec<_>>());
+
         quote! {
-            #orig
+            #item
 
             impl #impl_generics ::calimero_sdk::state::AppState for #ident #ty_generics #where_clause {
                 type Event<#lifetime> = #event;
             }
 
+            #merge
+
+            #guard
+
+            #abi
+
+            // lets the state be driven by `calimero_sdk::testing` natively
+            #[cfg(not(target_arch = "wasm32"))]
+            impl #impl_generics ::calimero_sdk::testing::SimulatedState for #ident #ty_generics #where_clause {
+                type Root = ::calimero_storage::collections::Root<Self>;
+
+                fn init(f: impl FnOnce() -> Self) -> Self::Root {
+                    ::calimero_storage::collections::Root::new(f)
+                }
+
+                fn fetch() -> ::core::option::Option<Self::Root> {
+                    ::calimero_storage::collections::Root::fetch()
+                }
+
+                fn migrate(f: impl FnOnce() -> Self) -> Self::Root {
+                    ::calimero_storage::collections::Root::migrate(f)
+                }
+
+                fn guard(root: &Self::Root) {
+                    root.guard();
+                }
+
+                fn commit(root: Self::Root) {
+                    root.commit();
+                }
+
+                fn sync(artifact: &[u8]) {
+                    ::calimero_storage::collections::Root::<Self>::sync(artifact).expect("fatal: sync failed");
+                }
+            }
+
             impl #impl_generics #ident #ty_generics #where_clause {
                 fn external() -> ::calimero_sdk::env::ext::External {
                     ::calimero_sdk::env::ext::External {}
@@ -257,9 +341,9 @@ impl<'a> TryFrom<StateImplInput<'a>> for StateImpl<'a> {
     fn try_from(input: StateImplInput<'a>) -> Result<Self, Self::Error> {
         let errors = Errors::new(input.item);
 
-        let (ident, generics) = match input.item {
-            StructOrEnumItem::Struct(item) => (&item.ident, &item.generics),
-            StructOrEnumItem::Enum(item) => (&item.ident, &item.generics),
+        let (ident, generics, attrs) = match input.item {
+            StructOrEnumItem::Struct(item) => (&item.ident, &item.generics, &item.attrs),
+            StructOrEnumItem::Enum(item) => (&item.ident, &item.generics, &item.attrs),
         };
 
         if ident == &*idents::input() {
@@ -286,13 +370,123 @@ impl<'a> TryFrom<StateImplInput<'a>> for StateImpl<'a> {
             }
         }
 
+        let mut fields = vec![];
+
+        if let StructOrEnumItem::Struct(item) = input.item {
+            for (index, field) in item.fields.iter().enumerate() {
+                let mut guards = vec![];
+
+                for attr in field.attrs.iter().filter(|attr| is_guard(attr)) {
+                    match attr.parse_args::<Guard>() {
+                        Ok(guard) => guards.push(guard),
+                        Err(err) => errors.subsume(err),
+                    }
+                }
+
+                if !guards.is_empty() {
+                    let member = field
+                        .ident
+                        .clone()
+                        .map_or_else(|| Member::from(index), Member::Named);
+
+                    fields.push(GuardedField { member, guards });
+                }
+            }
+        }
+
         errors.check()?;
 
         Ok(StateImpl {
             ident,
             generics,
             emits: &input.args.emits,
+            derives_merge: derives_merge(attrs),
+            fields,
             orig: input.item,
         })
     }
 }
+
+/// Whether the item has `Merge` in any of its `#[derive(...)]` attributes.
+fn derives_merge(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| path.segments.last().is_some_and(|seg| seg.ident == "Merge"))
+}
+
+/// Whether the attribute is `#[app::guard(..)]`.
+fn is_guard(attr: &Attribute) -> bool {
+    let segments = &attr.path().segments;
+
+    segments.len() == 2 && segments[0].ident == "app" && segments[1].ident == "guard"
+}
+
+/// The item without the guards on its fields, which aren't attributes the
+/// compiler knows what to do with once the state has been expanded.
+fn strip_guards(item: &StructOrEnumItem) -> TokenStream {
+    match item {
+        StructOrEnumItem::Struct(item) => {
+            let mut item = item.clone();
+
+            for field in &mut item.fields {
+                field.attrs.retain(|attr| !is_guard(attr));
+            }
+
+            item.to_token_stream()
+        }
+        StructOrEnumItem::Enum(item) => item.to_token_stream(),
+    }
+}
+
+/// Implements the guards declared on the fields, which the storage enforces
+/// for every change made to them, including those received from other nodes.
+fn guard(ident: &Ident, generics: &Generics, fields: &[GuardedField]) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if fields.is_empty() {
+        return quote! {
+            impl #impl_generics ::calimero_storage::guard::Guard for #ident #ty_generics #where_clause {}
+        };
+    }
+
+    let app = Ident::new("app", Span::call_site());
+
+    let (members, checks): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .map(|field| {
+            let checks = field.guards.iter().map(|guard| guard.check(&app));
+
+            (&field.member, quote! { #(#checks)&&* })
+        })
+        .unzip();
+
+    quote! {
+        impl #impl_generics ::calimero_storage::guard::Guard for #ident #ty_generics #where_clause {
+            #[allow(unused_variables, reason = "Not every guard checks the state")]
+            fn deny(&self) {
+                let #app = ::core::option::Option::Some(self);
+
+                #(
+                    if !(#checks) {
+                        ::calimero_storage::guard::Guard::deny(&self.#members);
+                    }
+                )*
+            }
+
+            #[allow(unused_variables, reason = "Not every guard checks the state")]
+            fn permits(&self, other: &Self) -> bool {
+                let #app = ::core::option::Option::Some(self);
+
+                #(
+                    ((#checks) || ::calimero_storage::guard::unchanged(&self.#members, &other.#members))
+                )&&*
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use serde_json::{from_str, json, Value};
+use syn::{parse_quote, Type};
+
+use super::*;
+
+fn json(fragment: &str) -> Value {
+    from_str(fragment).unwrap()
+}
+
+fn schema_of(ty: &Type) -> Value {
+    json(&schema(ty))
+}
+
+#[test]
+fn test_string_escapes() {
+    assert_eq!(string("plain"), r#""plain""#);
+    assert_eq!(
+        json(&string("\"quoted\"\\\n\t\u{1}")),
+        json!("\"quoted\"\\\n\t\u{1}")
+    );
+}
+
+#[test]
+fn test_schema_primitives() {
+    assert_eq!(schema_of(&parse_quote!(bool)), json!({ "type": "boolean" }));
+    assert_eq!(
+        schema_of(&parse_quote!(u64)),
+        json!({ "type": "integer", "minimum": 0_u8 })
+    );
+    assert_eq!(schema_of(&parse_quote!(i8)), json!({ "type": "integer" }));
+    assert_eq!(schema_of(&parse_quote!(f32)), json!({ "type": "number" }));
+    assert_eq!(schema_of(&parse_quote!(&str)), json!({ "type": "string" }));
+    assert_eq!(schema_of(&parse_quote!(())), json!({ "type": "null" }));
+}
+
+#[test]
+fn test_schema_containers() {
+    assert_eq!(
+        schema_of(&parse_quote!(Vec<String>)),
+        json!({ "type": "array", "items": { "type": "string" } })
+    );
+    assert_eq!(
+        schema_of(&parse_quote!([u8; 32])),
+        json!({ "type": "array", "items": { "type": "integer", "minimum": 0_u8 } })
+    );
+    assert_eq!(
+        schema_of(&parse_quote!(Option<Box<bool>>)),
+        json!({ "anyOf": [{ "type": "boolean" }, { "type": "null" }] })
+    );
+    assert_eq!(
+        schema_of(&parse_quote!(std::collections::BTreeMap<String, u8>)),
+        json!({
+            "type": "object",
+            "additionalProperties": { "type": "integer", "minimum": 0_u8 }
+        })
+    );
+    assert_eq!(
+        schema_of(&parse_quote!((String, bool))),
+        json!({
+            "type": "array",
+            "prefixItems": [{ "type": "string" }, { "type": "boolean" }],
+            "items": false
+        })
+    );
+}
+
+#[test]
+fn test_schema_custom_types() {
+    assert_eq!(
+        schema_of(&parse_quote!(crate::types::Item)),
+        json!({ "$ref": "#/types/Item" })
+    );
+    assert_eq!(
+        schema_of(&parse_quote!(Vec<Item>)),
+        json!({ "type": "array", "items": { "$ref": "#/types/Item" } })
+    );
+    assert_eq!(
+        schema_of(&parse_quote!(Page<Item>)),
+        json!({ "title": "Page<Item>" })
+    );
+}
+
+#[test]
+fn test_returns() {
+    assert_eq!(returns(&parse_quote!(())), (None, None));
+
+    let (value, error) = returns(&parse_quote!(Result<Option<u8>, String>));
+    assert_eq!(
+        value.map(|value| json(&value)),
+        Some(json!({ "anyOf": [{ "type": "integer", "minimum": 0_u8 }, { "type": "null" }] }))
+    );
+    assert_eq!(
+        error.map(|error| json(&error)),
+        Some(json!({ "type": "string" }))
+    );
+
+    let (value, error) = returns(&parse_quote!(calimero_sdk::Result<()>));
+    assert_eq!(value, None);
+    assert_eq!(
+        error.map(|error| json(&error)),
+        Some(json!({ "type": "string" }))
+    );
+
+    let (value, error) = returns(&parse_quote!(bool));
+    assert_eq!(
+        value.map(|value| json(&value)),
+        Some(json!({ "type": "boolean" }))
+    );
+    assert_eq!(error, None);
+}
+
+#[test]
+fn test_item_struct() {
+    let declared: StructOrEnumItem = parse_quote! {
+        struct Item {
+            name: String,
+            tags: Vec<Tag>,
+        }
+    };
+
+    assert_eq!(
+        json(&item(&declared)),
+        json!({
+            "type": {
+                "name": "Item",
+                "schema": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "tags": { "type": "array", "items": { "$ref": "#/types/Tag" } }
+                    },
+                    "required": ["name", "tags"]
+                }
+            }
+        })
+    );
+
+    let declared: StructOrEnumItem = parse_quote!(
+        struct Marker;
+    );
+
+    assert_eq!(
+        json(&item(&declared)),
+        json!({ "type": { "name": "Marker", "schema": { "type": "null" } } })
+    );
+}
+
+#[test]
+fn test_item_enum() {
+    let declared: StructOrEnumItem = parse_quote! {
+        enum Shape {
+            Empty,
+            Circle(u32),
+            Rect { width: u32, height: u32 },
+        }
+    };
+
+    let integer = json!({ "type": "integer", "minimum": 0_u8 });
+
+    assert_eq!(
+        json(&item(&declared)),
+        json!({
+            "type": {
+                "name": "Shape",
+                "schema": {
+                    "oneOf": [
+                        { "const": "Empty" },
+                        {
+                            "type": "object",
+                            "properties": { "Circle": integer },
+                            "required": ["Circle"],
+                            "additionalProperties": false
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "Rect": {
+                                    "type": "object",
+                                    "properties": { "width": integer, "height": integer },
+                                    "required": ["width", "height"]
+                                }
+                            },
+                            "required": ["Rect"],
+                            "additionalProperties": false
+                        }
+                    ]
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_section() {
+    assert!(section(&[]).is_empty());
+
+    let section = section(&[r#"{"event":{}}"#.to_owned()]).to_string();
+
+    assert!(section.contains(r#"link_section = "calimero_abi""#));
+    assert!(section.contains(r#"b"{\"event\":{}}\n""#));
+    assert!(section.contains("[u8 ; 13usize]"));
+}
@@ -0,0 +1,96 @@
+use serde_json::{from_str, json, Value};
+use syn::{parse_quote, ImplItemFn, Path};
+
+use super::*;
+use crate::reserved;
+
+fn abi(item: &ImplItemFn) -> Value {
+    reserved::init();
+
+    let type_: Path = parse_quote!(MyType);
+
+    let Ok(LogicMethod::Public(method)) = LogicMethod::try_from(LogicMethodImplInput {
+        item,
+        type_: &type_,
+    }) else {
+        panic!("expected a public method");
+    };
+
+    from_str(&method.abi()).unwrap()
+}
+
+#[test]
+fn test_abi_method() {
+    let item: ImplItemFn = parse_quote! {
+        #[app::guard(capability = ManageMembers)]
+        #[app::guard(Self::is_admin)]
+        pub fn set(&mut self, key: String, value: Option<Item>) -> Result<bool, Error> {
+            todo!()
+        }
+    };
+
+    assert_eq!(
+        abi(&item),
+        json!({
+            "method": {
+                "name": "set",
+                "arguments": [
+                    { "name": "key", "schema": { "type": "string" } },
+                    {
+                        "name": "value",
+                        "schema": { "anyOf": [{ "$ref": "#/types/Item" }, { "type": "null" }] }
+                    }
+                ],
+                "returns": { "type": "boolean" },
+                "error": { "$ref": "#/types/Error" },
+                "guards": [{ "capability": "manageMembers" }, { "state": "Self::is_admin" }],
+                "mutates": true,
+                "modifiers": []
+            }
+        })
+    );
+}
+
+#[test]
+fn test_abi_view() {
+    let item: ImplItemFn = parse_quote! {
+        pub fn get(&self, key: &str) -> Option<&str> {
+            todo!()
+        }
+    };
+
+    assert_eq!(
+        abi(&item),
+        json!({
+            "method": {
+                "name": "get",
+                "arguments": [{ "name": "key", "schema": { "type": "string" } }],
+                "returns": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+                "mutates": false,
+                "modifiers": []
+            }
+        })
+    );
+}
+
+#[test]
+fn test_abi_init() {
+    let item: ImplItemFn = parse_quote! {
+        #[app::init]
+        pub fn init() -> MyType {
+            todo!()
+        }
+    };
+
+    assert_eq!(
+        abi(&item),
+        json!({
+            "method": {
+                "name": "init",
+                "arguments": [],
+                "mutates": false,
+                "modifiers": ["init"]
+            }
+        })
+    );
+}
@@ -0,0 +1,87 @@
+use serde_json::{from_str, json, Value};
+use syn::parse_quote;
+
+use super::*;
+use crate::reserved;
+
+fn expand(item: &StructOrEnumItem) -> TokenStream {
+    reserved::init();
+
+    let args: StateArgs = parse_quote!();
+
+    let Ok(state) = StateImpl::try_from(StateImplInput { item, args: &args }) else {
+        panic!("expected a valid state");
+    };
+
+    state.to_token_stream()
+}
+
+#[test]
+fn test_abi_fields() {
+    let item: StructOrEnumItem = parse_quote! {
+        struct Club {
+            #[app::guard(capability = ManageMembers)]
+            #[app::guard(Self::is_admin)]
+            members: UnorderedSet<String>,
+            name: String,
+        }
+    };
+
+    reserved::init();
+
+    let args: StateArgs = parse_quote!();
+
+    let Ok(state) = StateImpl::try_from(StateImplInput {
+        item: &item,
+        args: &args,
+    }) else {
+        panic!("expected a valid state");
+    };
+
+    let fields = state
+        .fields
+        .iter()
+        .map(|field| from_str::<Value>(&field.abi()).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        fields,
+        vec![json!({
+            "field": {
+                "name": "members",
+                "guards": [{ "capability": "manageMembers" }, { "state": "Self::is_admin" }]
+            }
+        })]
+    );
+}
+
+#[test]
+fn test_guards_are_stripped() {
+    let item: StructOrEnumItem = parse_quote! {
+        struct Club {
+            #[app::guard(Self::is_admin)]
+            #[doc = "The members of the club"]
+            members: UnorderedSet<String>,
+        }
+    };
+
+    let expanded = expand(&item).to_string();
+
+    assert!(!expanded.contains("app :: guard"));
+    assert!(expanded.contains("The members of the club"));
+    assert!(expanded.contains("fn deny"));
+}
+
+#[test]
+fn test_unguarded_state() {
+    let item: StructOrEnumItem = parse_quote! {
+        struct Club {
+            members: UnorderedSet<String>,
+        }
+    };
+
+    let expanded = expand(&item).to_string();
+
+    assert!(expanded.contains(":: calimero_storage :: guard :: Guard for Club { }"));
+    assert!(!expanded.contains("fn deny"));
+}
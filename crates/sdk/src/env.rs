@@ -1,3 +1,4 @@
+use core::time::Duration;
 use std::panic::set_hook;
 
 use crate::event::AppEvent;
@@ -129,6 +130,38 @@ pub fn executor_id() -> [u8; 32] {
     read_register_sized(DATA_REGISTER).expect("Must have executor identity.")
 }
 
+/// A capability a member can hold in the context config.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Capability {
+    ManageApplication,
+    ManageMembers,
+    Proxy,
+}
+
+impl Capability {
+    pub(crate) const fn discriminant(self) -> u32 {
+        match self {
+            Self::ManageApplication => 0,
+            Self::ManageMembers => 1,
+            Self::Proxy => 2,
+        }
+    }
+}
+
+/// Whether the executor holds the capability in the context config.
+#[must_use]
+pub fn has_capability(capability: Capability) -> bool {
+    unsafe { sys::has_capability(capability.discriminant()) }
+        .try_into()
+        .unwrap_or_else(expected_boolean)
+}
+
+/// Aborts the call, as the executor is not authorized to make it.
+pub fn unauthorized() -> ! {
+    unsafe { sys::unauthorized() }
+}
+
 #[inline]
 #[must_use]
 pub fn input() -> Option<Vec<u8>> {
@@ -162,6 +195,12 @@ pub fn commit(root_hash: &[u8; 32], artifact: &[u8]) {
     unsafe { sys::commit(Buffer::from(&root_hash[..]), Buffer::from(artifact)) }
 }
 
+/// Hands the node a description of the changes made to entities, for it to
+/// pass on to subscribers.
+pub fn commit_changes(changes: &[u8]) {
+    unsafe { sys::commit_changes(Buffer::from(changes)) }
+}
+
 #[inline]
 pub fn storage_read(key: &[u8]) -> Option<Vec<u8>> {
     unsafe { sys::storage_read(Buffer::from(key), DATA_REGISTER) }
@@ -182,6 +221,31 @@ pub fn storage_write(key: &[u8], value: &[u8]) -> bool {
         .unwrap_or_else(expected_boolean)
 }
 
+/// Reads from node-local storage, which is never replicated to other members.
+#[inline]
+pub fn private_storage_read(key: &[u8]) -> Option<Vec<u8>> {
+    unsafe { sys::private_storage_read(Buffer::from(key), DATA_REGISTER) }
+        .try_into()
+        .unwrap_or_else(expected_boolean::<bool>)
+        .then(|| read_register(DATA_REGISTER).unwrap_or_else(expected_register))
+}
+
+/// Removes from node-local storage, which is never replicated to other members.
+#[inline]
+pub fn private_storage_remove(key: &[u8]) -> bool {
+    unsafe { sys::private_storage_remove(Buffer::from(key), DATA_REGISTER).try_into() }
+        .unwrap_or_else(expected_boolean)
+}
+
+/// Writes to node-local storage, which is never replicated to other members.
+#[inline]
+pub fn private_storage_write(key: &[u8], value: &[u8]) -> bool {
+    unsafe {
+        sys::private_storage_write(Buffer::from(key), Buffer::from(value), DATA_REGISTER).try_into()
+    }
+    .unwrap_or_else(expected_boolean)
+}
+
 /// Fill the buffer with random bytes.
 #[inline]
 pub fn random_bytes(buf: &mut [u8]) {
@@ -204,3 +268,22 @@ pub fn time_now() -> u64 {
 
     u64::from_le_bytes(bytes)
 }
+
+/// Gets the period after which orphaned entities are discarded, if the node
+/// has configured one.
+#[inline]
+#[must_use]
+pub fn orphan_expiry() -> Option<Duration> {
+    let mut bytes = [0; 8];
+
+    #[expect(
+        clippy::needless_borrows_for_generic_args,
+        reason = "we don't want to copy the buffer, but write to the same one that's returned"
+    )]
+    let configured = unsafe { sys::orphan_expiry(BufferMut::new(&mut bytes)) };
+
+    configured
+        .try_into()
+        .unwrap_or_else(expected_boolean::<bool>)
+        .then(|| Duration::from_nanos(u64::from_le_bytes(bytes)))
+}
@@ -2,7 +2,7 @@ use core::fmt;
 use std::borrow::Cow;
 use std::str::FromStr;
 
-use borsh::{to_vec as to_borsh_vec, to_vec, BorshDeserialize, BorshSerialize};
+use borsh::{from_slice, to_vec as to_borsh_vec, to_vec, BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
 use super::{expected_boolean, expected_register, panic_str, read_register, DATA_REGISTER};
@@ -174,7 +174,73 @@ impl DraftProposal {
     }
 }
 
+/// A proposal pending in the proxy contract of the context.
+///
+/// Proposals are removed from the proxy once they have gathered enough
+/// approvals to be executed, or are deleted.
+///
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Proposal {
+    /// The ID of the proposal.
+    pub id: ProposalId,
+
+    /// The identity that created the proposal.
+    pub author_id: [u8; 32],
+
+    /// The actions to be executed by the proposal.
+    pub actions: Vec<ProposalAction>,
+}
+
+/// A change to a proposal in the proxy contract, as observed by the node.
+///
+/// An application that declares a method named `on_proposal`, taking
+/// `&mut self` and a single `ProposalEvent`, is called with each one. It is
+/// only called on the node of the author of the proposal, as the author, and
+/// the changes it makes reach the other members like those of any other call.
+/// Whether a proposal was executed or deleted is likewise only known there.
+///
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "PascalCase")]
+#[non_exhaustive]
+pub enum ProposalEvent {
+    /// A proposal was created.
+    #[serde(rename_all = "camelCase")]
+    ProposalCreated {
+        /// The ID of the proposal.
+        proposal_id: ProposalId,
+    },
+
+    /// A proposal was approved.
+    #[serde(rename_all = "camelCase")]
+    ApprovalAdded {
+        /// The ID of the proposal.
+        proposal_id: ProposalId,
+
+        /// The number of approvals the proposal has gathered so far.
+        approvals: usize,
+    },
+
+    /// A proposal gathered enough approvals, and was executed.
+    #[serde(rename_all = "camelCase")]
+    ProposalExecuted {
+        /// The ID of the proposal.
+        proposal_id: ProposalId,
+    },
+
+    /// A proposal was deleted by its author.
+    #[serde(rename_all = "camelCase")]
+    ProposalDeleted {
+        /// The ID of the proposal.
+        proposal_id: ProposalId,
+    },
+}
+
 /// Interface for interacting with external proposals for blockchain actions.
+///
+/// Queries against the proxy contract are resolved at most once per
+/// execution, so they see the proxy as it was when first asked.
+///
 #[derive(Clone, Copy, Debug)]
 pub struct External;
 
@@ -188,6 +254,54 @@ impl External {
     pub fn approve(self, proposal_id: ProposalId) {
         unsafe { sys::approve_proposal(BufferMut::new(&proposal_id)) }
     }
+
+    /// Get a proposal, if it is still pending in the proxy contract.
+    #[must_use]
+    pub fn proposal(self, proposal_id: ProposalId) -> Option<Proposal> {
+        let proposal =
+            unsafe { sys::proxy_proposal(Buffer::from(&proposal_id.0[..]), DATA_REGISTER) }
+                .try_into()
+                .unwrap_or_else(expected_boolean::<bool>)
+                .then(|| read_register(DATA_REGISTER).unwrap_or_else(expected_register))?;
+
+        match from_slice(&proposal) {
+            Ok(proposal) => Some(proposal),
+            Err(err) => panic_str(&format!("Cannot deserialize proposal: {err:?}")),
+        }
+    }
+
+    /// Get the identities that have approved a pending proposal.
+    #[must_use]
+    pub fn approvers(self, proposal_id: ProposalId) -> Vec<[u8; 32]> {
+        let Some(approvers) =
+            unsafe { sys::proxy_approvers(Buffer::from(&proposal_id.0[..]), DATA_REGISTER) }
+                .try_into()
+                .unwrap_or_else(expected_boolean::<bool>)
+                .then(|| read_register(DATA_REGISTER).unwrap_or_else(expected_register))
+        else {
+            return Vec::new();
+        };
+
+        match from_slice(&approvers) {
+            Ok(approvers) => approvers,
+            Err(err) => panic_str(&format!("Cannot deserialize approvers: {err:?}")),
+        }
+    }
+
+    /// Get the number of approvals a pending proposal has gathered.
+    #[must_use]
+    pub fn approvals(self, proposal_id: ProposalId) -> usize {
+        self.approvers(proposal_id).len()
+    }
+
+    /// Get a value set in the proxy contract by a `SetContextValue` action.
+    #[must_use]
+    pub fn context_value(self, key: &[u8]) -> Option<Vec<u8>> {
+        unsafe { sys::proxy_context_value(Buffer::from(key), DATA_REGISTER) }
+            .try_into()
+            .unwrap_or_else(expected_boolean::<bool>)
+            .then(|| read_register(DATA_REGISTER).unwrap_or_else(expected_register))
+    }
 }
 
 /// Unique identifier for a proposal.
@@ -2,9 +2,12 @@ pub use {borsh, serde, serde_json};
 
 pub mod env;
 pub mod event;
+pub mod private;
 mod returns;
 pub mod state;
 mod sys;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod testing;
 pub mod types;
 
 use core::result::Result as CoreResult;
@@ -12,7 +15,9 @@ use core::result::Result as CoreResult;
 pub type Result<T> = CoreResult<T, types::Error>;
 
 pub mod app {
-    pub use calimero_sdk_macros::{destroy, emit, event, init, logic, state};
+    pub use calimero_sdk_macros::{
+        destroy, emit, event, guard, init, logic, migrate, schema, state,
+    };
 }
 
 #[doc(hidden)]
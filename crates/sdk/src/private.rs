@@ -0,0 +1,101 @@
+//! Node-local private state.
+//!
+//! Values stored here live alongside the context state on this node only. They
+//! are not part of the root hash, never appear in a delta, and are never sent
+//! to other members during sync, which makes them suitable for secrets such as
+//! key material, or for caches that each node maintains for itself.
+//!
+//! Keys are at most 32 bytes long, and values are serialised using Borsh.
+//!
+//! ```ignore
+//! let mut private = PrivateStorage::new();
+//!
+//! private.insert(b"draft", &draft)?;
+//!
+//! let draft: Option<String> = private.get(b"draft")?;
+//! ```
+
+#[cfg(test)]
+#[path = "tests/private.rs"]
+mod tests;
+
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+use crate::env;
+use crate::types::Error;
+
+/// The maximum length of a private storage key, in bytes.
+pub const MAX_KEY_LEN: usize = 32;
+
+/// The node-local storage of the context.
+///
+/// Every handle refers to the same storage, so one can be created wherever it
+/// is needed.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct PrivateStorage;
+
+impl PrivateStorage {
+    /// Creates a handle to the node-local storage of the context.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Gets the value stored under a key, if any.
+    ///
+    /// # Errors
+    ///
+    /// If the key is too long or the stored value cannot be deserialised, an
+    /// error will be returned.
+    pub fn get<T: BorshDeserialize>(&self, key: &[u8]) -> Result<Option<T>, Error> {
+        check_key(key)?;
+
+        env::private_storage_read(key)
+            .map(|value| from_slice(&value))
+            .transpose()
+            .map_err(Error::from)
+    }
+
+    /// Whether a value is stored under a key.
+    ///
+    /// # Errors
+    ///
+    /// If the key is too long, an error will be returned.
+    pub fn contains(&self, key: &[u8]) -> Result<bool, Error> {
+        check_key(key)?;
+
+        Ok(env::private_storage_read(key).is_some())
+    }
+
+    /// Stores a value under a key, returning whether a value was replaced.
+    ///
+    /// # Errors
+    ///
+    /// If the key is too long or the value cannot be serialised, an error will
+    /// be returned.
+    pub fn insert<T: BorshSerialize>(&mut self, key: &[u8], value: &T) -> Result<bool, Error> {
+        check_key(key)?;
+
+        Ok(env::private_storage_write(key, &to_vec(value)?))
+    }
+
+    /// Removes the value stored under a key, returning whether one existed.
+    ///
+    /// # Errors
+    ///
+    /// If the key is too long, an error will be returned.
+    pub fn remove(&mut self, key: &[u8]) -> Result<bool, Error> {
+        check_key(key)?;
+
+        Ok(env::private_storage_remove(key))
+    }
+}
+
+fn check_key(key: &[u8]) -> Result<(), Error> {
+    if key.len() > MAX_KEY_LEN {
+        return Err(Error::msg("private storage key exceeds 32 bytes"));
+    }
+
+    Ok(())
+}
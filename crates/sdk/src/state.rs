@@ -17,3 +17,13 @@ impl<T: AppState> Identity<T> for T {}
 pub trait AppStateInit: Sized {
     type Return: Identity<Self>;
 }
+
+/// Evaluates the guards declared on methods with `#[app::guard]`.
+///
+/// This is implemented by `#[app::logic]`.
+#[doc(hidden)]
+pub trait AppGuard: Sized {
+    /// Whether the executor may call `method`, against the state if there is
+    /// any yet.
+    fn authorize(app: Option<&Self>, method: &str) -> bool;
+}
@@ -14,6 +14,8 @@ wasm_imports! {
         // --
         fn context_id(register_id: RegisterId);
         fn executor_id(register_id: RegisterId);
+        fn has_capability(capability: u32) -> Bool;
+        fn unauthorized() -> !;
         // --
         fn input(register_id: RegisterId);
         fn value_return(value: ValueReturn<'_>);
@@ -21,11 +23,16 @@ wasm_imports! {
         fn emit(event: Event<'_>);
         // --
         fn commit(root: Buffer<'_>, artifact: Buffer<'_>);
+        fn commit_changes(changes: Buffer<'_>);
         // --
         fn storage_read(key: Buffer<'_>, register_id: RegisterId) -> Bool;
         fn storage_remove(key: Buffer<'_>, register_id: RegisterId) -> Bool;
         fn storage_write(key: Buffer<'_>, value: Buffer<'_>, register_id: RegisterId) -> Bool;
         // --
+        fn private_storage_read(key: Buffer<'_>, register_id: RegisterId) -> Bool;
+        fn private_storage_remove(key: Buffer<'_>, register_id: RegisterId) -> Bool;
+        fn private_storage_write(key: Buffer<'_>, value: Buffer<'_>, register_id: RegisterId) -> Bool;
+        // --
         fn fetch(
             url: Buffer<'_>,
             method: Buffer<'_>,
@@ -36,9 +43,14 @@ wasm_imports! {
         // --
         fn random_bytes(buf: BufferMut<'_>);
         fn time_now(buf: BufferMut<'_>);
+        fn orphan_expiry(buf: BufferMut<'_>) -> Bool;
         // --
         fn send_proposal(value: Buffer<'_>, buf: BufferMut<'_>);
         fn approve_proposal(value: Buffer<'_>);
+        // --
+        fn proxy_proposal(id: Buffer<'_>, register_id: RegisterId) -> Bool;
+        fn proxy_approvers(id: Buffer<'_>, register_id: RegisterId) -> Bool;
+        fn proxy_context_value(key: Buffer<'_>, register_id: RegisterId) -> Bool;
     }
 }
 
@@ -53,10 +65,12 @@ macro_rules! wasm_imports {
                     )*
                 }
             } else {
+                // natively, the host functions are provided by a simulation,
+                // which panics with the same explanation as before when there
+                // is none, so only tests running one can tell the difference
                 $(
-                    #[expect(unused_variables, reason = "Needed due to macro expansion")]
                     pub unsafe fn $func_name($($arg: $arg_ty),*) $(-> $returns)? {
-                        panic!("host function `{}` is only available when compiled for wasm32", stringify!($func_name));
+                        crate::testing::host::$func_name($($arg),*)
                     }
                 )*
             }
@@ -0,0 +1,624 @@
+//! A native harness for testing applications across simulated replicas.
+//!
+//! Applications normally only run inside the runtime, which provides the host
+//! functions they call into. Outside of wasm these are instead provided by a
+//! [`Simulation`], which holds the state of a number of replicas of the same
+//! application in memory. Methods can be called on any replica, as any
+//! executor, and the state deltas this produces are held as [`Message`]s until
+//! they are delivered, dropped, or duplicated, in whichever order the test
+//! chooses. Replicas can also be partitioned from one another, and brought back
+//! together through the same state sync the node falls back on, before checking
+//! that they have all converged.
+//!
+//! ```ignore
+//! let mut sim = Simulation::<KvStore>::new(3);
+//!
+//! sim.init(0, ALICE, KvStore::init);
+//! sim.call(1, BOB, |app| app.set("key".to_owned(), "value".to_owned()))?;
+//! sim.deliver_all();
+//!
+//! sim.assert_converged();
+//! ```
+
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::DerefMut;
+use core::time::Duration;
+use std::collections::BTreeMap;
+
+use crate::env::Capability;
+use crate::event::{self, AppEventExt};
+use crate::state::{AppGuard, AppState};
+
+pub(crate) mod host;
+
+use host::Host;
+
+/// The identifier of the context all replicas belong to.
+pub const CONTEXT_ID: [u8; 32] = [236; 32];
+
+/// The time at which simulations start, in nanoseconds since the Unix epoch.
+const GENESIS: u64 = 1_700_000_000_000_000_000;
+
+/// The most artifacts a state sync may exchange before it is deemed stuck.
+const MAX_SYNC_ROUNDS: usize = 1_000;
+
+/// A committed root hash, and the artifact committed along with it.
+type Commit = ([u8; 32], Vec<u8>);
+
+/// Access to the persisted state of an application.
+///
+/// This is implemented by `#[app::state]`, in terms of the root collection of
+/// the storage crate, which the SDK can't depend on itself.
+#[doc(hidden)]
+pub trait SimulatedState: AppState {
+    type Root: DerefMut<Target = Self>;
+
+    fn init(f: impl FnOnce() -> Self) -> Self::Root;
+
+    fn fetch() -> Option<Self::Root>;
+
+    fn migrate(f: impl FnOnce() -> Self) -> Self::Root;
+
+    fn guard(root: &Self::Root);
+
+    fn commit(root: Self::Root);
+
+    fn sync(artifact: &[u8]);
+}
+
+/// Whether host functions are currently being provided by a simulation.
+#[must_use]
+pub fn is_active() -> bool {
+    host::is_active()
+}
+
+/// An event emitted by an application.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EmittedEvent {
+    pub kind: String,
+    pub data: Vec<u8>,
+}
+
+/// A proposal sent by an application.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Proposal {
+    pub id: [u8; 32],
+    pub actions: Vec<u8>,
+}
+
+/// The proxy contract of the context, shared by every replica.
+///
+/// Proposals sent by an application are pending as soon as the call that sent
+/// them returns, approved by their author, and are never executed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Proxy {
+    proposals: BTreeMap<[u8; 32], ([u8; 32], Vec<u8>)>,
+    approvers: BTreeMap<[u8; 32], Vec<[u8; 32]>>,
+    context_values: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl Proxy {
+    fn approve(&mut self, proposal_id: [u8; 32], identity: [u8; 32]) {
+        if !self.proposals.contains_key(&proposal_id) {
+            return;
+        }
+
+        let approvers = self.approvers.entry(proposal_id).or_default();
+
+        if !approvers.contains(&identity) {
+            approvers.push(identity);
+        }
+    }
+
+    /// The proposal, as borsh-encoded by the proxy contract.
+    pub(crate) fn proposal(&self, proposal_id: &[u8]) -> Option<Vec<u8>> {
+        let (author_id, actions) = self.proposals.get(proposal_id)?;
+
+        // the actions are already a borsh-encoded sequence
+        Some([proposal_id, author_id, actions].concat())
+    }
+
+    /// The approvers of the proposal, as borsh-encoded by the proxy contract.
+    pub(crate) fn approvers(&self, proposal_id: &[u8]) -> Option<Vec<u8>> {
+        let approvers = self.approvers.get(proposal_id)?;
+
+        borsh::to_vec(approvers).ok()
+    }
+
+    pub(crate) fn context_value(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.context_values.get(key).cloned()
+    }
+}
+
+/// The state held by a single replica.
+#[derive(Debug, Default)]
+pub struct Replica {
+    identity: [u8; 32],
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    private_storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    root_hash: Option<[u8; 32]>,
+    logs: Vec<String>,
+    events: Vec<EmittedEvent>,
+    proposals: Vec<Proposal>,
+    approvals: Vec<[u8; 32]>,
+}
+
+impl Replica {
+    /// The root hash of the replica's state, as of its last commit.
+    #[must_use]
+    pub const fn root_hash(&self) -> Option<[u8; 32]> {
+        self.root_hash
+    }
+
+    /// Reads a value from the replica's node-local storage.
+    #[must_use]
+    pub fn private_storage(&self, key: &[u8]) -> Option<&[u8]> {
+        self.private_storage.get(key).map(Vec::as_slice)
+    }
+
+    /// Everything the application has logged on this replica.
+    #[must_use]
+    pub fn logs(&self) -> &[String] {
+        &self.logs
+    }
+
+    /// Every event the application has emitted on this replica.
+    #[must_use]
+    pub fn events(&self) -> &[EmittedEvent] {
+        &self.events
+    }
+
+    /// Every proposal the application has sent from this replica.
+    #[must_use]
+    pub fn proposals(&self) -> &[Proposal] {
+        &self.proposals
+    }
+
+    /// Every proposal the application has approved from this replica.
+    #[must_use]
+    pub fn approvals(&self) -> &[[u8; 32]] {
+        &self.approvals
+    }
+}
+
+/// Identifies a message held by a [`Simulation`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MessageId(u64);
+
+/// A state delta on its way from one replica to another.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Message {
+    pub from: usize,
+    pub to: usize,
+    /// The executor of the call that produced the delta.
+    pub executor: [u8; 32],
+    /// The root hash of the sender after the call.
+    pub root_hash: [u8; 32],
+    pub artifact: Vec<u8>,
+}
+
+/// A number of replicas of an application, and the messages between them.
+///
+/// Replicas are identified by their index, from `0` to `replicas - 1`. Every
+/// call that changes a replica's state broadcasts the resulting delta to all
+/// the replicas it can currently reach, as one [`Message`] per recipient.
+/// Nothing is delivered until the test says so.
+#[derive(Debug)]
+pub struct Simulation<T> {
+    replicas: Vec<Replica>,
+    groups: Vec<usize>,
+    messages: BTreeMap<MessageId, Message>,
+    next_message: u64,
+    capabilities: BTreeMap<[u8; 32], Vec<Capability>>,
+    proxy: Proxy,
+    clock: u64,
+    rng: u64,
+    orphan_expiry: Option<u64>,
+    _state: PhantomData<T>,
+}
+
+impl<T> Simulation<T>
+where
+    T: SimulatedState,
+    for<'a> T::Event<'a>: AppEventExt,
+{
+    /// Creates a simulation with the given number of replicas, none of which
+    /// have any state yet.
+    #[must_use]
+    pub fn new(replicas: usize) -> Self {
+        Self {
+            replicas: (0..replicas).map(|_| Replica::default()).collect(),
+            groups: vec![0; replicas],
+            messages: BTreeMap::new(),
+            next_message: 0,
+            capabilities: BTreeMap::new(),
+            proxy: Proxy::default(),
+            clock: GENESIS,
+            rng: 0,
+            orphan_expiry: None,
+            _state: PhantomData,
+        }
+    }
+
+    /// Seeds the random bytes given to the application.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = seed;
+        self
+    }
+
+    /// Sets the period after which the replicas discard orphaned entities,
+    /// as a node would from its configuration.
+    #[must_use]
+    pub fn with_orphan_expiry(mut self, expiry: Duration) -> Self {
+        self.orphan_expiry = Some(u64::try_from(expiry.as_nanos()).unwrap_or(u64::MAX));
+        self
+    }
+
+    /// Sets the identity a replica syncs as, which is what its peers evaluate
+    /// the guards of the state for when comparing their state with it.
+    /// Replicas sync as `[0; 32]` unless given one.
+    #[must_use]
+    pub fn with_identity(mut self, replica: usize, identity: [u8; 32]) -> Self {
+        self.replicas[replica].identity = identity;
+        self
+    }
+
+    /// Grants an identity a capability in the context config.
+    pub fn grant(&mut self, identity: [u8; 32], capability: Capability) {
+        let capabilities = self.capabilities.entry(identity).or_default();
+
+        if !capabilities.contains(&capability) {
+            capabilities.push(capability);
+        }
+    }
+
+    /// Approves a pending proposal in the proxy contract, as `identity`.
+    pub fn approve(&mut self, identity: [u8; 32], proposal_id: [u8; 32]) {
+        self.proxy.approve(proposal_id, identity);
+    }
+
+    /// Sets a value in the proxy contract, as a `SetContextValue` action
+    /// would.
+    pub fn set_context_value(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        let _ignored = self.proxy.context_values.insert(key, value);
+    }
+
+    /// Gets a replica.
+    #[must_use]
+    pub fn replica(&self, replica: usize) -> &Replica {
+        &self.replicas[replica]
+    }
+
+    /// Initialises the state on a replica, as `executor`, broadcasting it to
+    /// the rest.
+    pub fn init(&mut self, replica: usize, executor: [u8; 32], f: impl FnOnce() -> T) {
+        self.execute(replica, executor, || {
+            assert!(
+                T::fetch().is_none(),
+                "Cannot initialize over already existing state."
+            );
+
+            T::commit(T::init(f));
+        });
+    }
+
+    /// Upgrades the application to one whose state is of type `U`, migrating
+    /// the state of every replica that has one with `f`, as `executor`.
+    ///
+    /// Each replica broadcasts its migrated state to the rest, as a node does
+    /// on its first execution after an upgrade, for the replicas to converge
+    /// on one of them. The previous state can be read within `f` by fetching
+    /// it as `T`, just as an `#[app::migrate]` method would.
+    pub fn migrate<U>(self, executor: [u8; 32], f: impl Fn() -> U) -> Simulation<U>
+    where
+        U: SimulatedState,
+        for<'a> U::Event<'a>: AppEventExt,
+    {
+        let mut upgraded = Simulation {
+            replicas: self.replicas,
+            groups: self.groups,
+            messages: self.messages,
+            next_message: self.next_message,
+            capabilities: self.capabilities,
+            proxy: self.proxy,
+            clock: self.clock,
+            rng: self.rng,
+            orphan_expiry: self.orphan_expiry,
+            _state: PhantomData,
+        };
+
+        for replica in 0..upgraded.replicas.len() {
+            if upgraded.replicas[replica].root_hash.is_some() {
+                upgraded.execute(replica, executor, || U::commit(U::migrate(&f)));
+            }
+        }
+
+        upgraded
+    }
+
+    /// Calls a method on a replica, as `executor`, broadcasting any change to
+    /// its state to the rest.
+    ///
+    /// The guards on the fields of the state are enforced, and a change that
+    /// `executor` is denied panics, as the node would abort the call. Those on
+    /// the method can be checked with [`authorize`](Self::authorize).
+    pub fn call<R>(
+        &mut self,
+        replica: usize,
+        executor: [u8; 32],
+        f: impl FnOnce(&mut T) -> R,
+    ) -> R {
+        self.execute(replica, executor, || {
+            let mut app = fetch::<T>();
+
+            T::guard(&app);
+
+            let output = f(&mut app);
+
+            T::commit(app);
+
+            output
+        })
+    }
+
+    /// Reads the state of a replica, without changing it.
+    pub fn view<R>(&mut self, replica: usize, f: impl FnOnce(&T) -> R) -> R {
+        self.execute(replica, [0; 32], || f(&fetch::<T>()))
+    }
+
+    /// The messages that have yet to be delivered, in the order they were
+    /// sent.
+    pub fn messages(&self) -> impl Iterator<Item = (MessageId, &Message)> {
+        self.messages.iter().map(|(id, message)| (*id, message))
+    }
+
+    /// Delivers a message, applying its delta to the recipient.
+    ///
+    /// Returns whether the recipient's root hash matches the sender's
+    /// afterwards. A node would fall back on a [`sync`](Self::sync) with the
+    /// sender when it doesn't.
+    pub fn deliver(&mut self, id: MessageId) -> bool {
+        let Some(message) = self.messages.remove(&id) else {
+            panic!("no message with id {id:?}");
+        };
+
+        // applying a delta is never broadcast in turn
+        let _ignored = self.execute_raw(message.to, message.executor, || {
+            T::sync(&message.artifact);
+        });
+
+        self.replicas[message.to].root_hash == Some(message.root_hash)
+    }
+
+    /// Delivers every message, in the order they were sent.
+    pub fn deliver_all(&mut self) {
+        while let Some(id) = self.messages.keys().next().copied() {
+            let _ignored = self.deliver(id);
+        }
+    }
+
+    /// Drops a message, so that it never arrives.
+    pub fn discard(&mut self, id: MessageId) -> Message {
+        self.messages
+            .remove(&id)
+            .unwrap_or_else(|| panic!("no message with id {id:?}"))
+    }
+
+    /// Duplicates a message, so that it arrives twice.
+    pub fn duplicate(&mut self, id: MessageId) -> MessageId {
+        let Some(message) = self.messages.get(&id).cloned() else {
+            panic!("no message with id {id:?}");
+        };
+
+        self.send(message)
+    }
+
+    /// Partitions the replicas into groups, which can't reach each other.
+    ///
+    /// Deltas broadcast while partitioned only reach the sender's own group,
+    /// and messages already in flight are unaffected. Replicas that aren't
+    /// listed are isolated on their own.
+    pub fn partition(&mut self, groups: &[&[usize]]) {
+        let isolated = groups.len();
+
+        for (replica, group) in self.groups.iter_mut().enumerate() {
+            *group = groups
+                .iter()
+                .position(|members| members.contains(&replica))
+                .unwrap_or_else(|| isolated.saturating_add(replica));
+        }
+    }
+
+    /// Heals all partitions.
+    pub fn heal(&mut self) {
+        self.groups.fill(0);
+    }
+
+    /// Whether two replicas can currently reach each other.
+    #[must_use]
+    pub fn can_reach(&self, a: usize, b: usize) -> bool {
+        self.groups[a] == self.groups[b]
+    }
+
+    /// Lets time pass, such as for orphaned entities to expire.
+    pub fn advance_time(&mut self, by: Duration) {
+        let by = u64::try_from(by.as_nanos()).unwrap_or(u64::MAX);
+
+        self.clock = self.clock.saturating_add(by);
+    }
+
+    /// Synchronises two replicas, by exchanging comparisons of their state
+    /// until neither has anything more to send, as nodes do when they find
+    /// they have diverged.
+    pub fn sync(&mut self, a: usize, b: usize) {
+        assert!(self.can_reach(a, b), "replicas {a} and {b} are partitioned");
+
+        // the initiator opens with an empty artifact, asking for a comparison
+        let mut artifact = vec![];
+        let mut to = b;
+
+        for _ in 0..MAX_SYNC_ROUNDS {
+            // what is received is only trusted as far as the sender is
+            let from = if to == a { b } else { a };
+
+            let commit = self
+                .execute_raw(to, self.replicas[from].identity, || T::sync(&artifact))
+                .1;
+
+            artifact = commit.map(|(_, artifact)| artifact).unwrap_or_default();
+
+            if artifact.is_empty() {
+                return;
+            }
+
+            to = from;
+        }
+
+        panic!("sync between replicas {a} and {b} did not terminate");
+    }
+
+    /// Whether every replica has the same, non-empty state.
+    #[must_use]
+    pub fn is_converged(&self) -> bool {
+        let mut hashes = self.replicas.iter().map(Replica::root_hash);
+
+        hashes
+            .next()
+            .flatten()
+            .is_some_and(|first| hashes.all(|hash| hash == Some(first)))
+    }
+
+    /// Asserts that every replica has the same, non-empty state.
+    #[track_caller]
+    pub fn assert_converged(&self) {
+        assert!(
+            self.is_converged(),
+            "replicas have not converged: {:#?}",
+            self.replicas
+                .iter()
+                .map(|replica| replica
+                    .root_hash
+                    .map(|hash| bs58::encode(hash).into_string()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    fn send(&mut self, message: Message) -> MessageId {
+        let id = MessageId(self.next_message);
+
+        self.next_message = self.next_message.saturating_add(1);
+
+        let _ignored = self.messages.insert(id, message);
+
+        id
+    }
+
+    /// Executes on a replica, broadcasting the delta it produces, if any.
+    fn execute<R>(&mut self, replica: usize, executor: [u8; 32], f: impl FnOnce() -> R) -> R {
+        let (output, commit) = self.execute_raw(replica, executor, f);
+
+        if let Some((root_hash, artifact)) = commit {
+            if !artifact.is_empty() {
+                for to in 0..self.replicas.len() {
+                    if to != replica && self.can_reach(replica, to) {
+                        let _ignored = self.send(Message {
+                            from: replica,
+                            to,
+                            executor,
+                            root_hash,
+                            artifact: artifact.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Executes on a replica, returning what it committed, if anything.
+    fn execute_raw<R>(
+        &mut self,
+        replica: usize,
+        executor: [u8; 32],
+        f: impl FnOnce() -> R,
+    ) -> (R, Option<Commit>) {
+        let sent = self.replicas[replica].proposals.len();
+        let approved = self.replicas[replica].approvals.len();
+
+        let host = Host {
+            context_id: CONTEXT_ID,
+            executor_id: executor,
+            capabilities: self
+                .capabilities
+                .get(&executor)
+                .into_iter()
+                .flatten()
+                .map(|capability| capability.discriminant())
+                .collect(),
+            input: vec![],
+            registers: BTreeMap::new(),
+            proxy: self.proxy.clone(),
+            replica: mem::take(&mut self.replicas[replica]),
+            commit: None,
+            clock: self.clock,
+            rng: self.rng,
+            orphan_expiry: self.orphan_expiry,
+        };
+
+        event::register::<T>();
+
+        let (output, mut host) = host::run(host, f);
+
+        if let Some((root_hash, _)) = &host.commit {
+            host.replica.root_hash = Some(*root_hash);
+        }
+
+        for proposal in &host.replica.proposals[sent..] {
+            let _ignored = self
+                .proxy
+                .proposals
+                .insert(proposal.id, (executor, proposal.actions.clone()));
+
+            self.proxy.approve(proposal.id, executor);
+        }
+
+        for proposal_id in &host.replica.approvals[approved..] {
+            self.proxy.approve(*proposal_id, executor);
+        }
+
+        self.replicas[replica] = host.replica;
+        self.clock = host.clock;
+        self.rng = host.rng;
+
+        (output, host.commit)
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: SimulatedState + AppGuard,
+    for<'a> T::Event<'a>: AppEventExt,
+{
+    /// Whether `executor` passes the guards of `method` on a replica, as
+    /// declared with `#[app::guard]`.
+    ///
+    /// Methods called through [`call`](Self::call) are invoked directly, and
+    /// so only the guards on the fields of the state apply to them.
+    #[must_use]
+    pub fn authorize(&mut self, replica: usize, executor: [u8; 32], method: &str) -> bool {
+        self.execute_raw(replica, executor, || {
+            T::authorize(T::fetch().as_deref(), method)
+        })
+        .0
+    }
+}
+
+fn fetch<T: SimulatedState>() -> T::Root {
+    T::fetch().unwrap_or_else(|| panic!("Failed to find or read app state"))
+}
@@ -0,0 +1,380 @@
+//! Native implementations of the host functions.
+//!
+//! Outside of wasm, the functions declared in `sys` forward to these, which
+//! operate on the [`Host`] of whichever replica a [`Simulation`](super::Simulation)
+//! is currently executing on.
+
+use core::cell::RefCell;
+use std::collections::BTreeMap;
+
+use super::{Commit, EmittedEvent, Proposal, Proxy, Replica};
+use crate::sys::{Bool, Buffer, BufferMut, Event, Location, PtrSizedInt, RegisterId, ValueReturn};
+
+thread_local! {
+    static HOST: RefCell<Option<Host>> = const { RefCell::new(None) };
+}
+
+/// The environment of a single execution on a replica.
+#[derive(Debug)]
+pub(super) struct Host {
+    pub context_id: [u8; 32],
+    pub executor_id: [u8; 32],
+    pub capabilities: Vec<u32>,
+    pub input: Vec<u8>,
+    pub registers: BTreeMap<usize, Vec<u8>>,
+    pub proxy: Proxy,
+    pub replica: Replica,
+    pub commit: Option<Commit>,
+    pub clock: u64,
+    pub rng: u64,
+    pub orphan_expiry: Option<u64>,
+}
+
+impl Host {
+    fn set_register(&mut self, register_id: RegisterId, data: Vec<u8>) {
+        let _ignored = self.registers.insert(register_id.as_usize(), data);
+    }
+
+    /// Generates the next pseudo-random number, using SplitMix64 so that
+    /// simulations are reproducible from their seed.
+    const fn next_random(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.rng;
+        z = (z ^ (z >> 30_i32)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27_i32)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31_i32)
+    }
+}
+
+/// Clears the host when dropped, so that it doesn't outlive an execution that
+/// panics.
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let _ignored = HOST.with_borrow_mut(Option::take);
+    }
+}
+
+/// Runs `f` with `host` providing the host functions, returning the host
+/// afterwards along with the result.
+pub(super) fn run<R>(host: Host, f: impl FnOnce() -> R) -> (R, Host) {
+    HOST.with_borrow_mut(|slot| {
+        assert!(slot.is_none(), "simulations cannot be nested");
+
+        *slot = Some(host);
+    });
+
+    let guard = Guard;
+
+    let output = f();
+
+    #[expect(clippy::expect_used, reason = "Only the guard clears the host")]
+    let host = HOST
+        .with_borrow_mut(Option::take)
+        .expect("host was cleared during execution");
+
+    drop(guard);
+
+    (output, host)
+}
+
+/// Whether host functions are currently being provided by a simulation.
+pub(super) fn is_active() -> bool {
+    HOST.with_borrow(Option::is_some)
+}
+
+#[track_caller]
+fn with<R>(name: &str, f: impl FnOnce(&mut Host) -> R) -> R {
+    HOST.with_borrow_mut(|host| {
+        let Some(host) = host else {
+            panic!(
+                "host function `{name}` is only available when compiled for wasm32, \
+                 or while executing in a simulation"
+            );
+        };
+
+        f(host)
+    })
+}
+
+fn to_str<'a>(buf: &Buffer<'a>) -> &'a str {
+    (*buf)
+        .try_into()
+        .unwrap_or_else(|_| panic!("expected a valid utf8 string"))
+}
+
+#[expect(
+    clippy::needless_pass_by_value,
+    reason = "Must match the host function signature"
+)]
+pub fn panic(loc: Location<'_>) -> ! {
+    panic!(
+        "explicit panic at {}:{}:{}",
+        loc.file(),
+        loc.line(),
+        loc.column()
+    )
+}
+
+pub fn panic_utf8(msg: Buffer<'_>, _loc: Location<'_>) -> ! {
+    panic!("{}", to_str(&msg))
+}
+
+pub fn register_len(register_id: RegisterId) -> PtrSizedInt {
+    with("register_len", |host| {
+        host.registers
+            .get(&register_id.as_usize())
+            .map_or(PtrSizedInt::MAX, |data| PtrSizedInt::new(data.len()))
+    })
+}
+
+pub fn read_register(register_id: RegisterId, mut buf: BufferMut<'_>) -> Bool {
+    with("read_register", |host| {
+        let Some(data) = host.registers.get(&register_id.as_usize()) else {
+            panic!("invalid register id: {}", register_id.as_usize());
+        };
+
+        if data.len() != buf.len() {
+            return false.into();
+        }
+
+        buf.copy_from_slice(data);
+
+        true.into()
+    })
+}
+
+pub fn context_id(register_id: RegisterId) {
+    with("context_id", |host| {
+        host.set_register(register_id, host.context_id.to_vec());
+    });
+}
+
+pub fn executor_id(register_id: RegisterId) {
+    with("executor_id", |host| {
+        host.set_register(register_id, host.executor_id.to_vec());
+    });
+}
+
+pub fn has_capability(capability: u32) -> Bool {
+    with("has_capability", |host| {
+        host.capabilities.contains(&capability).into()
+    })
+}
+
+pub fn unauthorized() -> ! {
+    with("unauthorized", |_| {});
+
+    panic!("the executor is not authorized to call this method")
+}
+
+pub fn input(register_id: RegisterId) {
+    with("input", |host| {
+        host.set_register(register_id, host.input.clone());
+    });
+}
+
+pub fn value_return(_value: ValueReturn<'_>) {
+    // simulated calls hand back the method's output directly
+    with("value_return", |_| {});
+}
+
+pub fn log_utf8(msg: Buffer<'_>) {
+    with("log_utf8", |host| {
+        host.replica.logs.push(to_str(&msg).to_owned());
+    });
+}
+
+#[expect(
+    clippy::needless_pass_by_value,
+    reason = "Must match the host function signature"
+)]
+pub fn emit(event: Event<'_>) {
+    with("emit", |host| {
+        host.replica.events.push(EmittedEvent {
+            kind: event.kind().to_owned(),
+            data: event.data().to_vec(),
+        });
+    });
+}
+
+pub fn commit(root: Buffer<'_>, artifact: Buffer<'_>) {
+    with("commit", |host| {
+        assert!(host.commit.is_none(), "State previously committed");
+
+        let Ok(root_hash) = <[u8; 32]>::try_from(&*root) else {
+            panic!("root hash must be 32 bytes, got {}", root.len());
+        };
+
+        host.commit = Some((root_hash, artifact.to_vec()));
+    });
+}
+
+pub fn commit_changes(_changes: Buffer<'_>) {
+    // only subscribers to a node are told which entities changed
+    with("commit_changes", |_host| {});
+}
+
+pub fn storage_read(key: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("storage_read", |host| {
+        let value = host.replica.storage.get(&*key).cloned();
+
+        found(host, value, register_id)
+    })
+}
+
+pub fn storage_remove(key: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("storage_remove", |host| {
+        let removed = host.replica.storage.remove(&*key);
+
+        found(host, removed, register_id)
+    })
+}
+
+pub fn storage_write(key: Buffer<'_>, value: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("storage_write", |host| {
+        let replaced = host.replica.storage.insert(key.to_vec(), value.to_vec());
+
+        found(host, replaced, register_id)
+    })
+}
+
+pub fn private_storage_read(key: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("private_storage_read", |host| {
+        let value = host.replica.private_storage.get(&*key).cloned();
+
+        found(host, value, register_id)
+    })
+}
+
+pub fn private_storage_remove(key: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("private_storage_remove", |host| {
+        let removed = host.replica.private_storage.remove(&*key);
+
+        found(host, removed, register_id)
+    })
+}
+
+pub fn private_storage_write(key: Buffer<'_>, value: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("private_storage_write", |host| {
+        let replaced = host
+            .replica
+            .private_storage
+            .insert(key.to_vec(), value.to_vec());
+
+        found(host, replaced, register_id)
+    })
+}
+
+/// Puts a value that was read, removed, or replaced into the register.
+fn found(host: &mut Host, value: Option<Vec<u8>>, register_id: RegisterId) -> Bool {
+    let Some(value) = value else {
+        return false.into();
+    };
+
+    host.set_register(register_id, value);
+
+    true.into()
+}
+
+pub fn fetch(
+    _url: Buffer<'_>,
+    _method: Buffer<'_>,
+    _headers: Buffer<'_>,
+    _body: Buffer<'_>,
+    register_id: RegisterId,
+) -> Bool {
+    with("fetch", |host| {
+        host.set_register(
+            register_id,
+            b"network access is not available in simulations".to_vec(),
+        );
+
+        // the runtime reports failure as `true`
+        true.into()
+    })
+}
+
+pub fn random_bytes(mut buf: BufferMut<'_>) {
+    with("random_bytes", |host| {
+        for chunk in buf.chunks_mut(8) {
+            let bytes = host.next_random().to_le_bytes();
+
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    });
+}
+
+pub fn time_now(mut buf: BufferMut<'_>) {
+    with("time_now", |host| {
+        // every reading is distinct, so that last-write-wins is deterministic
+        host.clock = host.clock.saturating_add(1);
+
+        buf.copy_from_slice(&host.clock.to_le_bytes());
+    });
+}
+
+pub fn orphan_expiry(mut buf: BufferMut<'_>) -> Bool {
+    with("orphan_expiry", |host| {
+        let Some(expiry) = host.orphan_expiry else {
+            return false.into();
+        };
+
+        buf.copy_from_slice(&expiry.to_le_bytes());
+
+        true.into()
+    })
+}
+
+pub fn send_proposal(value: Buffer<'_>, mut buf: BufferMut<'_>) {
+    with("send_proposal", |host| {
+        let mut id = [0; 32];
+
+        for chunk in id.chunks_mut(8) {
+            chunk.copy_from_slice(&host.next_random().to_le_bytes());
+        }
+
+        host.replica.proposals.push(Proposal {
+            id,
+            actions: value.to_vec(),
+        });
+
+        buf.copy_from_slice(&id);
+    });
+}
+
+pub fn approve_proposal(value: Buffer<'_>) {
+    with("approve_proposal", |host| {
+        let Ok(approval) = <[u8; 32]>::try_from(&*value) else {
+            panic!("proposal id must be 32 bytes, got {}", value.len());
+        };
+
+        host.replica.approvals.push(approval);
+    });
+}
+
+pub fn proxy_proposal(id: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("proxy_proposal", |host| {
+        let proposal = host.proxy.proposal(&id);
+
+        found(host, proposal, register_id)
+    })
+}
+
+pub fn proxy_approvers(id: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("proxy_approvers", |host| {
+        let approvers = host.proxy.approvers(&id);
+
+        found(host, approvers, register_id)
+    })
+}
+
+pub fn proxy_context_value(key: Buffer<'_>, register_id: RegisterId) -> Bool {
+    with("proxy_context_value", |host| {
+        let value = host.proxy.context_value(&key);
+
+        found(host, value, register_id)
+    })
+}
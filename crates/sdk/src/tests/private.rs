@@ -0,0 +1,124 @@
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+use super::*;
+use crate::event::NoEvent;
+use crate::state::{AppState, AppStateInit};
+use crate::testing::{SimulatedState, Simulation};
+
+const ALICE: [u8; 32] = [1; 32];
+
+/// The key the state is kept under, in place of the storage crate's root.
+const STATE_KEY: &[u8] = b"state";
+
+/// An application with no state of its own worth replicating.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Device;
+
+impl AppStateInit for Device {
+    type Return = Self;
+}
+
+impl AppState for Device {
+    type Event<'a> = NoEvent;
+}
+
+impl SimulatedState for Device {
+    type Root = Box<Self>;
+
+    fn init(f: impl FnOnce() -> Self) -> Self::Root {
+        Box::new(f())
+    }
+
+    fn fetch() -> Option<Self::Root> {
+        env::storage_read(STATE_KEY).map(|state| Box::new(from_slice(&state).unwrap()))
+    }
+
+    fn migrate(f: impl FnOnce() -> Self) -> Self::Root {
+        Box::new(f())
+    }
+
+    fn guard(_root: &Self::Root) {}
+
+    fn commit(root: Self::Root) {
+        let _replaced = env::storage_write(STATE_KEY, &to_vec(&root).unwrap());
+    }
+
+    fn sync(_artifact: &[u8]) {}
+}
+
+fn setup(replicas: usize) -> Simulation<Device> {
+    let mut sim = Simulation::new(replicas);
+
+    for replica in 0..replicas {
+        sim.init(replica, ALICE, || Device);
+    }
+
+    sim
+}
+
+#[test]
+fn values_round_trip() {
+    let mut sim = setup(1);
+
+    sim.call(0, ALICE, |_| {
+        let mut private = PrivateStorage::new();
+
+        assert!(!private.insert(b"draft", &"hello".to_owned()).unwrap());
+        assert!(private
+            .insert(b"draft", &"hello, world".to_owned())
+            .unwrap());
+    });
+
+    sim.call(0, ALICE, |_| {
+        let mut private = PrivateStorage::new();
+
+        assert!(private.contains(b"draft").unwrap());
+        assert_eq!(
+            private.get::<String>(b"draft").unwrap().as_deref(),
+            Some("hello, world")
+        );
+
+        assert!(private.remove(b"draft").unwrap());
+        assert!(!private.remove(b"draft").unwrap());
+
+        assert!(!private.contains(b"draft").unwrap());
+        assert_eq!(private.get::<String>(b"draft").unwrap(), None);
+    });
+}
+
+#[test]
+fn values_stay_on_their_replica() {
+    let mut sim = setup(2);
+
+    sim.call(0, ALICE, |_| {
+        let _replaced = PrivateStorage::new().insert(b"key", &7_u32).unwrap();
+    });
+
+    assert_eq!(
+        sim.replica(0).private_storage(b"key"),
+        Some(&to_vec(&7_u32).unwrap()[..])
+    );
+    assert_eq!(sim.replica(1).private_storage(b"key"), None);
+
+    let found = sim.view(1, |_| PrivateStorage::new().get::<u32>(b"key").unwrap());
+
+    assert_eq!(found, None);
+}
+
+#[test]
+fn long_keys_are_refused() {
+    let mut sim = setup(1);
+
+    let key = [0; MAX_KEY_LEN + 1];
+
+    sim.call(0, ALICE, |_| {
+        let mut private = PrivateStorage::new();
+
+        assert!(private.insert(&key, &1_u8).is_err());
+        assert!(private.get::<u8>(&key).is_err());
+        assert!(private.contains(&key).is_err());
+        assert!(private.remove(&key).is_err());
+
+        assert!(private.insert(&key[..MAX_KEY_LEN], &1_u8).is_ok());
+    });
+}
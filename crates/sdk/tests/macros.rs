@@ -18,4 +18,8 @@ fn all() {
     t.pass("tests/macros/valid_args.rs");
     t.compile_fail("tests/macros/invalid_args.rs");
     t.compile_fail("tests/macros/invalid_methods.rs");
+    t.pass("tests/macros/valid_guards.rs");
+    t.compile_fail("tests/macros/invalid_guards.rs");
+    t.pass("tests/macros/valid_migrate.rs");
+    t.compile_fail("tests/macros/invalid_migrate.rs");
 }
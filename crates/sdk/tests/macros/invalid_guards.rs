@@ -0,0 +1,29 @@
+use calimero_sdk::app;
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+
+#[app::state]
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct MyType;
+
+#[app::logic]
+impl MyType {
+    #[app::init]
+    #[app::guard(capability = ManageMembers)]
+    pub fn init() -> MyType {
+        MyType
+    }
+
+    #[app::guard(Self::is_admin)]
+    pub fn method_00() {}
+    #[app::guard(role = Admin)]
+    pub fn method_01(&self) {}
+    #[app::guard(Self::is_admin)]
+    fn method_02(&self) {}
+
+    fn is_admin(&self, _executor: &[u8; 32]) -> bool {
+        true
+    }
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use calimero_sdk::app;
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+
+#[app::state]
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct MyType;
+
+#[app::logic]
+impl MyType {
+    #[app::init]
+    pub fn init() -> MyType {
+        MyType
+    }
+
+    #[app::migrate]
+    pub fn migrate(&self) -> MyType {
+        MyType
+    }
+    #[app::migrate]
+    fn migrate_00() -> MyType {
+        MyType
+    }
+    #[app::migrate]
+    pub fn migrate_01(count: u64) -> MyType {
+        MyType
+    }
+    #[app::migrate]
+    #[app::guard(capability = ManageApplication)]
+    pub fn migrate_02() -> MyType {
+        MyType
+    }
+}
+
+fn main() {}
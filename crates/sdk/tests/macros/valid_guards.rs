@@ -0,0 +1,43 @@
+use calimero_sdk::app;
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+
+#[app::state]
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct MyType {
+    #[app::guard(Self::is_admin)]
+    admin: [u8; 32],
+    #[app::guard(capability = ManageMembers)]
+    members: Vec<[u8; 32]>,
+}
+
+#[app::logic]
+impl MyType {
+    #[app::init]
+    pub fn init() -> MyType {
+        MyType {
+            admin: [0; 32],
+            members: vec![],
+        }
+    }
+
+    #[app::guard(Self::is_admin)]
+    pub fn method_00(&mut self) {}
+    #[app::guard(capability = ManageMembers)]
+    pub fn method_01() {}
+    #[app::guard(capability = ManageApplication)]
+    #[app::guard(MyType::is_admin)]
+    pub fn method_02(&self) {}
+    #[app::guard(is_anyone)]
+    pub fn method_03(&self) {}
+
+    fn is_admin(&self, executor: &[u8; 32]) -> bool {
+        self.admin == *executor
+    }
+}
+
+fn is_anyone(_app: &MyType, _executor: &[u8; 32]) -> bool {
+    true
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use calimero_sdk::app;
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+
+#[app::state]
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct MyType {
+    count: u64,
+}
+
+#[app::logic]
+impl MyType {
+    #[app::init]
+    pub fn init() -> MyType {
+        MyType { count: 0 }
+    }
+
+    #[app::migrate]
+    pub fn migrate() -> MyType {
+        MyType { count: 1 }
+    }
+}
+
+fn main() {}
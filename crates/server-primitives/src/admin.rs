@@ -1,7 +1,10 @@
+use calimero_primitives::abi::Abi;
 use calimero_primitives::application::{Application, ApplicationId};
 use calimero_primitives::context::{Context, ContextId, ContextInvitationPayload};
 use calimero_primitives::hash::Hash;
-use calimero_primitives::identity::{ClientKey, ContextUser, PrivateKey, PublicKey, WalletType};
+use calimero_primitives::identity::{
+    ApiKey, ClientKey, ContextUser, PrivateKey, PublicKey, Scopes, WalletType,
+};
 use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -117,12 +120,23 @@ impl ListApplicationsResponse {
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetApplicationDetailsResponse {
-    pub data: Application,
+    pub data: ApplicationDetails,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationDetails {
+    #[serde(flatten)]
+    pub application: Application,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abi: Option<Abi>,
 }
 
 impl GetApplicationDetailsResponse {
-    pub const fn new(application: Application) -> Self {
-        Self { data: application }
+    pub const fn new(application: Application, abi: Option<Abi>) -> Self {
+        Self {
+            data: ApplicationDetails { application, abi },
+        }
     }
 }
 
@@ -241,6 +255,27 @@ impl GetContextStorageResponse {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetContextDiagnosticsResponseData {
+    /// The number of actions held back waiting for their parents to arrive.
+    pub orphans: usize,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetContextDiagnosticsResponse {
+    pub data: GetContextDiagnosticsResponseData,
+}
+
+impl GetContextDiagnosticsResponse {
+    pub const fn new(orphans: usize) -> Self {
+        Self {
+            data: GetContextDiagnosticsResponseData { orphans },
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContextIdentitiesResponseData {
@@ -447,6 +482,86 @@ impl GenerateContextIdentityResponse {
     }
 }
 
+// -------------------------------------------- API Key API --------------------------------------------
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Scopes,
+    pub expires_at: Option<u64>,
+}
+
+impl CreateApiKeyRequest {
+    pub const fn new(name: String, scopes: Scopes, expires_at: Option<u64>) -> Self {
+        Self {
+            name,
+            scopes,
+            expires_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyResponseData {
+    pub api_key: ApiKey,
+    pub secret: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyResponse {
+    pub data: CreateApiKeyResponseData,
+}
+
+impl CreateApiKeyResponse {
+    pub const fn new(api_key: ApiKey, secret: String) -> Self {
+        Self {
+            data: CreateApiKeyResponseData { api_key, secret },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListApiKeysResponseData {
+    pub api_keys: Vec<ApiKey>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListApiKeysResponse {
+    pub data: ListApiKeysResponseData,
+}
+
+impl ListApiKeysResponse {
+    pub const fn new(api_keys: Vec<ApiKey>) -> Self {
+        Self {
+            data: ListApiKeysResponseData { api_keys },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokedApiKeyResponseData {
+    pub is_revoked: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeApiKeyResponse {
+    pub data: RevokedApiKeyResponseData,
+}
+
+impl RevokeApiKeyResponse {
+    pub const fn new(is_revoked: bool) -> Self {
+        Self {
+            data: RevokedApiKeyResponseData { is_revoked },
+        }
+    }
+}
+
 // -------------------------------------------- Misc API --------------------------------------------
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -456,6 +571,8 @@ pub struct AddPublicKeyRequest {
     pub payload: Payload,
     pub wallet_metadata: WalletMetadata,
     pub context_id: Option<ContextId>,
+    #[serde(default)]
+    pub scopes: Scopes,
 }
 
 impl AddPublicKeyRequest {
@@ -465,12 +582,14 @@ impl AddPublicKeyRequest {
         payload: Payload,
         wallet_metadata: WalletMetadata,
         context_id: Option<ContextId>,
+        scopes: Scopes,
     ) -> Self {
         Self {
             wallet_signature,
             payload,
             wallet_metadata,
             context_id,
+            scopes,
         }
     }
 }
@@ -576,6 +695,9 @@ pub struct IntermediateAddPublicKeyRequest {
     pub payload: IntermediatePayload,
     pub wallet_metadata: WalletMetadata, // Reuse WalletMetadata as it fits the intermediate step
     pub context_id: Option<ContextId>,
+    /// What the key may be used for, anything unless narrowed down.
+    #[serde(default)]
+    pub scopes: Scopes,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,10 +1,12 @@
 use calimero_node_primitives::CallError;
 use calimero_primitives::context::ContextId;
+use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::PublicKey;
+use calimero_storage::proof::MerkleProof;
 use eyre::Error as EyreError;
 use serde::de::Error as SerdeError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde_json::Value;
+use serde_json::{from_slice as from_json_slice, Value};
 use thiserror::Error as ThisError;
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -73,6 +75,23 @@ impl Request<RequestPayload> {
 #[serde(tag = "method", content = "params", rename_all = "snake_case")]
 pub enum RequestPayload {
     Execute(ExecuteRequest),
+    MultiExecute(MultiExecuteRequest),
+    Prove(ProveRequest),
+    Diff(DiffRequest),
+}
+
+/// Either a single message, or a batch of them, which is answered with a
+/// batch of responses.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "This will never have any other variants"
+)]
+pub enum Batch<T> {
+    // attempted first, since a single message may be any json value
+    Batch(Vec<T>),
+    Single(T),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -126,6 +145,10 @@ pub enum ResponseBodyError {
 pub enum ServerResponseError {
     #[error("parse error: {0}")]
     ParseError(String),
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("forbidden: {0}")]
+    Forbidden(String),
     #[error(
         "internal error: {}",
         err.as_ref().map_or_else(|| "<opaque>".to_owned(), ToString::to_string)
@@ -144,6 +167,16 @@ pub struct ExecuteRequest {
     pub method: String,
     pub args_json: Value,
     pub executor_public_key: PublicKey,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_hash: Option<Hash>,
+    /// Identifies the call across retries, so that it is only executed once,
+    /// with later attempts receiving the same result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Whether the call is rejected should it change the state, as set by the
+    /// server from the scopes of the client's token, never by the client.
+    #[serde(skip)]
+    pub read_only: bool,
 }
 
 impl ExecuteRequest {
@@ -153,12 +186,17 @@ impl ExecuteRequest {
         method: String,
         args_json: Value,
         executor_public_key: PublicKey,
+        root_hash: Option<Hash>,
+        idempotency_key: Option<String>,
     ) -> Self {
         Self {
             context_id,
             method,
             args_json,
             executor_public_key,
+            root_hash,
+            idempotency_key,
+            read_only: false,
         }
     }
 }
@@ -187,4 +225,191 @@ pub enum ExecuteError {
     CallError(CallError),
     #[error("function call error: {0}")]
     FunctionCallError(String),
+    /// The error returned by the application method itself.
+    #[error("application error: {0}")]
+    ApplicationError(Value),
+    /// The executor did not pass the guards of the application method.
+    #[error("the executor is not authorized to call this method")]
+    Unauthorized,
+}
+
+impl ExecuteError {
+    /// Wraps the error returned by an application method, which is passed on
+    /// as a string when it isn't JSON.
+    #[must_use]
+    pub fn application(error: &[u8]) -> Self {
+        Self::ApplicationError(
+            from_json_slice(error)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(error).into_owned())),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MultiExecuteRequest {
+    pub context_id: ContextId,
+    /// Executed in order, as one, so that if any of them fails, none of them
+    /// is applied.
+    pub calls: Vec<MultiExecuteCall>,
+    pub executor_public_key: PublicKey,
+}
+
+impl MultiExecuteRequest {
+    #[must_use]
+    pub const fn new(
+        context_id: ContextId,
+        calls: Vec<MultiExecuteCall>,
+        executor_public_key: PublicKey,
+    ) -> Self {
+        Self {
+            context_id,
+            calls,
+            executor_public_key,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MultiExecuteCall {
+    pub method: String,
+    pub args_json: Value,
+}
+
+impl MultiExecuteCall {
+    #[must_use]
+    pub const fn new(method: String, args_json: Value) -> Self {
+        Self { method, args_json }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MultiExecuteResponse {
+    /// The output of each call, in order.
+    pub outputs: Vec<Option<Value>>,
+}
+
+impl MultiExecuteResponse {
+    #[must_use]
+    pub const fn new(outputs: Vec<Option<Value>>) -> Self {
+        Self { outputs }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ThisError)]
+#[serde(tag = "type", content = "data")]
+#[non_exhaustive]
+pub enum MultiExecuteError {
+    #[error("codec error: {message}")]
+    SerdeError { message: String },
+    #[error("error occurred while handling request: {0}")]
+    CallError(CallError),
+    /// One of the calls failed, so none of them were applied.
+    #[error("call {index} failed: {error}")]
+    CallFailed { index: usize, error: ExecuteError },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ProveRequest {
+    pub context_id: ContextId,
+    pub id: Hash,
+}
+
+impl ProveRequest {
+    #[must_use]
+    pub const fn new(context_id: ContextId, id: Hash) -> Self {
+        Self { context_id, id }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ProveResponse {
+    pub root_hash: Hash,
+    pub proof: MerkleProof,
+}
+
+impl ProveResponse {
+    #[must_use]
+    pub const fn new(root_hash: Hash, proof: MerkleProof) -> Self {
+        Self { root_hash, proof }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ThisError)]
+#[serde(tag = "type", content = "data")]
+#[non_exhaustive]
+pub enum ProveError {
+    #[error("context not found: {0}")]
+    ContextNotFound(ContextId),
+    #[error("entity not found: {0}")]
+    EntityNotFound(Hash),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DiffRequest {
+    pub context_id: ContextId,
+    pub from: Hash,
+    pub to: Hash,
+}
+
+impl DiffRequest {
+    #[must_use]
+    pub const fn new(context_id: ContextId, from: Hash, to: Hash) -> Self {
+        Self {
+            context_id,
+            from,
+            to,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DiffResponse {
+    pub changes: Vec<StateChange>,
+}
+
+impl DiffResponse {
+    #[must_use]
+    pub const fn new(changes: Vec<StateChange>) -> Self {
+        Self { changes }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct StateChange {
+    pub key: Hash,
+    pub old: Option<Vec<u8>>,
+    pub new: Option<Vec<u8>>,
+}
+
+impl StateChange {
+    #[must_use]
+    pub const fn new(key: Hash, old: Option<Vec<u8>>, new: Option<Vec<u8>>) -> Self {
+        Self { key, old, new }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ThisError)]
+#[serde(tag = "type", content = "data")]
+#[non_exhaustive]
+pub enum DiffError {
+    #[error("context not found: {0}")]
+    ContextNotFound(ContextId),
+    #[error("root hash not retained: {0}")]
+    RootHashNotRetained(Hash),
 }
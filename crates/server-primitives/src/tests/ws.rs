@@ -0,0 +1,101 @@
+use calimero_primitives::events::{ExecutionEvent, StateMutationPayload};
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+const EXECUTOR: [u8; 32] = [2; 32];
+
+fn state_mutation() -> ContextEvent {
+    ContextEvent::new(
+        CONTEXT_ID.into(),
+        ContextEventPayload::StateMutation(StateMutationPayload::new([3; 32].into())),
+    )
+    .with_executor(EXECUTOR.into())
+}
+
+fn execution_event(kinds: &[&str]) -> ContextEvent {
+    ContextEvent::new(
+        CONTEXT_ID.into(),
+        ContextEventPayload::ExecutionEvent(ExecutionEventPayload::new(
+            kinds
+                .iter()
+                .map(|kind| ExecutionEvent::new((*kind).to_owned(), vec![]))
+                .collect(),
+        )),
+    )
+}
+
+fn kinds(event: &ContextEvent) -> Vec<&str> {
+    let ContextEventPayload::ExecutionEvent(payload) = &event.payload else {
+        panic!("unexpected payload: {:?}", event.payload);
+    };
+
+    payload.events.iter().map(|event| &*event.kind).collect()
+}
+
+#[test]
+fn test_empty_filter_passes_every_event() {
+    let filter = EventFilter::default();
+
+    assert!(filter.apply(&state_mutation()).is_some());
+    assert_eq!(
+        kinds(&filter.apply(&execution_event(&["a", "b"])).unwrap()),
+        ["a", "b"]
+    );
+}
+
+#[test]
+fn test_filter_by_type() {
+    let filter = EventFilter::new(Some(vec!["StateMutation".to_owned()]), None, None);
+
+    assert!(filter.apply(&state_mutation()).is_some());
+    assert!(filter.apply(&execution_event(&["a"])).is_none());
+}
+
+#[test]
+fn test_filter_by_executor() {
+    let filter = EventFilter::new(None, None, Some(vec![EXECUTOR.into()]));
+
+    assert!(filter.apply(&state_mutation()).is_some());
+
+    // events produced by no execution have no executor to match
+    assert!(filter.apply(&execution_event(&["a"])).is_none());
+
+    let filter = EventFilter::new(None, None, Some(vec![[4; 32].into()]));
+
+    assert!(filter.apply(&state_mutation()).is_none());
+}
+
+#[test]
+fn test_filter_by_kind_narrows_down_execution_events() {
+    let filter = EventFilter::new(None, Some(vec!["b".to_owned()]), None);
+
+    assert_eq!(
+        kinds(
+            &filter
+                .apply(&execution_event(&["a", "b", "c", "b"]))
+                .unwrap()
+        ),
+        ["b", "b"]
+    );
+
+    assert!(filter.apply(&execution_event(&["a", "c"])).is_none());
+
+    // other payloads are left as they are
+    assert!(filter.apply(&state_mutation()).is_some());
+}
+
+#[test]
+fn test_filter_criteria_are_combined() {
+    let filter = EventFilter::new(
+        Some(vec!["ExecutionEvent".to_owned()]),
+        Some(vec!["a".to_owned()]),
+        None,
+    );
+
+    assert!(filter.apply(&state_mutation()).is_none());
+    assert_eq!(
+        kinds(&filter.apply(&execution_event(&["a", "b"])).unwrap()),
+        ["a"]
+    );
+}
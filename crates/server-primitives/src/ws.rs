@@ -1,12 +1,24 @@
+#[cfg(test)]
+#[path = "tests/ws.rs"]
+mod tests;
+
 use calimero_primitives::context::ContextId;
+use calimero_primitives::events::{ContextEvent, ContextEventPayload, ExecutionEventPayload};
+use calimero_primitives::identity::PublicKey;
 use eyre::Error as EyreError;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use thiserror::Error as ThisError;
+
+use crate::jsonrpc::ExecuteRequest;
 
 /// Client ID is a locally unique identifier of a WebSocket client connection.
 pub type ConnectionId = u64;
 /// Request Id is a locally unique identifier of a WebSocket request.
 pub type RequestId = u64;
+/// Resume Token identifies a logged event, so that the events logged after it
+/// can be replayed to a client that missed them.
+pub type ResumeToken = u64;
 
 // **************************** request *******************************
 #[derive(Debug, Deserialize, Serialize)]
@@ -22,6 +34,9 @@ pub struct Request<P> {
 pub enum RequestPayload {
     Subscribe(SubscribeRequest),
     Unsubscribe(UnsubscribeRequest),
+    /// Executes a method, as the JSON-RPC `execute` method does, which reads
+    /// the state as it was at a past root hash when one is given.
+    Execute(ExecuteRequest),
 }
 // *************************************************************************
 
@@ -30,6 +45,9 @@ pub enum RequestPayload {
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: Option<RequestId>,
+    /// Set on events, to resume from after this one when reconnecting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_token: Option<ResumeToken>,
     #[serde(flatten)]
     pub body: ResponseBody,
 }
@@ -56,6 +74,9 @@ pub enum ResponseBodyError {
 #[serde(tag = "type", content = "data")]
 pub enum ServerResponseError {
     ParseError(String),
+    /// The token the connection was authenticated with doesn't permit the
+    /// request.
+    Forbidden(String),
     InternalError {
         #[serde(skip)]
         err: Option<EyreError>,
@@ -68,6 +89,88 @@ pub enum ServerResponseError {
 #[serde(rename_all = "camelCase")]
 pub struct SubscribeRequest {
     pub context_ids: Vec<ContextId>,
+    #[serde(default)]
+    pub filter: EventFilter,
+    /// Replays the events of the contexts logged after this one, that are
+    /// still retained, ahead of any new ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_token: Option<ResumeToken>,
+}
+
+/// Narrows down the events a subscription receives to those meeting every
+/// criterion given.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EventFilter {
+    /// The types of payload to receive, such as `StateMutation` or
+    /// `ExecutionEvent`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<String>>,
+    /// The kinds of application event to receive, which narrows down the
+    /// events of an `ExecutionEvent` payload, leaving others as they are.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kinds: Option<Vec<String>>,
+    /// The identities whose executions produced the events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executors: Option<Vec<PublicKey>>,
+}
+
+impl EventFilter {
+    #[must_use]
+    pub const fn new(
+        types: Option<Vec<String>>,
+        kinds: Option<Vec<String>>,
+        executors: Option<Vec<PublicKey>>,
+    ) -> Self {
+        Self {
+            types,
+            kinds,
+            executors,
+        }
+    }
+
+    /// Returns the event as the subscription should receive it, if at all.
+    #[must_use]
+    pub fn apply(&self, event: &ContextEvent) -> Option<ContextEvent> {
+        if let Some(types) = &self.types {
+            if !types.iter().any(|name| name == event.payload.name()) {
+                return None;
+            }
+        }
+
+        if let Some(executors) = &self.executors {
+            if !event
+                .executor_id
+                .is_some_and(|executor_id| executors.contains(&executor_id))
+            {
+                return None;
+            }
+        }
+
+        let (Some(kinds), ContextEventPayload::ExecutionEvent(payload)) =
+            (&self.kinds, &event.payload)
+        else {
+            return Some(event.clone());
+        };
+
+        let events: Vec<_> = payload
+            .events
+            .iter()
+            .filter(|execution_event| kinds.contains(&execution_event.kind))
+            .cloned()
+            .collect();
+
+        if events.is_empty() {
+            return None;
+        }
+
+        let mut event = event.clone();
+
+        event.payload = ContextEventPayload::ExecutionEvent(ExecutionEventPayload::new(events));
+
+        Some(event)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -75,6 +178,16 @@ pub struct SubscribeRequest {
 pub struct SubscribeResponse {
     pub context_ids: Vec<ContextId>,
 }
+
+#[derive(Debug, Deserialize, Serialize, ThisError)]
+#[serde(tag = "type", content = "data")]
+#[non_exhaustive]
+pub enum SubscribeError {
+    /// Some of the events logged after the resume token are no longer
+    /// retained, so the client has to catch up by other means.
+    #[error("events after resume token {resume_token} are no longer retained")]
+    ResumeTokenExpired { resume_token: ResumeToken },
+}
 // *************************************************************************
 
 // **************************** unsubscribe method *******************************
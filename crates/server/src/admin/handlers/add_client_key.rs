@@ -3,7 +3,7 @@ use std::sync::Arc;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::{Extension, Json};
-use calimero_primitives::identity::{ClientKey, WalletType};
+use calimero_primitives::identity::{ClientKey, Scopes, WalletType};
 use calimero_server_primitives::admin::{
     AddPublicKeyRequest, EthSignatureMessageMetadata, ICPSignatureMessageMetadata,
     IntermediateAddPublicKeyRequest, JwtRefreshRequest, JwtTokenRequest,
@@ -77,6 +77,7 @@ pub fn transform_request(
         Payload::new(intermediate.payload.message, metadata_enum),
         intermediate.wallet_metadata,
         intermediate.context_id,
+        intermediate.scopes,
     ))
 }
 
@@ -119,9 +120,11 @@ pub async fn add_client_key_handler(
 //* Register client key to authenticate client requests  */
 pub async fn generate_jwt_token_handler(
     Extension(state): Extension<Arc<AdminState>>,
+    // those of the client key that signed the request
+    Extension(scopes): Extension<Scopes>,
     Json(req): Json<JwtTokenRequest>,
 ) -> impl IntoResponse {
-    match generate_jwt_tokens(req, &state.store) {
+    match generate_jwt_tokens(req, scopes, &state.store) {
         Ok(jwt_tokens) => {
             let tokens = JwtTokens {
                 access_token: jwt_tokens.access_token,
@@ -168,7 +171,8 @@ pub fn store_client_key(
         req.payload.message.public_key.clone(),
         Utc::now().timestamp_millis() as u64,
         req.context_id,
-    );
+    )
+    .with_scopes(req.scopes.clone());
     let _ = add_client_key(store, client_key).map_err(parse_api_error)?;
     info!("Client key stored successfully.");
     Ok(req)
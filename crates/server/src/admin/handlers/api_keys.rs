@@ -0,0 +1,3 @@
+pub mod create_api_key;
+pub mod list_api_keys;
+pub mod revoke_api_key;
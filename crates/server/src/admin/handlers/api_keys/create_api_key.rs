@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use calimero_primitives::identity::Scopes;
+use calimero_server_primitives::admin::{CreateApiKeyRequest, CreateApiKeyResponse};
+use reqwest::StatusCode;
+
+use crate::admin::service::{ApiError, ApiResponse};
+use crate::admin::utils::api_keys::generate_api_key;
+use crate::AdminState;
+
+pub async fn handler(
+    scopes: Option<Extension<Scopes>>,
+    Extension(state): Extension<Arc<AdminState>>,
+    Json(req): Json<CreateApiKeyRequest>,
+) -> impl IntoResponse {
+    // a key narrowed down to some contexts or methods could otherwise create
+    // one that isn't
+    if let Some(Extension(scopes)) = scopes {
+        if scopes.context_ids.is_some() || scopes.methods.is_some() {
+            return ApiError {
+                status_code: StatusCode::FORBIDDEN,
+                message: "Only unrestricted admins may create API keys.".into(),
+            }
+            .into_response();
+        }
+    }
+
+    match generate_api_key(req.name, req.scopes, req.expires_at, &state.store) {
+        Ok((api_key, secret)) => ApiResponse {
+            payload: CreateApiKeyResponse::new(api_key, secret),
+        }
+        .into_response(),
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use axum::response::IntoResponse;
+use axum::Extension;
+use calimero_server_primitives::admin::ListApiKeysResponse;
+
+use crate::admin::service::{parse_api_error, ApiResponse};
+use crate::admin::storage::api_keys::list_api_keys;
+use crate::AdminState;
+
+pub async fn handler(Extension(state): Extension<Arc<AdminState>>) -> impl IntoResponse {
+    match list_api_keys(&state.store).map_err(parse_api_error) {
+        Ok(api_keys) => ApiResponse {
+            payload: ListApiKeysResponse::new(api_keys),
+        }
+        .into_response(),
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use axum::extract::Path;
+use axum::response::IntoResponse;
+use axum::Extension;
+use calimero_primitives::hash::Hash;
+use calimero_server_primitives::admin::RevokeApiKeyResponse;
+
+use crate::admin::service::{parse_api_error, ApiResponse};
+use crate::admin::storage::api_keys::delete_api_key;
+use crate::AdminState;
+
+pub async fn handler(
+    Path(id): Path<Hash>,
+    Extension(state): Extension<Arc<AdminState>>,
+) -> impl IntoResponse {
+    match delete_api_key(&state.store, &id).map_err(parse_api_error) {
+        Ok(is_revoked) => ApiResponse {
+            payload: RevokeApiKeyResponse::new(is_revoked),
+        }
+        .into_response(),
+        Err(err) => err.into_response(),
+    }
+}
@@ -5,6 +5,7 @@ use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Extension;
 use calimero_server_primitives::admin::GetApplicationDetailsResponse;
+use tracing::warn;
 
 use crate::admin::service::{parse_api_error, ApiError, ApiResponse};
 use crate::AdminState;
@@ -21,25 +22,30 @@ pub async fn handler(
         .into_response();
     };
 
-    let application = state
-        .ctx_manager
-        .get_application(&app_id_result)
-        .map_err(|err| parse_api_error(err).into_response());
-
-    #[expect(clippy::option_if_let_else, reason = "Clearer here")]
-    match application {
-        Ok(application) => match application {
-            Some(application) => ApiResponse {
-                payload: GetApplicationDetailsResponse::new(application),
-            }
-            .into_response(),
-            None => ApiError {
+    let application = match state.ctx_manager.get_application(&app_id_result) {
+        Ok(Some(application)) => application,
+        Ok(None) => {
+            return ApiError {
                 status_code: StatusCode::NOT_FOUND,
                 message: "Context not found".into(),
             }
-            .into_response(),
+            .into_response()
+        }
+        Err(err) => return parse_api_error(err).into_response(),
+    };
+
+    // the details are still of use without the ABI, which is only descriptive
+    let abi = match state.ctx_manager.get_application_abi(&app_id_result).await {
+        Ok(abi) => abi.map(|abi| (*abi).clone()),
+        Err(err) => {
+            warn!(%app_id_result, %err, "Failed to read the application ABI");
+
+            None
         }
-        .into_response(),
-        Err(err) => err.into_response(),
+    };
+
+    ApiResponse {
+        payload: GetApplicationDetailsResponse::new(application, abi),
     }
+    .into_response()
 }
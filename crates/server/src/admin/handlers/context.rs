@@ -2,6 +2,7 @@ pub mod create_context;
 pub mod delete_context;
 pub mod get_context;
 pub mod get_context_client_keys;
+pub mod get_context_diagnostics;
 pub mod get_context_identities;
 pub mod get_context_storage;
 pub mod get_context_users;
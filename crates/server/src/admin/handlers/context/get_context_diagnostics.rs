@@ -0,0 +1,60 @@
+use core::cell::RefCell;
+use std::sync::Arc;
+
+use axum::extract::Path;
+use axum::response::IntoResponse;
+use axum::Extension;
+use calimero_primitives::context::ContextId;
+use calimero_server_primitives::admin::GetContextDiagnosticsResponse;
+use calimero_storage::interface::diagnostics;
+use calimero_store::key::ContextState as ContextStateKey;
+use eyre::Report;
+use reqwest::StatusCode;
+
+use crate::admin::service::{parse_api_error, ApiError, ApiResponse};
+use crate::AdminState;
+
+pub async fn handler(
+    Path(context_id): Path<ContextId>,
+    Extension(state): Extension<Arc<AdminState>>,
+) -> impl IntoResponse {
+    match state.ctx_manager.get_context(&context_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return ApiError {
+                status_code: StatusCode::NOT_FOUND,
+                message: "Context not found".into(),
+            }
+            .into_response()
+        }
+        Err(err) => return parse_api_error(err).into_response(),
+    }
+
+    let handle = state.store.handle();
+
+    // the storage reads can't fail, so the first store error is kept aside
+    let failure = RefCell::new(None::<Report>);
+
+    let diagnostics =
+        diagnostics(
+            |key| match handle.get(&ContextStateKey::new(context_id, key.to_bytes())) {
+                Ok(entry) => entry.map(|entry| entry.value.into_boxed().into_vec()),
+                Err(err) => {
+                    let _ignored = failure.borrow_mut().get_or_insert(err.into());
+                    None
+                }
+            },
+        );
+
+    if let Some(err) = failure.into_inner() {
+        return parse_api_error(err).into_response();
+    }
+
+    match diagnostics {
+        Ok(diagnostics) => ApiResponse {
+            payload: GetContextDiagnosticsResponse::new(diagnostics.orphans),
+        }
+        .into_response(),
+        Err(err) => parse_api_error(err.into()).into_response(),
+    }
+}
@@ -29,15 +29,16 @@ use super::storage::ssl::get_ssl;
 use crate::admin::handlers::add_client_key::{
     add_client_key_handler, generate_jwt_token_handler, refresh_jwt_token_handler,
 };
+use crate::admin::handlers::api_keys::{create_api_key, list_api_keys, revoke_api_key};
 use crate::admin::handlers::applications::{
     get_application, get_application_details, install_application, install_dev_application,
     list_applications, uninstall_application,
 };
 use crate::admin::handlers::challenge::request_challenge_handler;
 use crate::admin::handlers::context::{
-    create_context, delete_context, get_context, get_context_client_keys, get_context_identities,
-    get_context_storage, get_context_users, get_contexts, invite_to_context, join_context,
-    update_context_application,
+    create_context, delete_context, get_context, get_context_client_keys, get_context_diagnostics,
+    get_context_identities, get_context_storage, get_context_users, get_contexts,
+    invite_to_context, join_context, update_context_application,
 };
 use crate::admin::handlers::did::fetch_did_handler;
 use crate::admin::handlers::identity::generate_context_identity;
@@ -125,6 +126,10 @@ pub(crate) fn setup(
             "/contexts/:context_id/storage",
             get(get_context_storage::handler),
         )
+        .route(
+            "/contexts/:context_id/diagnostics",
+            get(get_context_diagnostics::handler),
+        )
         .route(
             "/contexts/:context_id/identities",
             get(get_context_identities::handler),
@@ -137,8 +142,18 @@ pub(crate) fn setup(
             post(generate_context_identity::handler),
         )
         .route("/identity/keys", delete(delete_auth_keys_handler))
+        .route(
+            "/api-keys",
+            get(list_api_keys::handler).post(create_api_key::handler),
+        )
+        .route("/api-keys/:id", delete(revoke_api_key::handler))
+        .layer(AuthSignatureLayer::new(store.clone()))
+        .layer(Extension(Arc::clone(&shared_state)));
+
+    // any client key may issue tokens to itself, carrying its own scopes
+    let token_router = Router::new()
         .route("/generate-jwt-token", post(generate_jwt_token_handler))
-        .layer(AuthSignatureLayer::new(store))
+        .layer(AuthSignatureLayer::unscoped(store))
         .layer(Extension(Arc::clone(&shared_state)));
 
     let unprotected_router = Router::new()
@@ -250,11 +265,17 @@ pub(crate) fn setup(
             "/dev/contexts/:context_id/proposals/:proposal_id",
             get(get_proposal_handler),
         )
+        .route(
+            "/dev/api-keys",
+            get(list_api_keys::handler).post(create_api_key::handler),
+        )
+        .route("/dev/api-keys/:id", delete(revoke_api_key::handler))
         .route_layer(from_fn(dev_mode_auth));
 
     let admin_router = Router::new()
         .merge(unprotected_router)
         .merge(protected_router)
+        .merge(token_router)
         .merge(dev_router)
         .layer(Extension(shared_state))
         .layer(session_layer);
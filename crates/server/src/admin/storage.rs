@@ -1,3 +1,4 @@
+pub mod api_keys;
 pub mod client_keys;
 pub mod did;
 pub mod jwt_secret;
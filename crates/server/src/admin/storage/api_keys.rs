@@ -0,0 +1,139 @@
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::ApiKey;
+use calimero_store::entry::{Entry, Json};
+use calimero_store::key::Generic;
+use calimero_store::Store;
+
+const SCOPE: [u8; 16] = *b"api_keys::server";
+const USES_SCOPE: [u8; 16] = *b"api_keys::usages";
+
+// how far behind the recorded use of a key may fall, in milliseconds, so
+// it isn't written on every request
+const USE_RESOLUTION: u64 = 60_000;
+
+struct ApiKeyEntry {
+    key: Generic,
+}
+
+impl Entry for ApiKeyEntry {
+    type Key = Generic;
+    type Codec = Json;
+    type DataType<'a> = ApiKey;
+
+    fn key(&self) -> &Self::Key {
+        &self.key
+    }
+}
+
+impl ApiKeyEntry {
+    fn new(id: &Hash) -> Self {
+        Self {
+            key: Generic::new(SCOPE, **id),
+        }
+    }
+}
+
+// kept apart from the key itself, so recording a use can't bring a revoked
+// key back
+struct ApiKeyUseEntry {
+    key: Generic,
+}
+
+impl Entry for ApiKeyUseEntry {
+    type Key = Generic;
+    type Codec = Json;
+    type DataType<'a> = u64;
+
+    fn key(&self) -> &Self::Key {
+        &self.key
+    }
+}
+
+impl ApiKeyUseEntry {
+    fn new(id: &Hash) -> Self {
+        Self {
+            key: Generic::new(USES_SCOPE, **id),
+        }
+    }
+}
+
+pub fn put_api_key(store: &Store, api_key: &ApiKey) -> eyre::Result<()> {
+    let entry = ApiKeyEntry::new(&api_key.id);
+    let mut handle = store.handle();
+
+    handle.put(&entry, api_key)?;
+
+    Ok(())
+}
+
+pub fn get_api_key(store: &Store, id: &Hash) -> eyre::Result<Option<ApiKey>> {
+    let handle = store.handle();
+
+    let Some(mut api_key) = handle.get(&ApiKeyEntry::new(id))? else {
+        return Ok(None);
+    };
+
+    api_key.last_used_at = handle.get(&ApiKeyUseEntry::new(id))?;
+
+    Ok(Some(api_key))
+}
+
+pub fn list_api_keys(store: &Store) -> eyre::Result<Vec<ApiKey>> {
+    let handle = store.handle();
+
+    let mut iter = handle.iter::<ApiKeyEntry>()?;
+
+    let first = iter
+        .seek(Generic::new(SCOPE, [0; 32]))
+        .transpose()
+        .map(|k| (k, iter.read()));
+
+    let mut api_keys = Vec::new();
+
+    for (k, v) in first.into_iter().chain(iter.entries()) {
+        let (k, mut v) = (k?, v?);
+
+        if k.scope() != SCOPE {
+            break;
+        }
+
+        v.last_used_at = handle.get(&ApiKeyUseEntry::new(&v.id))?;
+
+        api_keys.push(v);
+    }
+
+    Ok(api_keys)
+}
+
+/// Returns whether there was a key to revoke.
+pub fn delete_api_key(store: &Store, id: &Hash) -> eyre::Result<bool> {
+    let mut handle = store.handle();
+
+    let entry = ApiKeyEntry::new(id);
+
+    if !handle.has(&entry)? {
+        return Ok(false);
+    }
+
+    handle.delete(&entry)?;
+    handle.delete(&ApiKeyUseEntry::new(id))?;
+
+    Ok(true)
+}
+
+/// Records that a key was used at `now`, unless a use was recorded recently.
+pub fn record_api_key_use(store: &Store, id: &Hash, now: u64) -> eyre::Result<()> {
+    let mut handle = store.handle();
+
+    let entry = ApiKeyUseEntry::new(id);
+
+    if let Some(last_used_at) = handle.get(&entry)? {
+        if now.saturating_sub(last_used_at) < USE_RESOLUTION {
+            return Ok(());
+        }
+    }
+
+    handle.put(&entry, &now)?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+pub mod api_keys;
 pub mod auth;
 #[cfg(test)]
 mod auth_tests;
@@ -0,0 +1,38 @@
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::{ApiKey, Scopes};
+use calimero_store::Store;
+use chrono::Utc;
+use rand::random;
+
+use crate::admin::service::{parse_api_error, ApiError};
+use crate::admin::storage::api_keys::put_api_key;
+
+/// Marks a bearer token as an API key rather than a JWT.
+pub const API_KEY_PREFIX: &str = "mero_";
+
+/// Creates a key, returning it along with its secret, which isn't stored
+/// and so can't be recovered afterwards.
+pub fn generate_api_key(
+    name: String,
+    scopes: Scopes,
+    expires_at: Option<u64>,
+    store: &Store,
+) -> Result<(ApiKey, String), ApiError> {
+    let secret = format!(
+        "{API_KEY_PREFIX}{}",
+        bs58::encode(random::<[u8; 32]>()).into_string()
+    );
+
+    #[expect(clippy::cast_sign_loss, reason = "Essentially infallible")]
+    let api_key = ApiKey::new(
+        Hash::new(secret.as_bytes()),
+        name,
+        scopes,
+        Utc::now().timestamp_millis() as u64,
+        expires_at,
+    );
+
+    put_api_key(store, &api_key).map_err(parse_api_error)?;
+
+    Ok((api_key, secret))
+}
@@ -2,6 +2,7 @@ use core::str::from_utf8;
 
 use calimero_primitives::context::ContextId;
 use calimero_primitives::hash;
+use calimero_primitives::identity::Scopes;
 use calimero_server_primitives::admin::JwtTokenRequest;
 use calimero_store::Store;
 use chrono::{Duration, Utc};
@@ -21,6 +22,9 @@ pub struct Claims {
     executor_public_key: String,
     pub exp: usize,
     token_type: TokenType,
+    /// Those of the client key the token was issued to.
+    #[serde(default)]
+    pub scopes: Scopes,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,7 +41,11 @@ enum TokenType {
     Refresh,
 }
 
-pub fn generate_jwt_tokens(req: JwtTokenRequest, store: &Store) -> Result<JwtToken, ApiError> {
+pub fn generate_jwt_tokens(
+    req: JwtTokenRequest,
+    scopes: Scopes,
+    store: &Store,
+) -> Result<JwtToken, ApiError> {
     let jwt_secret = match get_jwt_secret(store) {
         Ok(Some(secret)) => secret.jwt_secret().to_vec(),
         Ok(None) => {
@@ -70,6 +78,7 @@ pub fn generate_jwt_tokens(req: JwtTokenRequest, store: &Store) -> Result<JwtTok
         executor_public_key: executor_public_key.clone(),
         exp: access_expiration.timestamp() as usize,
         token_type: TokenType::Access,
+        scopes: scopes.clone(),
     };
 
     let access_token = encode(
@@ -96,6 +105,7 @@ pub fn generate_jwt_tokens(req: JwtTokenRequest, store: &Store) -> Result<JwtTok
         executor_public_key,
         exp: refresh_expiration.timestamp() as usize,
         token_type: TokenType::Refresh,
+        scopes,
     };
 
     let refresh_token = encode(
@@ -183,6 +193,7 @@ pub fn refresh_access_token(refresh_token: &str, store: &Store) -> Result<JwtTok
 
     let context_id = token_data.claims.context_id;
     let executor = token_data.claims.executor_public_key.clone();
+    let scopes = token_data.claims.scopes;
 
     let db_key = format!("{}{}", context_id, token_data.claims.exp);
     let db_key_hash = hash::Hash::new(db_key.as_bytes());
@@ -236,6 +247,7 @@ pub fn refresh_access_token(refresh_token: &str, store: &Store) -> Result<JwtTok
         executor_public_key: executor.clone(),
         exp: access_expiration.timestamp() as usize,
         token_type: TokenType::Access,
+        scopes: scopes.clone(),
     };
 
     let access_token = encode(
@@ -249,7 +261,7 @@ pub fn refresh_access_token(refresh_token: &str, store: &Store) -> Result<JwtTok
     })?;
 
     let payload = JwtTokenRequest::new(context_id, executor);
-    let jwt_tokens = generate_jwt_tokens(payload, store).map_err(|err| ApiError {
+    let jwt_tokens = generate_jwt_tokens(payload, scopes, store).map_err(|err| ApiError {
         status_code: StatusCode::BAD_REQUEST,
         message: format!("Failed to generate access token: {err}"),
     })?;
@@ -5,6 +5,7 @@ use multiaddr::{Multiaddr, Protocol};
 
 use crate::admin::service::AdminConfig;
 use crate::jsonrpc::JsonRpcConfig;
+use crate::metrics::MetricsConfig;
 use crate::ws::WsConfig;
 
 pub const DEFAULT_PORT: u16 = 2528; // (CHAT in T9) + 100
@@ -28,6 +29,9 @@ pub struct ServerConfig {
 
     #[cfg(feature = "websocket")]
     pub websocket: Option<WsConfig>,
+
+    #[cfg(feature = "metrics")]
+    pub metrics: Option<MetricsConfig>,
 }
 
 impl ServerConfig {
@@ -38,6 +42,7 @@ impl ServerConfig {
         admin: Option<AdminConfig>,
         jsonrpc: Option<JsonRpcConfig>,
         websocket: Option<WsConfig>,
+        metrics: Option<MetricsConfig>,
     ) -> Self {
         Self {
             listen,
@@ -45,6 +50,7 @@ impl ServerConfig {
             admin,
             jsonrpc,
             websocket,
+            metrics,
         }
     }
 }
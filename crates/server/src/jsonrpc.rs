@@ -2,23 +2,35 @@ use std::sync::Arc;
 
 use axum::routing::{post, MethodRouter};
 use axum::{Extension, Json};
-use calimero_node_primitives::{CallError as PrimitiveCallError, ExecutionRequest, ServerSender};
+use calimero_node_primitives::{
+    CallError as PrimitiveCallError, ExecutionRequest, MultiExecutionRequest, ServerSender,
+};
 use calimero_primitives::context::ContextId;
-use calimero_primitives::identity::PublicKey;
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::{PublicKey, Scopes};
+use calimero_runtime::errors::FunctionCallError;
+use calimero_runtime::logic::Outcome;
 use calimero_server_primitives::jsonrpc::{
-    Request as PrimitiveRequest, RequestPayload, Response as PrimitiveResponse, ResponseBody,
-    ResponseBodyError, ResponseBodyResult, ServerResponseError,
+    Batch, Request as PrimitiveRequest, RequestPayload, Response as PrimitiveResponse,
+    ResponseBody, ResponseBodyError, ResponseBodyResult, ServerResponseError, Version,
 };
+use calimero_store::Store;
 use eyre::{eyre, Error as EyreError};
 use serde::{Deserialize, Serialize};
 use serde_json::{from_value as from_json_value, to_value as to_json_value, Value};
 use thiserror::Error as ThisError;
 use tokio::sync::oneshot;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, info_span, Instrument};
 
 use crate::config::ServerConfig;
 
-mod execute;
+mod diff;
+pub(crate) mod execute;
+mod multi_execute;
+mod prove;
+
+/// The most requests a single batch may hold, as they're handled in turn.
+const MAX_BATCH_SIZE: usize = 100;
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[non_exhaustive]
@@ -36,11 +48,13 @@ impl JsonRpcConfig {
 
 pub(crate) struct ServiceState {
     server_sender: ServerSender,
+    store: Store,
 }
 
 pub(crate) fn service(
     config: &ServerConfig,
     server_sender: ServerSender,
+    store: Store,
 ) -> Option<(&'static str, MethodRouter)> {
     let _config = match &config.jsonrpc {
         Some(config) if config.enabled => config,
@@ -56,19 +70,120 @@ pub(crate) fn service(
         info!("JSON RPC server listening on {}/http{{{}}}", listen, path);
     }
 
-    let state = Arc::new(ServiceState { server_sender });
+    let state = Arc::new(ServiceState {
+        server_sender,
+        store,
+    });
 
     Some((path, post(handle_request).layer(Extension(state))))
 }
 
 async fn handle_request(
     Extension(state): Extension<Arc<ServiceState>>,
-    Json(request): Json<PrimitiveRequest<Value>>,
-) -> Json<PrimitiveResponse> {
+    // absent on the dev routes, which aren't authenticated with tokens
+    scopes: Option<Extension<Scopes>>,
+    Json(message): Json<Batch<Value>>,
+) -> Json<Batch<PrimitiveResponse>> {
+    let scopes = scopes.map(|Extension(scopes)| scopes);
+
+    let requests = match message {
+        Batch::Single(request) => {
+            return Json(Batch::Single(
+                handle_single(state, scopes.as_ref(), request).await,
+            ))
+        }
+        Batch::Batch(requests) => requests,
+    };
+
+    if requests.is_empty() {
+        return Json(Batch::Single(PrimitiveResponse::new(
+            Version::TwoPointZero,
+            None,
+            ResponseBody::Error(ResponseBodyError::ServerError(
+                ServerResponseError::InvalidRequest("empty batch".to_owned()),
+            )),
+        )));
+    }
+
+    if requests.len() > MAX_BATCH_SIZE {
+        return Json(Batch::Single(PrimitiveResponse::new(
+            Version::TwoPointZero,
+            None,
+            ResponseBody::Error(ResponseBodyError::ServerError(
+                ServerResponseError::InvalidRequest(format!(
+                    "batch of {} requests exceeds the limit of {}",
+                    requests.len(),
+                    MAX_BATCH_SIZE
+                )),
+            )),
+        )));
+    }
+
+    let mut responses = Vec::with_capacity(requests.len());
+
+    // handled in order, so that calls later in the batch observe the effects
+    // of the ones before them
+    for request in requests {
+        responses.push(handle_single(Arc::clone(&state), scopes.as_ref(), request).await);
+    }
+
+    Json(Batch::Batch(responses))
+}
+
+async fn handle_single(
+    state: Arc<ServiceState>,
+    scopes: Option<&Scopes>,
+    request: Value,
+) -> PrimitiveResponse {
+    let request = match from_json_value::<PrimitiveRequest<Value>>(request) {
+        Ok(request) => request,
+        Err(err) => {
+            error!(%err, "Failed to deserialize Request");
+
+            return PrimitiveResponse::new(
+                Version::TwoPointZero,
+                None,
+                ResponseBody::Error(ResponseBodyError::ServerError(
+                    ServerResponseError::InvalidRequest(err.to_string()),
+                )),
+            );
+        }
+    };
+
     debug!(?request, "Received request");
     let body = match from_json_value::<RequestPayload>(request.payload) {
+        Ok(payload) if scopes.is_some_and(|scopes| !permits(scopes, &payload)) => {
+            ResponseBody::Error(ResponseBodyError::ServerError(
+                ServerResponseError::Forbidden(
+                    "not permitted by the scopes of the token".to_owned(),
+                ),
+            ))
+        }
         Ok(payload) => match payload {
-            RequestPayload::Execute(request) => request.handle(state).await.to_res_body(),
+            RequestPayload::Execute(mut request) => {
+                request.read_only = scopes.is_some_and(|scopes| scopes.read_only);
+
+                let span = info_span!(
+                    "jsonrpc_execute",
+                    context_id = %request.context_id,
+                    method = %request.method,
+                    author = %request.executor_public_key,
+                );
+
+                request.handle(state).instrument(span).await.to_res_body()
+            }
+            RequestPayload::MultiExecute(request) => {
+                let span = info_span!(
+                    "jsonrpc_multi_execute",
+                    context_id = %request.context_id,
+                    calls = request.calls.len(),
+                    author = %request.executor_public_key,
+                );
+
+                request.handle(state).instrument(span).await.to_res_body()
+            }
+            RequestPayload::Prove(request) => request.handle(state).await.to_res_body(),
+            RequestPayload::Diff(request) => request.handle(state).await.to_res_body(),
         },
         Err(err) => {
             error!(%err, "Failed to deserialize RequestPayload");
@@ -79,8 +194,27 @@ async fn handle_request(
         }
     };
 
-    let response = PrimitiveResponse::new(request.jsonrpc, request.id, body);
-    Json(response)
+    PrimitiveResponse::new(request.jsonrpc, request.id, body)
+}
+
+fn permits(scopes: &Scopes, payload: &RequestPayload) -> bool {
+    match payload {
+        // whether it changes the state is only known once executed, where
+        // the changes are rejected when the scopes are read-only
+        RequestPayload::Execute(request) => {
+            scopes.permits_call(&request.context_id, &request.method)
+        }
+        // calls made as one are made to change the state
+        RequestPayload::MultiExecute(request) => {
+            !scopes.read_only
+                && request
+                    .calls
+                    .iter()
+                    .all(|call| scopes.permits_call(&request.context_id, &call.method))
+        }
+        RequestPayload::Prove(request) => scopes.permits_context(&request.context_id),
+        RequestPayload::Diff(request) => scopes.permits_context(&request.context_id),
+    }
 }
 
 pub(crate) trait Request {
@@ -139,54 +273,102 @@ pub(crate) enum CallError {
     CallError(PrimitiveCallError),
     #[error("function call error: {0}")]
     FunctionCallError(String), // TODO use FunctionCallError from runtime-primitives once they are migrated
+    #[error("application error")]
+    ApplicationError(Vec<u8>),
+    #[error("unauthorized")]
+    Unauthorized,
     #[error(transparent)]
     InternalError(EyreError),
 }
 
+#[expect(clippy::too_many_arguments, reason = "Acceptable here")]
 pub(crate) async fn call(
     sender: ServerSender,
     context_id: ContextId,
     method: String,
     args: Vec<u8>,
     executor_public_key: PublicKey,
+    root_hash: Option<Hash>,
+    idempotency_key: Option<String>,
+    read_only: bool,
 ) -> Result<Option<String>, CallError> {
     let (outcome_sender, outcome_receiver) = oneshot::channel();
 
     sender
-        .send(ExecutionRequest::new(
-            context_id,
-            method,
-            args,
-            executor_public_key,
-            outcome_sender,
-        ))
+        .send(
+            ExecutionRequest::new(
+                context_id,
+                method,
+                args,
+                executor_public_key,
+                root_hash,
+                idempotency_key,
+                outcome_sender,
+            )
+            .with_read_only(read_only)
+            .into(),
+        )
         .await
         .map_err(|e| CallError::InternalError(eyre!("Failed to send call message: {}", e)))?;
 
     match outcome_receiver.await.map_err(|e| {
         CallError::InternalError(eyre!("Failed to receive call outcome result: {}", e))
     })? {
-        Ok(outcome) => {
-            let x = outcome.logs.len().checked_ilog10().unwrap_or(0) as usize + 1;
-            for (i, log) in outcome.logs.iter().enumerate() {
-                info!("execution log {i:>x$}| {}", log);
-            }
+        Ok(outcome) => output(outcome),
+        Err(err) => Err(CallError::CallError(err)),
+    }
+}
 
-            let Some(returns) = outcome
-                .returns
-                .map_err(|e| CallError::FunctionCallError(e.to_string()))?
-            else {
-                return Ok(None);
-            };
+/// Executes the calls as one, returning the output of each call executed,
+/// which ends at the first that failed, if any.
+pub(crate) async fn call_many(
+    sender: ServerSender,
+    context_id: ContextId,
+    calls: Vec<(String, Vec<u8>)>,
+    executor_public_key: PublicKey,
+) -> Result<Vec<Result<Option<String>, CallError>>, CallError> {
+    let (outcome_sender, outcome_receiver) = oneshot::channel();
 
-            Ok(Some(String::from_utf8(returns).map_err(|e| {
-                CallError::InternalError(eyre!("Failed to convert call result to string: {}", e))
-            })?))
-        }
+    sender
+        .send(
+            MultiExecutionRequest::new(context_id, calls, executor_public_key, outcome_sender)
+                .into(),
+        )
+        .await
+        .map_err(|e| CallError::InternalError(eyre!("Failed to send call message: {}", e)))?;
+
+    match outcome_receiver.await.map_err(|e| {
+        CallError::InternalError(eyre!("Failed to receive call outcome result: {}", e))
+    })? {
+        Ok(outcomes) => Ok(outcomes.into_iter().map(output).collect()),
         Err(err) => Err(CallError::CallError(err)),
     }
 }
 
+fn output(outcome: Outcome) -> Result<Option<String>, CallError> {
+    let x = outcome.logs.len().checked_ilog10().unwrap_or(0) as usize + 1;
+    for (i, log) in outcome.logs.iter().enumerate() {
+        info!("execution log {i:>x$}| {}", log);
+    }
+
+    let returns = match outcome.returns {
+        Ok(returns) => returns,
+        Err(FunctionCallError::ExecutionError(err)) => {
+            return Err(CallError::ApplicationError(err))
+        }
+        Err(FunctionCallError::Unauthorized) => return Err(CallError::Unauthorized),
+        Err(err) => return Err(CallError::FunctionCallError(err.to_string())),
+    };
+
+    let Some(returns) = returns else {
+        return Ok(None);
+    };
+
+    Ok(Some(String::from_utf8(returns).map_err(|e| {
+        CallError::InternalError(eyre!("Failed to convert call result to string: {}", e))
+    })?))
+}
+
 macro_rules! mount_method {
     ($request:ident -> Result<$response:ident, $error:ident>, $handle:path) => {
         impl crate::jsonrpc::Request for $request {
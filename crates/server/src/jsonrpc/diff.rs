@@ -0,0 +1,47 @@
+#[cfg(test)]
+#[path = "../tests/jsonrpc/diff.rs"]
+mod tests;
+
+use std::sync::Arc;
+
+use calimero_context::history;
+use calimero_server_primitives::jsonrpc::{DiffError, DiffRequest, DiffResponse, StateChange};
+use calimero_store::key::ContextMeta as ContextMetaKey;
+use calimero_store::Store;
+use eyre::{bail, Result as EyreResult};
+
+use crate::jsonrpc::{mount_method, ServiceState};
+
+mount_method!(DiffRequest-> Result<DiffResponse, DiffError>, handle);
+
+async fn handle(request: DiffRequest, state: Arc<ServiceState>) -> EyreResult<DiffResponse> {
+    diff(&state.store, &request)
+}
+
+fn diff(store: &Store, request: &DiffRequest) -> EyreResult<DiffResponse> {
+    if !store
+        .handle()
+        .has(&ContextMetaKey::new(request.context_id))?
+    {
+        bail!(DiffError::ContextNotFound(request.context_id));
+    }
+
+    for root_hash in [request.from, request.to] {
+        if history::roots(store, &request.context_id, root_hash)?.is_none() {
+            bail!(DiffError::RootHashNotRetained(root_hash));
+        }
+    }
+
+    let Some(changes) = history::diff(store, &request.context_id, request.from, request.to)? else {
+        bail!(DiffError::RootHashNotRetained(request.from));
+    };
+
+    Ok(DiffResponse::new(
+        changes
+            .into_iter()
+            .map(|(key, old, new)| {
+                StateChange::new(key.into(), old.map(Into::into), new.map(Into::into))
+            })
+            .collect(),
+    ))
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use calimero_node_primitives::ServerSender;
 use calimero_server_primitives::jsonrpc::{ExecuteError, ExecuteRequest, ExecuteResponse};
 use eyre::{bail, Result as EyreResult};
 use serde_json::{from_str as from_json_str, to_vec as to_json_vec, Value};
@@ -10,6 +11,15 @@ use crate::jsonrpc::{call, mount_method, CallError, ServiceState};
 mount_method!(ExecuteRequest-> Result<ExecuteResponse, ExecuteError>, handle);
 
 async fn handle(request: ExecuteRequest, state: Arc<ServiceState>) -> EyreResult<ExecuteResponse> {
+    execute(state.server_sender.clone(), request).await
+}
+
+/// Executes the request, failing with an [`ExecuteError`] where it should be
+/// reported to the client.
+pub(crate) async fn execute(
+    server_sender: ServerSender,
+    request: ExecuteRequest,
+) -> EyreResult<ExecuteResponse> {
     let args = match to_json_vec(&request.args_json) {
         Ok(args) => args,
         Err(err) => {
@@ -20,11 +30,14 @@ async fn handle(request: ExecuteRequest, state: Arc<ServiceState>) -> EyreResult
     };
 
     match call(
-        state.server_sender.clone(),
+        server_sender,
         request.context_id,
         request.method,
         args,
         request.executor_public_key,
+        request.root_hash,
+        request.idempotency_key,
+        request.read_only,
     )
     .await
     {
@@ -43,6 +56,8 @@ async fn handle(request: ExecuteRequest, state: Arc<ServiceState>) -> EyreResult
                 CallError::FunctionCallError(message) => {
                     bail!(ExecuteError::FunctionCallError(message))
                 }
+                CallError::ApplicationError(err) => bail!(ExecuteError::application(&err)),
+                CallError::Unauthorized => bail!(ExecuteError::Unauthorized),
                 CallError::InternalError(err) => bail!(err),
             }
         }
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use calimero_server_primitives::jsonrpc::{
+    ExecuteError, MultiExecuteError, MultiExecuteRequest, MultiExecuteResponse,
+};
+use eyre::{bail, Result as EyreResult};
+use serde_json::{from_str as from_json_str, to_vec as to_json_vec, Value};
+use tracing::error;
+
+use crate::jsonrpc::{call_many, mount_method, CallError, ServiceState};
+
+mount_method!(MultiExecuteRequest-> Result<MultiExecuteResponse, MultiExecuteError>, handle);
+
+async fn handle(
+    request: MultiExecuteRequest,
+    state: Arc<ServiceState>,
+) -> EyreResult<MultiExecuteResponse> {
+    let mut calls = Vec::with_capacity(request.calls.len());
+
+    for call in request.calls {
+        match to_json_vec(&call.args_json) {
+            Ok(args) => calls.push((call.method, args)),
+            Err(err) => {
+                bail!(MultiExecuteError::SerdeError {
+                    message: err.to_string()
+                })
+            }
+        }
+    }
+
+    let results = match call_many(
+        state.server_sender.clone(),
+        request.context_id,
+        calls,
+        request.executor_public_key,
+    )
+    .await
+    {
+        Ok(results) => results,
+        Err(CallError::CallError(err)) => bail!(MultiExecuteError::CallError(err)),
+        Err(err) => bail!(err),
+    };
+
+    let mut outputs = Vec::with_capacity(results.len());
+
+    for (index, result) in results.into_iter().enumerate() {
+        let output = match result {
+            Ok(output) => output,
+            Err(err) => {
+                error!(%err, index, "Failed to execute JSON RPC method");
+
+                let error = match err {
+                    CallError::CallError(err) => ExecuteError::CallError(err),
+                    CallError::FunctionCallError(message) => {
+                        ExecuteError::FunctionCallError(message)
+                    }
+                    CallError::ApplicationError(err) => ExecuteError::application(&err),
+                    CallError::Unauthorized => ExecuteError::Unauthorized,
+                    CallError::InternalError(err) => bail!(err),
+                };
+
+                bail!(MultiExecuteError::CallFailed { index, error })
+            }
+        };
+
+        let Some(output) = output else {
+            outputs.push(None);
+            continue;
+        };
+
+        match from_json_str::<Value>(&output) {
+            Ok(v) => outputs.push(Some(v)),
+            Err(err) => bail!(MultiExecuteError::SerdeError {
+                message: err.to_string()
+            }),
+        }
+    }
+
+    Ok(MultiExecuteResponse::new(outputs))
+}
@@ -0,0 +1,44 @@
+use core::cell::RefCell;
+use std::sync::Arc;
+
+use calimero_server_primitives::jsonrpc::{ProveError, ProveRequest, ProveResponse};
+use calimero_storage::address::Id;
+use calimero_storage::index::generate_proof;
+use calimero_store::key::{ContextMeta as ContextMetaKey, ContextState as ContextStateKey};
+use eyre::{bail, Report, Result as EyreResult};
+
+use crate::jsonrpc::{mount_method, ServiceState};
+
+mount_method!(ProveRequest-> Result<ProveResponse, ProveError>, handle);
+
+async fn handle(request: ProveRequest, state: Arc<ServiceState>) -> EyreResult<ProveResponse> {
+    // the root hash and the proof have to come from the same state
+    let handle = state.store.snapshot()?;
+
+    let Some(meta) = handle.get(&ContextMetaKey::new(request.context_id))? else {
+        bail!(ProveError::ContextNotFound(request.context_id));
+    };
+
+    // the storage reads can't fail, so the first store error is kept aside
+    let failure = RefCell::new(None::<Report>);
+
+    let proof = generate_proof(Id::new(*request.id), |key| {
+        match handle.get(&ContextStateKey::new(request.context_id, key.to_bytes())) {
+            Ok(entry) => entry.map(|entry| entry.value.into_boxed().into_vec()),
+            Err(err) => {
+                let _ignored = failure.borrow_mut().get_or_insert(err);
+                None
+            }
+        }
+    });
+
+    if let Some(err) = failure.into_inner() {
+        return Err(err);
+    }
+
+    let Some(proof) = proof? else {
+        bail!(ProveError::EntityNotFound(request.id));
+    };
+
+    Ok(ProveResponse::new(meta.root_hash.into(), proof))
+}
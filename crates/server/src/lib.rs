@@ -16,6 +16,7 @@ use config::ServerConfig;
 use eyre::{bail, Result as EyreResult};
 use libp2p::identity::Keypair;
 use multiaddr::Protocol;
+use prometheus_client::registry::Registry;
 use tokio::net::TcpListener;
 use tokio::sync::broadcast;
 use tokio::task::JoinSet;
@@ -34,6 +35,8 @@ pub mod admin;
 pub mod config;
 #[cfg(feature = "jsonrpc")]
 pub mod jsonrpc;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 #[cfg(feature = "admin")]
 mod middleware;
 mod verifywalletsignatures;
@@ -68,6 +71,7 @@ pub async fn start(
     ctx_manager: ContextManager,
     node_events: broadcast::Sender<NodeEvent>,
     store: Store,
+    registry: Arc<Registry>,
 ) -> EyreResult<()> {
     let mut config = config;
     let mut addrs = Vec::with_capacity(config.listen.len());
@@ -122,7 +126,9 @@ pub async fn start(
 
     #[cfg(feature = "jsonrpc")]
     {
-        if let Some((path, handler)) = jsonrpc::service(&config, server_sender.clone()) {
+        if let Some((path, handler)) =
+            jsonrpc::service(&config, server_sender.clone(), store.clone())
+        {
             app = app
                 .route(path, handler.clone())
                 .route_layer(JwtLayer::new(store.clone()))
@@ -140,7 +146,20 @@ pub async fn start(
 
     #[cfg(feature = "websocket")]
     {
-        if let Some((path, handler)) = ws::service(&config, node_events.clone()) {
+        if let Some(routes) =
+            ws::service(&config, &node_events, server_sender.clone(), store.clone())?
+        {
+            for (path, handler) in routes {
+                app = app.route(path, handler);
+            }
+
+            serviced = true;
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    {
+        if let Some((path, handler)) = metrics::service(&config, registry, store.clone()) {
             app = app.route(path, handler);
 
             serviced = true;
@@ -0,0 +1,92 @@
+#[cfg(test)]
+#[path = "tests/metrics.rs"]
+mod tests;
+
+use std::sync::Arc;
+
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, MethodRouter};
+use axum::Extension;
+use calimero_primitives::identity::Scopes;
+use calimero_store::Store;
+use prometheus_client::encoding::text::encode;
+use prometheus_client::registry::Registry;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::config::ServerConfig;
+use crate::middleware::jwt::JwtLayer;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct MetricsConfig {
+    #[serde(default = "calimero_primitives::common::bool_true")]
+    pub enabled: bool,
+}
+
+impl MetricsConfig {
+    #[must_use]
+    pub const fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
+pub(crate) fn service(
+    config: &ServerConfig,
+    registry: Arc<Registry>,
+    store: Store,
+) -> Option<(&'static str, MethodRouter)> {
+    let _config = match &config.metrics {
+        Some(config) if config.enabled => config,
+        _ => {
+            info!("Metrics server is disabled");
+            return None;
+        }
+    };
+
+    let path = "/metrics"; // todo! source from config
+
+    for listen in &config.listen {
+        info!("Metrics server listening on {}/http{{{}}}", listen, path);
+    }
+
+    Some((
+        path,
+        get(handle_request)
+            .layer(Extension(registry))
+            .route_layer(JwtLayer::new(store)),
+    ))
+}
+
+async fn handle_request(
+    Extension(registry): Extension<Arc<Registry>>,
+    Extension(scopes): Extension<Scopes>,
+) -> Response {
+    // the metrics describe the node as a whole, so only its admins may read them
+    if !scopes.permits_admin(false) {
+        return (
+            StatusCode::FORBIDDEN,
+            "Not permitted by the granted scopes.",
+        )
+            .into_response();
+    }
+
+    let mut body = String::new();
+
+    if let Err(err) = encode(&mut body, &registry) {
+        error!(%err, "Failed to encode metrics");
+
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    (
+        [(
+            CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )],
+        body,
+    )
+        .into_response()
+}
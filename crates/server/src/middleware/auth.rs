@@ -8,7 +8,7 @@ use axum::extract::Request;
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
 use calimero_primitives::context::ContextId;
-use calimero_primitives::identity::{ClientKey, WalletType};
+use calimero_primitives::identity::{ClientKey, Scopes, WalletType};
 use calimero_store::Store;
 use chrono::Utc;
 use libp2p::futures::future::BoxFuture;
@@ -16,18 +16,30 @@ use serde_json::from_slice as from_json_slice;
 use tower::{Layer, Service};
 use tracing::debug;
 
-use crate::admin::storage::client_keys::exists_client_key;
+use crate::admin::storage::client_keys::get_client_key;
 use crate::admin::storage::root_key::exists_root_keys;
 use crate::admin::utils::auth::verify_near_public_key;
+use crate::middleware::jwt;
 
 #[derive(Clone)]
 pub struct AuthSignatureLayer {
     store: Store,
+    // whether the routes make up the admin API, which requires the admin
+    // scope, and also accepts tokens in place of signatures
+    admin: bool,
 }
 
 impl AuthSignatureLayer {
     pub const fn new(store: Store) -> Self {
-        Self { store }
+        Self { store, admin: true }
+    }
+
+    /// For routes any client key may use, such as issuing tokens to itself.
+    pub const fn unscoped(store: Store) -> Self {
+        Self {
+            store,
+            admin: false,
+        }
     }
 }
 
@@ -38,6 +50,7 @@ impl<S> Layer<S> for AuthSignatureLayer {
         AuthSignatureMiddleware {
             inner,
             store: self.store.clone(),
+            admin: self.admin,
         }
     }
 }
@@ -46,6 +59,7 @@ impl<S> Layer<S> for AuthSignatureLayer {
 pub struct AuthSignatureMiddleware<S> {
     inner: S,
     store: Store,
+    admin: bool,
 }
 
 impl<S> Service<Request<Body>> for AuthSignatureMiddleware<S>
@@ -62,15 +76,30 @@ where
         self.inner.poll_ready(cx)
     }
 
-    fn call(&mut self, req: Request<Body>) -> Self::Future {
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
         // todo! experiment with Interior<Store>: WriteLayer<Interior>
-        let result = auth(req.headers(), &self.store.clone());
-
-        if let Err(err) = result {
-            let error_response = err.into_response();
+        let result = if self.admin && req.headers().contains_key("authorization") {
+            jwt::auth(req.headers(), &self.store).map_err(IntoResponse::into_response)
+        } else {
+            auth(req.headers(), &self.store).map_err(IntoResponse::into_response)
+        };
+
+        let scopes = match result {
+            Ok(scopes) => scopes,
+            Err(error_response) => return Box::pin(async move { Ok(error_response) }),
+        };
+
+        if self.admin && !scopes.permits_admin(!req.method().is_safe()) {
+            let error_response = (
+                StatusCode::FORBIDDEN,
+                "Not permitted by the granted scopes.",
+            )
+                .into_response();
             return Box::pin(async move { Ok(error_response) });
         }
 
+        let _ = req.extensions_mut().insert(scopes);
+
         let future = self.inner.call(req);
 
         Box::pin(async move {
@@ -89,7 +118,8 @@ struct AuthHeaders {
     context_id: Option<ContextId>,
 }
 
-pub fn auth(headers: &HeaderMap, store: &Store) -> Result<(), UnauthorizedError<'static>> {
+/// Returns the scopes of the client key that signed the request.
+pub fn auth(headers: &HeaderMap, store: &Store) -> Result<Scopes, UnauthorizedError<'static>> {
     let auth_headers = get_auth_headers(headers).map_err(|e| {
         debug!("Failed to extract authentication headers {}", e);
         UnauthorizedError::new("Failed to extract authentication headers.")
@@ -103,10 +133,10 @@ pub fn auth(headers: &HeaderMap, store: &Store) -> Result<(), UnauthorizedError<
         auth_headers.context_id,
     );
 
-    let key_exists = exists_client_key(store, &client_key)
+    let stored_key = get_client_key(store, &client_key.signing_key)
         .map_err(|_| UnauthorizedError::new("Issue during extracting client key"))?;
 
-    if !key_exists {
+    if stored_key.is_none() {
         //Only if there are no root keys, we add root key and client key from the request
         let root_keys = exists_root_keys(store)
             .map_err(|_| UnauthorizedError::new("Issue during extracting root keys"))?;
@@ -123,7 +153,7 @@ pub fn auth(headers: &HeaderMap, store: &Store) -> Result<(), UnauthorizedError<
     .map_err(|_| UnauthorizedError::new("Invalid client key."))?;
 
     if is_signature_valid {
-        Ok(())
+        Ok(stored_key.map(|key| key.scopes).unwrap_or_default())
     } else {
         Err(UnauthorizedError::new(
             "Invalid signature for provided key.",
@@ -7,14 +7,18 @@ use axum::body::Body;
 use axum::extract::Request;
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::Scopes;
 use calimero_store::Store;
 use chrono::Utc;
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use libp2p::futures::future::BoxFuture;
 use tower::{Layer, Service};
-use tracing::debug;
+use tracing::{debug, warn};
 
+use crate::admin::storage::api_keys::{get_api_key, record_api_key_use};
 use crate::admin::storage::jwt_secret::get_jwt_secret;
+use crate::admin::utils::api_keys::API_KEY_PREFIX;
 use crate::admin::utils::jwt::Claims;
 
 #[derive(Clone)]
@@ -59,14 +63,18 @@ where
         self.inner.poll_ready(cx)
     }
 
-    fn call(&mut self, req: Request<Body>) -> Self::Future {
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
         // todo! experiment with Interior<Store>: WriteLayer<Interior>
-        let result = auth(req.headers(), &self.store);
+        let scopes = match auth(req.headers(), &self.store) {
+            Ok(scopes) => scopes,
+            Err(err) => {
+                let error_response = err.into_response();
+                return Box::pin(async move { Ok(error_response) });
+            }
+        };
 
-        if let Err(err) = result {
-            let error_response = err.into_response();
-            return Box::pin(async move { Ok(error_response) });
-        }
+        // enforced by the routes, which know what a request amounts to
+        let _ = req.extensions_mut().insert(scopes);
 
         Box::pin(self.inner.call(req))
     }
@@ -77,12 +85,22 @@ struct JwtHeader {
     token: String,
 }
 
-pub fn auth(headers: &HeaderMap, store: &Store) -> Result<(), UnauthorizedError<'static>> {
+pub fn auth(headers: &HeaderMap, store: &Store) -> Result<Scopes, UnauthorizedError<'static>> {
     let jwt_header = get_jwt_token_from_headers(headers).map_err(|e| {
         debug!("Failed to extract authentication headers {}", e);
         UnauthorizedError::new("Failed to extract authentication headers.")
     })?;
 
+    verify(&jwt_header.token, store)
+}
+
+/// Verifies a token obtained other than through the authorization header,
+/// returning the scopes it was granted.
+pub fn verify(token: &str, store: &Store) -> Result<Scopes, UnauthorizedError<'static>> {
+    if token.starts_with(API_KEY_PREFIX) {
+        return verify_api_key(token, store);
+    }
+
     let jwt_secret = match get_jwt_secret(store) {
         Ok(Some(secret)) => *secret.jwt_secret(),
         Ok(None) => {
@@ -94,7 +112,7 @@ pub fn auth(headers: &HeaderMap, store: &Store) -> Result<(), UnauthorizedError<
     };
 
     let token_data = decode::<Claims>(
-        &jwt_header.token,
+        token,
         &DecodingKey::from_secret(&jwt_secret),
         &Validation::default(),
     )
@@ -110,7 +128,32 @@ pub fn auth(headers: &HeaderMap, store: &Store) -> Result<(), UnauthorizedError<
         return Err(UnauthorizedError::new("Token expired."));
     }
 
-    Ok(())
+    Ok(token_data.claims.scopes)
+}
+
+fn verify_api_key(secret: &str, store: &Store) -> Result<Scopes, UnauthorizedError<'static>> {
+    let api_key = match get_api_key(store, &Hash::new(secret.as_bytes())) {
+        Ok(Some(api_key)) => api_key,
+        Ok(None) => {
+            return Err(UnauthorizedError::new("API key not valid."));
+        }
+        Err(_) => {
+            return Err(UnauthorizedError::new("Failed to fetch API key."));
+        }
+    };
+
+    #[expect(clippy::cast_sign_loss, reason = "Essentially infallible")]
+    let now = Utc::now().timestamp_millis() as u64;
+    if api_key.is_expired(now) {
+        return Err(UnauthorizedError::new("API key expired."));
+    }
+
+    // not worth turning the request away over
+    if let Err(err) = record_api_key_use(store, &api_key.id, now) {
+        warn!(%err, "Failed to record the use of API key {}", api_key.id);
+    }
+
+    Ok(api_key.scopes)
 }
 
 fn get_jwt_token_from_headers(headers: &HeaderMap) -> Result<JwtHeader, UnauthorizedError<'_>> {
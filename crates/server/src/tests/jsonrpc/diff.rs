@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use calimero_primitives::hash::Hash;
+use calimero_store::config::StoreConfig;
+use calimero_store::db::RocksDB;
+use calimero_store::key::{ApplicationMeta as ApplicationMetaKey, ContextState as ContextStateKey};
+use calimero_store::slice::Slice;
+use calimero_store::types::{ContextMeta as ContextMetaValue, ContextState as ContextStateValue};
+use tempdir::TempDir;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+const STATE_KEY: [u8; 32] = [10; 32];
+
+// a context at root 2, having moved there from root 1, where the entry was
+// at its earlier value
+fn setup() -> (TempDir, Store) {
+    let dir = TempDir::new("_calimero_server_diff").unwrap();
+
+    let config = StoreConfig::new(dir.path().to_owned().try_into().unwrap());
+
+    let store = Store::open::<RocksDB>(&config).unwrap();
+
+    let mut handle = store.handle();
+
+    handle
+        .put(
+            &ContextStateKey::new(CONTEXT_ID.into(), STATE_KEY),
+            &ContextStateValue::from(Slice::from(b"new".to_vec())),
+        )
+        .unwrap();
+
+    history::record(
+        &store,
+        &mut store.clone(),
+        8,
+        CONTEXT_ID.into(),
+        [1; 32].into(),
+        BTreeMap::from([(STATE_KEY, Some(Box::from(&b"old"[..])))]),
+    )
+    .unwrap();
+
+    handle
+        .put(
+            &ContextMetaKey::new(CONTEXT_ID.into()),
+            &ContextMetaValue::new(ApplicationMetaKey::new([0; 32].into()), [2; 32]),
+        )
+        .unwrap();
+
+    (dir, store)
+}
+
+fn request(context_id: [u8; 32], from: [u8; 32], to: [u8; 32]) -> DiffRequest {
+    DiffRequest::new(context_id.into(), from.into(), to.into())
+}
+
+#[test]
+fn changes_between_retained_roots_are_listed() {
+    let (_dir, store) = setup();
+
+    let response = diff(&store, &request(CONTEXT_ID, [1; 32], [2; 32])).unwrap();
+
+    let [change] = &response.changes[..] else {
+        panic!("unexpected changes: {:?}", response.changes);
+    };
+
+    assert_eq!(change.key, Hash::from(STATE_KEY));
+    assert_eq!(change.old.as_deref(), Some(&b"old"[..]));
+    assert_eq!(change.new.as_deref(), Some(&b"new"[..]));
+
+    let response = diff(&store, &request(CONTEXT_ID, [2; 32], [2; 32])).unwrap();
+
+    assert!(response.changes.is_empty());
+}
+
+#[test]
+fn unknown_contexts_are_reported() {
+    let (_dir, store) = setup();
+
+    let err = diff(&store, &request([3; 32], [1; 32], [2; 32])).unwrap_err();
+
+    assert!(matches!(
+        err.downcast::<DiffError>(),
+        Ok(DiffError::ContextNotFound(context_id)) if context_id == [3; 32].into()
+    ));
+}
+
+#[test]
+fn roots_not_retained_are_reported() {
+    let (_dir, store) = setup();
+
+    for (from, to) in [([4; 32], [2; 32]), ([1; 32], [4; 32])] {
+        let err = diff(&store, &request(CONTEXT_ID, from, to)).unwrap_err();
+
+        assert!(matches!(
+            err.downcast::<DiffError>(),
+            Ok(DiffError::RootHashNotRetained(root_hash)) if root_hash == [4; 32].into()
+        ));
+    }
+}
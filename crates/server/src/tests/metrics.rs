@@ -0,0 +1,50 @@
+use axum::body::to_bytes;
+use calimero_primitives::identity::Role;
+use prometheus_client::metrics::counter::Counter;
+
+use super::*;
+
+fn registry() -> Arc<Registry> {
+    let mut registry = Registry::default();
+
+    let calls = Counter::<u64>::default();
+
+    registry.register("calls", "Calls made", calls.clone());
+
+    let _ = calls.inc_by(3);
+
+    Arc::new(registry)
+}
+
+#[tokio::test]
+async fn test_metrics_are_encoded_for_admins() {
+    let scopes = Scopes::new(Role::Admin, None, None, true);
+
+    let response = handle_request(Extension(registry()), Extension(scopes)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "application/openmetrics-text; version=1.0.0; charset=utf-8"
+    );
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    assert_eq!(
+        body,
+        "# HELP calls Calls made.\n# TYPE calls counter\ncalls_total 3\n# EOF\n"
+    );
+}
+
+#[tokio::test]
+async fn test_metrics_need_the_admin_role() {
+    let scopes = Scopes::new(Role::Application, None, None, false);
+
+    let response = handle_request(Extension(registry()), Extension(scopes)).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    assert_eq!(body, "Not permitted by the granted scopes.");
+}
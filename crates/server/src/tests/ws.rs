@@ -0,0 +1,347 @@
+use axum::http::{StatusCode, Uri};
+use calimero_node_primitives::{CallError, ExecutionRequest, ServerRequest};
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::{ApiKey, Role};
+use calimero_runtime::errors::{FunctionCallError, MethodResolutionError};
+use calimero_runtime::logic::Outcome;
+use calimero_server_primitives::jsonrpc::{ExecuteError, ExecuteRequest};
+use calimero_store::config::StoreConfig;
+use calimero_store::db::RocksDB;
+use serde_json::json;
+use tempdir::TempDir;
+use tokio::join;
+
+use super::*;
+use crate::admin::storage::api_keys::put_api_key;
+use crate::admin::utils::api_keys::API_KEY_PREFIX;
+
+const PERMITTED: [u8; 32] = [1; 32];
+const OTHER: [u8; 32] = [2; 32];
+
+// a service whose store holds an API key only permitted the first context
+fn setup() -> (TempDir, Arc<ServiceState>, String) {
+    let (dir, state, token, _node) = setup_with_node();
+
+    (dir, state, token)
+}
+
+// as above, along with the receiving end of the calls made to the node
+fn setup_with_node() -> (
+    TempDir,
+    Arc<ServiceState>,
+    String,
+    mpsc::Receiver<ServerRequest>,
+) {
+    let dir = TempDir::new("_calimero_server_ws").unwrap();
+
+    let config = StoreConfig::new(dir.path().to_owned().try_into().unwrap());
+
+    let store = Store::open::<RocksDB>(&config).unwrap();
+
+    let token = format!("{API_KEY_PREFIX}subscriber");
+
+    let scopes = Scopes::new(Role::Application, Some(vec![PERMITTED.into()]), None, true);
+
+    let api_key = ApiKey::new(
+        Hash::new(token.as_bytes()),
+        "subscriber".to_owned(),
+        scopes,
+        0,
+        None,
+    );
+
+    put_api_key(&store, &api_key).unwrap();
+
+    let (node_events, _) = broadcast::channel(8);
+    let (server_sender, node) = mpsc::channel(8);
+
+    let state = Arc::new(ServiceState {
+        events: EventLog::start(store.clone(), 8, &node_events).unwrap(),
+        server_sender,
+        store,
+        connections: RwLock::default(),
+    });
+
+    (dir, state, token, node)
+}
+
+async fn send(state: &Arc<ServiceState>, scopes: Scopes, request: Value) -> Response {
+    let (commands, mut receiver) = mpsc::channel(8);
+
+    let connection_id = random();
+
+    let connection_state = ConnectionState {
+        commands,
+        scopes,
+        inner: Arc::default(),
+    };
+
+    drop(
+        state
+            .connections
+            .write()
+            .await
+            .insert(connection_id, connection_state),
+    );
+
+    handle_text_message(connection_id, Arc::clone(state), request.to_string()).await;
+
+    let Some(Command::Send(response)) = receiver.recv().await else {
+        panic!("expected a response");
+    };
+
+    response
+}
+
+async fn subscribe(
+    state: &Arc<ServiceState>,
+    scopes: Scopes,
+    context_id: ContextId,
+) -> ResponseBody {
+    let request = json!({
+        "id": 1_u8,
+        "method": "subscribe",
+        "params": { "contextIds": [context_id] },
+    });
+
+    send(state, scopes, request).await.body
+}
+
+fn execute(id: u64, context_id: [u8; 32], method: &str) -> Value {
+    let request = ExecuteRequest::new(
+        context_id.into(),
+        method.to_owned(),
+        json!({}),
+        [3; 32].into(),
+        None,
+        None,
+    );
+
+    json!({ "id": id, "method": "execute", "params": request })
+}
+
+// answers the next call made to the node, returning the method called and
+// whether it was only permitted to read the state
+async fn answer(
+    node: &mut mpsc::Receiver<ServerRequest>,
+    outcome: Result<Outcome, CallError>,
+) -> (String, bool) {
+    let Some(ServerRequest::Execute(request)) = node.recv().await else {
+        panic!("expected a call");
+    };
+
+    let ExecutionRequest {
+        method,
+        read_only,
+        outcome_sender,
+        ..
+    } = request;
+
+    outcome_sender.send(outcome).unwrap();
+
+    (method, read_only)
+}
+
+fn failing(error: FunctionCallError) -> Result<Outcome, CallError> {
+    let mut outcome = Outcome::returning(None, vec![]);
+
+    outcome.returns = Err(error);
+
+    Ok(outcome)
+}
+
+async fn sse(state: &Arc<ServiceState>, token: Option<&str>, context_id: ContextId) -> StatusCode {
+    let uri = match token {
+        Some(token) => format!("/sse?contextIds={context_id}&token={token}"),
+        None => format!("/sse?contextIds={context_id}"),
+    };
+
+    let query = Query::try_from_uri(&uri.parse::<Uri>().unwrap()).unwrap();
+
+    sse::handler(HeaderMap::new(), query, Extension(Arc::clone(state)))
+        .await
+        .status()
+}
+
+#[tokio::test]
+async fn test_ws_requires_a_token() {
+    let (_dir, state, token) = setup();
+
+    assert!(authenticate(None, &HeaderMap::new(), &state.store).is_err());
+    assert!(authenticate(Some("mero_unknown"), &HeaderMap::new(), &state.store).is_err());
+    assert!(authenticate(Some(&token), &HeaderMap::new(), &state.store).is_ok());
+}
+
+#[tokio::test]
+async fn test_ws_subscriptions_need_permitted_contexts() {
+    let (_dir, state, token) = setup();
+
+    let scopes = authenticate(Some(&token), &HeaderMap::new(), &state.store).unwrap();
+
+    let body = subscribe(&state, scopes.clone(), OTHER.into()).await;
+
+    assert!(matches!(
+        body,
+        ResponseBody::Error(ResponseBodyError::ServerError(
+            ServerResponseError::Forbidden(_)
+        ))
+    ));
+
+    let body = subscribe(&state, scopes, PERMITTED.into()).await;
+
+    assert!(matches!(body, ResponseBody::Result(_)));
+}
+
+#[tokio::test]
+async fn test_sse_requires_a_token() {
+    let (_dir, state, _token) = setup();
+
+    assert_eq!(
+        sse(&state, None, PERMITTED.into()).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        sse(&state, Some("mero_unknown"), PERMITTED.into()).await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[tokio::test]
+async fn test_sse_subscriptions_need_permitted_contexts() {
+    let (_dir, state, token) = setup();
+
+    assert_eq!(
+        sse(&state, Some(&token), OTHER.into()).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        sse(&state, Some(&token), PERMITTED.into()).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn test_ws_execute_replies_with_the_id_of_the_request() {
+    let (_dir, state, token, mut node) = setup_with_node();
+
+    let scopes = authenticate(Some(&token), &HeaderMap::new(), &state.store).unwrap();
+
+    let returning = Outcome::returning(Some(b"42".to_vec()), vec![]);
+
+    let (response, (method, _)) = join!(
+        send(&state, scopes.clone(), execute(7, PERMITTED, "get")),
+        answer(&mut node, Ok(returning)),
+    );
+
+    assert_eq!(method, "get");
+    assert_eq!(response.id, Some(7));
+    assert_eq!(
+        to_json_value(&response.body).unwrap(),
+        to_json_value(ResponseBody::Result(json!({ "output": 42 }))).unwrap()
+    );
+
+    let (response, _) = join!(
+        send(&state, scopes, execute(8, PERMITTED, "set")),
+        answer(&mut node, Ok(Outcome::returning(None, vec![]))),
+    );
+
+    assert_eq!(response.id, Some(8));
+    assert_eq!(
+        to_json_value(&response.body).unwrap(),
+        to_json_value(ResponseBody::Result(json!({ "output": null }))).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_ws_execute_needs_permitted_calls() {
+    let (_dir, state, _token, mut node) = setup_with_node();
+
+    let scopes = Scopes::new(
+        Role::Application,
+        Some(vec![PERMITTED.into()]),
+        Some(vec!["get*".to_owned()]),
+        false,
+    );
+
+    for request in [execute(1, OTHER, "get"), execute(2, PERMITTED, "set")] {
+        let response = send(&state, scopes.clone(), request).await;
+
+        assert!(matches!(
+            response.body,
+            ResponseBody::Error(ResponseBodyError::ServerError(
+                ServerResponseError::Forbidden(_)
+            ))
+        ));
+    }
+
+    assert!(node.try_recv().is_err());
+
+    let (_, (method, read_only)) = join!(
+        send(&state, scopes, execute(3, PERMITTED, "get_all")),
+        answer(&mut node, Ok(Outcome::returning(None, vec![]))),
+    );
+
+    assert_eq!(method, "get_all");
+    assert!(!read_only);
+
+    let read_only_scopes = Scopes::new(Role::Application, None, None, true);
+
+    let (_, (_, read_only)) = join!(
+        send(&state, read_only_scopes, execute(4, PERMITTED, "set")),
+        answer(&mut node, Ok(Outcome::returning(None, vec![]))),
+    );
+
+    assert!(read_only);
+}
+
+#[tokio::test]
+async fn test_ws_execute_reports_call_errors() {
+    let (_dir, state, token, mut node) = setup_with_node();
+
+    let scopes = authenticate(Some(&token), &HeaderMap::new(), &state.store).unwrap();
+
+    let not_found = MethodResolutionError::MethodNotFound {
+        name: "get".to_owned(),
+    };
+
+    let cases = [
+        (
+            Err(CallError::ContextNotFound),
+            ExecuteError::CallError(CallError::ContextNotFound),
+        ),
+        (
+            failing(FunctionCallError::ExecutionError(
+                br#"{"reason":"nope"}"#.to_vec(),
+            )),
+            ExecuteError::ApplicationError(json!({ "reason": "nope" })),
+        ),
+        (
+            failing(FunctionCallError::ExecutionError(b"nope".to_vec())),
+            ExecuteError::ApplicationError(json!("nope")),
+        ),
+        (
+            failing(FunctionCallError::Unauthorized),
+            ExecuteError::Unauthorized,
+        ),
+        (
+            failing(FunctionCallError::MethodResolutionError(not_found)),
+            ExecuteError::FunctionCallError(r#"method "get" not found"#.to_owned()),
+        ),
+    ];
+
+    for (id, (outcome, expected)) in (1..).zip(cases) {
+        let (response, _) = join!(
+            send(&state, scopes.clone(), execute(id, PERMITTED, "get")),
+            answer(&mut node, outcome),
+        );
+
+        assert_eq!(response.id, Some(id));
+        assert_eq!(
+            to_json_value(&response.body).unwrap(),
+            to_json_value(ResponseBody::Error(ResponseBodyError::HandlerError(
+                to_json_value(expected).unwrap()
+            )))
+            .unwrap()
+        );
+    }
+}
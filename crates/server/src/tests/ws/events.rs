@@ -0,0 +1,219 @@
+use calimero_primitives::events::{ContextEventPayload, StateMutationPayload};
+use calimero_store::config::StoreConfig;
+use calimero_store::db::RocksDB;
+use tempdir::TempDir;
+
+use super::*;
+
+const CONTEXT_A: [u8; 32] = [1; 32];
+const CONTEXT_B: [u8; 32] = [2; 32];
+
+fn open() -> (TempDir, Store) {
+    let dir = TempDir::new("_calimero_server_events").unwrap();
+
+    let config = StoreConfig::new(dir.path().to_owned().try_into().unwrap());
+
+    let store = Store::open::<RocksDB>(&config).unwrap();
+
+    (dir, store)
+}
+
+fn event(context_id: [u8; 32], root_hash: u8) -> NodeEvent {
+    NodeEvent::Context(ContextEvent::new(
+        context_id.into(),
+        ContextEventPayload::StateMutation(StateMutationPayload::new([root_hash; 32].into())),
+    ))
+}
+
+// emits the events, waiting for the log to have sequenced them
+async fn emit(
+    log: &EventLog,
+    node_events: &broadcast::Sender<NodeEvent>,
+    events: impl IntoIterator<Item = NodeEvent>,
+) {
+    let mut receiver = log.subscribe();
+
+    let mut emitted = 0;
+
+    for event in events {
+        let _ignored = node_events.send(event).unwrap();
+
+        emitted += 1;
+    }
+
+    for _ in 0..emitted {
+        let _ignored = receiver.recv().await.unwrap();
+    }
+}
+
+async fn replay(log: &EventLog, context_ids: &[[u8; 32]], after: ResumeToken) -> Vec<ResumeToken> {
+    let context_ids: Vec<ContextId> = context_ids.iter().copied().map(Into::into).collect();
+
+    let (events, _) = log.replay(&context_ids, after).await.unwrap();
+
+    events.into_iter().map(|event| event.sequence).collect()
+}
+
+async fn expired(log: &EventLog, context_id: [u8; 32], after: ResumeToken) -> bool {
+    match log.replay(&[context_id.into()], after).await {
+        Ok(_) => false,
+        Err(err) => matches!(
+            err.downcast::<SubscribeError>(),
+            Ok(SubscribeError::ResumeTokenExpired { resume_token }) if resume_token == after
+        ),
+    }
+}
+
+#[tokio::test]
+async fn test_events_are_replayed_after_the_resume_token() {
+    let (_dir, store) = open();
+
+    let (node_events, _) = broadcast::channel(8);
+
+    let log = EventLog::start(store, 8, &node_events).unwrap();
+
+    emit(
+        &log,
+        &node_events,
+        [
+            event(CONTEXT_A, 1),
+            event(CONTEXT_B, 2),
+            event(CONTEXT_A, 3),
+        ],
+    )
+    .await;
+
+    assert_eq!(replay(&log, &[CONTEXT_A], 0).await, [1, 3]);
+    assert_eq!(replay(&log, &[CONTEXT_A], 1).await, [3]);
+    assert_eq!(replay(&log, &[CONTEXT_A, CONTEXT_B], 1).await, [2, 3]);
+    assert!(replay(&log, &[CONTEXT_A, CONTEXT_B], 3).await.is_empty());
+
+    let (_, latest) = log.replay(&[CONTEXT_B.into()], 3).await.unwrap();
+
+    assert_eq!(latest, 3);
+
+    // handed out by no log this one knows of
+    assert!(expired(&log, CONTEXT_A, 4).await);
+}
+
+#[tokio::test]
+async fn test_resume_tokens_expire_once_events_are_evicted() {
+    let (_dir, store) = open();
+
+    let (node_events, _) = broadcast::channel(8);
+
+    let log = EventLog::start(store, 2, &node_events).unwrap();
+
+    emit(
+        &log,
+        &node_events,
+        [
+            event(CONTEXT_A, 1),
+            event(CONTEXT_A, 2),
+            event(CONTEXT_B, 3),
+            event(CONTEXT_A, 4),
+        ],
+    )
+    .await;
+
+    assert!(expired(&log, CONTEXT_A, 0).await);
+    assert_eq!(replay(&log, &[CONTEXT_A], 1).await, [2, 4]);
+
+    // the other context's events are all still retained
+    assert_eq!(replay(&log, &[CONTEXT_B], 0).await, [3]);
+}
+
+#[tokio::test]
+async fn test_expiry_and_sequences_survive_a_restart() {
+    let (_dir, store) = open();
+
+    let (node_events, _) = broadcast::channel(8);
+
+    let log = EventLog::start(store.clone(), 2, &node_events).unwrap();
+
+    emit(
+        &log,
+        &node_events,
+        [
+            event(CONTEXT_A, 1),
+            event(CONTEXT_A, 2),
+            event(CONTEXT_A, 3),
+        ],
+    )
+    .await;
+
+    drop(node_events);
+
+    let (node_events, _) = broadcast::channel(8);
+
+    let log = EventLog::start(store, 2, &node_events).unwrap();
+
+    // what's retained doesn't exceed the size, yet the first event is gone
+    assert!(expired(&log, CONTEXT_A, 0).await);
+    assert_eq!(replay(&log, &[CONTEXT_A], 1).await, [2, 3]);
+
+    emit(&log, &node_events, [event(CONTEXT_A, 4)]).await;
+
+    assert_eq!(replay(&log, &[CONTEXT_A], 2).await, [3, 4]);
+}
+
+#[tokio::test]
+async fn test_nothing_is_replayed_when_no_events_are_retained() {
+    let (_dir, store) = open();
+
+    let (node_events, _) = broadcast::channel(8);
+
+    let log = EventLog::start(store.clone(), 0, &node_events).unwrap();
+
+    emit(
+        &log,
+        &node_events,
+        [event(CONTEXT_A, 1), event(CONTEXT_A, 2)],
+    )
+    .await;
+
+    assert!(expired(&log, CONTEXT_A, 1).await);
+    assert!(replay(&log, &[CONTEXT_A], 2).await.is_empty());
+
+    drop(node_events);
+
+    let (node_events, _) = broadcast::channel(8);
+
+    let log = EventLog::start(store, 0, &node_events).unwrap();
+
+    emit(&log, &node_events, [event(CONTEXT_A, 3)]).await;
+
+    // sequences carry on from before the restart
+    assert!(expired(&log, CONTEXT_A, 2).await);
+    assert!(replay(&log, &[CONTEXT_A], 3).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_events_the_log_missed_expire_earlier_resume_tokens() {
+    let (_dir, store) = open();
+
+    let (node_events, _) = broadcast::channel(1);
+
+    let log = EventLog::start(store, 8, &node_events).unwrap();
+
+    emit(&log, &node_events, [event(CONTEXT_A, 1)]).await;
+
+    let mut receiver = log.subscribe();
+
+    // sent ahead of the log getting to them, overflowing its receiver
+    for root_hash in 2..=4 {
+        let _ignored = node_events.send(event(CONTEXT_A, root_hash)).unwrap();
+    }
+
+    assert!(matches!(receiver.recv().await.unwrap(), Logged::Skipped));
+
+    let Logged::Event(logged) = receiver.recv().await.unwrap() else {
+        panic!("expected an event");
+    };
+
+    assert_eq!(logged.sequence, 2);
+
+    assert!(expired(&log, CONTEXT_A, 0).await);
+    assert!(expired(&log, CONTEXT_A, 1).await);
+    assert!(replay(&log, &[CONTEXT_A], 2).await.is_empty());
+}
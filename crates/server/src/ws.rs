@@ -1,19 +1,28 @@
+#[cfg(test)]
+#[path = "tests/ws.rs"]
+mod tests;
+
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use axum::extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade};
-use axum::response::IntoResponse;
+use axum::extract::Query;
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response as HttpResponse};
 use axum::routing::{get, MethodRouter};
 use axum::Extension;
+use calimero_node_primitives::ServerSender;
 use calimero_primitives::context::ContextId;
-use calimero_primitives::events::NodeEvent;
+use calimero_primitives::events::{ContextEvent, NodeEvent};
+use calimero_primitives::identity::Scopes;
 use calimero_server_primitives::ws::{
-    Command, ConnectionId, Request as WsRequest, RequestPayload, Response, ResponseBody,
-    ResponseBodyError, ServerResponseError,
+    Command, ConnectionId, EventFilter, Request as WsRequest, RequestPayload, Response,
+    ResponseBody, ResponseBodyError, ResumeToken, ServerResponseError,
 };
-use eyre::Error as EyreError;
+use calimero_store::Store;
+use eyre::{Error as EyreError, Result as EyreResult};
 use futures_util::stream::SplitSink;
 use futures_util::{SinkExt, StreamExt};
 use rand::random;
@@ -23,9 +32,13 @@ use serde_json::{
     to_value as to_json_value, Value,
 };
 use tokio::spawn;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::{broadcast, mpsc, RwLock};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
+mod events;
+mod execute;
+mod sse;
 mod subscribe;
 mod unsubscribe;
 
@@ -34,65 +47,128 @@ mod unsubscribe;
 pub struct WsConfig {
     #[serde(default = "calimero_primitives::common::bool_true")]
     pub enabled: bool,
+    /// The number of events retained per context for subscribers to resume
+    /// from, none being retained when zero.
+    #[serde(default = "default_event_log_size")]
+    pub event_log_size: usize,
 }
 
 impl WsConfig {
     #[must_use]
     pub const fn new(enabled: bool) -> Self {
-        Self { enabled }
+        Self {
+            enabled,
+            event_log_size: default_event_log_size(),
+        }
     }
 }
 
+const fn default_event_log_size() -> usize {
+    1000
+}
+
+// "try again later", the connection being closed on events it missed
+const CLOSE_MISSED_EVENTS: u16 = 1013;
+
+#[derive(Debug)]
+pub(crate) struct Subscription {
+    filter: EventFilter,
+    // events up to this one were replayed when subscribing, and are skipped
+    // when they come through live
+    replayed_until: ResumeToken,
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct ConnectionStateInner {
-    subscriptions: HashSet<ContextId>,
+    subscriptions: HashMap<ContextId, Subscription>,
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct ConnectionState {
     commands: mpsc::Sender<Command>,
+    // the scopes of the token the client presented when connecting
+    scopes: Scopes,
     inner: Arc<RwLock<ConnectionStateInner>>,
 }
 
 pub(crate) struct ServiceState {
-    node_events: broadcast::Sender<NodeEvent>,
+    events: Arc<EventLog>,
+    server_sender: ServerSender,
+    store: Store,
     connections: RwLock<HashMap<ConnectionId, ConnectionState>>,
 }
 
 pub(crate) fn service(
     config: &ServerConfig,
-    node_events: broadcast::Sender<NodeEvent>,
-) -> Option<(&'static str, MethodRouter)> {
-    let _config = match &config.websocket {
+    node_events: &broadcast::Sender<NodeEvent>,
+    server_sender: ServerSender,
+    store: Store,
+) -> EyreResult<Option<Vec<(&'static str, MethodRouter)>>> {
+    let ws_config = match &config.websocket {
         Some(config) if config.enabled => config,
         _ => {
             info!("WebSocket server is disabled");
-            return None;
+            return Ok(None);
         }
     };
 
     let path = "/ws"; // todo! source from config
+    let sse_path = "/sse";
 
     for listen in &config.listen {
         info!("WebSocket server listening on {}/ws{{{}}}", listen, path);
+        info!("SSE server listening on {}/sse{{{}}}", listen, sse_path);
     }
 
+    let events = EventLog::start(store.clone(), ws_config.event_log_size, node_events)?;
+
     let state = Arc::new(ServiceState {
-        node_events,
+        events,
+        server_sender,
+        store,
         connections: RwLock::default(),
     });
 
-    Some((path, get(ws_handler).layer(Extension(state))))
+    Ok(Some(vec![
+        (path, get(ws_handler).layer(Extension(Arc::clone(&state)))),
+        (sse_path, get(sse::handler).layer(Extension(state))),
+    ]))
+}
+
+#[derive(Debug, Deserialize)]
+struct WsQuery {
+    token: Option<String>,
 }
 
 async fn ws_handler(
     ws: WebSocketUpgrade,
+    headers: HeaderMap,
+    Query(query): Query<WsQuery>,
     Extension(state): Extension<Arc<ServiceState>>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+) -> HttpResponse {
+    let scopes = match authenticate(query.token.as_deref(), &headers, &state.store) {
+        Ok(scopes) => scopes,
+        Err(err) => return err.into_response(),
+    };
+
+    ws.on_upgrade(move |socket| handle_socket(socket, state, scopes))
+        .into_response()
+}
+
+// browsers can't set headers on websocket connections or event sources, so
+// the token can also be passed as a query parameter
+fn authenticate(
+    token: Option<&str>,
+    headers: &HeaderMap,
+    store: &Store,
+) -> Result<Scopes, UnauthorizedError<'static>> {
+    match token {
+        Some(token) => verify(token, store),
+        None => auth(headers, store),
+    }
 }
 
-async fn handle_socket(socket: WebSocket, state: Arc<ServiceState>) {
+async fn handle_socket(socket: WebSocket, state: Arc<ServiceState>, scopes: Scopes) {
     let (commands_sender, commands_receiver) = mpsc::channel(32);
     let (connection_id, _) = loop {
         let connection_id = random();
@@ -103,6 +179,7 @@ async fn handle_socket(socket: WebSocket, state: Arc<ServiceState>) {
             Entry::Vacant(entry) => {
                 let connection_state = ConnectionState {
                     commands: commands_sender.clone(),
+                    scopes: scopes.clone(),
                     inner: Arc::default(),
                 };
                 let _ = entry.insert(connection_state.clone());
@@ -113,10 +190,10 @@ async fn handle_socket(socket: WebSocket, state: Arc<ServiceState>) {
 
     debug!(%connection_id, "Client connection established");
 
-    drop(spawn(handle_node_events(
+    drop(spawn(handle_events(
         connection_id,
         Arc::clone(&state),
-        state.node_events.subscribe(),
+        state.events.subscribe(),
         commands_sender.clone(),
     )));
 
@@ -167,55 +244,64 @@ async fn handle_socket(socket: WebSocket, state: Arc<ServiceState>) {
     drop(state.remove(&connection_id));
 }
 
-async fn handle_node_events(
+async fn handle_events(
     connection_id: ConnectionId,
     state: Arc<ServiceState>,
-    mut node_events_receiver: broadcast::Receiver<NodeEvent>,
+    mut events_receiver: broadcast::Receiver<Logged>,
     command_sender: mpsc::Sender<Command>,
 ) {
-    while let Ok(event) = node_events_receiver.recv().await {
+    loop {
+        let LoggedEvent { sequence, event } = match events_receiver.recv().await {
+            Ok(Logged::Event(event)) => event,
+            Ok(Logged::Skipped) | Err(RecvError::Lagged(_)) => {
+                warn!(%connection_id, "Client missed events, closing the connection");
+
+                // rather than carry on past the missed events, the client is
+                // to reconnect and resume from the last event it received
+                drop(
+                    command_sender
+                        .send(Command::Close(
+                            CLOSE_MISSED_EVENTS,
+                            "missed events, resume from the last resume token received".to_owned(),
+                        ))
+                        .await,
+                );
+
+                break;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
         let Some(connection_state) = state.connections.read().await.get(&connection_id).cloned()
         else {
             error!(%connection_id, "Unexpected state, client_id not found in client state map");
             return;
         };
 
+        // held until the event is queued, so that it can't overtake the
+        // events being replayed to a new subscription
+        let inner = connection_state.inner.read().await;
+
         debug!(
             %connection_id,
             "Received node event: {:?}, subscriptions state: {:?}",
             event,
-            connection_state.inner.read().await.subscriptions
+            inner.subscriptions
         );
 
-        let event = match event {
-            NodeEvent::Context(event)
-                if {
-                    connection_state
-                        .inner
-                        .read()
-                        .await
-                        .subscriptions
-                        .contains(&event.context_id)
-                } =>
-            {
-                NodeEvent::Context(event)
-            }
-            NodeEvent::Context(_) => continue,
-            _ => unreachable!("Unexpected event type"),
-        };
-
-        let body = match to_json_value(event) {
-            Ok(v) => ResponseBody::Result(v),
-            Err(err) => ResponseBody::Error(ResponseBodyError::ServerError(
-                ServerResponseError::InternalError {
-                    err: Some(err.into()),
-                },
-            )),
+        let Some(event) = inner
+            .subscriptions
+            .get(&event.context_id)
+            .filter(|subscription| sequence > subscription.replayed_until)
+            .and_then(|subscription| subscription.filter.apply(&event))
+        else {
+            continue;
         };
 
-        let response = Response { id: None, body };
-
-        if let Err(err) = command_sender.send(Command::Send(response)).await {
+        if let Err(err) = command_sender
+            .send(Command::Send(event_response(sequence, event)))
+            .await
+        {
             error!(
                 %connection_id,
                 %err,
@@ -225,6 +311,23 @@ async fn handle_node_events(
     }
 }
 
+pub(crate) fn event_response(sequence: ResumeToken, event: ContextEvent) -> Response {
+    let body = match to_json_value(NodeEvent::Context(event)) {
+        Ok(v) => ResponseBody::Result(v),
+        Err(err) => ResponseBody::Error(ResponseBodyError::ServerError(
+            ServerResponseError::InternalError {
+                err: Some(err.into()),
+            },
+        )),
+    };
+
+    Response {
+        id: None,
+        resume_token: Some(sequence),
+        body,
+    }
+}
+
 async fn handle_commands(
     connection_id: ConnectionId,
     mut command_receiver: mpsc::Receiver<Command>,
@@ -293,6 +396,18 @@ async fn handle_text_message(
 
     let body = match from_json_value::<RequestPayload>(message.payload) {
         Ok(payload) => match payload {
+            RequestPayload::Subscribe(request)
+                if !request
+                    .context_ids
+                    .iter()
+                    .all(|context_id| connection_state.scopes.permits_context(context_id)) =>
+            {
+                ResponseBody::Error(ResponseBodyError::ServerError(
+                    ServerResponseError::Forbidden(
+                        "not permitted by the scopes of the token".to_owned(),
+                    ),
+                ))
+            }
             RequestPayload::Subscribe(request) => request
                 .handle(Arc::clone(&state), connection_state.clone())
                 .await
@@ -301,6 +416,26 @@ async fn handle_text_message(
                 .handle(Arc::clone(&state), connection_state.clone())
                 .await
                 .to_res_body(),
+            RequestPayload::Execute(request)
+                if !connection_state
+                    .scopes
+                    .permits_call(&request.context_id, &request.method) =>
+            {
+                ResponseBody::Error(ResponseBodyError::ServerError(
+                    ServerResponseError::Forbidden(
+                        "not permitted by the scopes of the token".to_owned(),
+                    ),
+                ))
+            }
+            // changes are rejected when the scopes are read-only
+            RequestPayload::Execute(mut request) => {
+                request.read_only = connection_state.scopes.read_only;
+
+                request
+                    .handle(Arc::clone(&state), connection_state.clone())
+                    .await
+                    .to_res_body()
+            }
         },
         Err(err) => {
             error!(%connection_id, %err, "Failed to deserialize RequestPayload");
@@ -315,6 +450,7 @@ async fn handle_text_message(
         .commands
         .send(Command::Send(Response {
             id: message.id,
+            resume_token: None,
             body,
         }))
         .await
@@ -403,3 +539,5 @@ macro_rules! mount_method {
 pub(crate) use mount_method;
 
 use crate::config::ServerConfig;
+use crate::middleware::jwt::{auth, verify, UnauthorizedError};
+use crate::ws::events::{EventLog, Logged, LoggedEvent};
@@ -0,0 +1,287 @@
+#[cfg(test)]
+#[path = "../tests/ws/events.rs"]
+mod tests;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use calimero_primitives::context::ContextId;
+use calimero_primitives::events::{ContextEvent, NodeEvent};
+use calimero_server_primitives::ws::{ResumeToken, SubscribeError};
+use calimero_store::entry::{Entry, Json};
+use calimero_store::key::{ContextEvent as ContextEventKey, Generic};
+use calimero_store::types::ContextEvent as ContextEventValue;
+use calimero_store::Store;
+use eyre::{bail, Result as EyreResult};
+use serde_json::{from_slice as from_json_slice, to_vec as to_json_vec};
+use tokio::spawn;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, Mutex};
+use tracing::{error, warn};
+
+const COUNTER_SCOPE: [u8; 16] = *b"ws:event:counter";
+const EVICTED_SCOPE: [u8; 16] = *b"ws:event:evicted";
+const SKIPPED_SCOPE: [u8; 16] = *b"ws:event:skipped";
+
+// a sequence kept in the store, so that resume tokens handed out before a
+// restart keep their meaning after it
+struct SequenceEntry {
+    key: Generic,
+}
+
+impl Entry for SequenceEntry {
+    type Key = Generic;
+    type Codec = Json;
+    type DataType<'a> = ResumeToken;
+
+    fn key(&self) -> &Self::Key {
+        &self.key
+    }
+}
+
+impl SequenceEntry {
+    // the latest sequence handed out
+    fn counter() -> Self {
+        Self {
+            key: Generic::new(COUNTER_SCOPE, [0; 32]),
+        }
+    }
+
+    // the latest sequence of the context dropped to keep the log within its size
+    fn evicted(context_id: &ContextId) -> Self {
+        Self {
+            key: Generic::new(EVICTED_SCOPE, **context_id),
+        }
+    }
+
+    // the sequence after which the log lagged behind the node, missing events
+    fn skipped() -> Self {
+        Self {
+            key: Generic::new(SKIPPED_SCOPE, [0; 32]),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct LoggedEvent {
+    pub sequence: ResumeToken,
+    pub event: ContextEvent,
+}
+
+/// What live subscribers receive from the log.
+#[derive(Clone, Debug)]
+pub(crate) enum Logged {
+    Event(LoggedEvent),
+    /// The log lagged behind the node, and missed events it can't replay.
+    Skipped,
+}
+
+#[derive(Debug)]
+struct EventLogInner {
+    last: ResumeToken,
+    skipped_until: Option<ResumeToken>,
+    // sequences of the events of every context kept in the store, oldest first
+    contexts: HashMap<ContextId, VecDeque<ResumeToken>>,
+}
+
+/// Sequences the events emitted by the node, keeping the latest of every
+/// context in the store, so that subscribers can resume from where they
+/// left off.
+#[derive(Debug)]
+pub(crate) struct EventLog {
+    store: Store,
+    size: usize,
+    inner: Mutex<EventLogInner>,
+    events: broadcast::Sender<Logged>,
+}
+
+impl EventLog {
+    pub(crate) fn start(
+        store: Store,
+        size: usize,
+        node_events: &broadcast::Sender<NodeEvent>,
+    ) -> EyreResult<Arc<Self>> {
+        let mut handle = store.handle();
+
+        let mut inner = EventLogInner {
+            last: handle.get(&SequenceEntry::counter())?.unwrap_or_default(),
+            skipped_until: handle.get(&SequenceEntry::skipped())?,
+            contexts: HashMap::new(),
+        };
+
+        let mut evicted = vec![];
+
+        for key in handle.iter::<ContextEventKey>()?.keys() {
+            let key = key?;
+
+            inner.last = inner.last.max(key.sequence());
+
+            let sequences = inner.contexts.entry(key.context_id()).or_default();
+
+            sequences.push_back(key.sequence());
+
+            // the log may have been kept at a larger size before
+            if sequences.len() > size {
+                if let Some(sequence) = sequences.pop_front() {
+                    evicted.push(ContextEventKey::new(key.context_id(), sequence));
+                }
+            }
+        }
+
+        for key in evicted {
+            handle.put(&SequenceEntry::evicted(&key.context_id()), &key.sequence())?;
+            handle.delete(&key)?;
+        }
+
+        let (events, _) = broadcast::channel(32);
+
+        let log = Arc::new(Self {
+            store,
+            size,
+            inner: Mutex::new(inner),
+            events,
+        });
+
+        drop(spawn(Arc::clone(&log).record(node_events.subscribe())));
+
+        Ok(log)
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<Logged> {
+        self.events.subscribe()
+    }
+
+    async fn record(self: Arc<Self>, mut node_events: broadcast::Receiver<NodeEvent>) {
+        loop {
+            let event = match node_events.recv().await {
+                Ok(NodeEvent::Context(event)) => event,
+                Ok(_) => unreachable!("Unexpected event type"),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(%skipped, "Event log lagged behind node events");
+
+                    let mut inner = self.inner.lock().await;
+
+                    let sequence = inner.last;
+
+                    if let Err(err) = self
+                        .store
+                        .handle()
+                        .put(&SequenceEntry::skipped(), &sequence)
+                    {
+                        error!(%err, %sequence, "Failed to record skipped events");
+                    }
+
+                    inner.skipped_until = Some(sequence);
+
+                    drop(self.events.send(Logged::Skipped));
+
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            let mut inner = self.inner.lock().await;
+
+            inner.last = inner.last.saturating_add(1);
+
+            let sequence = inner.last;
+
+            if let Err(err) = self.retain(&mut inner, sequence, &event) {
+                error!(%err, %sequence, "Failed to retain event");
+            }
+
+            drop(
+                self.events
+                    .send(Logged::Event(LoggedEvent { sequence, event })),
+            );
+        }
+    }
+
+    fn retain(
+        &self,
+        inner: &mut EventLogInner,
+        sequence: ResumeToken,
+        event: &ContextEvent,
+    ) -> EyreResult<()> {
+        let mut handle = self.store.handle();
+
+        handle.put(&SequenceEntry::counter(), &sequence)?;
+
+        if self.size == 0 {
+            handle.put(&SequenceEntry::evicted(&event.context_id), &sequence)?;
+
+            return Ok(());
+        }
+
+        handle.put(
+            &ContextEventKey::new(event.context_id, sequence),
+            &ContextEventValue::new(to_json_vec(event)?.into_boxed_slice()),
+        )?;
+
+        let sequences = inner.contexts.entry(event.context_id).or_default();
+
+        sequences.push_back(sequence);
+
+        while sequences.len() > self.size {
+            let Some(evicted) = sequences.pop_front() else {
+                break;
+            };
+
+            handle.put(&SequenceEntry::evicted(&event.context_id), &evicted)?;
+            handle.delete(&ContextEventKey::new(event.context_id, evicted))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the events of the contexts logged after the given one, in
+    /// order, along with the latest sequence at the time of the replay.
+    pub(crate) async fn replay(
+        &self,
+        context_ids: &[ContextId],
+        after: ResumeToken,
+    ) -> EyreResult<(Vec<LoggedEvent>, ResumeToken)> {
+        let inner = self.inner.lock().await;
+
+        // handed out by another log, or followed by events the log missed
+        if after > inner.last || inner.skipped_until.is_some_and(|skipped| after <= skipped) {
+            bail!(SubscribeError::ResumeTokenExpired {
+                resume_token: after
+            });
+        }
+
+        let handle = self.store.handle();
+
+        let mut events = vec![];
+
+        for context_id in context_ids {
+            if handle
+                .get(&SequenceEntry::evicted(context_id))?
+                .is_some_and(|evicted| evicted > after)
+            {
+                bail!(SubscribeError::ResumeTokenExpired {
+                    resume_token: after
+                });
+            }
+
+            let Some(sequences) = inner.contexts.get(context_id) else {
+                continue;
+            };
+
+            for sequence in sequences.iter().filter(|s| **s > after) {
+                let Some(value) = handle.get(&ContextEventKey::new(*context_id, *sequence))? else {
+                    continue;
+                };
+
+                events.push(LoggedEvent {
+                    sequence: *sequence,
+                    event: from_json_slice(&value.data)?,
+                });
+            }
+        }
+
+        events.sort_unstable_by_key(|event| event.sequence);
+
+        Ok((events, inner.last))
+    }
+}
@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use calimero_server_primitives::jsonrpc::{ExecuteError, ExecuteRequest, ExecuteResponse};
+use eyre::Result as EyreResult;
+
+use crate::jsonrpc::execute::execute;
+use crate::ws::{mount_method, ConnectionState, ServiceState};
+
+mount_method!(ExecuteRequest-> Result<ExecuteResponse, ExecuteError>, handle);
+
+async fn handle(
+    request: ExecuteRequest,
+    state: Arc<ServiceState>,
+    _connection_state: ConnectionState,
+) -> EyreResult<ExecuteResponse> {
+    execute(state.server_sender.clone(), request).await
+}
@@ -0,0 +1,150 @@
+use core::future::ready;
+use core::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::Query;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response as HttpResponse};
+use axum::{Extension, Json};
+use calimero_primitives::context::ContextId;
+use calimero_primitives::identity::PublicKey;
+use calimero_server_primitives::ws::{EventFilter, ResumeToken, SubscribeError};
+use futures_util::{stream, StreamExt};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::error;
+
+use crate::ws::events::{Logged, LoggedEvent};
+use crate::ws::{authenticate, ServiceState};
+
+/// The same parameters as a WebSocket subscription, with lists given as
+/// comma-separated values, along with the token authenticating the client,
+/// unless given in the authorization header.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SseQuery {
+    token: Option<String>,
+    context_ids: String,
+    types: Option<String>,
+    kinds: Option<String>,
+    executors: Option<String>,
+    resume_token: Option<ResumeToken>,
+}
+
+fn split(values: &str) -> impl Iterator<Item = &str> {
+    values
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn parse<T: FromStr>(values: &str) -> Result<Vec<T>, T::Err> {
+    split(values).map(str::parse).collect()
+}
+
+fn strings(values: Option<&String>) -> Option<Vec<String>> {
+    values.map(|values| split(values).map(str::to_owned).collect())
+}
+
+pub(crate) async fn handler(
+    headers: HeaderMap,
+    Query(query): Query<SseQuery>,
+    Extension(state): Extension<Arc<ServiceState>>,
+) -> HttpResponse {
+    let scopes = match authenticate(query.token.as_deref(), &headers, &state.store) {
+        Ok(scopes) => scopes,
+        Err(err) => return err.into_response(),
+    };
+
+    let Ok(context_ids) = parse::<ContextId>(&query.context_ids) else {
+        return (StatusCode::BAD_REQUEST, "Invalid context ids").into_response();
+    };
+
+    if !context_ids
+        .iter()
+        .all(|context_id| scopes.permits_context(context_id))
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            "Not permitted by the scopes of the token",
+        )
+            .into_response();
+    }
+
+    let executors = match query.executors.as_deref().map(parse::<PublicKey>) {
+        Some(Ok(executors)) => Some(executors),
+        Some(Err(_)) => {
+            return (StatusCode::BAD_REQUEST, "Invalid executors").into_response();
+        }
+        None => None,
+    };
+
+    let filter = EventFilter::new(
+        strings(query.types.as_ref()),
+        strings(query.kinds.as_ref()),
+        executors,
+    );
+
+    // a reconnecting EventSource reports the last event it received
+    let resume_token = match headers.get("last-event-id").map(|id| id.to_str()) {
+        Some(Ok(id)) => match id.parse() {
+            Ok(id) => Some(id),
+            Err(_) => return (StatusCode::BAD_REQUEST, "Invalid Last-Event-ID").into_response(),
+        },
+        Some(Err(_)) => return (StatusCode::BAD_REQUEST, "Invalid Last-Event-ID").into_response(),
+        None => query.resume_token,
+    };
+
+    // subscribed ahead of the replay, so that nothing is missed in between
+    let receiver = state.events.subscribe();
+
+    let (replayed, replayed_until) = match resume_token {
+        Some(resume_token) => match state.events.replay(&context_ids, resume_token).await {
+            Ok(replay) => replay,
+            Err(err) => {
+                return match err.downcast::<SubscribeError>() {
+                    Ok(err) => (StatusCode::GONE, Json(err)).into_response(),
+                    Err(err) => {
+                        error!(%err, "Failed to replay events");
+
+                        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                    }
+                };
+            }
+        },
+        None => (vec![], 0),
+    };
+
+    let live = stream::unfold(receiver, move |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(Logged::Event(event)) if event.sequence > replayed_until => {
+                    return Some((event, receiver))
+                }
+                Ok(Logged::Event(_)) => {}
+                // ending the stream has the client reconnect, resuming from
+                // the last event it received rather than carrying on past the
+                // ones it missed
+                Ok(Logged::Skipped) | Err(RecvError::Lagged(_) | RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let events =
+        stream::iter(replayed)
+            .chain(live)
+            .filter_map(move |LoggedEvent { sequence, event }| {
+                let event = context_ids
+                    .contains(&event.context_id)
+                    .then(|| filter.apply(&event))
+                    .flatten()
+                    .map(|event| Event::default().id(sequence.to_string()).json_data(event));
+
+                ready(event)
+            });
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
@@ -1,21 +1,50 @@
 use std::sync::Arc;
 
-use calimero_server_primitives::ws::{SubscribeRequest, SubscribeResponse};
-use calimero_server_primitives::Infallible;
+use calimero_server_primitives::ws::{
+    Command, SubscribeError, SubscribeRequest, SubscribeResponse,
+};
 use eyre::Result as EyreResult;
 
-use crate::ws::{mount_method, ConnectionState, ServiceState};
+use crate::ws::{event_response, mount_method, ConnectionState, ServiceState, Subscription};
 
-mount_method!(SubscribeRequest-> Result<SubscribeResponse, Infallible>, handle);
+mount_method!(SubscribeRequest-> Result<SubscribeResponse, SubscribeError>, handle);
 
 async fn handle(
     request: SubscribeRequest,
-    _state: Arc<ServiceState>,
+    state: Arc<ServiceState>,
     connection_state: ConnectionState,
 ) -> EyreResult<SubscribeResponse> {
     let mut inner = connection_state.inner.write().await;
+
+    // the missed events are queued ahead of any new ones, which wait on the
+    // lock held here
+    let replayed_until = match request.resume_token {
+        Some(resume_token) => {
+            let (events, replayed_until) = state
+                .events
+                .replay(&request.context_ids, resume_token)
+                .await?;
+
+            for event in events {
+                connection_state
+                    .commands
+                    .send(Command::Send(event_response(event.sequence, event.event)))
+                    .await?;
+            }
+
+            replayed_until
+        }
+        None => 0,
+    };
+
     request.context_ids.iter().for_each(|id| {
-        let _ = inner.subscriptions.insert(*id);
+        let _ = inner.subscriptions.insert(
+            *id,
+            Subscription {
+                filter: request.filter.clone(),
+                replayed_until,
+            },
+        );
     });
 
     Ok(SubscribeResponse {
@@ -390,3 +390,151 @@ pub fn collection_derive(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Derives the [`Merge`](calimero_storage::merge::Merge) trait for a struct.
+///
+/// This macro implements [`Merge`](calimero_storage::merge::Merge) by merging
+/// each field of the struct in turn, using the strategy specified for that
+/// field. Fields without a strategy use "last write wins", which is the same as
+/// the default behaviour for the whole struct.
+///
+/// # Struct attributes
+///
+/// None.
+///
+/// # Field attributes
+///
+/// * `#[merge(lww)]`         - Keeps whichever version was updated most
+///                             recently. This is the default.
+/// * `#[merge(max)]`         - Keeps the greater of the two versions.
+/// * `#[merge(min)]`         - Keeps the lesser of the two versions.
+/// * `#[merge(union)]`       - Combines the elements of both versions, keeping
+///                             removals, for [`UnionSet`](calimero_storage::merge::UnionSet)
+///                             fields.
+/// * `#[merge(nested)]`      - Merges the field using its own [`Merge`](calimero_storage::merge::Merge)
+///                             implementation. For collections, this also
+///                             registers how their values are merged, so that
+///                             they are merged when synchronised.
+/// * `#[merge(with = path)]` - Merges the field using a custom function, with
+///                             the same signature as the functions in
+///                             [`strategy`](calimero_storage::merge::strategy).
+/// * `#[merge(skip)]`        - Always keeps the local version.
+///
+/// Fields marked with `#[storage]`, `#[private]`, or `#[skip]` always keep the
+/// local version.
+///
+/// # Examples
+///
+/// ```
+/// use calimero_storage::entities::Element;
+/// use calimero_storage_macros::{AtomicUnit, Merge};
+/// use calimero_storage::merge::UnionSet;
+/// use borsh::{BorshSerialize, BorshDeserialize};
+///
+/// #[derive(AtomicUnit, Clone, Debug, Eq, Merge, PartialEq, PartialOrd, BorshSerialize, BorshDeserialize)]
+/// #[type_id(45)]
+/// struct Score {
+///     name: String,
+///     #[merge(max)]
+///     best: u64,
+///     #[merge(union)]
+///     badges: UnionSet<String>,
+///     #[storage]
+///     storage: Element,
+/// }
+/// ```
+///
+/// # Panics
+///
+/// This macro will panic during compilation if:
+///
+///   - It is applied to anything other than a struct
+///   - The struct has unnamed fields
+///   - A `#[merge]` attribute has an unknown or invalid strategy
+///
+#[proc_macro_derive(Merge, attributes(merge))]
+pub fn merge_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(_) | Data::Union(_) => panic!("Merge can only be derived for structs"),
+    };
+
+    let named_fields = match fields {
+        Fields::Named(fields) => &fields.named,
+        Fields::Unnamed(_) | Fields::Unit => {
+            panic!("Merge can only be derived for structs with named fields")
+        }
+    };
+
+    let mut nested = Vec::new();
+
+    let merges = named_fields.iter().filter_map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+
+        if field.attrs.iter().any(|attr| {
+            attr.path().is_ident("storage")
+                || attr.path().is_ident("private")
+                || attr.path().is_ident("skip")
+        }) {
+            return None;
+        }
+
+        let mut strategy = Some(quote! { calimero_storage::merge::strategy::lww });
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("merge"))
+        {
+            attr.parse_nested_meta(|meta| {
+                strategy = if meta.path.is_ident("skip") {
+                    None
+                } else if meta.path.is_ident("with") {
+                    let path: syn::Path = meta.value()?.parse()?;
+                    Some(quote! { #path })
+                } else if meta.path.is_ident("nested") {
+                    nested.push(ident);
+                    Some(quote! { calimero_storage::merge::strategy::nested })
+                } else if ["lww", "max", "min", "union"]
+                    .iter()
+                    .any(|ident| meta.path.is_ident(ident))
+                {
+                    let ident = &meta.path;
+                    Some(quote! { calimero_storage::merge::strategy::#ident })
+                } else {
+                    return Err(meta.error("unknown merge strategy"));
+                };
+
+                Ok(())
+            })
+            .expect("Invalid #[merge] attribute");
+        }
+
+        strategy.map(|strategy| {
+            quote! {
+                #strategy(&mut self.#ident, other.#ident, newer);
+            }
+        })
+    });
+
+    let merges = merges.collect::<Vec<_>>();
+
+    let expanded = quote! {
+        impl #impl_generics calimero_storage::merge::Merge for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn merge(&mut self, other: Self, newer: calimero_storage::merge::Newer) {
+                #(#merges)*
+            }
+
+            fn register(&self) {
+                #(calimero_storage::merge::Merge::register(&self.#nested);)*
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
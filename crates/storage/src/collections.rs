@@ -10,7 +10,12 @@ use std::sync::LazyLock;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use indexmap::IndexSet;
+use sha2::{Digest, Sha256};
 
+pub mod ordered_map;
+pub use ordered_map::OrderedMap;
+pub mod sequence;
+pub use sequence::Sequence;
 pub mod unordered_map;
 pub use unordered_map::UnorderedMap;
 pub mod unordered_set;
@@ -18,6 +23,7 @@ pub use unordered_set::UnorderedSet;
 pub mod vector;
 pub use vector::Vector;
 mod root;
+pub(crate) mod summary;
 #[doc(hidden)]
 pub use root::Root;
 pub mod error;
@@ -26,8 +32,11 @@ pub use error::StoreError;
 // fixme! macro expects `calimero_storage` to be in deps
 use crate as calimero_storage;
 use crate::address::{Id, Path};
+pub use crate::entities::Cursor;
 use crate::entities::{ChildInfo, Data, Element};
+use crate::guard::{self, Guard};
 use crate::interface::{Interface, StorageError};
+use crate::merge::{self, Children, Merge, Newer};
 use crate::store::{MainStorage, StorageAdaptor};
 use crate::{AtomicUnit, Collection};
 
@@ -113,22 +122,87 @@ struct Entry<T> {
     storage: Element,
 }
 
+impl<T: Merge> Merge for Entry<T> {
+    fn merge(&mut self, other: Self, newer: Newer) {
+        self.item.merge(other.item, newer);
+    }
+}
+
+impl<T: Guard> Guard for Entry<T> {
+    fn permits(&self, other: &Self) -> bool {
+        self.item.permits(&other.item)
+    }
+}
+
+/// The items of maps are stored as key-value pairs, of which only the value
+/// can differ between versions.
+impl<K, V: Merge> Merge for (K, V) {
+    fn merge(&mut self, other: Self, newer: Newer) {
+        self.1.merge(other.1, newer);
+    }
+}
+
+/// A page of items from a collection.
+///
+/// Pages are obtained through the `iter_page()` methods on the collections,
+/// such as [`UnorderedMap::iter_page()`]. Only the items on the page are
+/// loaded from storage, which allows large collections to be traversed a
+/// piece at a time.
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Page<T> {
+    /// The items on this page, in iteration order.
+    pub items: Vec<T>,
+
+    /// The position from which to request the next page, or [`None`] if this
+    /// is the last one.
+    pub next: Option<Cursor>,
+}
+
 #[expect(unused_qualifications, reason = "AtomicUnit macro is unsanitized")]
 type StoreResult<T> = std::result::Result<T, StoreError>;
 
 static ROOT_ID: LazyLock<Id> = LazyLock::new(|| Id::root());
 
+/// A collection that can be held as a value inside another collection.
+///
+/// A nested collection is not stored inline in the entry that holds it.
+/// Instead, it becomes a child of that entry, and each of its own elements is a
+/// separate child entity with its own metadata. This means that concurrent
+/// changes to different inner elements are merged, rather than the whole value
+/// being replaced under last-write-wins.
+///
+/// The ID of a nested collection is derived from the ID of the entry holding
+/// it, so nodes that create the same entry concurrently agree on its identity.
+///
+/// Nested collections are created through the `*_nested()` methods on the
+/// outer collection, such as [`UnorderedMap::get_or_insert_nested()`].
+///
+pub trait Nested: BorshSerialize + BorshDeserialize {
+    /// Creates an empty collection for the entry with the given ID, without
+    /// storing it.
+    #[doc(hidden)]
+    fn detached(parent_id: Id) -> Self;
+
+    /// Stores the collection as a child of the entry with the given ID.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    #[doc(hidden)]
+    fn attach(&mut self, parent_id: Id) -> Result<(), StoreError>;
+}
+
 impl<T: BorshSerialize + BorshDeserialize, S: StorageAdaptor> Collection<T, S> {
     /// Creates a new collection.
     #[expect(clippy::expect_used, reason = "fatal error if it happens")]
     fn new(id: Option<Id>) -> Self {
         let id = id.unwrap_or_else(|| Id::random());
 
-        let mut this = Self {
-            children_ids: RefCell::new(None),
-            storage: Element::new(&Path::new("::unused").expect("valid path"), Some(id)),
-            _priv: PhantomData,
-        };
+        let mut this = Self::new_unsaved(id);
 
         if id.is_root() {
             let _ignored = <Interface<S>>::save(&mut this).expect("save");
@@ -140,6 +214,48 @@ impl<T: BorshSerialize + BorshDeserialize, S: StorageAdaptor> Collection<T, S> {
         this
     }
 
+    /// Creates a collection without storing it.
+    #[expect(clippy::expect_used, reason = "fatal error if it happens")]
+    fn new_unsaved(id: Id) -> Self {
+        Self {
+            children_ids: RefCell::new(None),
+            storage: Element::new(&Path::new("::unused").expect("valid path"), Some(id)),
+            _priv: PhantomData,
+        }
+    }
+
+    /// Creates a nested collection for the entry with the given ID, without
+    /// storing it.
+    ///
+    /// See [`Nested`].
+    ///
+    fn new_detached(parent_id: Id) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(parent_id.as_bytes());
+        hasher.update(b"nested");
+
+        Self::new_unsaved(Id::new(hasher.finalize().into()))
+    }
+
+    /// Stores the collection as a child of the entry with the given ID.
+    fn attach(&mut self, parent_id: Id) -> StoreResult<()> {
+        let _ = <Interface<S>>::add_child_to(parent_id, &RootHandle, self)?;
+
+        Ok(())
+    }
+
+    /// Registers how the entries of the collection are merged, if they can be.
+    fn register_children(&self, children: Option<Children>) {
+        if let Some(entries) = children {
+            merge::register_children(self.id(), entries);
+        }
+    }
+
+    /// Denies the executor any change to the collection and its entries.
+    fn deny(&self) {
+        guard::deny(self.id());
+    }
+
     /// Inserts an item into the collection.
     fn insert(&mut self, id: Option<Id>, item: T) -> StoreResult<T> {
         let path = self.path();
@@ -173,7 +289,43 @@ impl<T: BorshSerialize + BorshDeserialize, S: StorageAdaptor> Collection<T, S> {
     }
 
     fn len(&self) -> StoreResult<usize> {
-        Ok(self.children_cache()?.len())
+        if let Some(children) = self.children_ids.borrow().as_ref() {
+            return Ok(children.len());
+        }
+
+        Ok(<Interface<S>>::child_count_for(self.id(), &RootHandle)?)
+    }
+
+    fn iter_page(&self, cursor: Option<Cursor>, limit: usize) -> StoreResult<Page<T>> {
+        if limit == 0 {
+            return Err(StoreError::EmptyPage);
+        }
+
+        let mut children = <Interface<S>>::child_info_page_for(
+            self.id(),
+            &RootHandle,
+            cursor,
+            limit.saturating_add(1),
+        )?;
+
+        let next = if children.len() > limit {
+            children.truncate(limit);
+            children.last().map(Cursor::after)
+        } else {
+            None
+        };
+
+        let items = children
+            .into_iter()
+            .map(|child| {
+                let entry = <Interface<S>>::find_by_id::<Entry<_>>(child.id())?
+                    .ok_or(StoreError::StorageError(StorageError::NotFound(child.id())))?;
+
+                Ok(entry.item)
+            })
+            .collect::<StoreResult<_>>()?;
+
+        Ok(Page { items, next })
     }
 
     fn entries(
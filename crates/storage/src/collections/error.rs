@@ -16,6 +16,18 @@ pub enum StoreError {
     /// Error while interacting with a path.
     #[error(transparent)]
     PathError(#[from] PathError),
+    /// The index supplied is outside the bounds of the collection.
+    #[error("index {index} is out of bounds for length {len}")]
+    IndexOutOfBounds {
+        /// The index that was supplied.
+        index: usize,
+        /// The length of the collection.
+        len: usize,
+    },
+    /// A page was requested that can't hold any items, which would give no
+    /// position to resume from.
+    #[error("a page must be able to hold at least one item")]
+    EmptyPage,
 }
 
 impl Serialize for StoreError {
@@ -0,0 +1,897 @@
+//! This module provides functionality for the ordered map data structure.
+//!
+//! An [`OrderedMap`] stores its entries in exactly the same way as an
+//! [`UnorderedMap`](super::UnorderedMap), with each entry being a separate
+//! child entity whose ID is derived from the key. It therefore has the same
+//! conflict resolution and synchronisation behaviour.
+//!
+//! In addition, it keeps a sorted index of the keys as a local summary of its
+//! entries (see the `summary` module). This is kept in pages, which are updated
+//! in place as entries are inserted and removed, and only the entries that have
+//! changed through synchronisation are read to bring it up to date. Values are
+//! only ever deserialised for the entries that are actually returned, and the
+//! pages of keys only as a range scan reaches them, which means that range
+//! scans and pagination do not load the whole collection.
+//!
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+
+use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+use serde::ser::{Error as _, SerializeMap};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::summary::{Pager, Pages, Summarised, Summary};
+use super::{Collection, Entry, Nested, StorageAdaptor, StoreResult};
+use crate::address::Id;
+use crate::collections::error::StoreError;
+use crate::entities::Data;
+use crate::guard::Guard;
+use crate::interface::StorageError;
+use crate::merge::{merge_data, Children, Merge};
+use crate::store::MainStorage;
+
+/// A map collection that stores key-value pairs sorted by key.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct OrderedMap<K, V, S: StorageAdaptor = MainStorage> {
+    #[borsh(bound(serialize = "", deserialize = ""))]
+    inner: Collection<(K, V), S>,
+}
+
+/// The keys of an [`OrderedMap`], kept in order as a summary of its entries so
+/// that ordered reads do not need to load every entry.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct Keys<K> {
+    /// The ID of the entry for each key.
+    #[borsh(bound(serialize = "K: BorshSerialize", deserialize = "K: BorshDeserialize"))]
+    ids: Pages<K, Id>,
+
+    /// The serialised key of each entry, so that an entry can be forgotten
+    /// once only its ID is known.
+    keys: Pages<Id, Vec<u8>>,
+}
+
+impl<K, V> OrderedMap<K, V, MainStorage>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    V: BorshSerialize + BorshDeserialize,
+{
+    /// Create a new ordered map collection.
+    pub fn new() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<K, V, S> OrderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    /// Create a new ordered map collection.
+    fn new_internal() -> Self {
+        Self {
+            inner: Collection::new(None),
+        }
+    }
+
+    /// Compute the ID for a key.
+    fn compute_id(&self, key: &[u8]) -> Id {
+        let mut hasher = Sha256::new();
+        hasher.update(self.inner.id().as_bytes());
+        hasher.update(key);
+        Id::new(hasher.finalize().into())
+    }
+
+    /// Insert a key-value pair into the map.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, StoreError>
+    where
+        K: AsRef<[u8]>,
+    {
+        let id = self.compute_id(key.as_ref());
+
+        let mut keys = self.keys()?;
+
+        let vacant = match self.replace(id, value)? {
+            Ok(old) => {
+                keys.changed(id);
+                keys.save(&self.inner)?;
+
+                return Ok(Some(old));
+            }
+            Err(vacant) => vacant,
+        };
+
+        let (stored, _) = self.inner.insert(Some(id), (key, vacant))?;
+
+        keys.summary.insert::<S>(&mut keys.pager, stored, id)?;
+        keys.changed(id);
+        keys.save(&self.inner)?;
+
+        Ok(None)
+    }
+
+    /// Get the nested collection for a key in the map, inserting an empty one
+    /// if the key does not exist.
+    ///
+    /// The returned collection is a handle to the stored one, so changes made
+    /// through it are persisted directly, without needing to insert the value
+    /// again. See [`Nested`] for how nested collections are synchronised.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn get_or_insert_nested(&mut self, key: K) -> Result<V, StoreError>
+    where
+        K: AsRef<[u8]>,
+        V: Nested,
+    {
+        let id = self.compute_id(key.as_ref());
+
+        if let Some((_, value)) = self.inner.get(id)? {
+            return Ok(value);
+        }
+
+        let mut keys = self.keys()?;
+
+        let (stored, mut value) = self.inner.insert(Some(id), (key, V::detached(id)))?;
+
+        value.attach(id)?;
+
+        keys.summary.insert::<S>(&mut keys.pager, stored, id)?;
+        keys.changed(id);
+        keys.save(&self.inner)?;
+
+        Ok(value)
+    }
+
+    /// Get the value for a key in the map.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn get<Q>(&self, key: &Q) -> Result<Option<V>, StoreError>
+    where
+        K: Borrow<Q>,
+        Q: AsRef<[u8]> + ?Sized,
+    {
+        let id = self.compute_id(key.as_ref());
+
+        Ok(self.inner.get(id)?.map(|(_, v)| v))
+    }
+
+    /// Check if the map contains a key.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn contains<Q>(&self, key: &Q) -> Result<bool, StoreError>
+    where
+        K: Borrow<Q>,
+        Q: AsRef<[u8]> + ?Sized,
+    {
+        self.get(key).map(|v| v.is_some())
+    }
+
+    /// Remove a key from the map, returning the value at the key if it
+    /// previously existed.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn remove<Q>(&mut self, key: &Q) -> Result<Option<V>, StoreError>
+    where
+        K: Borrow<Q>,
+        Q: AsRef<[u8]> + Ord + ?Sized,
+    {
+        let id = self.compute_id(key.as_ref());
+
+        let mut keys = self.keys()?;
+
+        let Some(entry) = self.inner.get_mut(id)? else {
+            return Ok(None);
+        };
+
+        let (_, v) = entry.remove()?;
+
+        keys.summary.remove::<S, Q>(key, id)?;
+        keys.changed(id);
+        keys.save(&self.inner)?;
+
+        Ok(Some(v))
+    }
+
+    /// Get the number of entries in the map.
+    ///
+    /// This only consults the index of the collection, and does not read any
+    /// of the entries.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn len(&self) -> Result<usize, StoreError> {
+        self.inner.len()
+    }
+
+    /// Check whether the map contains no entries.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn is_empty(&self) -> Result<bool, StoreError> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Get the entry with the smallest key.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn first(&self) -> Result<Option<(K, V)>, StoreError> {
+        self.range::<K, _>(..)?.next().transpose()
+    }
+
+    /// Get the entry with the largest key.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn last(&self) -> Result<Option<(K, V)>, StoreError> {
+        self.range::<K, _>(..)?.next_back().transpose()
+    }
+
+    /// Get an iterator over the entries whose keys fall within the given range,
+    /// in ascending key order.
+    ///
+    /// Values are loaded lazily as the iterator advances, so taking only the
+    /// first few entries does not read the rest of the range. Any error that
+    /// occurs while loading a value is returned in its place.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn range<Q, R>(
+        &self,
+        range: R,
+    ) -> Result<impl DoubleEndedIterator<Item = Result<(K, V), StoreError>> + '_, StoreError>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let ids = self.keys()?.summary.ids.into_range::<S, Q, R>(range)?;
+
+        Ok(ids.map(move |entry| {
+            let (_, id) = entry?;
+
+            self.inner
+                .get(id)?
+                .ok_or(StoreError::StorageError(StorageError::NotFound(id)))
+        }))
+    }
+
+    /// Get an iterator over the entries starting from the given key, inclusive,
+    /// in ascending key order. This is intended for pagination, where the key
+    /// acts as the cursor.
+    ///
+    /// As with [`range()`](Self::range()), values are loaded lazily, and any
+    /// error that occurs while loading one is returned in its place.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn iter_from<'a, Q>(
+        &'a self,
+        key: &'a Q,
+    ) -> Result<impl DoubleEndedIterator<Item = Result<(K, V), StoreError>> + 'a, StoreError>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.range((Bound::Included(key), Bound::Unbounded))
+    }
+
+    /// Get an iterator over all the entries in the map, in ascending key order.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn entries(&self) -> Result<impl DoubleEndedIterator<Item = (K, V)> + '_, StoreError> {
+        let entries = self.range::<K, _>(..)?.collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries.into_iter())
+    }
+
+    /// Clear the map, removing all entries.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn clear(&mut self) -> Result<(), StoreError> {
+        self.inner.clear()?;
+
+        Summarised::<Keys<K>>::reset(&self.inner)
+    }
+
+    /// Replace the value of an existing entry, returning the old value, or hand
+    /// the new value back if there is no entry to replace it in.
+    fn replace(&mut self, id: Id, value: V) -> Result<Result<V, V>, StoreError> {
+        if let Some(mut entry) = self.inner.get_mut(id)? {
+            return Ok(Ok(mem::replace(&mut entry.1, value)));
+        }
+
+        Ok(Err(value))
+    }
+
+    /// Load the sorted index of keys to entry IDs, bringing it up to date with
+    /// any entries that have changed since it was last saved.
+    fn keys(&self) -> Result<Summarised<Keys<K>>, StoreError> {
+        Summarised::load(&self.inner)
+    }
+}
+
+impl<K> Keys<K>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+{
+    /// Adds the key of an entry.
+    fn insert<S: StorageAdaptor>(&mut self, pager: &mut Pager, key: K, id: Id) -> StoreResult<()> {
+        let stored = to_vec(&key).map_err(StorageError::SerializationError)?;
+
+        drop(self.keys.insert::<S>(pager, id, stored)?);
+        _ = self.ids.insert::<S>(pager, key, id)?;
+
+        Ok(())
+    }
+
+    /// Removes the key of an entry.
+    fn remove<S, Q>(&mut self, key: &Q, id: Id) -> StoreResult<()>
+    where
+        S: StorageAdaptor,
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        drop(self.keys.remove::<S, _>(&id)?);
+        _ = self.ids.remove::<S, Q>(key)?;
+
+        Ok(())
+    }
+}
+
+impl<K, V> Summary<(K, V)> for Keys<K>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+{
+    fn record<S: StorageAdaptor>(
+        &mut self,
+        pager: &mut Pager,
+        id: Id,
+        item: (K, V),
+    ) -> StoreResult<()> {
+        let (key, _) = item;
+
+        self.insert::<S>(pager, key, id)
+    }
+
+    fn forget<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<()> {
+        let Some(stored) = self.keys.remove::<S, _>(&id)? else {
+            return Ok(());
+        };
+
+        let key = K::try_from_slice(&stored).map_err(StorageError::DeserializationError)?;
+
+        let _ignored = self.ids.remove::<S, K>(&key)?;
+
+        Ok(())
+    }
+
+    fn write<S: StorageAdaptor>(&mut self) -> StoreResult<()> {
+        self.ids.write::<S>()?;
+        self.keys.write::<S>()
+    }
+}
+
+impl<K> Default for Keys<K> {
+    fn default() -> Self {
+        Self {
+            ids: Pages::default(),
+            keys: Pages::default(),
+        }
+    }
+}
+
+impl<K, V, S> Eq for OrderedMap<K, V, S>
+where
+    K: Eq + Ord + BorshSerialize + BorshDeserialize,
+    V: Eq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+}
+
+impl<K, V, S> PartialEq for OrderedMap<K, V, S>
+where
+    K: PartialEq + Ord + BorshSerialize + BorshDeserialize,
+    V: PartialEq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn eq(&self, other: &Self) -> bool {
+        let l = self.entries().unwrap();
+        let r = other.entries().unwrap();
+
+        l.eq(r)
+    }
+}
+
+impl<K, V, S> Ord for OrderedMap<K, V, S>
+where
+    K: Ord + BorshSerialize + BorshDeserialize,
+    V: Ord + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let l = self.entries().unwrap();
+        let r = other.entries().unwrap();
+
+        l.cmp(r)
+    }
+}
+
+impl<K, V, S> PartialOrd for OrderedMap<K, V, S>
+where
+    K: Ord + BorshSerialize + BorshDeserialize,
+    V: PartialOrd + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let l = self.entries().ok()?;
+        let r = other.entries().ok()?;
+
+        l.partial_cmp(r)
+    }
+}
+
+impl<K, V, S> fmt::Debug for OrderedMap<K, V, S>
+where
+    K: fmt::Debug + Ord + BorshSerialize + BorshDeserialize,
+    V: fmt::Debug + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, clippy::unwrap_in_result, reason = "'tis fine")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("OrderedMap")
+                .field("entries", &self.inner)
+                .finish()
+        } else {
+            f.debug_map().entries(self.entries().unwrap()).finish()
+        }
+    }
+}
+
+impl<K, V, S> Default for OrderedMap<K, V, S>
+where
+    K: Ord + BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn default() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<K, V, S> Merge for OrderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    V: BorshSerialize + BorshDeserialize + Merge,
+    S: StorageAdaptor,
+{
+    fn children() -> Option<Children> {
+        Some(Children::new(merge_data::<Entry<(K, V)>>, V::children))
+    }
+
+    fn register(&self) {
+        self.inner.register_children(Self::children());
+    }
+}
+
+impl<K, V, S> Guard for OrderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn deny(&self) {
+        self.inner.deny();
+    }
+}
+
+impl<K, V, S> Nested for OrderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn detached(parent_id: Id) -> Self {
+        Self {
+            inner: Collection::new_detached(parent_id),
+        }
+    }
+
+    fn attach(&mut self, parent_id: Id) -> Result<(), StoreError> {
+        self.inner.attach(parent_id)
+    }
+}
+
+impl<K, V, S> Serialize for OrderedMap<K, V, S>
+where
+    K: Ord + BorshSerialize + BorshDeserialize + Serialize,
+    V: BorshSerialize + BorshDeserialize + Serialize,
+    S: StorageAdaptor,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        let len = self.len().map_err(Ser::Error::custom)?;
+
+        let mut seq = serializer.serialize_map(Some(len))?;
+
+        for (k, v) in self.entries().map_err(Ser::Error::custom)? {
+            seq.serialize_entry(&k, &v)?;
+        }
+
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Bound;
+
+    use crate::collections::{OrderedMap, Root, StoreError};
+    use crate::entities::Data;
+    use crate::interface::StorageError;
+    use crate::store::{Key, MainStorage, StorageAdaptor};
+
+    #[test]
+    fn test_ordered_map_basic_operations() {
+        let mut map = Root::new(OrderedMap::new);
+
+        assert!(map
+            .insert("key".to_owned(), "value".to_owned())
+            .expect("insert failed")
+            .is_none());
+
+        assert_eq!(
+            map.get("key").expect("get failed").as_deref(),
+            Some("value")
+        );
+        assert_eq!(
+            map.insert("key".to_owned(), "value2".to_owned())
+                .expect("insert failed")
+                .as_deref(),
+            Some("value")
+        );
+        assert!(map.contains("key").expect("contains failed"));
+
+        assert_eq!(
+            map.remove("key").expect("remove failed").as_deref(),
+            Some("value2")
+        );
+        assert_eq!(map.remove("key").expect("remove failed"), None);
+        assert!(map.is_empty().expect("is_empty failed"));
+    }
+
+    #[test]
+    fn test_ordered_map_entries_are_sorted() {
+        let mut map = Root::new(OrderedMap::new);
+
+        for key in ["delta", "alpha", "charlie", "bravo"] {
+            let _ = map
+                .insert(key.to_owned(), key.len())
+                .expect("insert failed");
+        }
+
+        let keys: Vec<String> = map
+            .entries()
+            .expect("entries failed")
+            .map(|(k, _)| k)
+            .collect();
+
+        assert_eq!(keys, ["alpha", "bravo", "charlie", "delta"]);
+        assert_eq!(map.len().expect("len failed"), 4);
+    }
+
+    #[test]
+    fn test_ordered_map_first_and_last() {
+        let mut map = Root::new(OrderedMap::<String, u32>::new);
+
+        assert_eq!(map.first().expect("first failed"), None);
+        assert_eq!(map.last().expect("last failed"), None);
+
+        let _ = map.insert("b".to_owned(), 2).expect("insert failed");
+        let _ = map.insert("c".to_owned(), 3).expect("insert failed");
+        let _ = map.insert("a".to_owned(), 1).expect("insert failed");
+
+        assert_eq!(
+            map.first().expect("first failed"),
+            Some(("a".to_owned(), 1))
+        );
+        assert_eq!(map.last().expect("last failed"), Some(("c".to_owned(), 3)));
+
+        let _ = map.remove("c").expect("remove failed");
+
+        assert_eq!(map.last().expect("last failed"), Some(("b".to_owned(), 2)));
+    }
+
+    #[test]
+    fn test_ordered_map_range() {
+        let mut map = Root::new(OrderedMap::new);
+
+        for key in ["a", "b", "c", "d", "e"] {
+            let _ = map.insert(key.to_owned(), ()).expect("insert failed");
+        }
+
+        let keys: Vec<String> = map
+            .range::<str, _>((Bound::Included("b"), Bound::Excluded("d")))
+            .expect("range failed")
+            .map(|entry| entry.expect("entry failed").0)
+            .collect();
+        assert_eq!(keys, ["b", "c"]);
+
+        let reversed: Vec<String> = map
+            .range("b".to_owned()..="d".to_owned())
+            .expect("range failed")
+            .rev()
+            .map(|entry| entry.expect("entry failed").0)
+            .collect();
+        assert_eq!(reversed, ["d", "c", "b"]);
+    }
+
+    #[test]
+    fn test_ordered_map_iter_from() {
+        let mut map = Root::new(OrderedMap::new);
+
+        for key in ["a", "b", "c", "d", "e"] {
+            let _ = map.insert(key.to_owned(), ()).expect("insert failed");
+        }
+
+        let page: Vec<String> = map
+            .iter_from("bb")
+            .expect("iter_from failed")
+            .take(2)
+            .map(|entry| entry.expect("entry failed").0)
+            .collect();
+        assert_eq!(page, ["c", "d"]);
+    }
+
+    #[test]
+    fn test_ordered_map_clear() {
+        let mut map = Root::new(OrderedMap::new);
+
+        let _ = map.insert("a".to_owned(), 1).expect("insert failed");
+        let _ = map.insert("b".to_owned(), 2).expect("insert failed");
+
+        map.clear().expect("clear failed");
+
+        assert_eq!(map.len().expect("len failed"), 0);
+        assert_eq!(map.first().expect("first failed"), None);
+    }
+
+    #[test]
+    fn test_ordered_map_reads_only_the_entries_needed() {
+        let mut map = Root::new(OrderedMap::new);
+
+        for key in ["a", "b", "c"] {
+            let _ = map.insert(key.to_owned(), ()).expect("insert failed");
+        }
+
+        let a = map.compute_id(b"a");
+
+        assert!(MainStorage::storage_remove(Key::Entry(a)));
+
+        let _ = map.insert("d".to_owned(), ()).expect("insert failed");
+
+        let page: Vec<String> = map
+            .iter_from("b")
+            .expect("iter_from failed")
+            .map(|entry| entry.expect("entry failed").0)
+            .collect();
+        assert_eq!(page, ["b", "c", "d"]);
+
+        assert!(matches!(
+            map.first(),
+            Err(StoreError::StorageError(StorageError::NotFound(id))) if id == a
+        ));
+    }
+
+    #[test]
+    fn test_ordered_map_follows_changes_made_elsewhere() {
+        let mut map = Root::new(OrderedMap::new);
+
+        let _ = map.insert("b".to_owned(), 2).expect("insert failed");
+        let _ = map.insert("c".to_owned(), 3).expect("insert failed");
+
+        // Changes that arrive through synchronisation bypass the map
+        let a = map.compute_id(b"a");
+        let _inserted = map
+            .inner
+            .insert(Some(a), ("a".to_owned(), 1))
+            .expect("insert failed");
+
+        let c = map.compute_id(b"c");
+        let _removed = map
+            .inner
+            .get_mut(c)
+            .expect("get failed")
+            .expect("entry missing")
+            .remove()
+            .expect("remove failed");
+
+        let b = map.compute_id(b"b");
+        map.inner
+            .get_mut(b)
+            .expect("get failed")
+            .expect("entry missing")
+            .1 = 20;
+
+        assert_eq!(
+            map.entries().expect("entries failed").collect::<Vec<_>>(),
+            [("a".to_owned(), 1), ("b".to_owned(), 20)]
+        );
+    }
+
+    #[test]
+    fn test_ordered_map_keeps_many_keys_in_pages() {
+        let mut map = Root::new(OrderedMap::new);
+
+        // Every key from 0 to 199, in a scattered order
+        for n in 0..200_u32 {
+            let key = format!("{:04}", n.wrapping_mul(7919) % 200);
+            let _ = map.insert(key, n).expect("insert failed");
+        }
+
+        let keys: Vec<String> = map
+            .entries()
+            .expect("entries failed")
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(
+            keys,
+            (0..200).map(|n| format!("{n:04}")).collect::<Vec<_>>()
+        );
+
+        let range: Vec<String> = map
+            .range("0023".to_owned().."0121".to_owned())
+            .expect("range failed")
+            .rev()
+            .map(|entry| entry.expect("entry failed").0)
+            .collect();
+        assert_eq!(
+            range,
+            (23..121)
+                .rev()
+                .map(|n| format!("{n:04}"))
+                .collect::<Vec<_>>()
+        );
+
+        for n in (0..200).step_by(3) {
+            let _ = map.remove(&format!("{n:04}")).expect("remove failed");
+        }
+
+        assert_eq!(map.len().expect("len failed"), 133);
+        assert_eq!(
+            map.first()
+                .expect("first failed")
+                .map(|(key, _)| key)
+                .as_deref(),
+            Some("0001")
+        );
+        assert_eq!(
+            map.last()
+                .expect("last failed")
+                .map(|(key, _)| key)
+                .as_deref(),
+            Some("0199")
+        );
+
+        // The keys are kept in pages, rather than alongside the summary
+        let summary = MainStorage::storage_read(Key::Summary(map.inner.id())).expect("no summary");
+        assert!(summary.len() < 4096, "summary is {} bytes", summary.len());
+    }
+
+    #[test]
+    fn test_ordered_map_follows_many_changes_made_elsewhere() {
+        let mut map = Root::new(OrderedMap::new);
+
+        for n in 0..300_u32 {
+            let _ = map.insert(format!("{n:04}"), n).expect("insert failed");
+        }
+
+        // Changes that arrive through synchronisation bypass the map
+        for n in (0..300_u32).step_by(3) {
+            let id = map.compute_id(format!("{n:04}").as_bytes());
+            let _removed = map
+                .inner
+                .get_mut(id)
+                .expect("get failed")
+                .expect("entry missing")
+                .remove()
+                .expect("remove failed");
+        }
+
+        for n in 300..350_u32 {
+            let key = format!("{n:04}");
+            let id = map.compute_id(key.as_bytes());
+            let _inserted = map.inner.insert(Some(id), (key, n)).expect("insert failed");
+        }
+
+        let expected: Vec<(String, u32)> = (0..350)
+            .filter(|n| n % 3 != 0 || *n >= 300)
+            .map(|n| (format!("{n:04}"), n))
+            .collect();
+
+        assert_eq!(
+            map.entries().expect("entries failed").collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(map.len().expect("len failed"), expected.len());
+        assert_eq!(
+            map.iter_from("0298")
+                .expect("iter_from failed")
+                .map(|entry| entry.expect("entry failed").1)
+                .take(3)
+                .collect::<Vec<_>>(),
+            [298, 299, 300]
+        );
+    }
+}
@@ -7,10 +7,14 @@ use std::ptr;
 
 use borsh::{from_slice, BorshDeserialize, BorshSerialize};
 
-use super::{Collection, ROOT_ID};
+use super::{Collection, Entry, ROOT_ID};
 use crate::address::Id;
+use crate::entities::{Data, Element};
+use crate::env;
+use crate::guard::{self, Guard};
 use crate::integration::Comparison;
 use crate::interface::{Action, Interface, StorageError};
+use crate::merge::{self, Merge};
 use crate::store::{MainStorage, StorageAdaptor};
 use crate::sync::{self, SyncArtifact};
 
@@ -53,6 +57,8 @@ where
     /// Creates a new root collection with the given value.
     #[expect(clippy::unwrap_used, reason = "fatal error if it happens")]
     pub fn new_internal<F: FnOnce() -> T>(f: F) -> Self {
+        guard::reset();
+
         let mut inner = Collection::new(Some(*ROOT_ID));
 
         let id = Self::entry_id();
@@ -101,9 +107,78 @@ where
         })
     }
 
+    /// Evaluates the guards of the root state for the executor, ahead of
+    /// changing it.
+    ///
+    /// Any change made thereafter that the executor is denied, as described
+    /// in the [`guard`](crate::guard) module, aborts the execution when the
+    /// root collection is committed. This is what `#[app::logic]` methods
+    /// taking `&mut self` are wrapped in.
+    ///
+    pub fn guard(&self)
+    where
+        T: Guard,
+    {
+        guard::reset();
+
+        guard::register(Self::entry_id(), guard::check_data::<Entry<T>>);
+
+        self.get().deny();
+    }
+
+    /// Replaces the root state with a value of a new type.
+    ///
+    /// When an application is upgraded and the layout of its state changes,
+    /// the stored value can no longer be read as the new type. The previous
+    /// value can still be read by fetching the root as the old type, and then
+    /// converted inside the closure, with the result overwriting the stored
+    /// entry. This is what `#[app::migrate]` methods are wrapped in.
+    ///
+    #[expect(clippy::unwrap_used, reason = "fatal error if it happens")]
+    pub fn migrate<F: FnOnce() -> T>(f: F) -> Self {
+        guard::reset();
+
+        let mut inner = <Interface<S>>::root::<Collection<T, S>>().unwrap().unwrap();
+
+        let mut entry = Entry {
+            item: f(),
+            storage: Element::new(&inner.path(), Some(Self::entry_id())),
+        };
+
+        let _ = <Interface<S>>::save(&mut entry).unwrap();
+
+        inner.element_mut().update();
+
+        Self {
+            inner,
+            dirty: false,
+            value: RefCell::new(Some(entry.item)),
+        }
+    }
+
+    /// Consumes the root collection, returning the value it holds.
+    ///
+    /// This does not commit any changes.
+    ///
+    #[expect(clippy::unwrap_used, reason = "fatal error if it happens")]
+    pub fn into_inner(self) -> T {
+        let _ = self.get();
+
+        self.value.into_inner().unwrap()
+    }
+
     /// Commits the root collection.
+    ///
+    /// If the guards of the root state have been evaluated, and the executor
+    /// has made any change they deny it, the execution is aborted instead.
+    ///
     #[expect(clippy::unwrap_used, reason = "fatal error if it happens")]
     pub fn commit(mut self) {
+        // the version the guards compare against is about to be replaced
+        let previous = guard::is_active()
+            .then(|| <Interface<S>>::find_by_id_raw(Self::entry_id()))
+            .flatten();
+
         if self.dirty {
             if let Some(value) = self.value.into_inner() {
                 if let Some(mut entry) = self.inner.get_mut(Self::entry_id()).unwrap() {
@@ -112,6 +187,25 @@ where
             }
         }
 
+        if guard::is_active() {
+            // the changes are only checked once made, as refusing any of them
+            // aborts the execution, discarding them all
+            let permitted = sync::with_actions(|actions| {
+                actions
+                    .iter()
+                    .all(|action| guard::permits(action, |_| Ok(previous.clone())).unwrap())
+            });
+
+            guard::reset();
+
+            if !permitted {
+                drop(sync::take_actions());
+                drop(sync::take_changes());
+
+                env::unauthorized();
+            }
+        }
+
         <Interface<S>>::commit_root(Some(self.inner)).unwrap();
     }
 
@@ -122,27 +216,70 @@ where
     }
 
     /// Syncs the root collection.
+    ///
+    /// Conflicting versions of the root state are combined using its [`Merge`]
+    /// implementation, as are the values of any collections it registers for
+    /// merging. Any orphans that have outlived the expiry period are discarded
+    /// once the sync has been processed.
+    ///
+    /// The executor is held to the guards of the root state, as described in
+    /// the [`guard`](crate::guard) module, so that any change it is denied is
+    /// left out.
+    ///
     #[expect(clippy::missing_errors_doc, reason = "NO")]
-    pub fn sync(args: &[u8]) -> Result<(), StorageError> {
+    pub fn sync(args: &[u8]) -> Result<(), StorageError>
+    where
+        T: Merge + Guard,
+    {
+        merge::register(Self::entry_id(), merge::merge_data::<Entry<T>>);
+
+        guard::reset();
+
+        if let Some(entry) = <Interface<S>>::find_by_id::<Entry<T>>(Self::entry_id())? {
+            entry.item.register();
+
+            // the executor is the author of the changes, or the peer they
+            // are compared with, and is only allowed what the state permits
+            guard::register(Self::entry_id(), guard::check_data::<Entry<T>>);
+
+            entry.item.deny();
+        }
+
         let artifact =
             from_slice::<SyncArtifact>(args).map_err(StorageError::DeserializationError)?;
 
         match artifact {
             SyncArtifact::Actions(actions) => {
+                let mut compare = vec![];
+
                 for action in actions {
-                    let _ignored = match action {
-                        Action::Compare { id } => {
-                            sync::push_comparison(Comparison {
-                                data: <Interface<S>>::find_by_id_raw(id),
-                                comparison_data: <Interface<S>>::generate_comparison_data(Some(
-                                    id,
-                                ))?,
-                            });
-                        }
+                    match action {
+                        Action::Compare { id } => compare.push(id),
                         Action::Add { .. } | Action::Update { .. } | Action::Delete { .. } => {
                             <Interface<S>>::apply_action(action)?;
                         }
-                    };
+                    }
+                }
+
+                if !compare.is_empty() {
+                    // Applied actions ask for the entities to be compared in
+                    // turn, which can't be sent alongside comparisons, and so
+                    // those entities are compared from this side instead.
+                    for action in sync::take_actions() {
+                        match action {
+                            Action::Compare { id } => compare.push(id),
+                            Action::Add { .. } | Action::Update { .. } | Action::Delete { .. } => {
+                                sync::push_action(action);
+                            }
+                        }
+                    }
+                }
+
+                for id in compare {
+                    sync::push_comparison(Comparison {
+                        data: <Interface<S>>::find_by_id_raw(id),
+                        comparison_data: <Interface<S>>::generate_comparison_data(Some(id))?,
+                    });
                 }
             }
             SyncArtifact::Comparisons(comparisons) => {
@@ -163,6 +300,10 @@ where
             }
         }
 
+        let _purged = <Interface<S>>::purge_expired_orphans()?;
+
+        guard::reset();
+
         Self::commit_headless();
 
         Ok(())
@@ -192,3 +333,98 @@ where
         self.get()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+
+    use crate::address::Id;
+    use crate::collections::{Root, UnorderedMap};
+    use crate::entities::{ChildInfo, Metadata};
+    use crate::guard::Guard;
+    use crate::interface::{Action, Interface};
+    use crate::merge::Merge;
+    use crate::store::MainStorage;
+    use crate::sync::{self, SyncArtifact};
+
+    #[test]
+    fn test_root_migrate() {
+        let mut root = Root::new(|| (UnorderedMap::new(), 1_u32));
+
+        let _ignored = root
+            .0
+            .insert("key".to_owned(), "value".to_owned())
+            .expect("insert failed");
+
+        // a single execution commits once, so the migration is what commits here
+        drop(root);
+
+        let (map, count) = Root::<(UnorderedMap<String, String>, u32)>::fetch()
+            .expect("root not found")
+            .into_inner();
+
+        let root = Root::<(UnorderedMap<String, String>, String, u64)>::migrate(|| {
+            (map, String::from("migrated"), u64::from(count))
+        });
+
+        root.commit();
+
+        let root =
+            Root::<(UnorderedMap<String, String>, String, u64)>::fetch().expect("root not found");
+
+        assert_eq!(
+            root.0.get("key").expect("get failed").as_deref(),
+            Some("value")
+        );
+        assert_eq!(root.1, "migrated");
+        assert_eq!(root.2, 1);
+    }
+
+    #[derive(BorshDeserialize, BorshSerialize)]
+    struct State {
+        map: UnorderedMap<String, String>,
+    }
+
+    impl Merge for State {}
+
+    impl Guard for State {}
+
+    #[test]
+    fn test_root_sync_actions_alongside_compare() {
+        let mut root = Root::new(|| State {
+            map: UnorderedMap::new(),
+        });
+
+        let _ignored = root
+            .map
+            .insert("key".to_owned(), "value".to_owned())
+            .expect("insert failed");
+
+        // the sync is what commits here, in an execution of its own
+        drop(root);
+        let _setup = sync::take_actions();
+
+        let added = Id::random();
+        let root_info = ChildInfo::new(Id::root(), [0; 32], Metadata::default());
+
+        // Applying the add asks for the entity to be compared in turn, which
+        // has to be sent together with the comparison asked for here
+        let artifact = SyncArtifact::Actions(vec![
+            Action::Compare { id: Id::root() },
+            Action::Add {
+                id: added,
+                data: vec![1, 2, 3],
+                ancestors: vec![root_info],
+                metadata: Metadata::default(),
+            },
+        ]);
+
+        Root::<State>::sync(&to_vec(&artifact).expect("serialisation failed"))
+            .expect("sync failed");
+
+        assert_eq!(
+            <Interface<MainStorage>>::find_by_id_raw(added),
+            Some(vec![1, 2, 3])
+        );
+    }
+}
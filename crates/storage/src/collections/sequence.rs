@@ -0,0 +1,1608 @@
+//! This module provides functionality for the sequence data structure.
+//!
+//! A [`Sequence`] is a replicated ordered list, implemented as a Replicated
+//! Growable Array (RGA). Every item is stored as a separate child entity with
+//! a stable [`Id`], which acts as its position identifier. Each item records
+//! the position it was inserted directly after (its origin), and the final
+//! order is derived from these links, so concurrent inserts and deletes made on
+//! different nodes converge on the same order once all actions are applied.
+//!
+//! Deleted items are kept as tombstones, so that positions inserted after them
+//! on other nodes can still be anchored. Tombstones are not visible through the
+//! public API.
+//!
+//! The order is kept in a local summary of the items (see the `summary`
+//! module), in pages that each note how many of their items are visible, so
+//! resolving an index to a position involves reading a single page rather than
+//! loading every item from storage.
+//!
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Result as IoResult, Write};
+use std::vec;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::ser::{Error as _, SerializeSeq};
+use serde::Serialize;
+
+use super::summary::{Cache, Pager, Pages, Summarised, Summary, PAGE_SIZE};
+use super::{Collection, Entry, Nested, StoreResult};
+use crate::address::Id;
+use crate::collections::error::StoreError;
+use crate::env::time_now;
+use crate::guard::Guard;
+use crate::interface::StorageError;
+use crate::merge::{merge_data, strategy, Children, Merge, Newer};
+use crate::store::{MainStorage, StorageAdaptor};
+
+/// A sequence collection that stores values in a replicated order.
+///
+/// Unlike [`Vector`](super::Vector), items can be inserted and deleted at any
+/// position, and concurrent edits made on different nodes are merged without
+/// conflict. This makes it suitable for ordered lists as well as for
+/// collaborative text, using a `Sequence<String>` holding one character per
+/// item.
+///
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Sequence<V, S: StorageAdaptor = MainStorage> {
+    #[borsh(bound(serialize = "", deserialize = ""))]
+    inner: Collection<Slot<V>, S>,
+}
+
+/// An item in a [`Sequence`], as stored.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct Slot<V> {
+    /// The position that this item was inserted directly after, or [`None`] if
+    /// it was inserted at the head of the sequence.
+    origin: Option<Id>,
+
+    /// The insertion stamp. Items sharing the same origin are ordered by
+    /// descending stamp, so that the most recent insert comes first.
+    stamp: u64,
+
+    /// The value of the item, or [`None`] if it has been deleted.
+    value: Option<V>,
+}
+
+impl<V: Merge> Merge for Slot<V> {
+    fn merge(&mut self, other: Self, newer: Newer) {
+        // Where the item was cleared on either side, the newer version wins
+        match (self.value.as_mut(), other.value) {
+            (Some(local), Some(foreign)) => local.merge(foreign, newer),
+            (_, foreign) => strategy::lww(&mut self.value, foreign, newer),
+        }
+    }
+}
+
+/// The layout of a [`Sequence`], kept as a summary of its slots so that
+/// positions can be resolved without loading every slot.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+struct Layout {
+    /// Where each slot was placed, including tombstones.
+    placements: Pages<Id, Placement>,
+
+    /// The slots inserted directly after each position, in sequence order.
+    siblings: Pages<Sibling, ()>,
+
+    /// The positions of all slots, including tombstones, in sequence order.
+    order: Order,
+
+    /// The highest stamp of any slot.
+    stamp: u64,
+
+    /// Whether any slots were placed at the end because their origin is not
+    /// known, which can only happen temporarily while synchronising.
+    orphans: bool,
+
+    /// Whether the order has to be worked out again from the placements.
+    #[borsh(skip)]
+    stale: bool,
+}
+
+/// Where a slot in a [`Sequence`] was placed, as recorded in its [`Layout`].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+struct Placement {
+    /// The position that the slot was inserted directly after.
+    origin: Option<Id>,
+
+    /// The insertion stamp of the slot.
+    stamp: u64,
+
+    /// Whether the slot still holds a value, rather than being a tombstone.
+    visible: bool,
+
+    /// The page of the [`Order`] that the slot is in.
+    page: Id,
+}
+
+/// A slot in a [`Sequence`], as listed among those inserted directly after the
+/// same position. These sort in sequence order, by descending stamp, with the
+/// [`Id`] as a tie-breaker.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Eq, PartialEq)]
+struct Sibling {
+    /// The position that the slot was inserted directly after.
+    origin: Option<Id>,
+
+    /// The insertion stamp of the slot.
+    stamp: u64,
+
+    /// The position of the slot.
+    id: Id,
+}
+
+impl Sibling {
+    /// The slot as listed among its siblings.
+    const fn of(id: Id, placement: &Placement) -> Self {
+        Self {
+            origin: placement.origin,
+            stamp: placement.stamp,
+            id,
+        }
+    }
+
+    /// A key that sorts before every slot inserted after the given origin.
+    const fn before_all(origin: Option<Id>) -> Self {
+        Self {
+            origin,
+            stamp: u64::MAX,
+            id: Id::new([u8::MAX; 32]),
+        }
+    }
+}
+
+impl Ord for Sibling {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.origin
+            .cmp(&other.origin)
+            .then_with(|| (other.stamp, other.id).cmp(&(self.stamp, self.id)))
+    }
+}
+
+impl PartialOrd for Sibling {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The positions of all slots in a [`Sequence`], including tombstones, in
+/// sequence order.
+///
+/// These are kept in pages of up to [`PAGE_SIZE`] slots, each listed with the
+/// number of its slots that are visible, so that the slot at an index can be
+/// found by reading a single page.
+///
+#[derive(Default)]
+struct Order {
+    /// The pages, in sequence order.
+    runs: Vec<Run>,
+
+    /// The pages read so far, listing each slot along with whether it is
+    /// visible.
+    cache: Cache<(Id, bool)>,
+}
+
+/// A page of the [`Order`] of a [`Sequence`].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+struct Run {
+    /// Unique identifier of the page.
+    id: Id,
+
+    /// The number of visible slots in the page.
+    visible: usize,
+}
+
+impl BorshSerialize for Order {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.runs.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for Order {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        Ok(Self {
+            runs: BorshDeserialize::deserialize_reader(reader)?,
+            cache: Cache::default(),
+        })
+    }
+}
+
+/// Where a new slot is put in the [`Order`].
+#[derive(Clone, Copy)]
+enum Spot {
+    /// At the head of the sequence.
+    Head,
+
+    /// Directly after the given position.
+    After(Id),
+
+    /// Directly before the given position.
+    Before(Id),
+
+    /// At the end of the sequence.
+    End,
+}
+
+impl<V> Sequence<V, MainStorage>
+where
+    V: BorshSerialize + BorshDeserialize,
+{
+    /// Create a new sequence collection.
+    pub fn new() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<V, S> Sequence<V, S>
+where
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    /// Create a new sequence collection.
+    fn new_internal() -> Self {
+        Self {
+            inner: Collection::new(None),
+        }
+    }
+
+    /// Insert a value at a specific index in the sequence, shifting all values
+    /// after it to the right.
+    ///
+    /// Returns the position identifier of the new value, which remains stable
+    /// regardless of any other inserts or deletes.
+    ///
+    /// # Errors
+    ///
+    /// If the index is greater than the length of the sequence, an error will
+    /// be returned. If an error occurs when interacting with the storage
+    /// system, or a child [`Element`](crate::entities::Element) cannot be
+    /// found, an error will also be returned.
+    ///
+    pub fn insert(&mut self, index: usize, value: V) -> Result<Id, StoreError> {
+        let mut layout = self.layout()?;
+
+        let origin = match index.checked_sub(1) {
+            None => None,
+            Some(prev) => Some(layout.summary.nth::<S>(prev)?.ok_or_else(|| {
+                StoreError::IndexOutOfBounds {
+                    index,
+                    len: layout.summary.len(),
+                }
+            })?),
+        };
+
+        self.insert_slot(&mut layout, origin, value)
+    }
+
+    /// Insert a value directly after the given position, or at the head of the
+    /// sequence if no position is given.
+    ///
+    /// The position may refer to a value that has since been deleted, in which
+    /// case the new value will be placed where the deleted value used to be.
+    ///
+    /// # Errors
+    ///
+    /// If the position does not exist in the sequence, or an error occurs when
+    /// interacting with the storage system, an error will be returned.
+    ///
+    pub fn insert_after(&mut self, position: Option<Id>, value: V) -> Result<Id, StoreError> {
+        let mut layout = self.layout()?;
+
+        if let Some(origin) = position {
+            if !layout.summary.contains::<S>(origin)? {
+                return Err(StoreError::StorageError(StorageError::NotFound(origin)));
+            }
+        }
+
+        self.insert_slot(&mut layout, position, value)
+    }
+
+    /// Add a value to the end of the sequence.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn push(&mut self, value: V) -> Result<Id, StoreError> {
+        let mut layout = self.layout()?;
+
+        let origin = layout.summary.last::<S>()?;
+
+        self.insert_slot(&mut layout, origin, value)
+    }
+
+    /// Get the value at a specific index in the sequence.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn get(&self, index: usize) -> Result<Option<V>, StoreError> {
+        let Some(id) = self.position(index)? else {
+            return Ok(None);
+        };
+
+        Ok(self.slot(id)?.value)
+    }
+
+    /// Get the position identifier of the value at a specific index in the
+    /// sequence.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn position(&self, index: usize) -> Result<Option<Id>, StoreError> {
+        self.layout()?.summary.nth::<S>(index)
+    }
+
+    /// Get the current index of the value with the given position identifier,
+    /// if it is still present in the sequence.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn index_of(&self, position: Id) -> Result<Option<usize>, StoreError> {
+        self.layout()?.summary.index_of::<S>(position)
+    }
+
+    /// Remove and return the value at a specific index in the sequence.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn remove(&mut self, index: usize) -> Result<Option<V>, StoreError> {
+        let mut layout = self.layout()?;
+
+        let Some(id) = layout.summary.nth::<S>(index)? else {
+            return Ok(None);
+        };
+
+        let value = self.tombstone(&mut layout, id)?;
+
+        layout.save(&self.inner)?;
+
+        Ok(value)
+    }
+
+    /// Remove all values within the given range of indices, returning the
+    /// number of values removed.
+    ///
+    /// Any part of the range that lies beyond the end of the sequence is
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn delete_range<R: RangeBounds<usize>>(&mut self, range: R) -> Result<usize, StoreError> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => usize::MAX,
+        };
+
+        let mut layout = self.layout()?;
+
+        let ids = layout.summary.visible::<S>(start, end)?;
+
+        for id in &ids {
+            let _ignored = self.tombstone(&mut layout, *id)?;
+        }
+
+        layout.save(&self.inner)?;
+
+        Ok(ids.len())
+    }
+
+    /// Get an iterator over the values in the sequence, in order.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn entries(&self) -> Result<impl Iterator<Item = V>, StoreError> {
+        let values = self
+            .layout()?
+            .summary
+            .visible::<S>(0, usize::MAX)?
+            .into_iter()
+            .map(|id| Ok(self.slot(id)?.value))
+            .collect::<Result<Vec<_>, StoreError>>()?;
+
+        Ok(values.into_iter().flatten())
+    }
+
+    /// Get the number of values in the sequence.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn len(&self) -> Result<usize, StoreError> {
+        Ok(self.layout()?.summary.len())
+    }
+
+    /// Check whether the sequence contains no values.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn is_empty(&self) -> Result<bool, StoreError> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Clear the sequence, removing all values.
+    ///
+    /// The positions of the values are kept as tombstones, in the same way as
+    /// for [`remove()`](Self::remove()), so that values inserted after them
+    /// concurrently on other nodes can still be placed.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn clear(&mut self) -> Result<(), StoreError> {
+        let _ignored = self.delete_range(..)?;
+
+        Ok(())
+    }
+
+    /// Store a new value directly after the given origin.
+    ///
+    /// The stamp is chosen to be greater than any stamp already present, so
+    /// that the new value is placed immediately after its origin locally, even
+    /// if the clock of this node is behind that of other nodes.
+    ///
+    fn insert_slot(
+        &mut self,
+        layout: &mut Summarised<Layout>,
+        origin: Option<Id>,
+        value: V,
+    ) -> Result<Id, StoreError> {
+        let stamp = time_now().max(layout.summary.stamp.saturating_add(1));
+
+        let id = Id::random();
+
+        let slot = self.inner.insert(
+            Some(id),
+            Slot {
+                origin,
+                stamp,
+                value: Some(value),
+            },
+        )?;
+
+        layout.summary.record::<S>(&mut layout.pager, id, slot)?;
+        layout.changed(id);
+        layout.save(&self.inner)?;
+
+        Ok(id)
+    }
+
+    /// Mark the value at the given position as deleted, returning the value.
+    ///
+    /// The layout is updated, but not saved.
+    ///
+    fn tombstone(
+        &mut self,
+        layout: &mut Summarised<Layout>,
+        id: Id,
+    ) -> Result<Option<V>, StoreError> {
+        let Some(mut entry) = self.inner.get_mut(id)? else {
+            return Ok(None);
+        };
+
+        let value = entry.value.take();
+
+        let slot: Slot<V> = Slot {
+            origin: entry.origin,
+            stamp: entry.stamp,
+            value: None,
+        };
+
+        drop(entry);
+
+        layout.summary.record::<S>(&mut layout.pager, id, slot)?;
+        layout.changed(id);
+
+        Ok(value)
+    }
+
+    /// Load the slot at the given position.
+    fn slot(&self, id: Id) -> Result<Slot<V>, StoreError> {
+        self.inner
+            .get(id)?
+            .ok_or(StoreError::StorageError(StorageError::NotFound(id)))
+    }
+
+    /// Load the layout of the sequence, bringing it up to date with any slots
+    /// that have changed since it was last saved.
+    fn layout(&self) -> Result<Summarised<Layout>, StoreError> {
+        Summarised::load(&self.inner)
+    }
+}
+
+impl Layout {
+    /// Get the placement of a slot.
+    fn placement<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<Option<Placement>> {
+        Ok(self.placements.get::<S, _>(&id)?.copied())
+    }
+
+    /// Get the number of values that have not been deleted.
+    fn len(&self) -> usize {
+        self.order
+            .runs
+            .iter()
+            .fold(0, |len, run| len.saturating_add(run.visible))
+    }
+
+    /// Get the position of the value at the given index, counting only the
+    /// values that have not been deleted.
+    fn nth<S: StorageAdaptor>(&mut self, index: usize) -> StoreResult<Option<Id>> {
+        Ok(self
+            .visible::<S>(index, index.saturating_add(1))?
+            .first()
+            .copied())
+    }
+
+    /// Get the positions of the values at the given indices, counting only the
+    /// values that have not been deleted.
+    fn visible<S: StorageAdaptor>(&mut self, start: usize, end: usize) -> StoreResult<Vec<Id>> {
+        let mut ids = vec![];
+
+        let mut index = 0_usize;
+
+        for run in &self.order.runs {
+            if index >= end {
+                break;
+            }
+
+            let next = index.saturating_add(run.visible);
+
+            if next > start {
+                ids.extend(
+                    self.order
+                        .cache
+                        .get::<S>(run.id)?
+                        .iter()
+                        .filter(|&&(_, shown)| shown)
+                        .map(|&(id, _)| id)
+                        .skip(start.saturating_sub(index))
+                        .take(end.saturating_sub(index.max(start))),
+                );
+            }
+
+            index = next;
+        }
+
+        Ok(ids)
+    }
+
+    /// Get the index of the value at the given position, counting only the
+    /// values that have not been deleted.
+    fn index_of<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<Option<usize>> {
+        let Some(placement) = self
+            .placement::<S>(id)?
+            .filter(|placement| placement.visible)
+        else {
+            return Ok(None);
+        };
+
+        let mut index = 0_usize;
+
+        for run in &self.order.runs {
+            if run.id != placement.page {
+                index = index.saturating_add(run.visible);
+                continue;
+            }
+
+            let before = self
+                .order
+                .cache
+                .get::<S>(run.id)?
+                .iter()
+                .take_while(|&&(other, _)| other != id)
+                .filter(|&&(_, shown)| shown)
+                .count();
+
+            return Ok(Some(index.saturating_add(before)));
+        }
+
+        Ok(None)
+    }
+
+    /// Get the last position, including tombstones.
+    fn last<S: StorageAdaptor>(&mut self) -> StoreResult<Option<Id>> {
+        let Some(run) = self.order.runs.last() else {
+            return Ok(None);
+        };
+
+        Ok(self.order.cache.get::<S>(run.id)?.last().map(|&(id, _)| id))
+    }
+
+    /// Check whether the given position has been placed.
+    fn contains<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<bool> {
+        Ok(self.placement::<S>(id)?.is_some())
+    }
+
+    /// Check whether any slots were inserted directly after the given position.
+    fn has_children<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<bool> {
+        Ok(self
+            .siblings
+            .next_after::<S>(&Sibling::before_all(Some(id)))?
+            .is_some_and(|next| next.origin == Some(id)))
+    }
+
+    /// Get the first slot after the given one among those inserted directly
+    /// after the same position.
+    fn next_sibling<S: StorageAdaptor>(&mut self, sibling: &Sibling) -> StoreResult<Option<Id>> {
+        Ok(self
+            .siblings
+            .next_after::<S>(sibling)?
+            .filter(|next| next.origin == sibling.origin)
+            .map(|next| next.id))
+    }
+
+    /// Work out where a new slot belongs, once it has been listed among its
+    /// siblings.
+    ///
+    /// A slot comes directly after its origin if it is the first of its
+    /// siblings, or else directly before the sibling that follows it. Failing
+    /// both, it comes after everything inserted after its origin, which means
+    /// finding the nearest ancestor that has a sibling following it.
+    ///
+    /// Where the slot can't be placed like this, because its origin isn't known
+    /// or it already has children that arrived before it, it is put at the end
+    /// and the order is marked to be worked out again.
+    ///
+    fn spot_for<S: StorageAdaptor>(&mut self, sibling: Sibling) -> StoreResult<Spot> {
+        if self.stale || self.has_children::<S>(sibling.id)? {
+            self.stale = true;
+            return Ok(Spot::End);
+        }
+
+        let first = self
+            .siblings
+            .next_after::<S>(&Sibling::before_all(sibling.origin))?
+            .is_some_and(|first| *first == sibling);
+
+        if first {
+            return match sibling.origin {
+                None => Ok(Spot::Head),
+                Some(origin) if self.contains::<S>(origin)? => Ok(Spot::After(origin)),
+                Some(_) => {
+                    self.stale = true;
+                    Ok(Spot::End)
+                }
+            };
+        }
+
+        let mut current = sibling;
+
+        loop {
+            if let Some(next) = self.next_sibling::<S>(&current)? {
+                return Ok(Spot::Before(next));
+            }
+
+            let Some(origin) = current.origin else {
+                break;
+            };
+
+            let Some(placement) = self.placement::<S>(origin)? else {
+                break;
+            };
+
+            current = Sibling::of(origin, &placement);
+        }
+
+        // Anything placed at the end because its origin isn't known comes last
+        if self.orphans || current.origin.is_some() {
+            self.stale = true;
+        }
+
+        Ok(Spot::End)
+    }
+
+    /// Put a new slot into the order, returning the page it ends up in.
+    ///
+    /// The placement of the slot is left for the caller to record, but those of
+    /// any slots moved to a new page are updated.
+    ///
+    fn place<S: StorageAdaptor>(
+        &mut self,
+        pager: &mut Pager,
+        id: Id,
+        visible: bool,
+        spot: Spot,
+    ) -> StoreResult<Id> {
+        if self.order.runs.is_empty() {
+            let page = pager.allocate();
+            self.order.cache.create(page, vec![]);
+            self.order.runs.push(Run {
+                id: page,
+                visible: 0,
+            });
+        }
+
+        let (at, offset) = match spot {
+            Spot::Head => (0, 0),
+            Spot::End => {
+                let last = self.order.runs.len().saturating_sub(1);
+                let len = match self.order.runs.get(last) {
+                    Some(run) => self.order.cache.get::<S>(run.id)?.len(),
+                    None => 0,
+                };
+                (last, len)
+            }
+            Spot::After(other) => {
+                let (run, index) = self.locate::<S>(other)?;
+                (run, index.saturating_add(1))
+            }
+            Spot::Before(other) => self.locate::<S>(other)?,
+        };
+
+        let Some(run) = self.order.runs.get_mut(at) else {
+            return Err(StoreError::StorageError(StorageError::NotFound(id)));
+        };
+
+        let entries = self.order.cache.get_mut::<S>(run.id)?;
+
+        entries.insert(offset.min(entries.len()), (id, visible));
+
+        if visible {
+            run.visible = run.visible.saturating_add(1);
+        }
+
+        let page = run.id;
+
+        if entries.len() <= PAGE_SIZE {
+            return Ok(page);
+        }
+
+        let tail = entries.split_off(entries.len().div_ceil(2));
+
+        run.visible = entries.iter().filter(|&&(_, shown)| shown).count();
+
+        let tail_run = Run {
+            id: pager.allocate(),
+            visible: tail.iter().filter(|&&(_, shown)| shown).count(),
+        };
+
+        let moved = tail.iter().map(|&(other, _)| other).collect::<Vec<_>>();
+
+        self.order.cache.create(tail_run.id, tail);
+        self.order.runs.insert(at.saturating_add(1), tail_run);
+
+        let mut landed = page;
+
+        for other in moved {
+            if other == id {
+                landed = tail_run.id;
+                continue;
+            }
+
+            if let Some(mut placement) = self.placement::<S>(other)? {
+                placement.page = tail_run.id;
+                _ = self.placements.insert::<S>(pager, other, placement)?;
+            }
+        }
+
+        Ok(landed)
+    }
+
+    /// Find the page that a slot is in, and where it is in that page.
+    fn locate<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<(usize, usize)> {
+        let not_found = || StoreError::StorageError(StorageError::NotFound(id));
+
+        let placement = self.placement::<S>(id)?.ok_or_else(not_found)?;
+
+        let at = self
+            .order
+            .runs
+            .iter()
+            .position(|run| run.id == placement.page)
+            .ok_or_else(not_found)?;
+
+        let slot = self
+            .order
+            .cache
+            .get::<S>(placement.page)?
+            .iter()
+            .position(|&(other, _)| other == id)
+            .ok_or_else(not_found)?;
+
+        Ok((at, slot))
+    }
+
+    /// Mark a slot in the order as visible or not.
+    fn show<S: StorageAdaptor>(&mut self, id: Id, visible: bool) -> StoreResult<()> {
+        let (at, slot) = self.locate::<S>(id)?;
+
+        let Some(run) = self.order.runs.get_mut(at) else {
+            return Ok(());
+        };
+
+        if let Some(entry) = self.order.cache.get_mut::<S>(run.id)?.get_mut(slot) {
+            entry.1 = visible;
+        }
+
+        run.visible = if visible {
+            run.visible.saturating_add(1)
+        } else {
+            run.visible.saturating_sub(1)
+        };
+
+        Ok(())
+    }
+
+    /// Take a slot out of the order.
+    fn unplace<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<()> {
+        let (at, slot) = self.locate::<S>(id)?;
+
+        let Some(run) = self.order.runs.get_mut(at) else {
+            return Ok(());
+        };
+
+        let entries = self.order.cache.get_mut::<S>(run.id)?;
+
+        let (_, visible) = entries.remove(slot);
+
+        if visible {
+            run.visible = run.visible.saturating_sub(1);
+        }
+
+        if entries.is_empty() {
+            let empty = self.order.runs.remove(at);
+            self.order.cache.remove::<S>(empty.id);
+        }
+
+        Ok(())
+    }
+}
+
+/// Work out the order of all slots, including tombstones, from where they were
+/// placed, along with whether any had to be put at the end because their origin
+/// is not known.
+///
+/// The slots form a tree, where each slot is a child of its origin. Walking
+/// this tree depth-first, visiting siblings in descending order of stamp (with
+/// the [`Id`] as a tie-breaker), produces the sequence order. Slots whose origin
+/// is not known locally, which can only happen temporarily while synchronising,
+/// are placed at the end.
+///
+fn order(placements: &BTreeMap<Id, Placement>) -> (Vec<Id>, bool) {
+    let mut siblings: BTreeMap<Option<Id>, Vec<(u64, Id)>> = BTreeMap::new();
+
+    for (id, placement) in placements {
+        siblings
+            .entry(placement.origin)
+            .or_default()
+            .push((placement.stamp, *id));
+    }
+
+    for list in siblings.values_mut() {
+        list.sort_by(|a, b| b.cmp(a));
+    }
+
+    let mut ordered = Vec::with_capacity(placements.len());
+
+    let mut next = siblings.remove(&None);
+
+    let mut orphans = false;
+
+    while let Some(start) = next.take().or_else(|| {
+        let (_, list) = siblings.pop_first()?;
+        orphans = true;
+        Some(list)
+    }) {
+        let mut stack: Vec<vec::IntoIter<(u64, Id)>> = vec![start.into_iter()];
+
+        while let Some(top) = stack.last_mut() {
+            let Some((_, id)) = top.next() else {
+                let _ignored = stack.pop();
+                continue;
+            };
+
+            ordered.push(id);
+
+            if let Some(children) = siblings.remove(&Some(id)) {
+                stack.push(children.into_iter());
+            }
+        }
+    }
+
+    (ordered, orphans)
+}
+
+impl<V> Summary<Slot<V>> for Layout {
+    fn record<S: StorageAdaptor>(
+        &mut self,
+        pager: &mut Pager,
+        id: Id,
+        item: Slot<V>,
+    ) -> StoreResult<()> {
+        let visible = item.value.is_some();
+
+        self.stamp = self.stamp.max(item.stamp);
+
+        if let Some(mut placement) = self.placement::<S>(id)? {
+            // Only the value of a slot changes once it has been inserted
+            if placement.origin != item.origin || placement.stamp != item.stamp {
+                _ = self.siblings.remove::<S, _>(&Sibling::of(id, &placement))?;
+
+                placement.origin = item.origin;
+                placement.stamp = item.stamp;
+
+                _ = self
+                    .siblings
+                    .insert::<S>(pager, Sibling::of(id, &placement), ())?;
+
+                self.stale = true;
+            }
+
+            if placement.visible != visible {
+                self.show::<S>(id, visible)?;
+                placement.visible = visible;
+            }
+
+            _ = self.placements.insert::<S>(pager, id, placement)?;
+
+            return Ok(());
+        }
+
+        let sibling = Sibling {
+            origin: item.origin,
+            stamp: item.stamp,
+            id,
+        };
+
+        _ = self.siblings.insert::<S>(pager, sibling, ())?;
+
+        let spot = self.spot_for::<S>(sibling)?;
+
+        let page = self.place::<S>(pager, id, visible, spot)?;
+
+        _ = self.placements.insert::<S>(
+            pager,
+            id,
+            Placement {
+                origin: item.origin,
+                stamp: item.stamp,
+                visible,
+                page,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn record_all<S: StorageAdaptor>(
+        &mut self,
+        pager: &mut Pager,
+        items: Vec<(Id, Slot<V>)>,
+    ) -> StoreResult<()> {
+        // Slots are recorded after their origins, so that each can be placed
+        // directly rather than having to work out the whole order again
+        let ids = items.iter().map(|&(id, _)| id).collect::<BTreeSet<_>>();
+
+        let mut waiting: BTreeMap<Id, Vec<(Id, Slot<V>)>> = BTreeMap::new();
+        let mut ready = vec![];
+
+        for (id, slot) in items {
+            match slot.origin {
+                Some(origin) if ids.contains(&origin) && origin != id => {
+                    waiting.entry(origin).or_default().push((id, slot));
+                }
+                _ => ready.push((id, slot)),
+            }
+        }
+
+        while let Some((id, slot)) = ready.pop() {
+            self.record::<S>(pager, id, slot)?;
+
+            if let Some(children) = waiting.remove(&id) {
+                ready.extend(children);
+            }
+        }
+
+        for (id, slot) in waiting.into_values().flatten() {
+            self.record::<S>(pager, id, slot)?;
+        }
+
+        Ok(())
+    }
+
+    fn forget<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<()> {
+        let Some(placement) = self.placement::<S>(id)? else {
+            return Ok(());
+        };
+
+        self.unplace::<S>(id)?;
+
+        _ = self.placements.remove::<S, _>(&id)?;
+        _ = self.siblings.remove::<S, _>(&Sibling::of(id, &placement))?;
+
+        // Anything inserted after it no longer has a known origin
+        if self.has_children::<S>(id)? {
+            self.stale = true;
+        }
+
+        Ok(())
+    }
+
+    fn refresh<S: StorageAdaptor>(&mut self, pager: &mut Pager) -> StoreResult<()> {
+        if !mem::take(&mut self.stale) {
+            return Ok(());
+        }
+
+        let placements = self
+            .placements
+            .entries::<S>()?
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        let (ordered, orphans) = order(&placements);
+
+        self.orphans = orphans;
+
+        for run in mem::take(&mut self.order.runs) {
+            self.order.cache.remove::<S>(run.id);
+        }
+
+        for ids in ordered.chunks(PAGE_SIZE) {
+            let page = pager.allocate();
+
+            let mut entries = Vec::with_capacity(ids.len());
+
+            for id in ids {
+                let Some(mut placement) = placements.get(id).copied() else {
+                    continue;
+                };
+
+                entries.push((*id, placement.visible));
+
+                placement.page = page;
+
+                _ = self.placements.insert::<S>(pager, *id, placement)?;
+            }
+
+            self.order.runs.push(Run {
+                id: page,
+                visible: entries.iter().filter(|&&(_, shown)| shown).count(),
+            });
+
+            self.order.cache.create(page, entries);
+        }
+
+        Ok(())
+    }
+
+    fn write<S: StorageAdaptor>(&mut self) -> StoreResult<()> {
+        self.placements.write::<S>()?;
+        self.siblings.write::<S>()?;
+        self.order.cache.write::<S>()
+    }
+}
+
+impl<S: StorageAdaptor> Sequence<String, S> {
+    /// Insert a string at a specific character index in the sequence, storing
+    /// each character as a separate item.
+    ///
+    /// # Errors
+    ///
+    /// If the index is greater than the length of the sequence, an error will
+    /// be returned. If an error occurs when interacting with the storage
+    /// system, or a child [`Element`](crate::entities::Element) cannot be
+    /// found, an error will also be returned.
+    ///
+    pub fn insert_str(&mut self, index: usize, text: &str) -> Result<(), StoreError> {
+        let mut position = match index.checked_sub(1) {
+            None => None,
+            Some(prev) => Some(self.position(prev)?.ok_or(StoreError::IndexOutOfBounds {
+                index,
+                len: self.len()?,
+            })?),
+        };
+
+        for ch in text.chars() {
+            position = Some(self.insert_after(position, ch.to_string())?);
+        }
+
+        Ok(())
+    }
+
+    /// Get the contents of the sequence as a string.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn text(&self) -> Result<String, StoreError> {
+        Ok(self.entries()?.collect())
+    }
+}
+
+impl<V, S> Eq for Sequence<V, S>
+where
+    V: Eq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+}
+
+impl<V, S> PartialEq for Sequence<V, S>
+where
+    V: PartialEq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn eq(&self, other: &Self) -> bool {
+        let l = self.entries().unwrap();
+        let r = other.entries().unwrap();
+
+        l.eq(r)
+    }
+}
+
+impl<V, S> Ord for Sequence<V, S>
+where
+    V: Ord + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let l = self.entries().unwrap();
+        let r = other.entries().unwrap();
+
+        l.cmp(r)
+    }
+}
+
+impl<V, S> PartialOrd for Sequence<V, S>
+where
+    V: PartialOrd + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let l = self.entries().ok()?;
+        let r = other.entries().ok()?;
+
+        l.partial_cmp(r)
+    }
+}
+
+impl<V, S> fmt::Debug for Sequence<V, S>
+where
+    V: fmt::Debug + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, clippy::unwrap_in_result, reason = "'tis fine")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("Sequence")
+                .field("entries", &self.inner)
+                .finish()
+        } else {
+            f.debug_list().entries(self.entries().unwrap()).finish()
+        }
+    }
+}
+
+impl<V, S> Default for Sequence<V, S>
+where
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn default() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<V, S> Merge for Sequence<V, S>
+where
+    V: BorshSerialize + BorshDeserialize + Merge,
+    S: StorageAdaptor,
+{
+    fn children() -> Option<Children> {
+        Some(Children::new(merge_data::<Entry<Slot<V>>>, V::children))
+    }
+
+    fn register(&self) {
+        self.inner.register_children(Self::children());
+    }
+}
+
+impl<V, S> Guard for Sequence<V, S>
+where
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn deny(&self) {
+        self.inner.deny();
+    }
+}
+
+impl<V, S> Nested for Sequence<V, S>
+where
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn detached(parent_id: Id) -> Self {
+        Self {
+            inner: Collection::new_detached(parent_id),
+        }
+    }
+
+    fn attach(&mut self, parent_id: Id) -> Result<(), StoreError> {
+        self.inner.attach(parent_id)
+    }
+}
+
+impl<V, S> Serialize for Sequence<V, S>
+where
+    V: BorshSerialize + BorshDeserialize + Serialize,
+    S: StorageAdaptor,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        let len = self.len().map_err(Ser::Error::custom)?;
+
+        let mut seq = serializer.serialize_seq(Some(len))?;
+
+        for entry in self.entries().map_err(Ser::Error::custom)? {
+            seq.serialize_element(&entry)?;
+        }
+
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    use super::Slot;
+    use crate::address::Id;
+    use crate::collections::{Root, Sequence};
+    use crate::store::{Key, MainStorage, StorageAdaptor};
+
+    #[test]
+    fn test_sequence_push_and_get() {
+        let mut seq = Root::new(Sequence::new);
+
+        let _ = seq.push("a".to_owned()).unwrap();
+        let _ = seq.push("b".to_owned()).unwrap();
+
+        assert_eq!(seq.len().unwrap(), 2);
+        assert_eq!(seq.get(0).unwrap().as_deref(), Some("a"));
+        assert_eq!(seq.get(1).unwrap().as_deref(), Some("b"));
+        assert_eq!(seq.get(2).unwrap(), None);
+    }
+
+    #[test]
+    fn test_sequence_insert_in_middle() {
+        let mut seq = Root::new(Sequence::new);
+
+        let _ = seq.push(1).unwrap();
+        let _ = seq.push(3).unwrap();
+        let _ = seq.insert(1, 2).unwrap();
+        let _ = seq.insert(0, 0).unwrap();
+        let _ = seq.insert(4, 4).unwrap();
+
+        assert_eq!(seq.entries().unwrap().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        assert!(seq.insert(6, 6).is_err());
+    }
+
+    #[test]
+    fn test_sequence_positions_are_stable() {
+        let mut seq = Root::new(Sequence::new);
+
+        let a = seq.push(1).unwrap();
+        let c = seq.push(3).unwrap();
+
+        assert_eq!(seq.index_of(c).unwrap(), Some(1));
+
+        let _ = seq.insert_after(Some(a), 2).unwrap();
+
+        assert_eq!(seq.index_of(c).unwrap(), Some(2));
+        assert_eq!(seq.position(0).unwrap(), Some(a));
+
+        let _ = seq.remove(0).unwrap();
+
+        assert_eq!(seq.index_of(a).unwrap(), None);
+        assert_eq!(seq.index_of(c).unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_sequence_insert_after_deleted_position() {
+        let mut seq = Root::new(Sequence::new);
+
+        seq.insert_str(0, "abc").unwrap();
+
+        let b = seq.position(1).unwrap().unwrap();
+
+        assert_eq!(seq.remove(1).unwrap().as_deref(), Some("b"));
+
+        let _ = seq.insert_after(Some(b), "x".to_owned()).unwrap();
+
+        assert_eq!(seq.text().unwrap(), "axc");
+    }
+
+    #[test]
+    fn test_sequence_latest_insert_at_same_origin_comes_first() {
+        let mut seq = Root::new(Sequence::new);
+
+        let _ = seq.insert_after(None, 3).unwrap();
+        let _ = seq.insert_after(None, 2).unwrap();
+        let _ = seq.insert_after(None, 1).unwrap();
+
+        assert_eq!(seq.entries().unwrap().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_sequence_delete_range() {
+        let mut seq = Root::new(Sequence::new);
+
+        seq.insert_str(0, "hello world").unwrap();
+
+        assert_eq!(seq.delete_range(5..).unwrap(), 6);
+        assert_eq!(seq.text().unwrap(), "hello");
+
+        assert_eq!(seq.delete_range(1..=2).unwrap(), 2);
+        assert_eq!(seq.text().unwrap(), "hlo");
+
+        assert_eq!(seq.delete_range(10..20).unwrap(), 0);
+        assert_eq!(seq.len().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_sequence_insert_str() {
+        let mut seq = Root::new(Sequence::new);
+
+        seq.insert_str(0, "held").unwrap();
+        seq.insert_str(3, "lo worl").unwrap();
+
+        assert_eq!(seq.text().unwrap(), "hello world");
+    }
+
+    #[test]
+    fn test_sequence_clear() {
+        let mut seq = Root::new(Sequence::new);
+
+        let _ = seq.push(1).unwrap();
+        let _ = seq.remove(0).unwrap();
+        let _ = seq.push(2).unwrap();
+        seq.clear().unwrap();
+
+        assert!(seq.is_empty().unwrap());
+    }
+
+    #[test]
+    fn test_sequence_clear_leaves_positions_to_insert_after() {
+        let mut seq = Root::new(Sequence::new);
+
+        seq.insert_str(0, "ab").unwrap();
+
+        let b = seq.position(1).unwrap().unwrap();
+
+        seq.clear().unwrap();
+
+        assert!(seq.is_empty().unwrap());
+
+        // An insert made concurrently elsewhere, after a value that was cleared
+        let _inserted = seq
+            .inner
+            .insert(
+                Some(Id::random()),
+                Slot {
+                    origin: Some(b),
+                    stamp: 1,
+                    value: Some("x".to_owned()),
+                },
+            )
+            .unwrap();
+
+        let _ = seq.insert_after(Some(b), "y".to_owned()).unwrap();
+
+        assert_eq!(seq.text().unwrap(), "yx");
+    }
+
+    #[test]
+    fn test_sequence_reads_only_the_slots_needed() {
+        let mut seq = Root::new(Sequence::new);
+
+        seq.insert_str(0, "abc").unwrap();
+
+        let b = seq.position(1).unwrap().unwrap();
+
+        assert!(MainStorage::storage_remove(Key::Entry(b)));
+
+        assert_eq!(seq.len().unwrap(), 3);
+        assert_eq!(seq.index_of(b).unwrap(), Some(1));
+        assert_eq!(seq.get(2).unwrap().as_deref(), Some("c"));
+
+        let _ = seq.insert(3, "d".to_owned()).unwrap();
+
+        assert_eq!(seq.get(3).unwrap().as_deref(), Some("d"));
+        assert!(seq.get(1).is_err());
+    }
+
+    #[test]
+    fn test_sequence_follows_changes_made_elsewhere() {
+        let mut seq = Root::new(Sequence::new);
+
+        seq.insert_str(0, "abc").unwrap();
+
+        let a = seq.position(0).unwrap().unwrap();
+        let b = seq.position(1).unwrap().unwrap();
+
+        // Changes that arrive through synchronisation bypass the sequence
+        let _inserted = seq
+            .inner
+            .insert(
+                Some(Id::random()),
+                Slot {
+                    origin: Some(a),
+                    stamp: 0,
+                    value: Some("x".to_owned()),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(seq.text().unwrap(), "abcx");
+
+        let removed = seq.inner.get_mut(b).unwrap().unwrap().value.take();
+
+        assert_eq!(removed.as_deref(), Some("b"));
+        assert_eq!(seq.text().unwrap(), "acx");
+        assert_eq!(seq.len().unwrap(), 3);
+
+        let _ = seq.insert(1, "y".to_owned()).unwrap();
+
+        assert_eq!(seq.text().unwrap(), "aycx");
+    }
+
+    #[test]
+    fn test_sequence_keeps_many_slots_in_pages() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let mut seq = Root::new(Sequence::new);
+        let mut model: Vec<String> = vec![];
+
+        for n in 0..300 {
+            if model.is_empty() || rng.gen_ratio(3, 4) {
+                let index = rng.gen_range(0..=model.len());
+                let _ = seq.insert(index, n.to_string()).unwrap();
+                model.insert(index, n.to_string());
+            } else {
+                let index = rng.gen_range(0..model.len());
+                assert_eq!(seq.remove(index).unwrap(), Some(model.remove(index)));
+            }
+        }
+
+        assert_eq!(seq.len().unwrap(), model.len());
+        assert_eq!(seq.entries().unwrap().collect::<Vec<_>>(), model);
+
+        for index in (0..model.len()).step_by(17) {
+            let position = seq.position(index).unwrap().unwrap();
+            assert_eq!(seq.index_of(position).unwrap(), Some(index));
+            assert_eq!(seq.get(index).unwrap().as_ref(), model.get(index));
+        }
+
+        assert_eq!(seq.delete_range(50..100).unwrap(), 50);
+        model.drain(50..100).for_each(drop);
+
+        assert_eq!(seq.entries().unwrap().collect::<Vec<_>>(), model);
+    }
+
+    #[test]
+    fn test_sequence_places_slots_that_arrive_in_any_order() {
+        let mut rng = StdRng::seed_from_u64(11);
+
+        // Slots inserted on other nodes, with stamps that often clash
+        let mut slots: Vec<(Id, Option<Id>, u64)> = vec![];
+
+        for _ in 0..200 {
+            let origin = (!slots.is_empty() && rng.gen_ratio(9, 10))
+                .then(|| slots[rng.gen_range(0..slots.len())].0);
+            slots.push((Id::random(), origin, rng.gen_range(0..20)));
+        }
+
+        let mut children: BTreeMap<Option<Id>, Vec<(u64, Id)>> = BTreeMap::new();
+
+        for &(id, origin, stamp) in &slots {
+            children.entry(origin).or_default().push((stamp, id));
+        }
+
+        let mut expected = vec![];
+        let mut stack = vec![None];
+
+        while let Some(next) = stack.pop() {
+            if let Some(origin) = next {
+                expected.push(
+                    slots
+                        .iter()
+                        .position(|slot| slot.0 == origin)
+                        .unwrap()
+                        .to_string(),
+                );
+            }
+
+            if let Some(mut list) = children.remove(&next) {
+                list.sort();
+                stack.extend(list.into_iter().map(|(_, id)| Some(id)));
+            }
+        }
+
+        let mut seq = Root::new(Sequence::new);
+
+        let mut arriving = slots.iter().enumerate().collect::<Vec<_>>();
+        arriving.shuffle(&mut rng);
+
+        for (count, (n, &(id, origin, stamp))) in arriving.into_iter().enumerate() {
+            // Changes that arrive through synchronisation bypass the sequence
+            let _inserted = seq
+                .inner
+                .insert(
+                    Some(id),
+                    Slot {
+                        origin,
+                        stamp,
+                        value: Some(n.to_string()),
+                    },
+                )
+                .unwrap();
+
+            if count % 7 == 0 {
+                let _ = seq.len().unwrap();
+            }
+        }
+
+        assert_eq!(seq.entries().unwrap().collect::<Vec<_>>(), expected);
+
+        let _ = seq.insert(5, "new".to_owned()).unwrap();
+        expected.insert(5, "new".to_owned());
+
+        assert_eq!(seq.entries().unwrap().collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,873 @@
+//! Local summaries that collections keep of their children.
+//!
+//! Some collections need to know something about all of their children before
+//! they can answer even simple questions. A [`Sequence`](super::Sequence), for
+//! instance, has to know the order of its items to find the one at an index.
+//! Loading every child to find this out would cost one storage read per child
+//! for every operation.
+//!
+//! Instead, such a collection keeps a summary of its children. The summary is
+//! derived locally and is not part of the index, so it is never synchronised.
+//! Anything in it that grows with the number of children is kept in [`Pages`],
+//! of up to [`PAGE_SIZE`] entries each, so that an operation only reads and
+//! writes the pages it needs, and no single value grows beyond a page.
+//!
+//! Changes that arrive through synchronisation do not pass through the
+//! collection, though, so the summary records the Merkle hash of the collection
+//! that it matches. Whenever that has moved on, the chunks that the index keeps
+//! the children in are compared with those the summary last saw, and only the
+//! chunks whose digests differ are read again. The summary mirrors the children
+//! in each chunk as it last saw them, so only the children that were added,
+//! changed, or removed are then passed on to it.
+//!
+
+use core::borrow::Borrow;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use std::collections::btree_map::Entry as MapEntry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Result as IoResult, Write};
+use std::vec;
+
+use borsh::{object_length, to_vec, BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
+
+use super::{Collection, RootHandle, StoreResult};
+use crate::address::Id;
+use crate::collections::error::StoreError;
+use crate::entities::{Collection as _, Data};
+use crate::index::Index;
+use crate::interface::StorageError;
+use crate::store::{Key, StorageAdaptor};
+
+/// The most entries kept together in a page, beyond which it is split.
+pub(super) const PAGE_SIZE: usize = 64;
+
+/// The most bytes kept together in a page, beyond which it is split whatever
+/// the number of entries, so that pages of large entries stay well within the
+/// size of a value in storage.
+const PAGE_BYTES: usize = 64 * 1024;
+
+/// Something that a collection derives from its children of type `T`.
+pub(super) trait Summary<T>: BorshSerialize + BorshDeserialize + Default {
+    /// Records a child, which might already have been recorded as it was before
+    /// it changed.
+    fn record<S: StorageAdaptor>(&mut self, pager: &mut Pager, id: Id, item: T) -> StoreResult<()>;
+
+    /// Records a batch of children that changed without going through the
+    /// collection, in no particular order.
+    fn record_all<S: StorageAdaptor>(
+        &mut self,
+        pager: &mut Pager,
+        items: Vec<(Id, T)>,
+    ) -> StoreResult<()> {
+        for (id, item) in items {
+            self.record::<S>(pager, id, item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Forgets a child that is no longer in the collection.
+    fn forget<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<()>;
+
+    /// Brings anything worked out from the recorded children up to date, once a
+    /// batch of changes has been recorded.
+    fn refresh<S: StorageAdaptor>(&mut self, _pager: &mut Pager) -> StoreResult<()> {
+        Ok(())
+    }
+
+    /// Writes the pages of the summary that have changed.
+    fn write<S: StorageAdaptor>(&mut self) -> StoreResult<()>;
+}
+
+/// Hands out the [`Id`]s of the pages of a summary.
+///
+/// These are derived from the collection and a counter, so that the pages can
+/// all be found again when the collection is removed, without knowing what
+/// kind of summary they belong to.
+///
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug)]
+pub(super) struct Pager {
+    /// The [`Id`] of the collection that the summary is kept for.
+    id: Id,
+
+    /// The number from which the [`Id`] of the next page is derived.
+    next: u64,
+}
+
+impl Pager {
+    /// The [`Id`] of one of the pages of the summary of a collection.
+    fn page_id(id: Id, number: u64) -> Id {
+        let mut hasher = Sha256::new();
+        hasher.update(id.as_bytes());
+        hasher.update(number.to_le_bytes());
+        Id::new(hasher.finalize().into())
+    }
+
+    /// Allocates the [`Id`] of a new page.
+    pub(super) fn allocate(&mut self) -> Id {
+        let id = Self::page_id(self.id, self.next);
+        self.next = self.next.saturating_add(1);
+        id
+    }
+}
+
+/// What the summary last saw of one of the chunks of the index.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug)]
+struct Seen {
+    /// The digest of the chunk.
+    digest: [u8; 32],
+
+    /// The page mirroring the children in the chunk, with their hashes.
+    page: Id,
+}
+
+/// A summary, along with what is needed to tell when it is out of date.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub(super) struct Summarised<M> {
+    /// Hands out the pages that the summary is kept in. This comes first, so
+    /// that it can be read without knowing the type of the summary.
+    pub(super) pager: Pager,
+
+    /// The full Merkle hash of the collection when the summary last matched it.
+    hash: [u8; 32],
+
+    /// The chunks of the index that the children were kept in when the summary
+    /// last matched them.
+    chunks: BTreeMap<Id, Seen>,
+
+    /// The summary itself.
+    pub(super) summary: M,
+
+    /// The children recorded through the collection since the summary was
+    /// loaded, which don't need to be read again.
+    #[borsh(skip)]
+    recorded: BTreeSet<Id>,
+}
+
+impl<M> Summarised<M> {
+    /// Starts a new, empty summary for a collection.
+    fn new(id: Id) -> Self
+    where
+        M: Default,
+    {
+        Self {
+            pager: Pager { id, next: 0 },
+            hash: [0; 32],
+            chunks: BTreeMap::new(),
+            summary: M::default(),
+            recorded: BTreeSet::new(),
+        }
+    }
+
+    /// Loads the summary of a collection, bringing it up to date with any
+    /// changes that were made without going through the collection.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub(super) fn load<T, S>(collection: &Collection<T, S>) -> StoreResult<Self>
+    where
+        M: Summary<T>,
+        T: BorshSerialize + BorshDeserialize,
+        S: StorageAdaptor,
+    {
+        let id = collection.id();
+
+        let hash = hash_of::<S>(id)?;
+
+        let mut this = read_value::<Self, S>(Key::Summary(id))?.unwrap_or_else(|| Self::new(id));
+
+        if this.hash == hash {
+            return Ok(this);
+        }
+
+        this.reconcile(collection)?;
+
+        this.hash = hash;
+
+        this.write::<T, S>()?;
+
+        Ok(this)
+    }
+
+    /// Removes the summary of a collection and starts it again, such as once
+    /// the collection has been cleared.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub(super) fn reset<T, S>(collection: &Collection<T, S>) -> StoreResult<()>
+    where
+        M: Summary<T>,
+        T: BorshSerialize + BorshDeserialize,
+        S: StorageAdaptor,
+    {
+        remove::<S>(collection.id());
+
+        Self::new(collection.id()).save(collection)
+    }
+
+    /// Notes that a child was inserted, changed, or removed through the
+    /// collection, once the summary itself has been brought up to date with it.
+    ///
+    /// The change is not saved until [`save()`](Self::save()) is called, which
+    /// allows several changes to be saved at once.
+    ///
+    pub(super) fn changed(&mut self, id: Id) {
+        let _ignored = self.recorded.insert(id);
+    }
+
+    /// Saves the summary after changes made through the collection.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub(super) fn save<T, S>(&mut self, collection: &Collection<T, S>) -> StoreResult<()>
+    where
+        M: Summary<T>,
+        T: BorshSerialize + BorshDeserialize,
+        S: StorageAdaptor,
+    {
+        // The mirrors of the chunks that changed still have to be updated
+        self.reconcile(collection)?;
+
+        self.hash = hash_of::<S>(collection.id())?;
+
+        self.write::<T, S>()
+    }
+
+    /// Brings the summary up to date with the children of the collection.
+    ///
+    /// Only the chunks of the index whose digests differ from those last seen
+    /// are read, and only the children in them that have changed, and weren't
+    /// recorded through the collection, are passed on to the summary.
+    ///
+    fn reconcile<T, S>(&mut self, collection: &Collection<T, S>) -> StoreResult<()>
+    where
+        M: Summary<T>,
+        T: BorshSerialize + BorshDeserialize,
+        S: StorageAdaptor,
+    {
+        let mut unseen = mem::take(&mut self.chunks);
+
+        let mut listed = BTreeMap::new();
+        let mut unlisted = BTreeMap::new();
+
+        let chunks = <Index<S>>::get_child_chunks_of(collection.id(), RootHandle.name())?;
+
+        for (chunk, digest) in chunks {
+            let previous = unseen.remove(&chunk);
+
+            if let Some(kept) = previous.filter(|seen| seen.digest == digest) {
+                let _ignored = self.chunks.insert(chunk, kept);
+                continue;
+            }
+
+            let children = <Index<S>>::get_child_chunk(chunk)?
+                .into_iter()
+                .map(|child| (child.id(), child.merkle_hash()))
+                .collect::<Vec<_>>();
+
+            let page = match previous {
+                Some(seen) => {
+                    unlisted.extend(read_page::<(Id, [u8; 32]), S>(seen.page)?);
+                    seen.page
+                }
+                None => self.pager.allocate(),
+            };
+
+            write_value::<S>(Key::Summary(page), &children)?;
+
+            listed.extend(children);
+
+            let _ignored = self.chunks.insert(chunk, Seen { digest, page });
+        }
+
+        for seen in unseen.into_values() {
+            unlisted.extend(read_page::<(Id, [u8; 32]), S>(seen.page)?);
+
+            _ = S::storage_remove(Key::Summary(seen.page));
+        }
+
+        // Children only moved to another chunk, when one was split, are the
+        // same as they were
+        listed.retain(|id, hash| unlisted.remove(id) != Some(*hash));
+
+        for id in unlisted.into_keys() {
+            if !self.recorded.contains(&id) {
+                self.summary.forget::<S>(id)?;
+            }
+        }
+
+        let mut items = vec![];
+
+        for id in listed.into_keys() {
+            if self.recorded.contains(&id) {
+                continue;
+            }
+
+            let item = collection
+                .get(id)?
+                .ok_or(StoreError::StorageError(StorageError::NotFound(id)))?;
+
+            items.push((id, item));
+        }
+
+        self.summary.record_all::<S>(&mut self.pager, items)?;
+
+        self.summary.refresh::<S>(&mut self.pager)
+    }
+
+    /// Writes the summary to storage, along with any of its pages that have
+    /// changed.
+    fn write<T, S>(&mut self) -> StoreResult<()>
+    where
+        M: Summary<T>,
+        S: StorageAdaptor,
+    {
+        self.summary.write::<S>()?;
+
+        write_value::<S>(Key::Summary(self.pager.id), self)
+    }
+}
+
+/// Removes the summary kept of the children of a collection, along with all of
+/// its pages.
+///
+/// # Parameters
+///
+/// * `id` - The [`Id`] of the collection.
+///
+#[expect(clippy::redundant_pub_crate, reason = "Used by the index")]
+pub(crate) fn remove<S: StorageAdaptor>(id: Id) {
+    if let Some(data) = S::storage_read(Key::Summary(id)) {
+        if let Ok(pager) = Pager::deserialize(&mut data.as_slice()) {
+            for number in 0..pager.next {
+                _ = S::storage_remove(Key::Summary(Pager::page_id(id, number)));
+            }
+        }
+    }
+
+    _ = S::storage_remove(Key::Summary(id));
+}
+
+/// The pages of a summary that have been read, so that each is read at most
+/// once, and only written again if it has changed.
+pub(super) struct Cache<E> {
+    /// The pages read so far, and whether each has changed since.
+    pages: BTreeMap<Id, (Vec<E>, bool)>,
+}
+
+impl<E> Default for Cache<E> {
+    fn default() -> Self {
+        Self {
+            pages: BTreeMap::new(),
+        }
+    }
+}
+
+impl<E: BorshSerialize + BorshDeserialize> Cache<E> {
+    /// Gets the entries of a page, reading it if it hasn't been read yet.
+    fn page<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<&mut (Vec<E>, bool)> {
+        Ok(match self.pages.entry(id) {
+            MapEntry::Occupied(page) => page.into_mut(),
+            MapEntry::Vacant(page) => page.insert((read_page::<E, S>(id)?, false)),
+        })
+    }
+
+    /// Gets the entries of a page to read them.
+    pub(super) fn get<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<&[E]> {
+        Ok(&self.page::<S>(id)?.0)
+    }
+
+    /// Gets the entries of a page to change them.
+    pub(super) fn get_mut<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<&mut Vec<E>> {
+        let &mut (ref mut entries, ref mut changed) = self.page::<S>(id)?;
+
+        *changed = true;
+
+        Ok(entries)
+    }
+
+    /// Adds a new page.
+    pub(super) fn create(&mut self, id: Id, entries: Vec<E>) {
+        let _ignored = self.pages.insert(id, (entries, true));
+    }
+
+    /// Takes the entries of a page out of the cache, reading it if it hasn't
+    /// been read yet.
+    pub(super) fn take<S: StorageAdaptor>(&mut self, id: Id) -> StoreResult<Vec<E>> {
+        match self.pages.remove(&id) {
+            Some((entries, _)) => Ok(entries),
+            None => read_page::<E, S>(id),
+        }
+    }
+
+    /// Removes a page from storage.
+    pub(super) fn remove<S: StorageAdaptor>(&mut self, id: Id) {
+        let _ignored = self.pages.remove(&id);
+
+        _ = S::storage_remove(Key::Summary(id));
+    }
+
+    /// Writes the pages that have changed.
+    pub(super) fn write<S: StorageAdaptor>(&mut self) -> StoreResult<()> {
+        for (id, &mut (ref entries, ref mut changed)) in &mut self.pages {
+            if mem::take(changed) {
+                write_value::<S>(Key::Summary(*id), entries)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A map kept as part of a summary, in pages of up to [`PAGE_SIZE`] entries
+/// ordered by key, so that an entry can be found by reading a single page.
+///
+/// Only the list of pages, with the first key of each, is kept with the
+/// summary itself.
+///
+pub(super) struct Pages<K, V> {
+    /// The pages, in the order of the keys they hold, each with the lowest key
+    /// that belongs in it. Lower keys all belong in the first page.
+    pages: Vec<(Id, Option<K>)>,
+
+    /// The pages read so far.
+    cache: Cache<(K, V)>,
+}
+
+impl<K, V> Default for Pages<K, V> {
+    fn default() -> Self {
+        Self {
+            pages: Vec::new(),
+            cache: Cache::default(),
+        }
+    }
+}
+
+impl<K: BorshSerialize, V> BorshSerialize for Pages<K, V> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.pages.serialize(writer)
+    }
+}
+
+impl<K: BorshDeserialize, V> BorshDeserialize for Pages<K, V> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        Ok(Self {
+            pages: BorshDeserialize::deserialize_reader(reader)?,
+            cache: Cache::default(),
+        })
+    }
+}
+
+impl<K, V> Pages<K, V>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    V: BorshSerialize + BorshDeserialize,
+{
+    /// Which page the given key belongs in.
+    fn page_of<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.pages
+            .partition_point(|page| page.1.as_ref().is_none_or(|first| first.borrow() <= key))
+            .saturating_sub(1)
+    }
+
+    /// Gets the value for a key.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub(super) fn get<S, Q>(&mut self, key: &Q) -> StoreResult<Option<&V>>
+    where
+        S: StorageAdaptor,
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let Some(&(id, _)) = self.pages.get(self.page_of(key)) else {
+            return Ok(None);
+        };
+
+        let entries = self.cache.get::<S>(id)?;
+
+        Ok(entries
+            .binary_search_by(|entry| entry.0.borrow().cmp(key))
+            .ok()
+            .and_then(|slot| entries.get(slot))
+            .map(|entry| &entry.1))
+    }
+
+    /// Gets the first key after the given one, whether or not the given one is
+    /// present.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub(super) fn next_after<S: StorageAdaptor>(&mut self, key: &K) -> StoreResult<Option<&K>> {
+        let at = self.page_of(key);
+
+        let Some(&(id, _)) = self.pages.get(at) else {
+            return Ok(None);
+        };
+
+        let slot = self
+            .cache
+            .get::<S>(id)?
+            .partition_point(|entry| entry.0 <= *key);
+
+        // Pages are never left empty, so the next page starts with the next key
+        let (page, index) = if slot < self.cache.get::<S>(id)?.len() {
+            (id, slot)
+        } else if let Some(&(next, _)) = self.pages.get(at.saturating_add(1)) {
+            (next, 0)
+        } else {
+            return Ok(None);
+        };
+
+        Ok(self.cache.get::<S>(page)?.get(index).map(|entry| &entry.0))
+    }
+
+    /// Inserts a value for a key, returning the value it replaced, if any.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub(super) fn insert<S: StorageAdaptor>(
+        &mut self,
+        pager: &mut Pager,
+        key: K,
+        value: V,
+    ) -> StoreResult<Option<V>> {
+        if self.pages.is_empty() {
+            let id = pager.allocate();
+            self.cache.create(id, vec![]);
+            self.pages.push((id, None));
+        }
+
+        let at = self.page_of(&key);
+
+        let Some(&(id, _)) = self.pages.get(at) else {
+            return Ok(None);
+        };
+
+        let entries = self.cache.get_mut::<S>(id)?;
+
+        match entries.binary_search_by(|entry| entry.0.cmp(&key)) {
+            Ok(slot) => {
+                return Ok(entries
+                    .get_mut(slot)
+                    .map(|entry| mem::replace(&mut entry.1, value)));
+            }
+            Err(slot) => entries.insert(slot, (key, value)),
+        }
+
+        if oversized(entries)? {
+            let tail = entries.split_off(entries.len().div_ceil(2));
+
+            let first = tail.first().map(|entry| duplicate(&entry.0)).transpose()?;
+
+            let tail_id = pager.allocate();
+            self.cache.create(tail_id, tail);
+            self.pages.insert(at.saturating_add(1), (tail_id, first));
+        }
+
+        Ok(None)
+    }
+
+    /// Removes the value for a key, returning it if it was present.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub(super) fn remove<S, Q>(&mut self, key: &Q) -> StoreResult<Option<V>>
+    where
+        S: StorageAdaptor,
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let at = self.page_of(key);
+
+        let Some(&(id, _)) = self.pages.get(at) else {
+            return Ok(None);
+        };
+
+        let Ok(slot) = self
+            .cache
+            .get::<S>(id)?
+            .binary_search_by(|entry| entry.0.borrow().cmp(key))
+        else {
+            return Ok(None);
+        };
+
+        let entries = self.cache.get_mut::<S>(id)?;
+
+        let (_, value) = entries.remove(slot);
+
+        if entries.is_empty() {
+            let _ignored = self.pages.remove(at);
+            self.cache.remove::<S>(id);
+        }
+
+        Ok(Some(value))
+    }
+
+    /// Takes every entry, in order, reading all of the pages.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub(super) fn entries<S: StorageAdaptor>(&mut self) -> StoreResult<Vec<(K, V)>>
+    where
+        K: Copy,
+        V: Copy,
+    {
+        let mut entries = vec![];
+
+        for &(id, _) in &self.pages {
+            entries.extend_from_slice(self.cache.get::<S>(id)?);
+        }
+
+        Ok(entries)
+    }
+
+    /// Gets an iterator over the entries whose keys fall within the given
+    /// range, in order.
+    ///
+    /// Only the pages at either end of the range are read straight away, as
+    /// those are the ones that may hold keys outside of it. The pages between
+    /// them are each read once the iterator reaches them.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub(super) fn into_range<S, Q, R>(mut self, range: R) -> StoreResult<PageRange<K, V, S>>
+    where
+        S: StorageAdaptor,
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) | Bound::Excluded(key) => self.page_of(key),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(key) | Bound::Excluded(key) => self.page_of(key).saturating_add(1),
+            Bound::Unbounded => self.pages.len(),
+        };
+
+        let mut pages = self
+            .pages
+            .iter()
+            .take(end)
+            .skip(start)
+            .map(|&(id, _)| id)
+            .collect::<Vec<_>>();
+
+        let mut within = |page: Option<Id>| -> StoreResult<Vec<StoreResult<(K, V)>>> {
+            let Some(id) = page else {
+                return Ok(vec![]);
+            };
+
+            Ok(self
+                .cache
+                .take::<S>(id)?
+                .into_iter()
+                .filter(|entry| range.contains(entry.0.borrow()))
+                .map(Ok)
+                .collect())
+        };
+
+        let back = if pages.len() > 1 {
+            within(pages.pop())?
+        } else {
+            vec![]
+        };
+
+        let front = if pages.is_empty() {
+            vec![]
+        } else {
+            within(Some(pages.remove(0)))?
+        };
+
+        Ok(PageRange {
+            front: front.into_iter(),
+            pages: pages.into_iter(),
+            back: back.into_iter(),
+            cache: self.cache,
+            storage: PhantomData,
+        })
+    }
+
+    /// Writes the pages that have changed.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub(super) fn write<S: StorageAdaptor>(&mut self) -> StoreResult<()> {
+        self.cache.write::<S>()
+    }
+}
+
+/// An iterator over the entries of [`Pages`] within a range, reading each page
+/// only once the iterator reaches it.
+pub(super) struct PageRange<K, V, S> {
+    /// The entries not yet taken from the front.
+    front: vec::IntoIter<StoreResult<(K, V)>>,
+
+    /// The pages between the front and the back, not yet read.
+    pages: vec::IntoIter<Id>,
+
+    /// The entries not yet taken from the back.
+    back: vec::IntoIter<StoreResult<(K, V)>>,
+
+    /// The pages already read.
+    cache: Cache<(K, V)>,
+
+    /// The storage that the pages are read from.
+    storage: PhantomData<fn() -> S>,
+}
+
+impl<K, V, S> PageRange<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    /// Reads the entries of a page.
+    fn read(&mut self, id: Id) -> vec::IntoIter<StoreResult<(K, V)>> {
+        match self.cache.take::<S>(id) {
+            Ok(entries) => entries.into_iter().map(Ok).collect::<Vec<_>>(),
+            Err(err) => vec![Err(err)],
+        }
+        .into_iter()
+    }
+}
+
+impl<K, V, S> Iterator for PageRange<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    type Item = StoreResult<(K, V)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.front.next() {
+                return Some(entry);
+            }
+
+            let Some(id) = self.pages.next() else {
+                return self.back.next();
+            };
+
+            self.front = self.read(id);
+        }
+    }
+}
+
+impl<K, V, S> DoubleEndedIterator for PageRange<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.back.next_back() {
+                return Some(entry);
+            }
+
+            let Some(id) = self.pages.next_back() else {
+                return self.front.next_back();
+            };
+
+            self.back = self.read(id);
+        }
+    }
+}
+
+/// Whether a page has grown too large, and has to be split.
+///
+/// # Errors
+///
+/// If the entries cannot be serialised, an error will be returned.
+///
+pub(super) fn oversized<E: BorshSerialize>(entries: &[E]) -> StoreResult<bool> {
+    if entries.len() > PAGE_SIZE {
+        return Ok(true);
+    }
+
+    Ok(entries.len() > 1
+        && object_length(entries).map_err(StorageError::SerializationError)? > PAGE_BYTES)
+}
+
+/// Copies a value by way of its serialised form, for types that can't
+/// otherwise be cloned.
+fn duplicate<T: BorshSerialize + BorshDeserialize>(value: &T) -> StoreResult<T> {
+    let data = to_vec(value).map_err(StorageError::SerializationError)?;
+
+    Ok(T::try_from_slice(&data).map_err(StorageError::DeserializationError)?)
+}
+
+/// Reads and deserialises a value from storage.
+fn read_value<T: BorshDeserialize, S: StorageAdaptor>(key: Key) -> StoreResult<Option<T>> {
+    Ok(S::storage_read(key)
+        .map(|data| T::try_from_slice(&data))
+        .transpose()
+        .map_err(StorageError::DeserializationError)?)
+}
+
+/// Reads the entries of a page.
+fn read_page<E: BorshDeserialize, S: StorageAdaptor>(id: Id) -> StoreResult<Vec<E>> {
+    read_value::<Vec<E>, S>(Key::Summary(id))?
+        .ok_or(StoreError::StorageError(StorageError::NotFound(id)))
+}
+
+/// Serialises and writes a value to storage.
+fn write_value<S: StorageAdaptor>(key: Key, value: &impl BorshSerialize) -> StoreResult<()> {
+    let data = to_vec(value).map_err(StorageError::SerializationError)?;
+
+    // This reports whether a value was replaced, rather than any failure
+    _ = S::storage_write(key, &data);
+
+    Ok(())
+}
+
+/// Gets the full Merkle hash of an entity.
+fn hash_of<S: StorageAdaptor>(id: Id) -> StoreResult<[u8; 32]> {
+    let (full_hash, _) = <Index<S>>::get_hashes_for(id)?.ok_or(StorageError::IndexNotFound(id))?;
+
+    Ok(full_hash)
+}
@@ -9,10 +9,12 @@ use serde::ser::SerializeMap;
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
-use super::{Collection, StorageAdaptor};
+use super::{Collection, Cursor, Entry, Nested, Page, StorageAdaptor};
 use crate::address::Id;
 use crate::collections::error::StoreError;
 use crate::entities::Data;
+use crate::guard::Guard;
+use crate::merge::{merge_data, Children, Merge};
 use crate::store::MainStorage;
 
 /// A map collection that stores key-value pairs.
@@ -79,6 +81,37 @@ where
         Ok(None)
     }
 
+    /// Get the nested collection for a key in the map, inserting an empty one
+    /// if the key does not exist.
+    ///
+    /// The returned collection is a handle to the stored one, so changes made
+    /// through it are persisted directly, without needing to insert the value
+    /// again. See [`Nested`] for how nested collections are synchronised.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn get_or_insert_nested(&mut self, key: K) -> Result<V, StoreError>
+    where
+        K: AsRef<[u8]>,
+        V: Nested,
+    {
+        let id = self.compute_id(key.as_ref());
+
+        if let Some((_, value)) = self.inner.get(id)? {
+            return Ok(value);
+        }
+
+        let (_, mut value) = self.inner.insert(Some(id), (key, V::detached(id)))?;
+
+        value.attach(id)?;
+
+        Ok(value)
+    }
+
     /// Get an iterator over the entries in the map.
     ///
     /// # Errors
@@ -91,6 +124,26 @@ where
         Ok(self.inner.entries()?.flatten().fuse())
     }
 
+    /// Get a page of entries from the map.
+    ///
+    /// Unlike [`entries()`](UnorderedMap::entries()), only the entries on the requested page
+    /// are loaded. Pass [`None`] as the cursor to get the first page, and then
+    /// the [`next`](Page::next) cursor of each page to get the one after it.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned. A `limit` of zero is rejected with [`StoreError::EmptyPage`].
+    ///
+    pub fn iter_page(
+        &self,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Result<Page<(K, V)>, StoreError> {
+        self.inner.iter_page(cursor, limit)
+    }
+
     /// Get the number of entries in the map.
     ///
     /// # Errors
@@ -253,6 +306,49 @@ where
     }
 }
 
+impl<K, V, S> Merge for UnorderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize + Merge,
+    S: StorageAdaptor,
+{
+    fn children() -> Option<Children> {
+        Some(Children::new(merge_data::<Entry<(K, V)>>, V::children))
+    }
+
+    fn register(&self) {
+        self.inner.register_children(Self::children());
+    }
+}
+
+impl<K, V, S> Guard for UnorderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn deny(&self) {
+        self.inner.deny();
+    }
+}
+
+impl<K, V, S> Nested for UnorderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn detached(parent_id: Id) -> Self {
+        Self {
+            inner: Collection::new_detached(parent_id),
+        }
+    }
+
+    fn attach(&mut self, parent_id: Id) -> Result<(), StoreError> {
+        self.inner.attach(parent_id)
+    }
+}
+
 impl<K, V, S> Serialize for UnorderedMap<K, V, S>
 where
     K: BorshSerialize + BorshDeserialize + Serialize,
@@ -277,7 +373,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::collections::{Root, UnorderedMap};
+    use crate::collections::{Root, StoreError, UnorderedMap};
 
     #[test]
     fn test_unordered_map_basic_operations() {
@@ -470,4 +566,107 @@ mod tests {
         assert!(entries.contains(&("key1".to_string(), "value1".to_string())));
         assert!(entries.contains(&("key2".to_string(), "value3".to_string())));
     }
+
+    #[test]
+    fn test_unordered_map_iter_page() {
+        let mut map = Root::new(|| UnorderedMap::new());
+
+        for i in 0..5 {
+            assert!(map
+                .insert(format!("key{i}"), format!("value{i}"))
+                .expect("insert failed")
+                .is_none());
+        }
+
+        let first = map.iter_page(None, 2).expect("iter_page failed");
+        assert_eq!(first.items.len(), 2);
+        assert!(first.next.is_some());
+
+        let second = map.iter_page(first.next, 2).expect("iter_page failed");
+        assert_eq!(second.items.len(), 2);
+        assert!(second.next.is_some());
+
+        let third = map.iter_page(second.next, 2).expect("iter_page failed");
+        assert_eq!(third.items.len(), 1);
+        assert!(third.next.is_none());
+
+        let mut paged: Vec<_> = [first.items, second.items, third.items].concat();
+        let mut entries: Vec<_> = map.entries().expect("entries failed").collect();
+        paged.sort();
+        entries.sort();
+        assert_eq!(paged, entries);
+
+        let reloaded: UnorderedMap<String, String> =
+            borsh::from_slice(&borsh::to_vec(&*map).expect("serialize failed"))
+                .expect("deserialize failed");
+        assert_eq!(reloaded.len().expect("len failed"), 5);
+    }
+
+    #[test]
+    fn test_unordered_map_iter_page_of_nothing() {
+        let mut map = Root::new(|| UnorderedMap::new());
+
+        assert!(map
+            .insert("key".to_owned(), "value".to_owned())
+            .expect("insert failed")
+            .is_none());
+
+        let first = map.iter_page(None, 1).expect("iter_page failed");
+
+        assert!(matches!(
+            map.iter_page(first.next, 0),
+            Err(StoreError::EmptyPage)
+        ));
+    }
+
+    #[test]
+    fn test_unordered_map_nested() {
+        let mut map = Root::new(|| UnorderedMap::<String, UnorderedMap<String, String>>::new());
+
+        let mut inner = map
+            .get_or_insert_nested("outer".to_owned())
+            .expect("insert failed");
+
+        assert!(inner
+            .insert("a".to_owned(), "1".to_owned())
+            .expect("insert failed")
+            .is_none());
+
+        let mut again = map
+            .get_or_insert_nested("outer".to_owned())
+            .expect("get failed");
+
+        assert!(again
+            .insert("b".to_owned(), "2".to_owned())
+            .expect("insert failed")
+            .is_none());
+
+        let inner = map.get("outer").expect("get failed").expect("missing");
+
+        assert_eq!(inner.len().expect("len failed"), 2);
+        assert_eq!(inner.get("a").expect("get failed").as_deref(), Some("1"));
+        assert_eq!(inner.get("b").expect("get failed").as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_unordered_map_nested_remove() {
+        let mut map = Root::new(|| UnorderedMap::<String, UnorderedMap<String, String>>::new());
+
+        let mut inner = map
+            .get_or_insert_nested("outer".to_owned())
+            .expect("insert failed");
+
+        let _ignored = inner
+            .insert("a".to_owned(), "1".to_owned())
+            .expect("insert failed");
+
+        assert!(map.remove("outer").expect("remove failed").is_some());
+
+        let inner = map
+            .get_or_insert_nested("outer".to_owned())
+            .expect("insert failed");
+
+        assert_eq!(inner.len().expect("len failed"), 0);
+        assert_eq!(inner.get("a").expect("get failed"), None);
+    }
 }
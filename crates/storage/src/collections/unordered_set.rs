@@ -3,15 +3,18 @@
 use core::borrow::Borrow;
 use core::fmt;
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{to_vec, BorshDeserialize, BorshSerialize};
 use serde::ser::SerializeSeq;
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
-use super::Collection;
+use super::{Collection, Cursor, Entry, Nested, Page};
 use crate::address::Id;
 use crate::collections::error::StoreError;
 use crate::entities::Data;
+use crate::guard::Guard;
+use crate::interface::StorageError;
+use crate::merge::{merge_data, Children, Merge};
 use crate::store::{MainStorage, StorageAdaptor};
 
 /// A set collection that stores unqiue values once.
@@ -74,6 +77,47 @@ where
         Ok(true)
     }
 
+    /// Compute the ID for a nested collection in the set.
+    ///
+    /// A nested collection is identified by the collection itself rather than
+    /// by its contents, which change as it is used.
+    ///
+    fn compute_nested_id(&self, value: &V) -> Result<Id, StoreError> {
+        let data = to_vec(value).map_err(StorageError::SerializationError)?;
+
+        Ok(self.compute_id(&data))
+    }
+
+    /// Insert a new, empty nested collection into the set, and return it.
+    ///
+    /// Every nested collection inserted is a distinct element of the set. The
+    /// returned collection is a handle to the stored one, so changes made
+    /// through it are persisted directly, and it can be passed to
+    /// [`contains_nested()`](UnorderedSet::contains_nested()) or
+    /// [`remove_nested()`](UnorderedSet::remove_nested()) later. See [`Nested`]
+    /// for how nested collections are synchronised.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn insert_nested(&mut self) -> Result<V, StoreError>
+    where
+        V: Nested,
+    {
+        let detached = V::detached(Id::random());
+
+        let id = self.compute_nested_id(&detached)?;
+
+        let mut value = self.inner.insert(Some(id), detached)?;
+
+        value.attach(id)?;
+
+        Ok(value)
+    }
+
     /// Get an iterator over the entries in the set.
     ///
     /// # Errors
@@ -86,6 +130,22 @@ where
         Ok(self.inner.entries()?.flatten().fuse())
     }
 
+    /// Get a page of entries from the set.
+    ///
+    /// Unlike [`entries()`](UnorderedSet::entries()), only the entries on the requested page
+    /// are loaded. Pass [`None`] as the cursor to get the first page, and then
+    /// the [`next`](Page::next) cursor of each page to get the one after it.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned. A `limit` of zero is rejected with [`StoreError::EmptyPage`].
+    ///
+    pub fn iter_page(&self, cursor: Option<Cursor>, limit: usize) -> Result<Page<V>, StoreError> {
+        self.inner.iter_page(cursor, limit)
+    }
+
     /// Get the number of entries in the set.
     ///
     /// # Errors
@@ -116,6 +176,23 @@ where
         Ok(self.inner.get(id)?.is_some())
     }
 
+    /// Check whether a nested collection is in the set.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn contains_nested(&self, value: &V) -> Result<bool, StoreError>
+    where
+        V: Nested,
+    {
+        let id = self.compute_nested_id(value)?;
+
+        Ok(self.inner.get(id)?.is_some())
+    }
+
     /// Remove a key from the set, returning the value at the key if it previously existed.
     ///
     /// # Errors
@@ -140,6 +217,29 @@ where
         Ok(true)
     }
 
+    /// Remove a nested collection from the set, along with everything in it.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn remove_nested(&mut self, value: &V) -> Result<bool, StoreError>
+    where
+        V: Nested,
+    {
+        let id = self.compute_nested_id(value)?;
+
+        let Some(entry) = self.inner.get_mut(id)? else {
+            return Ok(false);
+        };
+
+        let _ignored = entry.remove()?;
+
+        Ok(true)
+    }
+
     /// Clear the set, removing all entries.
     ///
     /// # Errors
@@ -218,6 +318,46 @@ where
     }
 }
 
+impl<V, S> Merge for UnorderedSet<V, S>
+where
+    V: BorshSerialize + BorshDeserialize + Merge,
+    S: StorageAdaptor,
+{
+    fn children() -> Option<Children> {
+        Some(Children::new(merge_data::<Entry<V>>, V::children))
+    }
+
+    fn register(&self) {
+        self.inner.register_children(Self::children());
+    }
+}
+
+impl<V, S> Guard for UnorderedSet<V, S>
+where
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn deny(&self) {
+        self.inner.deny();
+    }
+}
+
+impl<V, S> Nested for UnorderedSet<V, S>
+where
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn detached(parent_id: Id) -> Self {
+        Self {
+            inner: Collection::new_detached(parent_id),
+        }
+    }
+
+    fn attach(&mut self, parent_id: Id) -> Result<(), StoreError> {
+        self.inner.attach(parent_id)
+    }
+}
+
 impl<V, S> Serialize for UnorderedSet<V, S>
 where
     V: BorshSerialize + BorshDeserialize + Serialize,
@@ -241,7 +381,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::collections::{Root, UnorderedSet};
+    use crate::collections::{Root, UnorderedSet, Vector};
 
     #[test]
     fn test_unordered_set_operations() {
@@ -319,4 +459,42 @@ mod tests {
         let entries: Vec<String> = set.entries().expect("entries failed").collect();
         assert_eq!(entries.len(), 1);
     }
+
+    #[test]
+    fn test_unordered_set_nested() {
+        let mut set = Root::new(|| UnorderedSet::<Vector<String>>::new());
+
+        let mut first = set.insert_nested().expect("insert failed");
+        first.push("a".to_owned()).expect("push failed");
+
+        let second = set.insert_nested().expect("insert failed");
+
+        assert_eq!(set.len().expect("len failed"), 2);
+        assert!(set.contains_nested(&first).expect("contains failed"));
+        assert!(set.contains_nested(&second).expect("contains failed"));
+
+        let lengths: Vec<usize> = set
+            .entries()
+            .expect("entries failed")
+            .map(|inner| inner.len().expect("len failed"))
+            .collect();
+
+        assert_eq!(lengths.len(), 2);
+        assert!(lengths.contains(&1));
+        assert!(lengths.contains(&0));
+    }
+
+    #[test]
+    fn test_unordered_set_nested_remove() {
+        let mut set = Root::new(|| UnorderedSet::<Vector<String>>::new());
+
+        let mut inner = set.insert_nested().expect("insert failed");
+        inner.push("a".to_owned()).expect("push failed");
+
+        assert!(set.remove_nested(&inner).expect("remove failed"));
+        assert!(!set.remove_nested(&inner).expect("remove failed"));
+
+        assert!(!set.contains_nested(&inner).expect("contains failed"));
+        assert_eq!(set.len().expect("len failed"), 0);
+    }
 }
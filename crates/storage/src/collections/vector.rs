@@ -8,8 +8,11 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::ser::SerializeSeq;
 use serde::Serialize;
 
-use super::Collection;
+use super::{Collection, Cursor, Entry, Nested, Page};
+use crate::address::Id;
 use crate::collections::error::StoreError;
+use crate::guard::Guard;
+use crate::merge::{merge_data, Children, Merge};
 use crate::store::{MainStorage, StorageAdaptor};
 
 /// A vector collection that stores key-value pairs.
@@ -56,6 +59,31 @@ where
         Ok(())
     }
 
+    /// Add an empty nested collection to the end of the vector, and return it.
+    ///
+    /// The returned collection is a handle to the stored one, so changes made
+    /// through it are persisted directly. See [`Nested`] for how nested
+    /// collections are synchronised.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn push_nested(&mut self) -> Result<V, StoreError>
+    where
+        V: Nested,
+    {
+        let id = Id::random();
+
+        let mut value = self.inner.insert(Some(id), V::detached(id))?;
+
+        value.attach(id)?;
+
+        Ok(value)
+    }
+
     /// Remove and return the last value from the vector.
     ///
     /// # Errors
@@ -124,6 +152,22 @@ where
         Ok(self.inner.entries()?.flatten().fuse())
     }
 
+    /// Get a page of entries from the vector.
+    ///
+    /// Unlike [`entries()`](Vector::entries()), only the entries on the requested page
+    /// are loaded. Pass [`None`] as the cursor to get the first page, and then
+    /// the [`next`](Page::next) cursor of each page to get the one after it.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned. A `limit` of zero is rejected with [`StoreError::EmptyPage`].
+    ///
+    pub fn iter_page(&self, cursor: Option<Cursor>, limit: usize) -> Result<Page<V>, StoreError> {
+        self.inner.iter_page(cursor, limit)
+    }
+
     /// Get the last value in the vector.
     ///
     /// # Errors
@@ -254,6 +298,46 @@ where
     }
 }
 
+impl<V, S> Merge for Vector<V, S>
+where
+    V: BorshSerialize + BorshDeserialize + Merge,
+    S: StorageAdaptor,
+{
+    fn children() -> Option<Children> {
+        Some(Children::new(merge_data::<Entry<V>>, V::children))
+    }
+
+    fn register(&self) {
+        self.inner.register_children(Self::children());
+    }
+}
+
+impl<V, S> Guard for Vector<V, S>
+where
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn deny(&self) {
+        self.inner.deny();
+    }
+}
+
+impl<V, S> Nested for Vector<V, S>
+where
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn detached(parent_id: Id) -> Self {
+        Self {
+            inner: Collection::new_detached(parent_id),
+        }
+    }
+
+    fn attach(&mut self, parent_id: Id) -> Result<(), StoreError> {
+        self.inner.attach(parent_id)
+    }
+}
+
 impl<V, S> Serialize for Vector<V, S>
 where
     V: BorshSerialize + BorshDeserialize + Serialize,
@@ -365,4 +449,41 @@ mod tests {
         vector.clear().unwrap();
         assert_eq!(vector.len().unwrap(), 0);
     }
+
+    #[test]
+    fn test_vector_iter_page() {
+        let mut vector = Root::new(|| Vector::new());
+
+        for i in 0..5 {
+            vector.push(format!("value{i}")).unwrap();
+        }
+
+        let mut cursor = None;
+        let mut paged = Vec::new();
+
+        loop {
+            let page = vector.iter_page(cursor, 2).unwrap();
+            assert!(page.items.len() <= 2);
+            paged.extend(page.items);
+
+            cursor = page.next;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(paged, vector.entries().unwrap().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_vector_push_nested() {
+        let mut vector = Root::new(|| Vector::<Vector<String>>::new());
+
+        let mut inner = vector.push_nested().unwrap();
+        inner.push("test_data".to_owned()).unwrap();
+
+        let inner = vector.get(0).unwrap().unwrap();
+        assert_eq!(inner.len().unwrap(), 1);
+        assert_eq!(inner.get(0).unwrap().as_deref(), Some("test_data"));
+    }
 }
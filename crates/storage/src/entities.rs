@@ -454,6 +454,46 @@ impl ChildInfo {
     }
 }
 
+/// A position among the children of an entity, from which iteration can be
+/// resumed.
+///
+/// Children are ordered by creation time and then by [`Id`], so a cursor
+/// remains valid even if the child it was taken from is removed in the
+/// meantime.
+///
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct Cursor {
+    /// The creation timestamp of the last child returned.
+    created_at: u64,
+
+    /// The unique identifier of the last child returned.
+    id: Id,
+}
+
+impl Cursor {
+    /// Creates a [`Cursor`] at the given position.
+    #[must_use]
+    pub(crate) const fn new(created_at: u64, id: Id) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Creates a [`Cursor`] that resumes iteration after the given child.
+    #[must_use]
+    pub const fn after(child: &ChildInfo) -> Self {
+        Self {
+            created_at: child.created_at(),
+            id: child.id(),
+        }
+    }
+
+    /// Whether the given child comes after this position.
+    #[must_use]
+    pub fn precedes(&self, child: &ChildInfo) -> bool {
+        (self.created_at, self.id) < (child.created_at(), child.id())
+    }
+}
+
 impl Display for ChildInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
@@ -1,10 +1,16 @@
 //! Environment bindings for the storage crate.
 
+#[cfg(test)]
+#[path = "tests/env.rs"]
+mod tests;
+
 #[cfg(target_arch = "wasm32")]
 use calimero_vm as imp;
 #[cfg(not(target_arch = "wasm32"))]
 use mocked as imp;
 
+use core::time::Duration;
+
 use crate::store::Key;
 
 /// Commits the root hash to the runtime.
@@ -14,6 +20,19 @@ pub fn commit(root_hash: &[u8; 32], artifact: &[u8]) {
     imp::commit(root_hash, artifact);
 }
 
+/// Commits the changes made to entities to the runtime.
+///
+pub fn commit_changes(changes: &[u8]) {
+    imp::commit_changes(changes);
+}
+
+/// Aborts the execution, as the executor is not authorized to make the
+/// changes it made.
+///
+pub fn unauthorized() -> ! {
+    imp::unauthorized()
+}
+
 /// Reads data from persistent storage.
 ///
 /// # Parameters
@@ -66,13 +85,20 @@ pub fn time_now() -> u64 {
 
 /// Return the context id.
 #[must_use]
-#[expect(clippy::missing_const_for_fn, reason = "Cannot be const here")]
 pub fn context_id() -> [u8; 32] {
     imp::context_id()
 }
 
-#[cfg(target_arch = "wasm32")]
+/// Get the period after which orphans are discarded, if the node has
+/// configured one.
+#[must_use]
+pub fn orphan_expiry() -> Option<Duration> {
+    imp::orphan_expiry()
+}
+
 mod calimero_vm {
+    use core::time::Duration;
+
     use calimero_sdk::env;
 
     use crate::store::Key;
@@ -82,6 +108,16 @@ mod calimero_vm {
         env::commit(root_hash, artifact);
     }
 
+    /// Commits the changes made to entities to the runtime.
+    pub(super) fn commit_changes(changes: &[u8]) {
+        env::commit_changes(changes);
+    }
+
+    /// Aborts the execution, as the executor is not authorized.
+    pub(super) fn unauthorized() -> ! {
+        env::unauthorized()
+    }
+
     /// Reads data from persistent storage.
     pub(super) fn storage_read(key: Key) -> Option<Vec<u8>> {
         env::storage_read(&key.to_bytes())
@@ -99,7 +135,7 @@ mod calimero_vm {
 
     /// Fills the buffer with random bytes.
     pub(super) fn random_bytes(buf: &mut [u8]) {
-        env::random_bytes(buf)
+        env::random_bytes(buf);
     }
 
     /// Return the context id.
@@ -114,15 +150,28 @@ mod calimero_vm {
     pub(super) fn time_now() -> u64 {
         env::time_now()
     }
+
+    /// Gets the configured orphan expiry.
+    pub(super) fn orphan_expiry() -> Option<Duration> {
+        env::orphan_expiry()
+    }
 }
 
+/// Natively, calls are routed to the SDK when running inside a simulation,
+/// which provides the host functions of each replica. This is what lets an
+/// application's state, which lives here rather than in the SDK, be held per
+/// replica. Otherwise, as for this crate's own tests, a single in-memory store
+/// is used.
 #[cfg(not(target_arch = "wasm32"))]
 mod mocked {
+    use core::time::Duration;
     use std::cell::RefCell;
     use std::time::{SystemTime, UNIX_EPOCH};
 
+    use calimero_sdk::testing;
     use rand::RngCore;
 
+    use super::calimero_vm;
     use crate::store::{Key, MockedStorage, StorageAdaptor};
 
     thread_local! {
@@ -133,7 +182,11 @@ mod mocked {
     type DefaultStore = MockedStorage<{ usize::MAX }>;
 
     /// Commits the root hash to the runtime.
-    pub(super) fn commit(root_hash: &[u8; 32], _artifact: &[u8]) {
+    pub(super) fn commit(root_hash: &[u8; 32], artifact: &[u8]) {
+        if testing::is_active() {
+            return calimero_vm::commit(root_hash, artifact);
+        }
+
         ROOT_HASH.with(|rh| {
             if rh.borrow_mut().replace(*root_hash).is_some() {
                 Option::expect(None, "State previously committed")
@@ -141,28 +194,66 @@ mod mocked {
         });
     }
 
+    /// Commits the changes made to entities to the runtime, which outside a
+    /// simulation has no one to pass them on to.
+    pub(super) fn commit_changes(changes: &[u8]) {
+        if testing::is_active() {
+            calimero_vm::commit_changes(changes);
+        }
+    }
+
+    /// Aborts the execution, as the executor is not authorized, which outside
+    /// a simulation is a panic.
+    pub(super) fn unauthorized() -> ! {
+        if testing::is_active() {
+            calimero_vm::unauthorized();
+        }
+
+        panic!("the executor is not authorized to make these changes")
+    }
+
     /// Reads data from persistent storage.
     pub(super) fn storage_read(key: Key) -> Option<Vec<u8>> {
+        if testing::is_active() {
+            return calimero_vm::storage_read(key);
+        }
+
         DefaultStore::storage_read(key)
     }
 
     /// Removes data from persistent storage.
     pub(super) fn storage_remove(key: Key) -> bool {
+        if testing::is_active() {
+            return calimero_vm::storage_remove(key);
+        }
+
         DefaultStore::storage_remove(key)
     }
 
     /// Writes data to persistent storage.
     pub(super) fn storage_write(key: Key, value: &[u8]) -> bool {
+        if testing::is_active() {
+            return calimero_vm::storage_write(key, value);
+        }
+
         DefaultStore::storage_write(key, value)
     }
 
     /// Fills the buffer with random bytes.
     pub(super) fn random_bytes(buf: &mut [u8]) {
+        if testing::is_active() {
+            return calimero_vm::random_bytes(buf);
+        }
+
         rand::thread_rng().fill_bytes(buf);
     }
 
     /// Return the context id.
-    pub(super) const fn context_id() -> [u8; 32] {
+    pub(super) fn context_id() -> [u8; 32] {
+        if testing::is_active() {
+            return calimero_vm::context_id();
+        }
+
         [236; 32]
     }
 
@@ -176,9 +267,23 @@ mod mocked {
     )]
     #[expect(clippy::expect_used, reason = "Effectively infallible here")]
     pub(super) fn time_now() -> u64 {
+        if testing::is_active() {
+            return calimero_vm::time_now();
+        }
+
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards to before the Unix epoch!")
             .as_nanos() as u64
     }
+
+    /// Gets the configured orphan expiry, of which there is none outside a
+    /// simulation.
+    pub(super) fn orphan_expiry() -> Option<Duration> {
+        if testing::is_active() {
+            return calimero_vm::orphan_expiry();
+        }
+
+        None
+    }
 }
@@ -0,0 +1,251 @@
+//! Guards on the application state, enforced wherever it is changed.
+//!
+//! The guards an application declares on its methods are only evaluated when
+//! those methods are called locally. Elsewhere, the changes they make arrive
+//! as a set of [`Action`]s, which say nothing of the method that produced
+//! them, and the same is true of the data exchanged when comparing state. The
+//! state can therefore also guard its own fields, using `#[app::guard(..)]`,
+//! and those guards are enforced by this crate for every change made to the
+//! fields, no matter where it comes from.
+//!
+//! Before a change is made, the guards of the state are evaluated for the
+//! executor — the caller, the author of a delta, or the peer being synced
+//! with — through the [`Guard`] implementation of the state, which denies the
+//! executor those fields whose guards it does not pass. Collections deny the
+//! entity they are stored under, and with it every entry beneath it, while
+//! values stored inline in the state must remain unchanged, which is checked
+//! by the function [`register()`]ed for the entity of the state.
+//!
+//! Actions received from elsewhere that the executor is denied are skipped by
+//! [`Interface::apply_action()`](crate::interface::Interface::apply_action()),
+//! and local changes that it is denied abort the execution when committed
+//! through [`Root::commit()`](crate::collections::Root::commit()).
+//!
+
+#[cfg(test)]
+#[path = "tests/guard.rs"]
+mod tests;
+
+use core::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+
+use borsh::{from_slice, to_vec, BorshSerialize};
+
+use crate::address::Id;
+use crate::entities::Data;
+use crate::interface::{Action, StorageError};
+use crate::merge::UnionSet;
+
+/// A function that checks whether the serialised local version of an entity
+/// may be replaced with the serialised foreign one.
+pub type CheckFn = fn(&[u8], &[u8]) -> Result<bool, StorageError>;
+
+thread_local! {
+    static DENIED: RefCell<BTreeSet<Id>> = const { RefCell::new(BTreeSet::new()) };
+    static CHECKS: RefCell<BTreeMap<Id, CheckFn>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Restricts the changes the executor may make to a value.
+///
+/// The default implementation permits everything, so an empty `impl` block
+/// suffices for values that are only ever stored inline. This is implemented
+/// for the application state by `#[app::state]`, according to the guards on
+/// its fields, and for the collections, which deny their entity as a whole.
+///
+pub trait Guard {
+    /// Denies the executor any change to the entities beneath this value.
+    ///
+    /// For the application state, this only applies to those fields whose
+    /// guards the executor does not pass.
+    ///
+    fn deny(&self) {}
+
+    /// Whether the executor may replace this, the local version of the value,
+    /// with `other`.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - The version of the value replacing this one.
+    ///
+    fn permits(&self, _other: &Self) -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
+}
+
+/// Denies the executor any change to an entity, or to those beneath it.
+///
+/// # Parameters
+///
+/// * `id` - The [`Id`] of the entity.
+///
+pub fn deny(id: Id) {
+    let _ignored = DENIED.with(|denied| denied.borrow_mut().insert(id));
+}
+
+/// Registers a check on the changes made to an entity.
+///
+/// Any previously-registered check for the same entity is replaced.
+///
+/// # Parameters
+///
+/// * `id`    - The [`Id`] of the entity.
+/// * `check` - The check to apply.
+///
+pub fn register(id: Id, check: CheckFn) {
+    let _ignored = CHECKS.with(|checks| checks.borrow_mut().insert(id, check));
+}
+
+/// Clears everything denied or registered, ahead of evaluating the guards for
+/// another executor.
+pub fn reset() {
+    DENIED.with(|denied| denied.borrow_mut().clear());
+    CHECKS.with(|checks| checks.borrow_mut().clear());
+}
+
+/// Whether any guards have been evaluated, and so may restrict changes.
+pub(crate) fn is_active() -> bool {
+    DENIED.with(|denied| !denied.borrow().is_empty())
+        || CHECKS.with(|checks| !checks.borrow().is_empty())
+}
+
+/// Whether the executor may apply an action.
+///
+/// # Parameters
+///
+/// * `action` - The action to check.
+/// * `local`  - Gets the local version of the entity being replaced, which is
+///              only needed if a check is registered for it.
+///
+/// # Errors
+///
+/// If the local version is needed but can't be read, or a registered check
+/// fails, an error will be returned.
+///
+pub(crate) fn permits(
+    action: &Action,
+    local: impl FnOnce(Id) -> Result<Option<Vec<u8>>, StorageError>,
+) -> Result<bool, StorageError> {
+    let (id, ancestors, data) = match action {
+        Action::Add {
+            id,
+            data,
+            ancestors,
+            ..
+        }
+        | Action::Update {
+            id,
+            data,
+            ancestors,
+            ..
+        } => (*id, ancestors, Some(data)),
+        Action::Delete { id, ancestors, .. } => (*id, ancestors, None),
+        Action::Compare { .. } => return Ok(true),
+    };
+
+    let denied = DENIED.with(|denied| {
+        let denied = denied.borrow();
+        denied.contains(&id)
+            || ancestors
+                .iter()
+                .any(|ancestor| denied.contains(&ancestor.id()))
+    });
+
+    if denied {
+        return Ok(false);
+    }
+
+    let Some(check) = CHECKS.with(|checks| checks.borrow().get(&id).copied()) else {
+        return Ok(true);
+    };
+
+    match (local(id)?, data) {
+        (Some(local), Some(foreign)) => check(&local, foreign),
+        // nothing is being replaced, or the entity is going altogether, which
+        // leaves nothing for the check to compare
+        (None, _) => Ok(true),
+        (Some(_), None) => Ok(false),
+    }
+}
+
+/// Checks the serialised versions of a [`Data`] type using its [`Guard`]
+/// implementation.
+///
+/// This is a [`CheckFn`], and is intended to be passed to [`register()`].
+///
+/// # Errors
+///
+/// If either version cannot be deserialised, an error will be returned.
+///
+pub fn check_data<D: Data + Guard>(local: &[u8], foreign: &[u8]) -> Result<bool, StorageError> {
+    let local = from_slice::<D>(local).map_err(StorageError::DeserializationError)?;
+    let foreign = from_slice::<D>(foreign).map_err(StorageError::DeserializationError)?;
+
+    Ok(local.permits(&foreign))
+}
+
+/// Whether two versions of a value stored inline are the same.
+///
+/// This is what `#[app::state]` compares the fields the executor is denied
+/// with.
+///
+#[must_use]
+pub fn unchanged<T: BorshSerialize>(local: &T, other: &T) -> bool {
+    match (to_vec(local), to_vec(other)) {
+        (Ok(local), Ok(other)) => local == other,
+        _ => false,
+    }
+}
+
+macro_rules! impl_guard {
+    ($($ty:ty),* $(,)?) => {
+        $(impl Guard for $ty {})*
+    };
+}
+
+impl_guard!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    String,
+);
+
+impl<T: Guard> Guard for Option<T> {
+    fn deny(&self) {
+        if let Some(value) = self {
+            value.deny();
+        }
+    }
+}
+
+impl<T: Guard> Guard for Box<T> {
+    fn deny(&self) {
+        (**self).deny();
+    }
+}
+
+impl<T> Guard for Vec<T> {}
+
+impl<T, const N: usize> Guard for [T; N] {}
+
+impl<T> Guard for BTreeSet<T> {}
+
+impl<K, V> Guard for BTreeMap<K, V> {}
+
+impl<T: Ord> Guard for UnionSet<T> {}
@@ -4,15 +4,18 @@
 #[path = "tests/index.rs"]
 mod tests;
 
+use core::iter;
 use core::marker::PhantomData;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeMap;
 
 use borsh::{to_vec, BorshDeserialize, BorshSerialize};
 use sha2::{Digest, Sha256};
 
 use crate::address::Id;
-use crate::entities::{ChildInfo, Metadata, UpdatedAt};
+use crate::collections::summary;
+use crate::entities::{ChildInfo, Cursor, Metadata, UpdatedAt};
 use crate::interface::StorageError;
+use crate::proof::{MerkleProof, ProofStep};
 use crate::store::{Key, StorageAdaptor};
 
 /// Stored index information for an entity in the storage system.
@@ -24,9 +27,10 @@ struct EntityIndex {
     /// Identifier of the parent entity, if any.
     parent_id: Option<Id>,
 
-    /// Information about the child entities, including their [`Id`]s and Merkle
-    /// hashes, organised by collection name.
-    children: BTreeMap<String, Vec<ChildInfo>>,
+    /// The number of child entities, organised by collection name. The
+    /// children themselves, with their [`Id`]s and Merkle hashes, are kept
+    /// apart in [`Chunks`].
+    children: BTreeMap<String, usize>,
 
     /// Merkle hash of the entity and its descendants.
     full_hash: [u8; 32],
@@ -37,6 +41,139 @@ struct EntityIndex {
 
     /// Metadata about the entity.
     metadata: Metadata,
+
+    /// The earlier "own" hashes of the entity, most recent first, up to
+    /// [`HISTORY_SIZE`]. These identify the versions that the current one
+    /// supersedes, so that a foreign version can be recognised as simply
+    /// older or newer rather than as a concurrent change needing a merge.
+    history: Vec<[u8; 32]>,
+}
+
+/// The most earlier hashes kept in the history of an entity.
+const HISTORY_SIZE: usize = 16;
+
+impl EntityIndex {
+    /// Replaces the "own" hash, keeping the one it supersedes in the history.
+    fn set_own_hash(&mut self, own_hash: [u8; 32]) {
+        if self.own_hash != own_hash && self.own_hash != [0; 32] {
+            self.history.insert(0, self.own_hash);
+            self.history.truncate(HISTORY_SIZE);
+        }
+        self.own_hash = own_hash;
+    }
+}
+
+/// The most children kept together in a chunk, beyond which it is split.
+const CHUNK_SIZE: usize = 64;
+
+/// The chunks making up the children of an entity in one of its collections.
+///
+/// Children are kept in order, in chunks of up to [`CHUNK_SIZE`], apart from
+/// the index of their parent. This means that they can be counted, and read a
+/// page at a time, without loading every one of them.
+///
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default)]
+struct Chunks {
+    /// The number from which the [`Id`] of the next chunk is derived.
+    next: u64,
+
+    /// The chunks, in the order of the children they hold.
+    chunks: Vec<ChunkInfo>,
+}
+
+/// Information about a chunk of children.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug)]
+struct ChunkInfo {
+    /// Unique identifier of the chunk.
+    id: Id,
+
+    /// The position of the first child in the chunk.
+    first: Cursor,
+
+    /// A digest of the children in the chunk, which changes whenever one of
+    /// them is added, removed, or changed. This lets summaries kept of the
+    /// children tell which chunks to read again.
+    digest: [u8; 32],
+}
+
+impl Chunks {
+    /// The [`Id`] the chunks of a collection are stored under.
+    fn id_for(parent_id: Id, collection: &str) -> Id {
+        let mut hasher = Sha256::new();
+        hasher.update(parent_id.as_bytes());
+        hasher.update(collection.as_bytes());
+        Id::new(hasher.finalize().into())
+    }
+
+    /// Which chunk the child at the given position belongs in.
+    fn position_of(&self, position: Cursor) -> usize {
+        self.chunks
+            .partition_point(|chunk| chunk.first <= position)
+            .saturating_sub(1)
+    }
+
+    /// Allocates the [`Id`] of a new chunk.
+    fn allocate(&mut self, id: Id) -> Id {
+        let mut hasher = Sha256::new();
+        hasher.update(id.as_bytes());
+        hasher.update(self.next.to_le_bytes());
+        self.next = self.next.saturating_add(1);
+        Id::new(hasher.finalize().into())
+    }
+}
+
+/// Works out the digest of the children in a chunk.
+fn digest_of(chunk: &[ChildInfo]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for child in chunk {
+        hasher.update(child.id().as_bytes());
+        hasher.update(child.merkle_hash());
+    }
+    hasher.finalize().into()
+}
+
+/// Reads and deserialises a value from storage.
+fn read_value<T, F>(read: &F, key: Key) -> Result<Option<T>, StorageError>
+where
+    T: BorshDeserialize,
+    F: Fn(Key) -> Option<Vec<u8>>,
+{
+    read(key)
+        .map(|data| T::try_from_slice(&data))
+        .transpose()
+        .map_err(StorageError::DeserializationError)
+}
+
+/// Reads a chunk of children.
+fn read_chunk<F>(read: &F, id: Id) -> Result<Vec<ChildInfo>, StorageError>
+where
+    F: Fn(Key) -> Option<Vec<u8>>,
+{
+    read_value(read, Key::Children(id))?.ok_or(StorageError::IndexNotFound(id))
+}
+
+/// Reads every child of an entity in one of its collections, in order.
+fn read_children<F>(
+    read: &F,
+    parent_id: Id,
+    collection: &str,
+) -> Result<Vec<ChildInfo>, StorageError>
+where
+    F: Fn(Key) -> Option<Vec<u8>>,
+{
+    let id = Chunks::id_for(parent_id, collection);
+
+    let Some(chunks) = read_value::<Chunks, _>(read, Key::Children(id))? else {
+        return Ok(vec![]);
+    };
+
+    let mut children = Vec::new();
+
+    for chunk in chunks.chunks {
+        children.extend(read_chunk(read, chunk.id)?);
+    }
+
+    Ok(children)
 }
 
 /// Manages the indexing system for efficient tree navigation.
@@ -74,34 +211,52 @@ impl<S: StorageAdaptor> Index<S> {
         let mut parent_index =
             Self::get_index(parent_id)?.ok_or(StorageError::IndexNotFound(parent_id))?;
 
-        let mut child_index = Self::get_index(child.id())?.unwrap_or_else(|| EntityIndex {
+        let existing = Self::get_index(child.id())?;
+
+        let count = parent_index
+            .children
+            .entry(collection.to_owned())
+            .or_insert(0);
+
+        // Children are ordered by creation time, which can differ between
+        // versions of the same child when it was created independently on
+        // two replicas (as map entries keyed alike are), so any existing
+        // entry is found by ID, lest the child be listed twice
+        if let Some(listed) = existing
+            .as_ref()
+            .filter(|index| index.parent_id == Some(parent_id))
+        {
+            let position = Cursor::new(listed.metadata.created_at, child.id());
+
+            if Self::take_child(parent_id, collection, child.id(), position)?.is_some() {
+                *count = count.saturating_sub(1);
+            }
+        }
+
+        let mut child_index = existing.unwrap_or_else(|| EntityIndex {
             id: child.id(),
             parent_id: None,
             children: BTreeMap::new(),
             full_hash: [0; 32],
             own_hash: [0; 32],
             metadata: child.metadata,
+            history: Vec::new(),
         });
         child_index.parent_id = Some(parent_id);
-        child_index.own_hash = child.merkle_hash();
+        child_index.set_own_hash(child.merkle_hash());
+        // The child is located among its siblings by its creation time, so
+        // this has to agree with where it is listed
+        child_index.metadata.created_at = child.metadata.created_at;
         Self::save_index(&child_index)?;
         child_index.full_hash = Self::calculate_full_merkle_hash_for(child.id(), false)?;
         Self::save_index(&child_index)?;
 
-        let children = parent_index
-            .children
-            .entry(collection.to_owned())
-            .or_insert_with(Vec::new);
-
-        let mut ordered = children.drain(..).collect::<BTreeSet<_>>();
-
-        let _ignored = ordered.replace(ChildInfo::new(
-            child.id(),
-            child_index.full_hash,
-            child.metadata,
-        ));
-
-        children.extend(ordered.into_iter());
+        Self::insert_child(
+            parent_id,
+            collection,
+            ChildInfo::new(child.id(), child_index.full_hash, child.metadata),
+        )?;
+        *count = count.saturating_add(1);
 
         Self::save_index(&parent_index)?;
         parent_index.full_hash = Self::calculate_full_merkle_hash_for(parent_id, false)?;
@@ -137,8 +292,9 @@ impl<S: StorageAdaptor> Index<S> {
             full_hash: [0; 32],
             own_hash: [0; 32],
             metadata: root.metadata,
+            history: Vec::new(),
         });
-        index.own_hash = root.merkle_hash();
+        index.set_own_hash(root.merkle_hash());
         Self::save_index(&index)?;
         Ok(())
     }
@@ -246,6 +402,14 @@ impl<S: StorageAdaptor> Index<S> {
         Ok(ancestors)
     }
 
+    /// Retrieves the earlier "own" hashes of a given entity, most recent
+    /// first.
+    pub(crate) fn get_history(id: Id) -> Result<Vec<[u8; 32]>, StorageError> {
+        Ok(Self::get_index(id)?
+            .map(|index| index.history)
+            .unwrap_or_default())
+    }
+
     /// Retrieves the metadata of a given entity.
     pub(crate) fn get_metadata(id: Id) -> Result<Option<Metadata>, StorageError> {
         Ok(Self::get_index(id)?.map(|index| index.metadata))
@@ -269,11 +433,144 @@ impl<S: StorageAdaptor> Index<S> {
         parent_id: Id,
         collection: &str,
     ) -> Result<Vec<ChildInfo>, StorageError> {
+        if Self::get_index(parent_id)?.is_none() {
+            return Err(StorageError::IndexNotFound(parent_id));
+        }
+
+        read_children(&S::storage_read, parent_id, collection)
+    }
+
+    /// Retrieves a page of the children of a given entity.
+    ///
+    /// Only the chunks holding the requested children are read, rather than
+    /// the full list.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id`  - The [`Id`] of the entity whose children are to be
+    ///                  retrieved.
+    /// * `collection` - The name of the collection from which to retrieve the
+    ///                  children.
+    /// * `cursor`     - The position after which to start, or [`None`] to
+    ///                  start from the beginning.
+    /// * `limit`      - The maximum number of children to retrieve.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or deserialising the index information,
+    /// an error will be returned.
+    ///
+    pub(crate) fn get_children_page_of(
+        parent_id: Id,
+        collection: &str,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Result<Vec<ChildInfo>, StorageError> {
+        if Self::get_index(parent_id)?.is_none() {
+            return Err(StorageError::IndexNotFound(parent_id));
+        }
+
+        let Some(chunks) = Self::get_chunks(parent_id, collection)? else {
+            return Ok(vec![]);
+        };
+
+        let start = cursor.map_or(0, |after| chunks.position_of(after));
+
+        let mut page = Vec::new();
+
+        for chunk in chunks.chunks.iter().skip(start) {
+            if page.len() >= limit {
+                break;
+            }
+
+            let remaining = limit.saturating_sub(page.len());
+
+            page.extend(
+                read_chunk(&S::storage_read, chunk.id)?
+                    .into_iter()
+                    .filter(|child| cursor.map_or(true, |after| after.precedes(child)))
+                    .take(remaining),
+            );
+        }
+
+        Ok(page)
+    }
+
+    /// Retrieves the chunks that the children of a given entity are kept in,
+    /// in order, each along with the digest of the children it holds.
+    ///
+    /// Only the list of chunks is read, not the chunks themselves, so this is
+    /// a cheap way of telling which children may have changed since the list
+    /// was last seen.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id`  - The [`Id`] of the entity whose children are to be
+    ///                  retrieved.
+    /// * `collection` - The name of the collection from which to retrieve the
+    ///                  children.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or deserialising the index information,
+    /// an error will be returned.
+    ///
+    pub(crate) fn get_child_chunks_of(
+        parent_id: Id,
+        collection: &str,
+    ) -> Result<Vec<(Id, [u8; 32])>, StorageError> {
+        Ok(Self::get_chunks(parent_id, collection)?
+            .map(|chunks| {
+                chunks
+                    .chunks
+                    .iter()
+                    .map(|chunk| (chunk.id, chunk.digest))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Retrieves the children held in one of the chunks listed by
+    /// [`get_child_chunks_of()`](Index::get_child_chunks_of()).
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The [`Id`] of the chunk.
+    ///
+    /// # Errors
+    ///
+    /// If the chunk cannot be found, or there's an issue deserialising it, an
+    /// error will be returned.
+    ///
+    pub(crate) fn get_child_chunk(id: Id) -> Result<Vec<ChildInfo>, StorageError> {
+        read_chunk(&S::storage_read, id)
+    }
+
+    /// Retrieves the number of children of a given entity.
+    ///
+    /// The count is kept in the index, so the children aren't read.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id`  - The [`Id`] of the entity whose children are to be
+    ///                  counted.
+    /// * `collection` - The name of the collection in which to count the
+    ///                  children.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or deserialising the index information,
+    /// an error will be returned.
+    ///
+    pub(crate) fn get_child_count_of(
+        parent_id: Id,
+        collection: &str,
+    ) -> Result<usize, StorageError> {
         Ok(Self::get_index(parent_id)?
             .ok_or(StorageError::IndexNotFound(parent_id))?
             .children
             .get(collection)
-            .cloned()
+            .copied()
             .unwrap_or_default())
     }
 
@@ -373,7 +670,7 @@ impl<S: StorageAdaptor> Index<S> {
         Ok(parent_index
             .children
             .get(collection)
-            .map_or(false, |children| !children.is_empty()))
+            .is_some_and(|count| *count > 0))
     }
 
     /// Recalculates the Merkle hashes of the ancestors of the entity.
@@ -395,18 +692,10 @@ impl<S: StorageAdaptor> Index<S> {
                 Self::get_index(parent_id)?.ok_or(StorageError::IndexNotFound(parent_id))?;
 
             // Update the child's hash in the parent's children list
-            for children in &mut parent_index.children.values_mut() {
-                if let Some(child) = children.iter_mut().find(|c| c.id() == current_id) {
-                    let new_child_hash = Self::calculate_full_merkle_hash_for(current_id, false)?;
-                    if child.merkle_hash() != new_child_hash {
-                        *child = ChildInfo::new(current_id, new_child_hash, child.metadata);
-                    }
-                    break;
-                }
-            }
+            let new_child_hash = Self::calculate_full_merkle_hash_for(current_id, false)?;
+            Self::update_child(&parent_index, current_id, new_child_hash)?;
 
             // Recalculate the parent's full hash
-            Self::save_index(&parent_index)?;
             let new_parent_hash = Self::calculate_full_merkle_hash_for(parent_id, false)?;
             parent_index.full_hash = new_parent_hash;
             Self::save_index(&parent_index)?;
@@ -445,8 +734,15 @@ impl<S: StorageAdaptor> Index<S> {
         let mut parent_index =
             Self::get_index(parent_id)?.ok_or(StorageError::IndexNotFound(parent_id))?;
 
-        if let Some(children) = parent_index.children.get_mut(collection) {
-            children.retain(|child| child.id() != child_id);
+        let position = Cursor::new(
+            Self::get_metadata(child_id)?.map_or(0, |metadata| metadata.created_at),
+            child_id,
+        );
+
+        if Self::take_child(parent_id, collection, child_id, position)?.is_some() {
+            if let Some(count) = parent_index.children.get_mut(collection) {
+                *count = count.saturating_sub(1);
+            }
         }
 
         Self::save_index(&parent_index)?;
@@ -459,16 +755,216 @@ impl<S: StorageAdaptor> Index<S> {
         Ok(())
     }
 
-    /// Removes the index information for an entity.
+    /// Removes the index information for an entity, including the chunks
+    /// listing its children and any summary kept of them.
     ///
     /// # Parameters
     ///
-    /// * `index` - The [`EntityIndex`] to be saved.
+    /// * `id` - The [`Id`] of the entity whose index is to be removed.
     ///
-    fn remove_index(id: Id) {
+    pub(crate) fn remove_index(id: Id) {
+        if let Ok(Some(index)) = Self::get_index(id) {
+            for collection in index.children.keys() {
+                let chunks_id = Chunks::id_for(id, collection);
+
+                if let Ok(Some(chunks)) = Self::get_chunks(id, collection) {
+                    for chunk in chunks.chunks {
+                        _ = S::storage_remove(Key::Children(chunk.id));
+                    }
+                }
+
+                _ = S::storage_remove(Key::Children(chunks_id));
+            }
+        }
+
+        summary::remove::<S>(id);
         _ = S::storage_remove(Key::Index(id));
     }
 
+    /// Retrieves the chunks making up the children of an entity in one of its
+    /// collections.
+    fn get_chunks(parent_id: Id, collection: &str) -> Result<Option<Chunks>, StorageError> {
+        read_value(
+            &S::storage_read,
+            Key::Children(Chunks::id_for(parent_id, collection)),
+        )
+    }
+
+    /// Saves a chunk of children, updating its digest to match.
+    fn save_chunk(info: &mut ChunkInfo, chunk: &[ChildInfo]) -> Result<(), StorageError> {
+        info.digest = digest_of(chunk);
+        _ = S::storage_write(
+            Key::Children(info.id),
+            &to_vec(chunk).map_err(StorageError::SerializationError)?,
+        );
+        Ok(())
+    }
+
+    /// Saves the chunks making up the children of an entity in one of its
+    /// collections, removing them once there are none left.
+    fn save_chunks(parent_id: Id, collection: &str, chunks: &Chunks) -> Result<(), StorageError> {
+        let key = Key::Children(Chunks::id_for(parent_id, collection));
+
+        if chunks.chunks.is_empty() {
+            _ = S::storage_remove(key);
+        } else {
+            _ = S::storage_write(
+                key,
+                &to_vec(chunks).map_err(StorageError::SerializationError)?,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Inserts a child into the chunks of a collection, in order, splitting
+    /// the chunk it lands in if that grows too large.
+    fn insert_child(parent_id: Id, collection: &str, child: ChildInfo) -> Result<(), StorageError> {
+        let chunks_id = Chunks::id_for(parent_id, collection);
+        let mut chunks = Self::get_chunks(parent_id, collection)?.unwrap_or_default();
+        let position = Cursor::after(&child);
+
+        let at = chunks.position_of(position);
+
+        let Some(info) = chunks.chunks.get_mut(at) else {
+            let mut info = ChunkInfo {
+                id: chunks.allocate(chunks_id),
+                first: position,
+                digest: [0; 32],
+            };
+            Self::save_chunk(&mut info, &[child])?;
+            chunks.chunks.push(info);
+            return Self::save_chunks(parent_id, collection, &chunks);
+        };
+
+        let mut chunk = read_chunk(&S::storage_read, info.id)?;
+        let slot = chunk.partition_point(|existing| *existing < child);
+        chunk.insert(slot, child);
+        info.first = info.first.min(position);
+
+        let split = (chunk.len() > CHUNK_SIZE).then(|| chunk.split_off(chunk.len().div_ceil(2)));
+
+        Self::save_chunk(info, &chunk)?;
+
+        if let Some(tail) = split {
+            let mut tail_info = ChunkInfo {
+                id: chunks.allocate(chunks_id),
+                first: tail.first().map_or(position, Cursor::after),
+                digest: [0; 32],
+            };
+            Self::save_chunk(&mut tail_info, &tail)?;
+            chunks.chunks.insert(at.saturating_add(1), tail_info);
+        }
+
+        Self::save_chunks(parent_id, collection, &chunks)
+    }
+
+    /// Locates a child among the chunks of a collection.
+    ///
+    /// The child is looked for where its position puts it, and only if it
+    /// isn't there, and `search` is set, are all of the chunks searched.
+    ///
+    fn locate_child(
+        chunks: &Chunks,
+        child_id: Id,
+        position: Cursor,
+        search: bool,
+    ) -> Result<Option<(usize, Vec<ChildInfo>, usize)>, StorageError> {
+        let expected = chunks.position_of(position);
+
+        let others = (0..chunks.chunks.len()).filter(|at| search && *at != expected);
+
+        for at in iter::once(expected).chain(others) {
+            let Some(info) = chunks.chunks.get(at) else {
+                continue;
+            };
+
+            let chunk = read_chunk(&S::storage_read, info.id)?;
+
+            if let Some(slot) = chunk.iter().position(|child| child.id() == child_id) {
+                return Ok(Some((at, chunk, slot)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Takes a child out of the chunks of a collection, returning it if it
+    /// was there.
+    fn take_child(
+        parent_id: Id,
+        collection: &str,
+        child_id: Id,
+        position: Cursor,
+    ) -> Result<Option<ChildInfo>, StorageError> {
+        let Some(mut chunks) = Self::get_chunks(parent_id, collection)? else {
+            return Ok(None);
+        };
+
+        let Some((at, mut chunk, slot)) = Self::locate_child(&chunks, child_id, position, true)?
+        else {
+            return Ok(None);
+        };
+
+        let child = chunk.remove(slot);
+
+        if let Some(first) = chunk.first() {
+            if let Some(info) = chunks.chunks.get_mut(at) {
+                info.first = Cursor::after(first);
+                Self::save_chunk(info, &chunk)?;
+            }
+        } else {
+            let info = chunks.chunks.remove(at);
+            _ = S::storage_remove(Key::Children(info.id));
+        }
+
+        Self::save_chunks(parent_id, collection, &chunks)?;
+
+        Ok(Some(child))
+    }
+
+    /// Updates the Merkle hash of a child where it is listed in the index of
+    /// its parent.
+    fn update_child(
+        parent_index: &EntityIndex,
+        child_id: Id,
+        merkle_hash: [u8; 32],
+    ) -> Result<(), StorageError> {
+        let position = Cursor::new(
+            Self::get_metadata(child_id)?.map_or(0, |metadata| metadata.created_at),
+            child_id,
+        );
+
+        // Which collection the child is in isn't known, so each is only
+        // searched in full once it isn't found where expected in any of them
+        for search in [false, true] {
+            for collection in parent_index.children.keys() {
+                let Some(mut chunks) = Self::get_chunks(parent_index.id, collection)? else {
+                    continue;
+                };
+
+                let Some((at, mut chunk, slot)) =
+                    Self::locate_child(&chunks, child_id, position, search)?
+                else {
+                    continue;
+                };
+
+                if let (Some(info), Some(child)) = (chunks.chunks.get_mut(at), chunk.get_mut(slot))
+                {
+                    if child.merkle_hash() != merkle_hash {
+                        *child = ChildInfo::new(child_id, merkle_hash, child.metadata);
+                        Self::save_chunk(info, &chunk)?;
+                        Self::save_chunks(parent_index.id, collection, &chunks)?;
+                    }
+                }
+
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Saves the index information for an entity.
     ///
     /// # Parameters
@@ -509,7 +1005,7 @@ impl<S: StorageAdaptor> Index<S> {
         updated_at: Option<UpdatedAt>,
     ) -> Result<[u8; 32], StorageError> {
         let mut index = Self::get_index(id)?.ok_or(StorageError::IndexNotFound(id))?;
-        index.own_hash = merkle_hash;
+        index.set_own_hash(merkle_hash);
         Self::save_index(&index)?;
         index.full_hash = Self::calculate_full_merkle_hash_for(id, false)?;
         if let Some(updated_at) = updated_at {
@@ -519,4 +1015,104 @@ impl<S: StorageAdaptor> Index<S> {
         <Index<S>>::recalculate_ancestor_hashes_for(id)?;
         Ok(index.full_hash)
     }
+
+    /// Adds earlier "own" hashes to the history of an entity.
+    ///
+    /// This is used when a version arrives from elsewhere, so that the versions
+    /// it superseded there are also recognised as superseded here. Hashes that
+    /// are already known, or that match the current one, are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`      - The [`Id`] of the entity.
+    /// * `history` - The earlier hashes to add, most recent first.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or saving the index information, an error
+    /// will be returned.
+    ///
+    pub(crate) fn extend_history(id: Id, history: &[[u8; 32]]) -> Result<(), StorageError> {
+        let mut index = Self::get_index(id)?.ok_or(StorageError::IndexNotFound(id))?;
+        for hash in history {
+            if *hash != index.own_hash && !index.history.contains(hash) {
+                index.history.push(*hash);
+            }
+        }
+        index.history.truncate(HISTORY_SIZE);
+        Self::save_index(&index)
+    }
+}
+
+/// Generates a Merkle inclusion proof for an entity.
+///
+/// This reads the index information directly through the supplied function,
+/// rather than through a [`StorageAdaptor`], so that proofs can also be
+/// generated outside of the runtime, e.g. by a node reading from its own store.
+/// See the [`proof`](crate::proof) module for details of the proof format.
+///
+/// # Parameters
+///
+/// * `id`   - The [`Id`] of the entity to generate the proof for.
+/// * `read` - A function that reads the raw value for a storage [`Key`].
+///
+/// # Errors
+///
+/// If there's an issue retrieving or deserialising the index information, or
+/// the index is inconsistent, an error will be returned. If the entity does
+/// not exist, [`None`] is returned.
+///
+pub fn generate_proof<F>(id: Id, read: F) -> Result<Option<MerkleProof>, StorageError>
+where
+    F: Fn(Key) -> Option<Vec<u8>>,
+{
+    let read_index = |id: Id| read_value::<EntityIndex, _>(&read, Key::Index(id));
+
+    let read_all_children = |index: &EntityIndex| {
+        let mut children = Vec::new();
+
+        for collection in index.children.keys() {
+            children.extend(read_children(&read, index.id, collection)?);
+        }
+
+        Ok::<_, StorageError>(children)
+    };
+
+    let (Some(mut current), Some(data)) = (read_index(id)?, read(Key::Entry(id))) else {
+        return Ok(None);
+    };
+
+    let children = read_all_children(&current)?
+        .iter()
+        .map(ChildInfo::merkle_hash)
+        .collect();
+
+    let mut path = Vec::new();
+
+    while let Some(parent_id) = current.parent_id {
+        let parent = read_index(parent_id)?.ok_or(StorageError::IndexNotFound(parent_id))?;
+
+        let siblings = read_all_children(&parent)?;
+
+        let position = siblings
+            .iter()
+            .position(|child| child.id() == current.id)
+            .ok_or(StorageError::InvalidDataFound(current.id))?;
+
+        let (left, right) = siblings.split_at(position);
+
+        path.push(ProofStep::new(
+            parent.own_hash,
+            left.iter().map(|child| child.merkle_hash()).collect(),
+            right
+                .iter()
+                .skip(1)
+                .map(|child| child.merkle_hash())
+                .collect(),
+        ));
+
+        current = parent;
+    }
+
+    Ok(Some(MerkleProof::new(data, children, path)))
 }
@@ -96,7 +96,8 @@
 //! self-contained without any wider impact. Order does not strictly matter, as
 //! the actions are commutative, and the outcome will be the same regardless of
 //! the order in which they are applied. Any conflicts are handled using the
-//! last-write-wins strategy.
+//! last-write-wins strategy, unless a merge function has been registered for
+//! the entity concerned, as described in the [`merge`](crate::merge) module.
 //!
 //! There are certain cases where a mis-ordering of action, which is
 //! essentially the same as having missing actions, can result in an invalid
@@ -106,12 +107,22 @@
 //! added, or we can store it as an orphaned entity to be resolved later. At
 //! present we follow the last approach, as it aligns well with the use of
 //! comparisons to bring nodes back into sync. We therefore know that the node
-//! will _eventually_ become consistent, which is all we guarantee.
+//! will _eventually_ become consistent, which is all we guarantee. The details
+//! of how orphans are held and later attached are described in the
+//! [`orphans`](crate::orphans) module.
 //!
 //! TODO: Examine whether this is the right approach, or whether we should for
 //! TODO: instance block and do a comparison on the parent to ensure that local
 //! TODO: state is as consistent as possible.
 //!
+//! Deletions need similar care, as a node may change an entity, or something
+//! nested beneath it, without yet knowing that another node has deleted it.
+//! Every deleted entity therefore leaves a tombstone behind, recording when it
+//! was deleted, and any version of the entity or its descendants that was last
+//! updated before then is discarded when it arrives, rather than bringing the
+//! entity back. A version updated after the deletion wins instead, in keeping
+//! with last-write-wins.
+//!
 //! Providing all generated actions are carried out, all nodes will eventually
 //! be in sync, without any need for comparisons, transmission of full states,
 //! or a transaction model (which requires linear history, and therefore
@@ -226,10 +237,15 @@ use sha2::{Digest, Sha256};
 use thiserror::Error as ThisError;
 
 use crate::address::{Id, Path};
-use crate::entities::{ChildInfo, Collection, Data, Metadata};
-use crate::index::Index;
+use crate::entities::{ChildInfo, Collection, Cursor, Data, Metadata};
+use crate::env::time_now;
+use crate::guard;
+use crate::index::{self, Index};
+use crate::merge::{self, Newer};
+use crate::orphans::{self, Orphans};
+use crate::proof::MerkleProof;
 use crate::store::{Key, MainStorage, StorageAdaptor};
-use crate::sync;
+use crate::sync::{self, ChangeKind, EntityChange};
 
 /// Convenient type alias for the main storage system.
 pub type MainInterface = Interface<MainStorage>;
@@ -298,6 +314,11 @@ pub enum Action {
 
         /// Details of the ancestors of the entity.
         ancestors: Vec<ChildInfo>,
+
+        /// When the entity was deleted. Any version of the entity, or of its
+        /// descendants, that was last updated before this is discarded rather
+        /// than brought back.
+        deleted_at: u64,
     },
 
     /// Update the entity with the given ID and type to have the supplied data.
@@ -313,6 +334,11 @@ pub enum Action {
 
         /// The metadata of the entity.
         metadata: Metadata,
+
+        /// The earlier "own" hashes of the entity, most recent first. These
+        /// are the versions that this one supersedes, which the receiver does
+        /// not need to merge with, and can simply replace.
+        history: Vec<[u8; 32]>,
     },
 }
 
@@ -339,6 +365,39 @@ pub struct ComparisonData {
 
     /// The metadata of the entity.
     metadata: Metadata,
+
+    /// The earlier "own" hashes of the entity, most recent first.
+    history: Vec<[u8; 32]>,
+}
+
+/// The outcome of reconciling a foreign version of an entity with the local
+/// one.
+#[derive(Debug)]
+enum Reconciled {
+    /// There is no merge function for the entity, so the version updated most
+    /// recently wins.
+    LastWriteWins,
+
+    /// The foreign version is the same as, or superseded by, the local one,
+    /// which is kept.
+    Local,
+
+    /// The foreign version supersedes the local one, and replaces it with the
+    /// given metadata.
+    Foreign(Metadata),
+
+    /// Both versions were changed independently, and have been merged into
+    /// the given data, metadata, and history.
+    Merged(Vec<u8>, Metadata, Vec<[u8; 32]>),
+}
+
+/// Diagnostic information about the state of the storage system.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Diagnostics {
+    /// The number of actions held in the orphan area, waiting for their
+    /// parents to arrive.
+    pub orphans: usize,
 }
 
 /// The primary interface for the storage system.
@@ -390,6 +449,25 @@ impl<S: StorageAdaptor> Interface<S> {
         Ok(true)
     }
 
+    /// Applies any orphans that were waiting for an entity to arrive.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id` - The [`Id`] of the entity that has just been saved.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or when
+    /// applying one of the orphaned actions, an error will be returned.
+    ///
+    fn adopt_orphans(parent_id: Id) -> Result<(), StorageError> {
+        for action in <Orphans<S>>::take(parent_id)? {
+            Self::apply_action(action)?;
+        }
+
+        Ok(())
+    }
+
     /// Applies an [`Action`] to the storage system.
     ///
     /// This function accepts a single incoming [`Action`] and applies it to the
@@ -404,6 +482,14 @@ impl<S: StorageAdaptor> Interface<S> {
     /// function to deal with the type being indicated in the serialised data,
     /// if appropriate, or in the ID or accompanying metadata.
     ///
+    /// If the parent of the entity does not exist yet, the [`Action`] is held
+    /// in the orphan area until it does, as described in the [`orphans`](crate::orphans)
+    /// module. Once the entity has been saved, any orphans waiting for it are
+    /// applied in turn.
+    ///
+    /// Actions that the executor is denied by the guards of the state, as
+    /// described in the [`guard`](crate::guard) module, are skipped.
+    ///
     /// After applying the [`Action`], the ancestor hashes will be recalculated,
     /// and this function will compare them against the expected hashes. If any
     /// of the hashes do not match, the ID of the first entity with a mismatched
@@ -419,6 +505,55 @@ impl<S: StorageAdaptor> Interface<S> {
     /// applying the [`Action`], an error will be returned.
     ///
     pub fn apply_action(action: Action) -> Result<(), StorageError> {
+        if !guard::permits(&action, |id| Ok(Self::find_by_id_raw(id)))? {
+            // The sender is denied this change by the guards of the state, so
+            // it is left out, whatever method produced it
+            return Ok(());
+        }
+
+        if let Action::Add {
+            id,
+            ref ancestors,
+            metadata,
+            ..
+        }
+        | Action::Update {
+            id,
+            ref ancestors,
+            metadata,
+            ..
+        } = action
+        {
+            if let Some(deleted_at) = Self::deleted_at(id)? {
+                if deleted_at >= *metadata.updated_at {
+                    // This version was deleted here, so the sender needs to
+                    // hear about the deletion instead
+                    sync::push_action(Action::Delete {
+                        id,
+                        ancestors: ancestors.clone(),
+                        deleted_at,
+                    });
+
+                    return Ok(());
+                }
+            }
+
+            if let Some(parent) = ancestors.first() {
+                if <Index<S>>::get_metadata(parent.id())?.is_none() {
+                    if Self::deleted_at(parent.id())?
+                        .is_some_and(|deleted_at| deleted_at >= *metadata.updated_at)
+                    {
+                        // The parent was deleted after this was last changed,
+                        // so it has gone along with it
+                        return Ok(());
+                    }
+
+                    // The parent hasn't arrived yet, so hold on to this until it does
+                    return <Orphans<S>>::buffer(parent.id(), action);
+                }
+            }
+        }
+
         match action {
             Action::Add {
                 id,
@@ -426,44 +561,131 @@ impl<S: StorageAdaptor> Interface<S> {
                 // todo! we only need parent_id
                 ancestors,
                 metadata,
-            }
-            | Action::Update {
+            } => Self::apply_version(id, data, &ancestors, metadata, &[])?,
+            Action::Update {
                 id,
                 data,
                 ancestors,
                 metadata,
-            } => {
-                if let Some(parent) = ancestors.first() {
-                    let own_hash = Sha256::digest(&data).into();
-
-                    <Index<S>>::add_child_to(
-                        parent.id(),
-                        "no collection, remove this nonsense",
-                        ChildInfo::new(id, own_hash, metadata),
-                    )?;
-                }
+                history,
+            } => Self::apply_version(id, data, &ancestors, metadata, &history)?,
+            Action::Compare { .. } => {
+                return Err(StorageError::ActionNotAllowed("Compare".to_owned()))
+            }
+            Action::Delete { id, deleted_at, .. } => {
+                <Orphans<S>>::discard(id, deleted_at)?;
+
+                let local_metadata = <Index<S>>::get_metadata(id)?;
 
-                if Self::save_internal(id, &data, metadata)?.is_none() {
-                    // we didn't save anything, so we skip updating the ancestors
+                if local_metadata.is_some_and(|local| *local.updated_at > deleted_at) {
+                    // The entity was changed here after it was deleted
+                    // elsewhere, so the change wins
                     return Ok(());
                 }
 
-                sync::push_action(Action::Compare { id });
-            }
-            Action::Compare { .. } => {
-                return Err(StorageError::ActionNotAllowed("Compare".to_owned()))
-            }
-            Action::Delete {
-                id, ancestors: _, ..
-            } => {
-                // todo! remove_child_from here
+                if local_metadata.is_some() {
+                    sync::record_change(EntityChange::new(
+                        id,
+                        ChangeKind::Deleted,
+                        &<Index<S>>::get_ancestors_of(id)?,
+                    ));
+                }
+
+                Self::remove_descendants_of(id, deleted_at)?;
+
+                if let Some(parent_id) = <Index<S>>::get_parent_id(id)? {
+                    Self::detach_from(parent_id, id)?;
+                }
+
+                <Index<S>>::remove_index(id);
                 let _ignored = S::storage_remove(Key::Entry(id));
+
+                Self::bury(id, deleted_at)?;
             }
         };
 
         Ok(())
     }
 
+    /// Applies a foreign version of an entity, received in an [`Add`](Action::Add)
+    /// or [`Update`](Action::Update) action.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`        - The [`Id`] of the entity.
+    /// * `foreign`   - The serialised foreign version of the entity.
+    /// * `ancestors` - Details of the ancestors of the entity.
+    /// * `foreign_metadata` - The metadata of the foreign version.
+    /// * `foreign_history`  - The earlier hashes of the foreign version.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or the
+    /// merge function fails, an error will be returned.
+    ///
+    fn apply_version(
+        id: Id,
+        foreign: Vec<u8>,
+        ancestors: &[ChildInfo],
+        foreign_metadata: Metadata,
+        foreign_history: &[[u8; 32]],
+    ) -> Result<(), StorageError> {
+        let (data, metadata, history) =
+            match Self::merge_with_local(id, &foreign, foreign_metadata, foreign_history)? {
+                Reconciled::LastWriteWins => (foreign, foreign_metadata, foreign_history.to_vec()),
+                Reconciled::Local => return Ok(()),
+                Reconciled::Foreign(superseding) => {
+                    (foreign, superseding, foreign_history.to_vec())
+                }
+                Reconciled::Merged(merged, merged_metadata, merged_history) => {
+                    (merged, merged_metadata, merged_history)
+                }
+            };
+
+        let local_metadata = <Index<S>>::get_metadata(id)?;
+
+        if local_metadata.is_some_and(|local| local.updated_at > metadata.updated_at) {
+            // The local version is newer, so it must stay indexed as
+            // is. Saving would reject the data further down anyway,
+            // but only after the parent had indexed the stale hash,
+            // leaving it disagreeing with the data actually stored.
+            return Ok(());
+        }
+
+        if let Some(parent) = ancestors.first() {
+            let own_hash = Sha256::digest(&data).into();
+
+            <Index<S>>::add_child_to(
+                parent.id(),
+                "no collection, remove this nonsense",
+                ChildInfo::new(id, own_hash, metadata),
+            )?;
+        }
+
+        if Self::save_internal(id, &data, metadata)?.is_none() {
+            // we didn't save anything, so we skip updating the ancestors
+            return Ok(());
+        }
+
+        <Index<S>>::extend_history(id, &history)?;
+
+        let kind = if local_metadata.is_some() {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Added
+        };
+
+        sync::record_change(EntityChange::new(
+            id,
+            kind,
+            &<Index<S>>::get_ancestors_of(id)?,
+        ));
+
+        sync::push_action(Action::Compare { id });
+
+        Self::adopt_orphans(id)
+    }
+
     /// The children of the [`Collection`].
     ///
     /// This gets the children of the [`Collection`], which are the [`Element`](crate::entities::Element)s
@@ -558,6 +780,56 @@ impl<S: StorageAdaptor> Interface<S> {
         <Index<S>>::get_children_of(parent_id, collection.name())
     }
 
+    /// A page of basic info for children of the [`Collection`].
+    ///
+    /// This is the same as [`child_info_for()`](Interface::child_info_for()),
+    /// except that only up to `limit` children are returned, starting after
+    /// the given [`Cursor`]. This allows large collections to be traversed
+    /// without holding every child in memory at once.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id`  - The ID of the parent entity that owns the
+    ///                  [`Collection`].
+    /// * `collection` - The [`Collection`] to get the children of.
+    /// * `cursor`     - The position after which to start, or [`None`] to
+    ///                  start from the beginning.
+    /// * `limit`      - The maximum number of children to return.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub fn child_info_page_for<C: Collection>(
+        parent_id: Id,
+        collection: &C,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Result<Vec<ChildInfo>, StorageError> {
+        <Index<S>>::get_children_page_of(parent_id, collection.name(), cursor, limit)
+    }
+
+    /// The number of children of the [`Collection`].
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id`  - The ID of the parent entity that owns the
+    ///                  [`Collection`].
+    /// * `collection` - The [`Collection`] to count the children of.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub fn child_count_for<C: Collection>(
+        parent_id: Id,
+        collection: &C,
+    ) -> Result<usize, StorageError> {
+        <Index<S>>::get_child_count_of(parent_id, collection.name())
+    }
+
     /// Compares a foreign entity with a local one.
     ///
     /// This function compares a foreign entity, usually from a remote node,
@@ -591,6 +863,20 @@ impl<S: StorageAdaptor> Interface<S> {
 
         let Some(local_entity) = Self::find_by_id_raw(id) else {
             if let Some(foreign_entity) = foreign_entity_data {
+                if let Some(deleted_at) = Self::deleted_at(id)? {
+                    if deleted_at >= *foreign_index_data.metadata.updated_at {
+                        // The foreign version was deleted here, so it needs
+                        // to be deleted there too
+                        actions.1.push(Action::Delete {
+                            id,
+                            ancestors: foreign_index_data.ancestors,
+                            deleted_at,
+                        });
+
+                        return Ok(actions);
+                    }
+                }
+
                 // Local entity doesn't exist, so we need to add it
                 actions.0.push(Action::Add {
                     id,
@@ -613,28 +899,70 @@ impl<S: StorageAdaptor> Interface<S> {
             return Ok(actions);
         }
 
+        let reconciled = match foreign_entity_data.as_deref() {
+            Some(foreign) if local_own_hash != foreign_index_data.own_hash => {
+                Self::merge_with_local(
+                    id,
+                    foreign,
+                    foreign_index_data.metadata,
+                    &foreign_index_data.history,
+                )?
+            }
+            _ => Reconciled::LastWriteWins,
+        };
+
         // Compare own hashes and timestamps
-        if local_own_hash != foreign_index_data.own_hash {
-            match foreign_entity_data {
-                Some(foreign_entity_data)
-                    if local_metadata.updated_at <= foreign_index_data.metadata.updated_at =>
-                {
-                    actions.0.push(Action::Update {
-                        id,
-                        data: foreign_entity_data,
-                        ancestors: foreign_index_data.ancestors,
-                        metadata: foreign_index_data.metadata,
-                    });
-                }
-                _ => {
-                    actions.1.push(Action::Update {
-                        id,
-                        data: local_entity,
-                        ancestors: <Index<S>>::get_ancestors_of(id)?,
-                        metadata: local_metadata,
-                    });
-                }
+        match (reconciled, foreign_entity_data) {
+            (Reconciled::Merged(merged, merged_metadata, merged_history), _) => {
+                // Both sides receive the merged result
+                actions.0.push(Action::Update {
+                    id,
+                    data: merged.clone(),
+                    ancestors: foreign_index_data.ancestors.clone(),
+                    metadata: merged_metadata,
+                    history: merged_history.clone(),
+                });
+                actions.1.push(Action::Update {
+                    id,
+                    data: merged,
+                    ancestors: <Index<S>>::get_ancestors_of(id)?,
+                    metadata: merged_metadata,
+                    history: merged_history,
+                });
             }
+            (Reconciled::Foreign(superseding), Some(foreign)) => {
+                actions.0.push(Action::Update {
+                    id,
+                    data: foreign,
+                    ancestors: foreign_index_data.ancestors.clone(),
+                    metadata: superseding,
+                    history: foreign_index_data.history.clone(),
+                });
+            }
+            (Reconciled::LastWriteWins, Some(foreign))
+                if local_own_hash != foreign_index_data.own_hash
+                    && local_metadata.updated_at <= foreign_index_data.metadata.updated_at =>
+            {
+                actions.0.push(Action::Update {
+                    id,
+                    data: foreign,
+                    ancestors: foreign_index_data.ancestors.clone(),
+                    metadata: foreign_index_data.metadata,
+                    history: foreign_index_data.history.clone(),
+                });
+            }
+            (Reconciled::Local | Reconciled::LastWriteWins, _)
+                if local_own_hash != foreign_index_data.own_hash =>
+            {
+                actions.1.push(Action::Update {
+                    id,
+                    data: local_entity,
+                    ancestors: <Index<S>>::get_ancestors_of(id)?,
+                    metadata: local_metadata,
+                    history: <Index<S>>::get_history(id)?,
+                });
+            }
+            _ => {}
         }
 
         // The list of collections from the type will be the same on both sides, as
@@ -673,10 +1001,14 @@ impl<S: StorageAdaptor> Interface<S> {
                                 let metadata = <Index<S>>::get_metadata(*child_id)?
                                     .ok_or(StorageError::IndexNotFound(*child_id))?;
 
+                                // The child's own ancestors, starting with
+                                // its parent, and not those of the entity
+                                // being compared, which would leave the other
+                                // side with nowhere to attach it
                                 actions.1.push(Action::Add {
                                     id: *child_id,
                                     data: local_child,
-                                    ancestors: <Index<S>>::get_ancestors_of(id)?,
+                                    ancestors: <Index<S>>::get_ancestors_of(*child_id)?,
                                     metadata,
                                 });
                             }
@@ -725,6 +1057,90 @@ impl<S: StorageAdaptor> Interface<S> {
         Ok(actions)
     }
 
+    /// Combines a foreign version of an entity with the local one.
+    ///
+    /// This uses the merge function registered for the entity, or for the
+    /// collection it belongs to, if there is one. If there is no merge
+    /// function, or no local version, the standard "last write wins" behaviour
+    /// applies.
+    ///
+    /// Otherwise, the histories of the two versions are used to tell whether
+    /// one simply supersedes the other, in which case that one is kept, or
+    /// whether both were changed independently. Only in the latter case are
+    /// they merged, with the merged data taking the metadata of the newer
+    /// version, and a history covering both.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`       - The [`Id`] of the entity.
+    /// * `foreign`  - The serialised foreign version of the entity.
+    /// * `metadata` - The metadata of the foreign version.
+    /// * `history`  - The earlier hashes of the foreign version.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or the
+    /// merge function fails, an error will be returned.
+    ///
+    fn merge_with_local(
+        id: Id,
+        foreign: &[u8],
+        mut metadata: Metadata,
+        history: &[[u8; 32]],
+    ) -> Result<Reconciled, StorageError> {
+        let Some(local) = Self::find_by_id_raw(id) else {
+            return Ok(Reconciled::LastWriteWins);
+        };
+
+        let ancestors = <Index<S>>::get_ancestors_of(id)?
+            .iter()
+            .map(ChildInfo::id)
+            .collect::<Vec<_>>();
+
+        let Some(merge) = merge::lookup(id, &ancestors) else {
+            return Ok(Reconciled::LastWriteWins);
+        };
+
+        if local == foreign {
+            return Ok(Reconciled::Local);
+        }
+
+        let local_hash: [u8; 32] = Sha256::digest(&local).into();
+        let foreign_hash: [u8; 32] = Sha256::digest(foreign).into();
+        let local_history = <Index<S>>::get_history(id)?;
+        let local_metadata =
+            <Index<S>>::get_metadata(id)?.ok_or(StorageError::IndexNotFound(id))?;
+
+        if local_history.contains(&foreign_hash) {
+            return Ok(Reconciled::Local);
+        }
+
+        if history.contains(&local_hash) {
+            // The foreign version carries on from the local one, so it must
+            // not lose to it even if the clocks disagree
+            *metadata.updated_at = (*metadata.updated_at).max(*local_metadata.updated_at);
+            return Ok(Reconciled::Foreign(metadata));
+        }
+
+        let (newer, metadata) = if local_metadata.updated_at <= metadata.updated_at {
+            (Newer::Foreign, metadata)
+        } else {
+            (Newer::Local, local_metadata)
+        };
+
+        let merged_history = [local_hash, foreign_hash]
+            .into_iter()
+            .chain(local_history)
+            .chain(history.iter().copied())
+            .collect();
+
+        Ok(Reconciled::Merged(
+            merge(&local, foreign, newer)?,
+            metadata,
+            merged_history,
+        ))
+    }
+
     /// Compares a foreign entity with a local one, and applies the resulting
     /// actions to bring the two entities into sync.
     ///
@@ -754,6 +1170,17 @@ impl<S: StorageAdaptor> Interface<S> {
         Ok(())
     }
 
+    /// Gathers diagnostic information about the storage system.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub fn diagnostics() -> Result<Diagnostics, StorageError> {
+        diagnostics(S::storage_read)
+    }
+
     /// Finds an [`Element`](crate::entities::Element) by its unique identifier.
     ///
     /// This will always retrieve a single [`Element`](crate::entities::Element),
@@ -865,6 +1292,26 @@ impl<S: StorageAdaptor> Interface<S> {
         Ok(children)
     }
 
+    /// Generates a Merkle inclusion proof for an entity.
+    ///
+    /// The proof contains the data of the entity along with the hashes needed
+    /// to recompute the root hash, and can be checked against a trusted root
+    /// hash using [`MerkleProof::verify()`]. See the [`proof`](crate::proof)
+    /// module for more information.
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The [`Id`] of the entity to generate the proof for.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned. If the entity does not exist, [`None`] is returned.
+    ///
+    pub fn generate_proof(id: Id) -> Result<Option<MerkleProof>, StorageError> {
+        index::generate_proof(id, S::storage_read)
+    }
+
     /// Generates comparison data for an entity.
     ///
     /// This function generates comparison data for the specified entity, which
@@ -899,6 +1346,8 @@ impl<S: StorageAdaptor> Interface<S> {
             })
             .collect::<Result<BTreeMap<_, _>, _>>()?;
 
+        let history = <Index<S>>::get_history(id)?;
+
         Ok(ComparisonData {
             id,
             own_hash,
@@ -906,6 +1355,7 @@ impl<S: StorageAdaptor> Interface<S> {
             ancestors,
             children,
             metadata,
+            history,
         })
     }
 
@@ -949,8 +1399,25 @@ impl<S: StorageAdaptor> Interface<S> {
             .map_or_else(|| Ok(None), |parent_id| Self::find_by_id(parent_id))
     }
 
+    /// Discards any orphans that have outlived the expiry period.
+    ///
+    /// Returns the number of orphans discarded. See the [`orphans`](crate::orphans)
+    /// module for more information.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub fn purge_expired_orphans() -> Result<usize, StorageError> {
+        <Orphans<S>>::purge_expired()
+    }
+
     /// Removes a child from a collection.
     ///
+    /// Any descendants of the child, such as the elements of a nested
+    /// collection, are removed along with it.
+    ///
     /// # Parameters
     ///
     /// * `parent_id`  - The ID of the parent entity that owns the
@@ -975,6 +1442,17 @@ impl<S: StorageAdaptor> Interface<S> {
             return Ok(false);
         }
 
+        let deleted_at = <Index<S>>::get_metadata(child_id)?
+            .map_or_else(time_now, |metadata| time_now().max(*metadata.updated_at));
+
+        sync::record_change(EntityChange::new(
+            child_id,
+            ChangeKind::Deleted,
+            &<Index<S>>::get_ancestors_of(child_id)?,
+        ));
+
+        Self::remove_descendants_of(child_id, deleted_at)?;
+
         <Index<S>>::remove_child_from(parent_id, collection.name(), child_id)?;
 
         let (parent_full_hash, _) =
@@ -986,14 +1464,128 @@ impl<S: StorageAdaptor> Interface<S> {
 
         _ = S::storage_remove(Key::Entry(child_id));
 
+        Self::bury(child_id, deleted_at)?;
+
         sync::push_action(Action::Delete {
             id: child_id,
             ancestors,
+            deleted_at,
         });
 
         Ok(true)
     }
 
+    /// Removes all descendants of an entity from the storage system.
+    ///
+    /// This is used when an entity is removed, so that anything nested beneath
+    /// it, such as the elements of a nested collection, does not linger in the
+    /// storage system. The entity itself is not removed.
+    ///
+    /// Each descendant is left with a tombstone, so that versions of it which
+    /// were changed elsewhere before the removal are not brought back when
+    /// they arrive later.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`         - The [`Id`] of the entity whose descendants are to be
+    ///                  removed.
+    /// * `deleted_at` - When the entity was deleted.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    fn remove_descendants_of(id: Id, deleted_at: u64) -> Result<(), StorageError> {
+        for collection in <Index<S>>::get_collection_names_for(id)? {
+            for child in <Index<S>>::get_children_of(id, &collection)? {
+                sync::record_change(EntityChange::new(
+                    child.id(),
+                    ChangeKind::Deleted,
+                    &<Index<S>>::get_ancestors_of(child.id())?,
+                ));
+
+                Self::remove_descendants_of(child.id(), deleted_at)?;
+
+                <Index<S>>::remove_index(child.id());
+                _ = S::storage_remove(Key::Entry(child.id()));
+
+                Self::bury(child.id(), deleted_at)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes an entity from whichever collection of its parent holds it.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id` - The [`Id`] of the parent entity.
+    /// * `child_id`  - The [`Id`] of the entity to remove.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    fn detach_from(parent_id: Id, child_id: Id) -> Result<(), StorageError> {
+        for collection in <Index<S>>::get_collection_names_for(parent_id)? {
+            if <Index<S>>::get_children_of(parent_id, &collection)?
+                .iter()
+                .any(|child| child.id() == child_id)
+            {
+                return <Index<S>>::remove_child_from(parent_id, &collection, child_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Leaves a tombstone for a deleted entity, recording when it was deleted.
+    ///
+    /// A later deletion of the same entity moves the tombstone on, but an
+    /// earlier one leaves it as it is.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`         - The [`Id`] of the deleted entity.
+    /// * `deleted_at` - When the entity was deleted.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    fn bury(id: Id, deleted_at: u64) -> Result<(), StorageError> {
+        if Self::deleted_at(id)?.is_some_and(|buried_at| buried_at >= deleted_at) {
+            return Ok(());
+        }
+
+        let data = to_vec(&deleted_at).map_err(StorageError::SerializationError)?;
+
+        _ = S::storage_write(Key::Tombstone(id), &data);
+
+        Ok(())
+    }
+
+    /// When an entity was last deleted, if it has ever been.
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The [`Id`] of the entity.
+    ///
+    /// # Errors
+    ///
+    /// If the tombstone cannot be deserialised, an error will be returned.
+    ///
+    fn deleted_at(id: Id) -> Result<Option<u64>, StorageError> {
+        S::storage_read(Key::Tombstone(id))
+            .map(|data| from_slice(&data))
+            .transpose()
+            .map_err(StorageError::DeserializationError)
+    }
+
     /// Retrieves the root entity for a given context.
     ///
     /// # Parameters
@@ -1179,6 +1771,14 @@ impl<S: StorageAdaptor> Interface<S> {
 
         let ancestors = <Index<S>>::get_ancestors_of(id)?;
 
+        let kind = if is_new {
+            ChangeKind::Added
+        } else {
+            ChangeKind::Updated
+        };
+
+        sync::record_change(EntityChange::new(id, kind, &ancestors));
+
         let action = if is_new {
             Action::Add {
                 id,
@@ -1192,11 +1792,14 @@ impl<S: StorageAdaptor> Interface<S> {
                 data,
                 ancestors,
                 metadata,
+                history: <Index<S>>::get_history(id)?,
             }
         };
 
         sync::push_action(action);
 
+        Self::adopt_orphans(id)?;
+
         Ok(Some(full_hash))
     }
 
@@ -1217,6 +1820,30 @@ impl<S: StorageAdaptor> Interface<S> {
     }
 }
 
+/// Gathers diagnostic information about the storage system.
+///
+/// This reads the stored data directly through the supplied function, rather
+/// than through a [`StorageAdaptor`], so that a node can report on a context's
+/// storage from its own store.
+///
+/// # Parameters
+///
+/// * `read` - A function that reads the raw value for a storage [`Key`].
+///
+/// # Errors
+///
+/// If there's an issue reading or deserialising the stored data, an error will
+/// be returned.
+///
+pub fn diagnostics<F>(read: F) -> Result<Diagnostics, StorageError>
+where
+    F: Fn(Key) -> Option<Vec<u8>>,
+{
+    Ok(Diagnostics {
+        orphans: orphans::count(&read)?,
+    })
+}
+
 /// Errors that can occur when working with the storage system.
 #[derive(Debug, ThisError)]
 #[non_exhaustive]
@@ -1227,7 +1854,9 @@ pub enum StorageError {
 
     /// An attempt was made to create an orphan, i.e. an entity that has not
     /// been registered as either a root or having a parent. This was probably
-    /// cause by calling `save()` without calling `add_child_to()` first.
+    /// cause by calling `save()` without calling `add_child_to()` first. Note
+    /// that this only applies to local changes, as incoming actions for which
+    /// the parent is missing are held in the [`orphans`](crate::orphans) area.
     #[error("Cannot create orphan with ID: {0}")]
     CannotCreateOrphan(Id),
 
@@ -60,13 +60,17 @@ pub mod address;
 pub mod collections;
 pub mod entities;
 pub mod env;
+pub mod guard;
 pub mod index;
 pub mod integration;
 pub mod interface;
+pub mod merge;
+pub mod orphans;
+pub mod proof;
 pub mod store;
 pub mod sync;
 
-pub use calimero_storage_macros::{AtomicUnit, Collection};
+pub use calimero_storage_macros::{AtomicUnit, Collection, Merge};
 
 /// Re-exported types, mostly for use in macros (for convenience).
 pub mod exports {
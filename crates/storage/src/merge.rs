@@ -0,0 +1,363 @@
+//! Merge strategies for resolving conflicts during synchronisation.
+//!
+//! By default, when the local and a foreign version of an entity differ, the
+//! version with the most recent [`updated_at()`](crate::entities::Element::updated_at())
+//! timestamp replaces the other one wholesale — i.e. "last write wins".
+//!
+//! Types can define a different way of combining the two versions by
+//! implementing the [`Merge`] trait, usually through `#[derive(Merge)]` from
+//! [`calimero_storage_macros`](crate::Merge), which allows a strategy to be
+//! chosen for each field. For the merge to be used, a [`MergeFn`] needs to be
+//! registered for the ID of the entity using [`register()`], or for the
+//! collection holding it using [`register_children()`]. The application root
+//! state is registered automatically when synchronising through
+//! [`Root::sync()`](crate::collections::Root::sync()), along with any of its
+//! collections marked `#[merge(nested)]`, and the collections nested within
+//! those, so that their values are merged by type wherever they are found.
+//!
+//! Whenever both sides hold a version of a registered entity that was changed
+//! independently of the other, the synchronisation process will invoke the
+//! merge function, and both sides will receive the merged result. A version
+//! that simply supersedes the other, as recorded in the history of each
+//! entity, replaces it without a merge. Merge functions should be commutative,
+//! so that all nodes converge on the same state regardless of the order in
+//! which updates arrive.
+//!
+
+#[cfg(test)]
+#[path = "tests/merge.rs"]
+mod tests;
+
+use core::cell::RefCell;
+use std::collections::BTreeMap;
+
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+use crate::address::Id;
+use crate::entities::Data;
+use crate::env::time_now;
+use crate::interface::StorageError;
+
+/// Which of the two versions being merged was updated most recently.
+///
+/// This is the information that "last write wins" is based upon, and is made
+/// available to merge strategies so that they can apply it selectively.
+///
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Newer {
+    /// The local version is newer.
+    Local,
+
+    /// The foreign version is newer, or both have the same timestamp.
+    Foreign,
+}
+
+/// A function that merges the serialised local and foreign versions of an
+/// entity, returning the serialised result.
+pub type MergeFn = fn(&[u8], &[u8], Newer) -> Result<Vec<u8>, StorageError>;
+
+thread_local! {
+    static MERGE_FNS: RefCell<BTreeMap<Id, MergeFn>> = const { RefCell::new(BTreeMap::new()) };
+    static CHILDREN: RefCell<BTreeMap<Id, Children>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// How the entities beneath a collection are merged.
+///
+/// The children of a collection are the entries holding its values, which are
+/// merged using the entry [`MergeFn`]. Where the values are collections too,
+/// each entry has a single child of its own, the nested collection, whose
+/// entries are merged in the same way in turn.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Children {
+    /// The merge function for the entries of the collection.
+    entry: MergeFn,
+
+    /// How the entries of the collections nested in the values are merged, if
+    /// the values are collections.
+    nested: fn() -> Option<Self>,
+}
+
+impl Children {
+    /// Creates a new [`Children`].
+    ///
+    /// # Parameters
+    ///
+    /// * `entry`  - The merge function for the entries of the collection.
+    /// * `nested` - How the entries of nested collections are merged.
+    ///
+    #[must_use]
+    pub const fn new(entry: MergeFn, nested: fn() -> Option<Self>) -> Self {
+        Self { entry, nested }
+    }
+
+    /// The merge function for a descendant of the collection, at the given
+    /// depth below its entries.
+    fn at(self, depth: usize) -> Option<MergeFn> {
+        match depth {
+            0 => Some(self.entry),
+            // The nested collection, which has nothing to merge of its own
+            1 => None,
+            _ => (self.nested)()?.at(depth.saturating_sub(2)),
+        }
+    }
+}
+
+/// Defines how a local and a foreign version of a value are combined.
+///
+/// The default implementation is "last write wins", so an empty `impl` block
+/// retains the standard behaviour.
+///
+pub trait Merge {
+    /// Merges the foreign version into this, the local version.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - The foreign version of the value.
+    /// * `newer` - Which of the two versions was updated most recently.
+    ///
+    fn merge(&mut self, other: Self, newer: Newer)
+    where
+        Self: Sized,
+    {
+        strategy::lww(self, other, newer);
+    }
+
+    /// How the entities beneath a value of this type are merged.
+    ///
+    /// This is [`None`] unless the type is a collection of values that
+    /// implement [`Merge`].
+    ///
+    #[must_use]
+    fn children() -> Option<Children>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Registers how the entities beneath this value are merged.
+    ///
+    /// Collections register their [`children()`](Merge::children()), and
+    /// `#[derive(Merge)]` registers each field marked `#[merge(nested)]`.
+    ///
+    fn register(&self) {}
+}
+
+/// Registers a merge function for an entity.
+///
+/// Any previously-registered function for the same entity is replaced.
+///
+/// # Parameters
+///
+/// * `id`    - The [`Id`] of the entity to which the merge function applies.
+/// * `merge` - The merge function to use.
+///
+pub fn register(id: Id, merge: MergeFn) {
+    let _ignored = MERGE_FNS.with(|fns| fns.borrow_mut().insert(id, merge));
+}
+
+/// Registers how the entities beneath a collection are merged.
+///
+/// Any previous registration for the same collection is replaced.
+///
+/// # Parameters
+///
+/// * `id`       - The [`Id`] of the collection.
+/// * `children` - How the entities beneath the collection are merged.
+///
+pub fn register_children(id: Id, children: Children) {
+    let _ignored = CHILDREN.with(|registry| registry.borrow_mut().insert(id, children));
+}
+
+/// Looks up the merge function for an entity.
+///
+/// A merge function registered for the entity itself takes precedence.
+/// Otherwise, the nearest of its ancestors that is a registered collection
+/// determines how it is merged.
+///
+/// # Parameters
+///
+/// * `id`        - The [`Id`] of the entity.
+/// * `ancestors` - The [`Id`]s of the ancestors of the entity, starting with
+///                 its parent.
+///
+pub(crate) fn lookup(id: Id, ancestors: &[Id]) -> Option<MergeFn> {
+    if let Some(merge) = MERGE_FNS.with(|fns| fns.borrow().get(&id).copied()) {
+        return Some(merge);
+    }
+
+    CHILDREN.with(|registry| {
+        let registered = registry.borrow();
+        ancestors
+            .iter()
+            .enumerate()
+            .find_map(|(depth, ancestor)| {
+                registered.get(ancestor).map(|children| children.at(depth))
+            })
+            .flatten()
+    })
+}
+
+/// Merges the serialised versions of a [`Data`] type using its [`Merge`]
+/// implementation.
+///
+/// This is a [`MergeFn`], and is intended to be passed to [`register()`].
+///
+/// # Errors
+///
+/// If either version cannot be deserialised, or the result cannot be
+/// serialised, an error will be returned.
+///
+pub fn merge_data<D: Data + Merge>(
+    local: &[u8],
+    foreign: &[u8],
+    newer: Newer,
+) -> Result<Vec<u8>, StorageError> {
+    let mut local = from_slice::<D>(local).map_err(StorageError::DeserializationError)?;
+    let foreign = from_slice::<D>(foreign).map_err(StorageError::DeserializationError)?;
+
+    local.merge(foreign, newer);
+
+    to_vec(&local).map_err(StorageError::SerializationError)
+}
+
+/// A set whose elements can be added and removed on different nodes, and
+/// merged using [`strategy::union`].
+///
+/// Each element records when it was last added and last removed, so that a
+/// removal is kept when merged with a version that still holds the element,
+/// unless the element was added again after it was removed. Removed elements
+/// are therefore retained, but not reported as members.
+///
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct UnionSet<T: Ord> {
+    /// The elements, with when each was last added and last removed.
+    elements: BTreeMap<T, (u64, u64)>,
+}
+
+impl<T: Ord> UnionSet<T> {
+    /// Creates a new, empty [`UnionSet`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            elements: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an element to the set.
+    ///
+    /// Returns whether the element was newly added.
+    ///
+    pub fn insert(&mut self, value: T) -> bool {
+        let times = self.elements.entry(value).or_insert((0, 0));
+        let absent = times.0 <= times.1;
+        if absent {
+            times.0 = time_now().max(times.1.saturating_add(1));
+        }
+        absent
+    }
+
+    /// Removes an element from the set.
+    ///
+    /// Returns whether the element was present.
+    ///
+    pub fn remove(&mut self, value: &T) -> bool {
+        match self.elements.get_mut(value) {
+            Some(times) if times.0 > times.1 => {
+                times.1 = time_now().max(times.0);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the set contains an element.
+    pub fn contains(&self, value: &T) -> bool {
+        self.elements
+            .get(value)
+            .is_some_and(|times| times.0 > times.1)
+    }
+
+    /// The elements of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.elements
+            .iter()
+            .filter(|&(_, times)| times.0 > times.1)
+            .map(|(value, _)| value)
+    }
+
+    /// The number of elements in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Whether the set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+impl<T: Ord> Default for UnionSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for UnionSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for value in iter {
+            let _ignored = set.insert(value);
+        }
+        set
+    }
+}
+
+/// Field-level merge strategies.
+///
+/// These are the functions used by `#[derive(Merge)]` for the respective
+/// `#[merge(...)]` field attributes, and can also be used directly in manual
+/// [`Merge`] implementations.
+///
+pub mod strategy {
+    use super::{Merge, Newer, UnionSet};
+
+    /// Keeps whichever version was updated most recently. This is the default.
+    pub fn lww<T>(local: &mut T, foreign: T, newer: Newer) {
+        if newer == Newer::Foreign {
+            *local = foreign;
+        }
+    }
+
+    /// Keeps the greater of the two versions.
+    pub fn max<T: Ord>(local: &mut T, foreign: T, _newer: Newer) {
+        if foreign > *local {
+            *local = foreign;
+        }
+    }
+
+    /// Keeps the lesser of the two versions.
+    pub fn min<T: Ord>(local: &mut T, foreign: T, _newer: Newer) {
+        if foreign < *local {
+            *local = foreign;
+        }
+    }
+
+    /// Combines the elements of both versions, keeping the removal of an
+    /// element on either side unless it was added again afterwards.
+    pub fn union<T: Ord>(local: &mut UnionSet<T>, foreign: UnionSet<T>, _newer: Newer) {
+        for (value, (added_at, removed_at)) in foreign.elements {
+            let times = local.elements.entry(value).or_insert((0, 0));
+            times.0 = times.0.max(added_at);
+            times.1 = times.1.max(removed_at);
+        }
+    }
+
+    /// Merges the two versions using their own [`Merge`] implementation.
+    pub fn nested<T: Merge>(local: &mut T, foreign: T, newer: Newer) {
+        local.merge(foreign, newer);
+    }
+}
@@ -0,0 +1,345 @@
+//! Buffering of orphaned entities.
+//!
+//! Actions are not guaranteed to arrive in causal order, and so it is possible
+//! for an [`Add`](Action::Add) or [`Update`](Action::Update) to be received for
+//! a child before its parent exists locally. Rather than failing, which would
+//! cause the whole batch of actions to be rejected, such actions are held in an
+//! orphan area, keyed by the [`Id`] of the missing parent.
+//!
+//! When the parent is subsequently saved, whether through a direct action or as
+//! the result of a comparison, any orphans waiting for it are applied in the
+//! order in which they were received. This cascades, so that an entire missing
+//! subtree is attached as soon as its topmost ancestor arrives.
+//!
+//! Orphans that are not claimed within the [`expiry()`] period are discarded,
+//! as by that point the comparison process is expected to have brought the
+//! nodes back into sync.
+//!
+
+#[cfg(test)]
+#[path = "tests/orphans.rs"]
+mod tests;
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::time::Duration;
+use std::collections::BTreeSet;
+
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+use crate::address::Id;
+use crate::env::{orphan_expiry, time_now};
+use crate::interface::{Action, StorageError};
+use crate::store::{Key, StorageAdaptor};
+
+/// The default period after which unclaimed orphans are discarded.
+pub const DEFAULT_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+thread_local! {
+    static EXPIRY: Cell<Duration> = const { Cell::new(DEFAULT_EXPIRY) };
+}
+
+/// Sets the period after which unclaimed orphans are discarded, where the node
+/// has not configured one.
+///
+/// # Parameters
+///
+/// * `expiry` - The new expiry period.
+///
+pub fn set_expiry(expiry: Duration) {
+    EXPIRY.with(|cell| cell.set(expiry));
+}
+
+/// The period after which unclaimed orphans are discarded.
+///
+/// This is the period configured by the node, if any, and otherwise the one
+/// set through [`set_expiry()`].
+///
+#[must_use]
+pub fn expiry() -> Duration {
+    orphan_expiry().unwrap_or_else(|| EXPIRY.with(Cell::get))
+}
+
+/// An action waiting for its parent to arrive.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+struct Orphan {
+    /// The action to apply once the parent exists.
+    action: Action,
+
+    /// The timestamp at which the action was buffered, in nanoseconds.
+    received_at: u64,
+}
+
+impl Orphan {
+    /// Whether the orphan has outlived the expiry period.
+    fn is_expired(&self, now: u64) -> bool {
+        let expiry = u64::try_from(expiry().as_nanos()).unwrap_or(u64::MAX);
+
+        now.saturating_sub(self.received_at) > expiry
+    }
+
+    /// The [`Id`] of the entity the action is for, and when it was last
+    /// updated, if it carries a version of the entity.
+    fn version(&self) -> Option<(Id, u64)> {
+        match self.action {
+            Action::Add { id, metadata, .. } | Action::Update { id, metadata, .. } => {
+                Some((id, *metadata.updated_at))
+            }
+            Action::Compare { .. } | Action::Delete { .. } => None,
+        }
+    }
+}
+
+/// Manages the orphan area.
+pub(crate) struct Orphans<S: StorageAdaptor>(PhantomData<S>);
+
+impl<S: StorageAdaptor> Orphans<S> {
+    /// Buffers an action until its parent arrives.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id` - The [`Id`] of the missing parent.
+    /// * `action`    - The [`Action`] to apply once the parent exists.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue reading or writing the orphan area, an error will
+    /// be returned.
+    ///
+    pub(crate) fn buffer(parent_id: Id, action: Action) -> Result<(), StorageError> {
+        let mut orphans = Self::get(parent_id)?;
+
+        if orphans.iter().any(|orphan| orphan.action == action) {
+            return Ok(());
+        }
+
+        orphans.push(Orphan {
+            action,
+            received_at: time_now(),
+        });
+
+        Self::save(parent_id, &orphans)?;
+
+        let mut parents = Self::parents()?;
+
+        if parents.insert(parent_id) {
+            Self::save_parents(&parents)?;
+        }
+
+        Ok(())
+    }
+
+    /// Discards the orphans superseded by the deletion of an entity.
+    ///
+    /// This covers any buffered versions of the entity itself, along with any
+    /// of its descendants waiting for it to arrive, that were last updated no
+    /// later than the deletion. Otherwise these would be applied, and thus
+    /// brought back, should the parent they were waiting for turn up.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`         - The [`Id`] of the entity that has been deleted.
+    /// * `deleted_at` - The timestamp at which the entity was deleted.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue reading or writing the orphan area, an error will
+    /// be returned.
+    ///
+    pub(crate) fn discard(id: Id, deleted_at: u64) -> Result<(), StorageError> {
+        let mut parents = Self::parents()?;
+        let before = parents.len();
+        let mut pending = vec![id];
+
+        while let Some(deleted_id) = pending.pop() {
+            for parent_id in parents.clone() {
+                let mut orphans = Self::get(parent_id)?;
+                let count = orphans.len();
+
+                orphans.retain(|orphan| {
+                    let Some((orphan_id, updated_at)) = orphan.version() else {
+                        return true;
+                    };
+
+                    if updated_at > deleted_at {
+                        return true;
+                    }
+
+                    if parent_id == deleted_id {
+                        pending.push(orphan_id);
+                    } else if orphan_id != deleted_id {
+                        return true;
+                    }
+
+                    false
+                });
+
+                if orphans.len() == count {
+                    continue;
+                }
+
+                if orphans.is_empty() {
+                    _ = parents.remove(&parent_id);
+                }
+
+                Self::save(parent_id, &orphans)?;
+            }
+        }
+
+        if parents.len() != before {
+            Self::save_parents(&parents)?;
+        }
+
+        Ok(())
+    }
+
+    /// Discards all orphans that have outlived the expiry period.
+    ///
+    /// Returns the number of orphans discarded.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue reading or writing the orphan area, an error will
+    /// be returned.
+    ///
+    pub(crate) fn purge_expired() -> Result<usize, StorageError> {
+        let now = time_now();
+        let mut parents = Self::parents()?;
+        let mut purged = 0;
+
+        for parent_id in parents.clone() {
+            let mut orphans = Self::get(parent_id)?;
+            let before = orphans.len();
+
+            orphans.retain(|orphan| !orphan.is_expired(now));
+
+            purged += before - orphans.len();
+
+            if orphans.is_empty() {
+                _ = parents.remove(&parent_id);
+            }
+
+            Self::save(parent_id, &orphans)?;
+        }
+
+        Self::save_parents(&parents)?;
+
+        Ok(purged)
+    }
+
+    /// Removes and returns the unexpired actions waiting for a parent, in the
+    /// order in which they were received.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id` - The [`Id`] of the parent that has arrived.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue reading or writing the orphan area, an error will
+    /// be returned.
+    ///
+    pub(crate) fn take(parent_id: Id) -> Result<Vec<Action>, StorageError> {
+        let orphans = Self::get(parent_id)?;
+
+        if orphans.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Self::save(parent_id, &[])?;
+
+        let mut parents = Self::parents()?;
+
+        if parents.remove(&parent_id) {
+            Self::save_parents(&parents)?;
+        }
+
+        let now = time_now();
+
+        Ok(orphans
+            .into_iter()
+            .filter(|orphan| !orphan.is_expired(now))
+            .map(|orphan| orphan.action)
+            .collect())
+    }
+
+    /// Retrieves the orphans waiting for a parent.
+    fn get(parent_id: Id) -> Result<Vec<Orphan>, StorageError> {
+        read_orphans(&S::storage_read, parent_id)
+    }
+
+    /// Saves the orphans waiting for a parent, removing the entry if empty.
+    fn save(parent_id: Id, orphans: &[Orphan]) -> Result<(), StorageError> {
+        if orphans.is_empty() {
+            _ = S::storage_remove(Key::Orphans(parent_id));
+        } else {
+            _ = S::storage_write(
+                Key::Orphans(parent_id),
+                &to_vec(orphans).map_err(StorageError::SerializationError)?,
+            );
+        }
+        Ok(())
+    }
+
+    /// Retrieves the IDs of all missing parents that have orphans waiting.
+    fn parents() -> Result<BTreeSet<Id>, StorageError> {
+        read_parents(&S::storage_read)
+    }
+
+    /// Saves the IDs of all missing parents that have orphans waiting.
+    fn save_parents(parents: &BTreeSet<Id>) -> Result<(), StorageError> {
+        if parents.is_empty() {
+            _ = S::storage_remove(Key::OrphanParents);
+        } else {
+            _ = S::storage_write(
+                Key::OrphanParents,
+                &to_vec(parents).map_err(StorageError::SerializationError)?,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Counts the orphans held, reading the orphan area through the supplied
+/// function.
+///
+/// # Parameters
+///
+/// * `read` - A function that reads the raw value for a storage [`Key`].
+///
+/// # Errors
+///
+/// If there's an issue reading the orphan area, an error will be returned.
+///
+pub(crate) fn count<F>(read: &F) -> Result<usize, StorageError>
+where
+    F: Fn(Key) -> Option<Vec<u8>>,
+{
+    read_parents(read)?
+        .into_iter()
+        .try_fold(0, |count, parent_id| {
+            Ok(count + read_orphans(read, parent_id)?.len())
+        })
+}
+
+/// Reads the orphans waiting for a parent.
+fn read_orphans<F>(read: &F, parent_id: Id) -> Result<Vec<Orphan>, StorageError>
+where
+    F: Fn(Key) -> Option<Vec<u8>>,
+{
+    read(Key::Orphans(parent_id)).map_or_else(
+        || Ok(vec![]),
+        |data| from_slice(&data).map_err(StorageError::DeserializationError),
+    )
+}
+
+/// Reads the IDs of all missing parents that have orphans waiting.
+fn read_parents<F>(read: &F) -> Result<BTreeSet<Id>, StorageError>
+where
+    F: Fn(Key) -> Option<Vec<u8>>,
+{
+    read(Key::OrphanParents).map_or_else(
+        || Ok(BTreeSet::new()),
+        |data| from_slice(&data).map_err(StorageError::DeserializationError),
+    )
+}
@@ -0,0 +1,135 @@
+//! Merkle inclusion proofs.
+//!
+//! Every entity in the storage system has an "own" hash, which is the hash of
+//! its serialised data, and a "full" hash, which combines the own hash with the
+//! full hashes of all of its children, in order. The full hash of the root
+//! entity is the root hash of the whole state, which is what gets committed and
+//! broadcast to other nodes.
+//!
+//! A [`MerkleProof`] contains the data of a single entity, the full hashes of
+//! its children, and, for each ancestor up to the root, the own hash of that
+//! ancestor together with the full hashes of the siblings to either side. This
+//! is enough to recompute the root hash from the entity data alone, and so a
+//! client that trusts a root hash can check that the data is part of the state
+//! it represents, without needing to trust the node that supplied it.
+//!
+//! Proofs are generated using [`Interface::generate_proof()`](crate::interface::Interface::generate_proof())
+//! or [`index::generate_proof()`](crate::index::generate_proof()). Verification
+//! is a pure function of the proof and the root hash, and this module depends
+//! only on [`core`], [`Vec`], and the hashing primitives, so that it can be used
+//! in constrained environments.
+//!
+
+#[cfg(test)]
+#[path = "tests/proof.rs"]
+mod tests;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A proof that some data is included in the state with a given root hash.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct MerkleProof {
+    /// The serialised data of the entity being proven.
+    pub data: Vec<u8>,
+
+    /// The full hashes of the entity's children, in order.
+    pub children: Vec<[u8; 32]>,
+
+    /// The steps from the entity's parent up to the root, in that order.
+    pub path: Vec<ProofStep>,
+}
+
+/// A single step in a [`MerkleProof`], representing one ancestor.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ProofStep {
+    /// The own hash of the ancestor.
+    pub own_hash: [u8; 32],
+
+    /// The full hashes of the children of the ancestor that come before the
+    /// child on the path, in order.
+    pub left: Vec<[u8; 32]>,
+
+    /// The full hashes of the children of the ancestor that come after the
+    /// child on the path, in order.
+    pub right: Vec<[u8; 32]>,
+}
+
+impl MerkleProof {
+    /// Creates a new [`MerkleProof`].
+    #[must_use]
+    pub const fn new(data: Vec<u8>, children: Vec<[u8; 32]>, path: Vec<ProofStep>) -> Self {
+        Self {
+            data,
+            children,
+            path,
+        }
+    }
+
+    /// The root hash implied by this proof.
+    ///
+    /// This is calculated by hashing the entity data, and then combining it
+    /// with the hashes at each step up to the root.
+    ///
+    #[must_use]
+    pub fn root_hash(&self) -> [u8; 32] {
+        let own_hash = Sha256::digest(&self.data).into();
+
+        let mut hash = full_hash(&own_hash, &[], None, &self.children);
+
+        for step in &self.path {
+            hash = full_hash(&step.own_hash, &step.left, Some(&hash), &step.right);
+        }
+
+        hash
+    }
+
+    /// Checks whether this proof is valid for the given root hash.
+    #[must_use]
+    pub fn verify(&self, root_hash: &[u8; 32]) -> bool {
+        self.root_hash() == *root_hash
+    }
+}
+
+impl ProofStep {
+    /// Creates a new [`ProofStep`].
+    #[must_use]
+    pub const fn new(own_hash: [u8; 32], left: Vec<[u8; 32]>, right: Vec<[u8; 32]>) -> Self {
+        Self {
+            own_hash,
+            left,
+            right,
+        }
+    }
+}
+
+/// Checks whether a [`MerkleProof`] is valid for the given root hash.
+///
+/// This is a convenience function that passes through to
+/// [`MerkleProof::verify()`].
+///
+#[must_use]
+pub fn verify(proof: &MerkleProof, root_hash: &[u8; 32]) -> bool {
+    proof.verify(root_hash)
+}
+
+/// Combines an own hash with the full hashes of the children of an entity, in
+/// the same way as the index does.
+fn full_hash(
+    own_hash: &[u8; 32],
+    left: &[[u8; 32]],
+    middle: Option<&[u8; 32]>,
+    right: &[[u8; 32]],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(own_hash);
+
+    for hash in left.iter().chain(middle).chain(right) {
+        hasher.update(hash);
+    }
+
+    hasher.finalize().into()
+}
@@ -14,6 +14,33 @@ pub enum Key {
 
     /// An entry key.
     Entry(Id),
+
+    /// The key for the orphans waiting for a missing parent.
+    Orphans(Id),
+
+    /// The key for the list of missing parents that have orphans waiting.
+    OrphanParents,
+
+    /// The key for the marker recording the application revision that the
+    /// state was last migrated to. This is maintained by the node, and is not
+    /// part of the index, so it does not affect the root hash.
+    Migration,
+
+    /// The key for the chunks making up the children of an entity in one of
+    /// its collections, or for one of those chunks.
+    Children(Id),
+
+    /// The key for the summary that a collection keeps of its children, such
+    /// as the order of a sequence. This is derived locally from the children,
+    /// and is not part of the index, so it is never synchronised and does not
+    /// affect the root hash.
+    Summary(Id),
+
+    /// The key for the tombstone left behind when an entity is deleted, which
+    /// records when the deletion happened. This is kept locally so that older
+    /// versions of the entity arriving later are not brought back, and is not
+    /// part of the index, so it does not affect the root hash.
+    Tombstone(Id),
 }
 
 impl Key {
@@ -30,6 +57,28 @@ impl Key {
                 bytes[0] = 1;
                 bytes[1..33].copy_from_slice(id.as_bytes());
             }
+            Self::Orphans(id) => {
+                bytes[0] = 2;
+                bytes[1..33].copy_from_slice(id.as_bytes());
+            }
+            Self::OrphanParents => {
+                bytes[0] = 3;
+            }
+            Self::Migration => {
+                bytes[0] = 4;
+            }
+            Self::Children(id) => {
+                bytes[0] = 5;
+                bytes[1..33].copy_from_slice(id.as_bytes());
+            }
+            Self::Summary(id) => {
+                bytes[0] = 6;
+                bytes[1..33].copy_from_slice(id.as_bytes());
+            }
+            Self::Tombstone(id) => {
+                bytes[0] = 7;
+                bytes[1..33].copy_from_slice(id.as_bytes());
+            }
         }
         Sha256::digest(bytes).into()
     }
@@ -1,13 +1,19 @@
 //! Synchronisation utilities for external runtimes.
 
+#[cfg(test)]
+#[path = "tests/sync.rs"]
+mod tests;
+
 use core::cell::RefCell;
 use std::io;
 
-use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
 
+use crate::address::Id;
+use crate::entities::ChildInfo;
 use crate::env;
 use crate::integration::Comparison;
-use crate::interface::Action;
+use crate::interface::{Action, StorageError};
 
 /// An artifact to aid synchronisation with an external runtime.
 #[derive(Debug, BorshSerialize)]
@@ -35,6 +41,7 @@ impl BorshDeserialize for SyncArtifact {
 thread_local! {
     static ACTIONS: RefCell<Vec<Action>> = const { RefCell::new(Vec::new())  };
     static COMPARISON: RefCell<Vec<Comparison>> = const { RefCell::new(Vec::new())  };
+    static CHANGES: RefCell<Vec<EntityChange>> = const { RefCell::new(Vec::new())  };
 }
 
 /// Records an action for eventual synchronisation.
@@ -47,6 +54,31 @@ pub fn push_action(action: Action) {
     ACTIONS.with(|actions| actions.borrow_mut().push(action));
 }
 
+/// Inspects the actions recorded so far.
+pub(crate) fn with_actions<R>(f: impl FnOnce(&[Action]) -> R) -> R {
+    ACTIONS.with(|actions| f(&actions.borrow()))
+}
+
+/// Takes the actions recorded so far, so that they are not synchronised.
+pub(crate) fn take_actions() -> Vec<Action> {
+    ACTIONS.with(RefCell::take)
+}
+
+/// Records a change made to an entity, to be reported to the runtime.
+///
+/// # Parameters
+///
+/// * `change` - The change to record.
+///
+pub(crate) fn record_change(change: EntityChange) {
+    CHANGES.with(|changes| changes.borrow_mut().push(change));
+}
+
+/// Takes the changes recorded so far.
+pub(crate) fn take_changes() -> Vec<EntityChange> {
+    CHANGES.with(RefCell::take)
+}
+
 /// Records a comparison for eventual synchronisation.
 ///
 /// # Parameters
@@ -58,7 +90,8 @@ pub fn push_comparison(comparison: Comparison) {
 }
 
 /// Commits the root hash to the runtime.
-/// This will also commit any recorded actions or comparisons.
+/// This will also commit any recorded actions or comparisons, along with the
+/// changes made to entities.
 /// If both actions and comparisons are present, this function will panic.
 /// This function must only be called once.
 ///
@@ -78,7 +111,137 @@ pub fn commit_root(root_hash: &[u8; 32]) -> eyre::Result<()> {
         _ => eyre::bail!("both actions and comparison are present"),
     };
 
+    let changes = take_changes();
+
+    if !changes.is_empty() {
+        env::commit_changes(&to_vec(&changes)?);
+    }
+
     env::commit(root_hash, &artifact);
 
     Ok(())
 }
+
+/// The kind of change made to an entity.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "These are the only kinds of change"
+)]
+pub enum ChangeKind {
+    /// The entity was added.
+    Added,
+
+    /// The entity was updated.
+    Updated,
+
+    /// The entity was deleted.
+    Deleted,
+}
+
+/// A change made to an entity.
+///
+/// Changes are recorded as they are made to the stored data, whether by the
+/// application itself or by applying actions received from other nodes, and
+/// so only describe what actually changed locally. Actions that are held back
+/// as orphans, or lose out to the local version, do not produce any.
+///
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EntityChange {
+    /// The unique identifier of the entity.
+    pub id: Id,
+
+    /// The kind of change made.
+    pub kind: ChangeKind,
+
+    /// The [`Id`] of the parent entity, which for the entries of a collection
+    /// is the collection itself. This is [`None`] for root entities.
+    pub collection: Option<Id>,
+
+    /// The IDs of the ancestors of the entity, from the root down to the
+    /// parent.
+    pub path: Vec<Id>,
+}
+
+impl EntityChange {
+    /// Creates a change to an entity in the given place in the hierarchy.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`        - The unique identifier of the entity.
+    /// * `kind`      - The kind of change made.
+    /// * `ancestors` - The ancestors of the entity, starting with its parent.
+    ///
+    pub(crate) fn new(id: Id, kind: ChangeKind, ancestors: &[ChildInfo]) -> Self {
+        Self {
+            id,
+            kind,
+            collection: ancestors.first().map(ChildInfo::id),
+            path: ancestors.iter().rev().map(ChildInfo::id).collect(),
+        }
+    }
+}
+
+/// Decodes the changes to entities committed by an execution.
+///
+/// This allows a node to tell which entities were affected by an execution,
+/// whether local or the result of applying a remote delta or comparison,
+/// without needing to know anything about the types involved.
+///
+/// # Parameters
+///
+/// * `data` - The serialised changes, which are empty if there were none.
+///
+/// # Errors
+///
+/// If the changes cannot be deserialised, an error will be returned.
+///
+pub fn changes(data: &[u8]) -> Result<Vec<EntityChange>, StorageError> {
+    if data.is_empty() {
+        return Ok(vec![]);
+    }
+
+    from_slice(data).map_err(StorageError::DeserializationError)
+}
+
+/// Combines the artifacts of consecutive executions into one.
+///
+/// The actions of each artifact are applied in the order given, so that
+/// applying the combined artifact has the same effect as applying each of
+/// them in turn. Artifacts without any changes are skipped, and the result is
+/// empty if none of them has any.
+///
+/// # Parameters
+///
+/// * `artifacts` - The serialised [`SyncArtifact`]s, in execution order.
+///
+/// # Errors
+///
+/// If any artifact cannot be deserialised, or contains comparisons, an error
+/// will be returned.
+///
+pub fn combine<'a, I>(artifacts: I) -> Result<Vec<u8>, StorageError>
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    let mut combined = vec![];
+
+    for artifact in artifacts {
+        match from_slice(artifact).map_err(StorageError::DeserializationError)? {
+            SyncArtifact::Actions(actions) => combined.extend(actions),
+            SyncArtifact::Comparisons(comparisons) if comparisons.is_empty() => {}
+            SyncArtifact::Comparisons(_) => {
+                return Err(StorageError::ActionNotAllowed(
+                    "Comparisons cannot be combined".to_owned(),
+                ))
+            }
+        }
+    }
+
+    if combined.is_empty() {
+        return Ok(vec![]);
+    }
+
+    to_vec(&SyncArtifact::Actions(combined)).map_err(StorageError::SerializationError)
+}
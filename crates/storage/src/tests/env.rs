@@ -0,0 +1,21 @@
+use calimero_sdk::testing;
+
+use super::*;
+use crate::address::Id;
+use crate::store::{MockedStorage, StorageAdaptor};
+
+#[test]
+fn storage__outside_a_simulation() {
+    assert!(!testing::is_active());
+
+    let key = Key::Entry(Id::random());
+
+    assert!(!storage_write(key, &[1, 2, 3]));
+    assert_eq!(
+        MockedStorage::<{ usize::MAX }>::storage_read(key),
+        Some(vec![1, 2, 3])
+    );
+
+    assert!(storage_remove(key));
+    assert_eq!(storage_read(key), None);
+}
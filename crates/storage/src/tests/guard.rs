@@ -0,0 +1,303 @@
+use super::*;
+use crate::entities::{ChildInfo, Metadata};
+
+fn add(id: Id, ancestors: &[Id], data: Vec<u8>) -> Action {
+    Action::Add {
+        id,
+        data,
+        ancestors: ancestors
+            .iter()
+            .map(|ancestor| ChildInfo::new(*ancestor, [0; 32], Metadata::default()))
+            .collect(),
+        metadata: Metadata::default(),
+    }
+}
+
+fn same_parity(local: &[u8], foreign: &[u8]) -> Result<bool, StorageError> {
+    Ok(local.len() % 2 == foreign.len() % 2)
+}
+
+#[cfg(test)]
+mod permits {
+    use super::*;
+
+    #[test]
+    fn denies_entities_and_their_descendants() {
+        reset();
+
+        let (collection, entry, other) = (Id::random(), Id::random(), Id::random());
+
+        deny(collection);
+
+        assert!(!permits(&add(collection, &[Id::root()], vec![]), |_| Ok(None)).unwrap());
+        assert!(!permits(&add(entry, &[collection, Id::root()], vec![]), |_| Ok(None)).unwrap());
+        assert!(permits(&add(other, &[Id::root()], vec![]), |_| Ok(None)).unwrap());
+        assert!(permits(&Action::Compare { id: collection }, |_| Ok(None)).unwrap());
+
+        reset();
+
+        assert!(permits(&add(entry, &[collection, Id::root()], vec![]), |_| Ok(None)).unwrap());
+    }
+
+    #[test]
+    fn checks_replaced_versions() {
+        reset();
+
+        let id = Id::random();
+
+        register(id, same_parity);
+
+        assert!(
+            permits(&add(id, &[Id::root()], vec![1, 2]), |_| Ok(Some(vec![
+                3, 4
+            ])))
+            .unwrap()
+        );
+        assert!(!permits(&add(id, &[Id::root()], vec![1]), |_| Ok(Some(vec![3, 4]))).unwrap());
+        // nothing to compare against, as the entity is new here
+        assert!(permits(&add(id, &[Id::root()], vec![1]), |_| Ok(None)).unwrap());
+
+        let delete = Action::Delete {
+            id,
+            ancestors: vec![],
+            deleted_at: 0,
+        };
+
+        assert!(!permits(&delete, |_| Ok(Some(vec![3, 4]))).unwrap());
+
+        reset();
+
+        assert!(!is_active());
+    }
+
+    #[test]
+    fn compares_values_stored_inline() {
+        assert!(unchanged(&"motto".to_owned(), &"motto".to_owned()));
+        assert!(!unchanged(&"motto".to_owned(), &"slogan".to_owned()));
+    }
+}
+
+#[cfg(test)]
+mod simulation {
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use calimero_sdk::env;
+    use calimero_sdk::event::NoEvent;
+    use calimero_sdk::state::{AppState, AppStateInit};
+    use calimero_sdk::testing::{SimulatedState, Simulation};
+
+    use super::*;
+    use crate::collections::{Root, UnorderedMap};
+    use crate::merge::Merge;
+
+    const ALICE: [u8; 32] = [1; 32];
+    const BOB: [u8; 32] = [2; 32];
+
+    /// A notice board, which only its admin may change, as `#[app::state]`
+    /// would implement it for `#[app::guard(Self::is_admin)]` on each field.
+    #[derive(BorshDeserialize, BorshSerialize)]
+    struct Board {
+        admin: [u8; 32],
+        motto: String,
+        notices: UnorderedMap<String, String>,
+    }
+
+    impl Board {
+        fn is_admin(&self, executor: &[u8; 32]) -> bool {
+            self.admin == *executor
+        }
+    }
+
+    impl Guard for Board {
+        fn deny(&self) {
+            if !self.is_admin(&env::executor_id()) {
+                self.notices.deny();
+            }
+        }
+
+        fn permits(&self, other: &Self) -> bool {
+            self.is_admin(&env::executor_id())
+                || (unchanged(&self.admin, &other.admin) && unchanged(&self.motto, &other.motto))
+        }
+    }
+
+    impl Merge for Board {}
+
+    impl AppStateInit for Board {
+        type Return = Self;
+    }
+
+    impl AppState for Board {
+        type Event<'a> = NoEvent;
+    }
+
+    impl SimulatedState for Board {
+        type Root = Root<Self>;
+
+        fn init(f: impl FnOnce() -> Self) -> Self::Root {
+            Root::new(f)
+        }
+
+        fn fetch() -> Option<Self::Root> {
+            Root::fetch()
+        }
+
+        fn migrate(f: impl FnOnce() -> Self) -> Self::Root {
+            Root::migrate(f)
+        }
+
+        fn guard(root: &Self::Root) {
+            root.guard();
+        }
+
+        fn commit(root: Self::Root) {
+            root.commit();
+        }
+
+        fn sync(artifact: &[u8]) {
+            Root::<Self>::sync(artifact).unwrap();
+        }
+    }
+
+    fn setup(sim: Simulation<Board>) -> Simulation<Board> {
+        let mut sim = sim;
+
+        sim.init(0, ALICE, || Board {
+            admin: ALICE,
+            motto: "Notices only".to_owned(),
+            notices: UnorderedMap::new(),
+        });
+        sim.deliver_all();
+
+        sim.assert_converged();
+
+        sim
+    }
+
+    /// Hands the board over to Bob on replica 1, without replica 0 hearing of
+    /// it, before Bob posts a notice there.
+    fn hand_over(sim: &mut Simulation<Board>) {
+        sim.call(1, ALICE, |board| board.admin = BOB);
+
+        let handover = sim.messages().map(|(id, _)| id).collect::<Vec<_>>();
+
+        for id in handover {
+            drop(sim.discard(id));
+        }
+
+        let _ignored = sim.call(1, BOB, |board| {
+            board
+                .notices
+                .insert("meeting".to_owned(), "Tuesday".to_owned())
+                .unwrap()
+        });
+    }
+
+    #[test]
+    fn admins_change_guarded_fields() {
+        let mut sim = setup(Simulation::new(2));
+
+        let _ignored = sim.call(0, ALICE, |board| {
+            board.motto = "Notices and events".to_owned();
+
+            board
+                .notices
+                .insert("meeting".to_owned(), "Monday".to_owned())
+                .unwrap()
+        });
+        sim.deliver_all();
+
+        sim.assert_converged();
+
+        let notice = sim.view(1, |board| board.notices.get("meeting").unwrap());
+
+        assert_eq!(notice.as_deref(), Some("Monday"));
+    }
+
+    #[test]
+    #[should_panic(expected = "not authorized")]
+    fn others_cannot_change_guarded_collections() {
+        let mut sim = setup(Simulation::new(1));
+
+        let _ignored = sim.call(0, BOB, |board| {
+            board
+                .notices
+                .insert("meeting".to_owned(), "Never".to_owned())
+                .unwrap()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "not authorized")]
+    fn others_cannot_change_guarded_values() {
+        let mut sim = setup(Simulation::new(1));
+
+        sim.call(0, BOB, |board| board.motto = "Anything goes".to_owned());
+    }
+
+    #[test]
+    fn deltas_are_held_to_the_guards_of_the_recipient() {
+        let mut sim = setup(Simulation::new(2));
+
+        hand_over(&mut sim);
+
+        let (id, _) = sim.messages().next().unwrap();
+
+        assert!(!sim.deliver(id));
+
+        let notice = sim.view(0, |board| board.notices.get("meeting").unwrap());
+
+        assert_eq!(notice, None);
+    }
+
+    #[test]
+    fn comparisons_are_held_to_the_guards_of_the_recipient() {
+        let mut sim = setup(Simulation::new(2).with_identity(1, BOB));
+
+        hand_over(&mut sim);
+
+        let messages = sim.messages().map(|(id, _)| id).collect::<Vec<_>>();
+
+        for id in messages {
+            drop(sim.discard(id));
+        }
+
+        sim.sync(0, 1);
+
+        assert!(!sim.is_converged());
+
+        let (admin, notice) = sim.view(0, |board| {
+            (board.admin, board.notices.get("meeting").unwrap())
+        });
+
+        assert_eq!(admin, ALICE);
+        assert_eq!(notice, None);
+    }
+
+    #[test]
+    fn comparisons_with_admins_are_applied() {
+        let mut sim = setup(Simulation::new(2).with_identity(1, ALICE));
+
+        let _ignored = sim.call(1, ALICE, |board| {
+            board.motto = "Notices and events".to_owned();
+
+            board
+                .notices
+                .insert("meeting".to_owned(), "Monday".to_owned())
+                .unwrap()
+        });
+
+        let messages = sim.messages().map(|(id, _)| id).collect::<Vec<_>>();
+
+        for id in messages {
+            drop(sim.discard(id));
+        }
+
+        sim.sync(0, 1);
+
+        sim.assert_converged();
+
+        let notice = sim.view(0, |board| board.notices.get("meeting").unwrap());
+
+        assert_eq!(notice.as_deref(), Some("Monday"));
+    }
+}
@@ -1,5 +1,5 @@
 use super::*;
-use crate::store::MainStorage;
+use crate::store::{MainStorage, StorageAdaptor};
 
 mod index__public_methods {
     use super::*;
@@ -43,9 +43,14 @@ mod index__public_methods {
         assert_eq!(updated_root_index.own_hash, root_hash);
         assert!(updated_root_index.parent_id.is_none());
         assert_eq!(updated_root_index.children.len(), 1);
+        assert_eq!(updated_root_index.children[collection_name], 1);
         assert_eq!(
-            updated_root_index.children[collection_name][0],
-            ChildInfo::new(child_id, child_full_hash, Metadata::default())
+            <Index<MainStorage>>::get_children_of(root_id, collection_name).unwrap(),
+            vec![ChildInfo::new(
+                child_id,
+                child_full_hash,
+                Metadata::default()
+            )]
         );
 
         let child_index = <Index<MainStorage>>::get_index(child_id).unwrap().unwrap();
@@ -55,6 +60,51 @@ mod index__public_methods {
         assert!(child_index.children.is_empty());
     }
 
+    #[test]
+    fn add_child_to__same_id_created_at_another_time() {
+        let root_id = Id::random();
+        assert!(<Index<MainStorage>>::add_root(ChildInfo::new(
+            root_id,
+            [1_u8; 32],
+            Metadata::default()
+        ))
+        .is_ok());
+
+        // The same entity can be created independently on two replicas, as
+        // collection entries are identified by their key, and each records
+        // its own creation time
+        let collection_name = "Books";
+        let child_id = Id::random();
+        let first = Metadata {
+            created_at: 1,
+            updated_at: 1.into(),
+        };
+        let second = Metadata {
+            created_at: 2,
+            updated_at: 2.into(),
+        };
+
+        assert!(<Index<MainStorage>>::add_child_to(
+            root_id,
+            collection_name,
+            ChildInfo::new(child_id, [2_u8; 32], first),
+        )
+        .is_ok());
+        assert!(<Index<MainStorage>>::add_child_to(
+            root_id,
+            collection_name,
+            ChildInfo::new(child_id, [3_u8; 32], second),
+        )
+        .is_ok());
+
+        let root_index = <Index<MainStorage>>::get_index(root_id).unwrap().unwrap();
+        assert_eq!(root_index.children[collection_name], 1);
+        let children = <Index<MainStorage>>::get_children_of(root_id, collection_name).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].id(), child_id);
+        assert_eq!(children[0].created_at(), second.created_at);
+    }
+
     #[test]
     fn add_root() {
         let root_id = Id::random();
@@ -382,6 +432,93 @@ mod index__public_methods {
         assert_eq!(<Index<MainStorage>>::get_parent_id(root_id).unwrap(), None);
     }
 
+    #[test]
+    fn get_children_page_of__across_chunks() {
+        let root_id = Id::random();
+        assert!(<Index<MainStorage>>::add_root(ChildInfo::new(
+            root_id,
+            [1_u8; 32],
+            Metadata::default()
+        ))
+        .is_ok());
+
+        let collection_name = "Books";
+        let total = CHUNK_SIZE * 3;
+
+        // Added out of order, so that children land in earlier chunks too
+        let mut expected = (0..total)
+            .rev()
+            .map(|n| {
+                let metadata = Metadata {
+                    created_at: n as u64,
+                    updated_at: (n as u64).into(),
+                };
+                let child = ChildInfo::new(Id::random(), [2_u8; 32], metadata);
+                assert!(
+                    <Index<MainStorage>>::add_child_to(root_id, collection_name, child).is_ok()
+                );
+                child.id()
+            })
+            .collect::<Vec<_>>();
+        expected.reverse();
+
+        let chunks = <Index<MainStorage>>::get_chunks(root_id, collection_name)
+            .unwrap()
+            .unwrap();
+        assert!(chunks.chunks.len() > 1);
+
+        let children = <Index<MainStorage>>::get_children_of(root_id, collection_name).unwrap();
+        assert_eq!(
+            children.iter().map(ChildInfo::id).collect::<Vec<_>>(),
+            expected
+        );
+
+        let mut paged = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page =
+                <Index<MainStorage>>::get_children_page_of(root_id, collection_name, cursor, 10)
+                    .unwrap();
+            let Some(last) = page.last() else {
+                break;
+            };
+            cursor = Some(Cursor::after(last));
+            paged.extend(page.iter().map(ChildInfo::id));
+        }
+        assert_eq!(paged, expected);
+
+        // Neither counting nor reading the first page touches the last chunk
+        let last = chunks.chunks.last().unwrap();
+        let data = MainStorage::storage_read(Key::Children(last.id)).unwrap();
+        assert!(MainStorage::storage_remove(Key::Children(last.id)));
+        assert_eq!(
+            <Index<MainStorage>>::get_child_count_of(root_id, collection_name).unwrap(),
+            total
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_children_page_of(root_id, collection_name, None, 10)
+                .unwrap()
+                .len(),
+            10
+        );
+        assert!(<Index<MainStorage>>::get_children_of(root_id, collection_name).is_err());
+        assert!(!MainStorage::storage_write(Key::Children(last.id), &data));
+
+        for id in &expected {
+            assert!(<Index<MainStorage>>::remove_child_from(root_id, collection_name, *id).is_ok());
+        }
+        assert_eq!(
+            <Index<MainStorage>>::get_child_count_of(root_id, collection_name).unwrap(),
+            0
+        );
+        assert!(<Index<MainStorage>>::get_chunks(root_id, collection_name)
+            .unwrap()
+            .is_none());
+        for chunk in &chunks.chunks {
+            assert!(MainStorage::storage_read(Key::Children(chunk.id)).is_none());
+        }
+    }
+
     #[test]
     fn has_children() {
         let root_id = Id::random();
@@ -441,7 +578,12 @@ mod index__public_methods {
         );
 
         let root_index = <Index<MainStorage>>::get_index(root_id).unwrap().unwrap();
-        assert!(root_index.children[collection_name].is_empty());
+        assert_eq!(root_index.children[collection_name], 0);
+        assert!(
+            <Index<MainStorage>>::get_children_of(root_id, collection_name)
+                .unwrap()
+                .is_empty()
+        );
         assert!(<Index<MainStorage>>::get_index(child_id).unwrap().is_none());
     }
 }
@@ -463,6 +605,7 @@ mod index__private_methods {
             full_hash: hash1,
             own_hash: hash2,
             metadata: Metadata::default(),
+            history: Vec::new(),
         };
         <Index<MainStorage>>::save_index(&index).unwrap();
 
@@ -483,6 +626,7 @@ mod index__private_methods {
             full_hash: hash1,
             own_hash: hash2,
             metadata: Metadata::default(),
+            history: Vec::new(),
         };
         <Index<MainStorage>>::save_index(&index).unwrap();
         assert_eq!(<Index<MainStorage>>::get_index(id).unwrap().unwrap(), index);
@@ -184,6 +184,7 @@ mod interface__apply_actions {
             data: serialized,
             ancestors: vec![],
             metadata: page.element().metadata,
+            history: vec![],
         };
 
         assert!(MainInterface::apply_action(action).is_ok());
@@ -203,6 +204,7 @@ mod interface__apply_actions {
         let action = Action::Delete {
             id: page.id(),
             ancestors: vec![],
+            deleted_at: page.element().updated_at(),
         };
 
         assert!(MainInterface::apply_action(action).is_ok());
@@ -230,6 +232,7 @@ mod interface__apply_actions {
             data: serialized,
             ancestors: vec![],
             metadata: page.element().metadata,
+            history: vec![],
         };
 
         // Updating a non-existent page should still succeed (it will be added)
@@ -240,6 +243,115 @@ mod interface__apply_actions {
         assert!(retrieved_page.is_some());
         assert_eq!(retrieved_page.unwrap().title, "Test Page");
     }
+
+    #[test]
+    fn apply_action__stale_update() {
+        let mut page = Page::new_from_element("Test Page", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+        let mut para = Paragraph::new_from_element(
+            "Current",
+            Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+        );
+        assert!(MainInterface::add_child_to(page.id(), &mut page.paragraphs, &mut para).unwrap());
+
+        let para_hashes = <Index<MainStorage>>::get_hashes_for(para.id()).unwrap();
+        let page_hashes = <Index<MainStorage>>::get_hashes_for(page.id()).unwrap();
+
+        let mut stale = para.clone();
+        stale.text = "Stale".to_owned();
+        let mut metadata = stale.element().metadata;
+        *metadata.updated_at = metadata.updated_at.saturating_sub(1);
+        let action = Action::Update {
+            id: para.id(),
+            data: to_vec(&stale).unwrap(),
+            ancestors: <Index<MainStorage>>::get_ancestors_of(para.id()).unwrap(),
+            metadata,
+            history: vec![],
+        };
+
+        assert!(MainInterface::apply_action(action).is_ok());
+
+        // Neither the data nor the hashes indexed for it may change
+        let retrieved = MainInterface::find_by_id::<Paragraph>(para.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(retrieved.text, "Current");
+        assert_eq!(
+            <Index<MainStorage>>::get_hashes_for(para.id()).unwrap(),
+            para_hashes
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_hashes_for(page.id()).unwrap(),
+            page_hashes
+        );
+    }
+
+    #[test]
+    fn apply_action__delete_leaves_tombstone() {
+        let mut page = Page::new_from_element("Test Page", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+        let mut para = Paragraph::new_from_element(
+            "Leaf",
+            Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+        );
+        assert!(MainInterface::add_child_to(page.id(), &mut page.paragraphs, &mut para).unwrap());
+
+        let ancestors = <Index<MainStorage>>::get_ancestors_of(para.id()).unwrap();
+
+        MainInterface::apply_action(Action::Delete {
+            id: para.id(),
+            ancestors: ancestors.clone(),
+            deleted_at: para.element().updated_at(),
+        })
+        .unwrap();
+
+        assert_none!(MainInterface::find_by_id::<Paragraph>(para.id()).unwrap());
+        assert!(MainInterface::children_of(page.id(), &page.paragraphs)
+            .unwrap()
+            .is_empty());
+
+        // The version that was deleted arrives late, and must not come back
+        drop(sync::take_actions());
+        MainInterface::apply_action(Action::Add {
+            id: para.id(),
+            data: to_vec(&para).unwrap(),
+            ancestors: ancestors.clone(),
+            metadata: para.element().metadata,
+        })
+        .unwrap();
+
+        assert_none!(MainInterface::find_by_id::<Paragraph>(para.id()).unwrap());
+        assert_eq!(
+            sync::take_actions(),
+            vec![Action::Delete {
+                id: para.id(),
+                ancestors,
+                deleted_at: para.element().updated_at(),
+            }]
+        );
+    }
+
+    #[test]
+    fn apply_action__delete_loses_to_later_change() {
+        let mut page = Page::new_from_element("Test Page", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+        let mut para = Paragraph::new_from_element(
+            "Leaf",
+            Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+        );
+        assert!(MainInterface::add_child_to(page.id(), &mut page.paragraphs, &mut para).unwrap());
+
+        MainInterface::apply_action(Action::Delete {
+            id: para.id(),
+            ancestors: <Index<MainStorage>>::get_ancestors_of(para.id()).unwrap(),
+            deleted_at: para.element().updated_at().saturating_sub(1),
+        })
+        .unwrap();
+
+        assert!(MainInterface::find_by_id::<Paragraph>(para.id())
+            .unwrap()
+            .is_some());
+    }
 }
 
 #[cfg(test)]
@@ -309,6 +421,40 @@ mod interface__comparison {
                     data: to_vec(&local).unwrap(),
                     ancestors: vec![],
                     metadata: local.element().metadata,
+                    history: vec![],
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn compare_trees__locally_deleted() {
+        let mut local = Page::new_from_element("Test Page", Element::root());
+        let mut foreign = local.clone();
+
+        assert!(MainInterface::save(&mut local).unwrap());
+        assert!(ForeignInterface::save(&mut foreign).unwrap());
+
+        MainInterface::apply_action(Action::Delete {
+            id: local.id(),
+            ancestors: vec![],
+            deleted_at: local.element().updated_at(),
+        })
+        .unwrap();
+
+        let result = compare_trees(
+            Some(&foreign),
+            ForeignInterface::generate_comparison_data(Some(foreign.id())).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            (
+                vec![],
+                vec![Action::Delete {
+                    id: foreign.id(),
+                    ancestors: vec![],
+                    deleted_at: local.element().updated_at(),
                 }]
             )
         );
@@ -340,6 +486,7 @@ mod interface__comparison {
                     data: to_vec(&foreign).unwrap(),
                     ancestors: vec![],
                     metadata: foreign.element().metadata,
+                    history: vec![],
                 }],
                 vec![]
             )
@@ -405,6 +552,7 @@ mod interface__comparison {
                     data: to_vec(&foreign_page).unwrap(),
                     ancestors: vec![],
                     metadata: foreign_page.element().metadata,
+                    history: vec![],
                 },
                 // Para1 needs comparison due to different hash
                 Action::Compare {
@@ -424,7 +572,7 @@ mod interface__comparison {
                 Action::Add {
                     id: local_para2.id(),
                     data: to_vec(&local_para2).unwrap(),
-                    ancestors: vec![],
+                    ancestors: <Index<MainStorage>>::get_ancestors_of(local_para2.id()).unwrap(),
                     metadata: local_para2.element().metadata,
                 },
                 // Para3 needs to be added locally, but we don't have the data, so we compare
@@ -465,6 +613,7 @@ mod interface__comparison {
                     local_page.element().metadata
                 )],
                 metadata: foreign_para1.element().metadata,
+                history: vec![],
             }]
         );
         assert_eq!(foreign_para1_actions, vec![]);
@@ -504,4 +653,55 @@ mod interface__comparison {
         );
         assert_eq!(foreign_para3_actions, vec![]);
     }
+
+    #[test]
+    fn compare_trees__local_only_child_keeps_its_parent() {
+        let page_element = Element::root();
+        let para_element = Element::new(&Path::new("::root::node::leaf1").unwrap(), None);
+        let other_element = Element::new(&Path::new("::root::node::leaf2").unwrap(), None);
+
+        let mut local_page = Page::new_from_element("Page", page_element.clone());
+        let mut local_para = Paragraph::new_from_element("Local only", para_element);
+        let mut foreign_page = Page::new_from_element("Page", page_element);
+        let mut foreign_para = Paragraph::new_from_element("Foreign only", other_element);
+
+        assert!(MainInterface::save(&mut local_page).unwrap());
+        assert!(MainInterface::add_child_to(
+            local_page.id(),
+            &mut local_page.paragraphs,
+            &mut local_para
+        )
+        .unwrap());
+        assert!(ForeignInterface::save(&mut foreign_page).unwrap());
+        assert!(ForeignInterface::add_child_to(
+            foreign_page.id(),
+            &mut foreign_page.paragraphs,
+            &mut foreign_para
+        )
+        .unwrap());
+
+        let (_, foreign_actions) = compare_trees(
+            Some(&foreign_page),
+            ForeignInterface::generate_comparison_data(Some(foreign_page.id())).unwrap(),
+        )
+        .unwrap();
+
+        let add = foreign_actions
+            .into_iter()
+            .find(|action| matches!(action, Action::Add { id, .. } if *id == local_para.id()))
+            .expect("the local-only child should be sent");
+
+        // The ancestors sent are the child's own, starting with its parent,
+        // which is what lets the other side attach it where it belongs
+        let Action::Add { ref ancestors, .. } = add else {
+            unreachable!()
+        };
+        assert_eq!(ancestors.first().map(ChildInfo::id), Some(local_page.id()));
+
+        assert_ok!(ForeignInterface::apply_action(add));
+        assert_eq!(
+            <Index<MockedStorage<0>>>::get_parent_id(local_para.id()).unwrap(),
+            Some(foreign_page.id())
+        );
+    }
 }
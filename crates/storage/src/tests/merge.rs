@@ -0,0 +1,474 @@
+use std::thread::sleep;
+use std::time::Duration;
+
+use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
+
+use super::*;
+use crate::entities::{Data, Element};
+use crate::interface::{Action, Interface, MainInterface};
+use crate::store::MockedStorage;
+// fixme! macro expects `calimero_storage` to be in deps
+use crate::{self as calimero_storage, AtomicUnit, Merge};
+
+/// A score that keeps the best result and every badge ever earned.
+#[derive(AtomicUnit, BorshDeserialize, BorshSerialize, Clone, Debug, Eq, Merge, PartialEq)]
+#[type_id(1)]
+struct Score {
+    name: String,
+    #[merge(max)]
+    best: u64,
+    #[merge(union)]
+    badges: UnionSet<String>,
+    #[storage]
+    storage: Element,
+}
+
+impl Score {
+    fn new(name: &str, best: u64, badges: &[&str]) -> Self {
+        Self {
+            name: name.to_owned(),
+            best,
+            badges: badges.iter().map(|&badge| badge.to_owned()).collect(),
+            storage: Element::root(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod strategies {
+    use super::*;
+
+    #[test]
+    fn lww() {
+        let mut value = 1;
+        strategy::lww(&mut value, 2, Newer::Local);
+        assert_eq!(value, 1);
+        strategy::lww(&mut value, 2, Newer::Foreign);
+        assert_eq!(value, 2);
+    }
+
+    #[test]
+    fn max_and_min() {
+        let mut value = 5;
+        strategy::max(&mut value, 3, Newer::Foreign);
+        assert_eq!(value, 5);
+        strategy::max(&mut value, 7, Newer::Local);
+        assert_eq!(value, 7);
+        strategy::min(&mut value, 9, Newer::Foreign);
+        assert_eq!(value, 7);
+        strategy::min(&mut value, 2, Newer::Local);
+        assert_eq!(value, 2);
+    }
+
+    #[test]
+    fn union() {
+        let mut value = UnionSet::from_iter([1, 2]);
+        strategy::union(&mut value, UnionSet::from_iter([2, 3]), Newer::Local);
+        assert_eq!(value.iter().collect::<Vec<_>>(), [&1, &2, &3]);
+    }
+
+    #[test]
+    fn union__keeps_removals() {
+        let mut local = UnionSet::from_iter([1, 2]);
+        let mut foreign = local.clone();
+
+        assert!(local.remove(&1));
+        assert!(foreign.insert(3));
+
+        let mut merged = local.clone();
+        strategy::union(&mut merged, foreign.clone(), Newer::Local);
+        assert_eq!(merged.iter().collect::<Vec<_>>(), [&2, &3]);
+
+        // The same result is reached from the other side
+        strategy::union(&mut foreign, local, Newer::Foreign);
+        assert_eq!(foreign, merged);
+    }
+
+    #[test]
+    fn union__keeps_later_additions() {
+        let mut local = UnionSet::from_iter([1]);
+        let foreign = local.clone();
+
+        assert!(local.remove(&1));
+        assert!(!local.contains(&1));
+        assert!(local.insert(1));
+
+        strategy::union(&mut local, foreign, Newer::Foreign);
+        assert!(local.contains(&1));
+        assert_eq!(local.len(), 1);
+    }
+
+    #[test]
+    fn derived() {
+        let mut local = Score::new("local", 10, &["a"]);
+        let foreign = Score::new("foreign", 5, &["b"]);
+
+        local.merge(foreign.clone(), Newer::Local);
+        assert_eq!(local.name, "local");
+        assert_eq!(local.best, 10);
+        assert_eq!(local.badges.iter().collect::<Vec<_>>(), ["a", "b"]);
+
+        local.merge(foreign, Newer::Foreign);
+        assert_eq!(local.name, "foreign");
+        assert_eq!(local.best, 10);
+    }
+}
+
+#[cfg(test)]
+mod sync {
+    use super::*;
+
+    type ForeignInterface = Interface<MockedStorage<0>>;
+
+    #[test]
+    fn apply_action__merges_registered() {
+        let mut local = Score::new("local", 10, &["a"]);
+        assert!(MainInterface::save(&mut local).unwrap());
+
+        sleep(Duration::from_millis(2));
+        let mut foreign = Score::new("foreign", 5, &["b"]);
+        foreign.element_mut().update();
+
+        register(local.id(), merge_data::<Score>);
+
+        MainInterface::apply_action(Action::Update {
+            id: foreign.id(),
+            data: to_vec(&foreign).unwrap(),
+            ancestors: vec![],
+            metadata: foreign.element().metadata,
+            history: vec![],
+        })
+        .unwrap();
+
+        let merged = MainInterface::find_by_id::<Score>(local.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.name, "foreign");
+        assert_eq!(merged.best, 10);
+        assert_eq!(merged.badges.iter().collect::<Vec<_>>(), ["a", "b"]);
+    }
+
+    #[test]
+    fn apply_action__replaces_superseded() {
+        let mut local = Score::new("local", 10, &["a"]);
+        assert!(MainInterface::save(&mut local).unwrap());
+        let local_hash: [u8; 32] = Sha256::digest(to_vec(&local).unwrap()).into();
+
+        register(local.id(), merge_data::<Score>);
+
+        // The foreign version was changed from the local one, so is not merged
+        sleep(Duration::from_millis(2));
+        let mut foreign = local.clone();
+        foreign.best = 5;
+        foreign.element_mut().update();
+
+        MainInterface::apply_action(Action::Update {
+            id: foreign.id(),
+            data: to_vec(&foreign).unwrap(),
+            ancestors: vec![],
+            metadata: foreign.element().metadata,
+            history: vec![local_hash],
+        })
+        .unwrap();
+
+        let stored = MainInterface::find_by_id::<Score>(local.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.best, 5);
+    }
+
+    #[test]
+    fn apply_action__keeps_superseding() {
+        let mut stale = Score::new("local", 10, &["a"]);
+        assert!(MainInterface::save(&mut stale).unwrap());
+
+        register(stale.id(), merge_data::<Score>);
+
+        sleep(Duration::from_millis(2));
+        let mut local = stale.clone();
+        local.best = 5;
+        local.element_mut().update();
+        assert!(MainInterface::save(&mut local).unwrap());
+
+        // The foreign version is one the local one was changed from, and is
+        // kept out even if it claims to be newer
+        sleep(Duration::from_millis(2));
+        stale.element_mut().update();
+
+        MainInterface::apply_action(Action::Update {
+            id: stale.id(),
+            data: to_vec(&stale).unwrap(),
+            ancestors: vec![],
+            metadata: stale.element().metadata,
+            history: vec![],
+        })
+        .unwrap();
+
+        let stored = MainInterface::find_by_id::<Score>(local.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.best, 5);
+    }
+
+    #[test]
+    fn lookup__registered_children() {
+        #[expect(clippy::unnecessary_wraps, reason = "Must match Merge::children()")]
+        fn nested_children() -> Option<Children> {
+            Some(Children::new(merge_data::<Score>, || None))
+        }
+
+        let collection = Id::random();
+        let entry = Id::random();
+        let nested = Id::random();
+        let nested_entry = Id::random();
+
+        register_children(
+            collection,
+            Children::new(merge_data::<Score>, nested_children),
+        );
+
+        assert!(lookup(entry, &[collection]).is_some());
+        assert!(lookup(nested, &[entry, collection]).is_none());
+        assert!(lookup(nested_entry, &[nested, entry, collection]).is_some());
+        assert!(lookup(Id::random(), &[nested_entry, nested, entry, collection]).is_none());
+        assert!(lookup(entry, &[Id::random()]).is_none());
+    }
+
+    #[test]
+    fn apply_action__lww_when_unregistered() {
+        let mut local = Score::new("local", 10, &["a"]);
+        assert!(MainInterface::save(&mut local).unwrap());
+
+        sleep(Duration::from_millis(2));
+        let mut foreign = Score::new("foreign", 5, &["b"]);
+        foreign.element_mut().update();
+
+        MainInterface::apply_action(Action::Update {
+            id: foreign.id(),
+            data: to_vec(&foreign).unwrap(),
+            ancestors: vec![],
+            metadata: foreign.element().metadata,
+            history: vec![],
+        })
+        .unwrap();
+
+        let stored = MainInterface::find_by_id::<Score>(local.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.name, "foreign");
+        assert_eq!(stored.best, 5);
+        assert_eq!(stored.badges, foreign.badges);
+    }
+
+    #[test]
+    fn compare_trees__merges_both_sides() {
+        let mut local = Score::new("local", 10, &["a"]);
+        assert!(MainInterface::save(&mut local).unwrap());
+
+        sleep(Duration::from_millis(2));
+        let mut foreign = Score::new("foreign", 5, &["b"]);
+        foreign.element_mut().update();
+        assert!(ForeignInterface::save(&mut foreign).unwrap());
+
+        register(local.id(), merge_data::<Score>);
+
+        let (local_actions, foreign_actions) = MainInterface::compare_trees(
+            Some(to_vec(&foreign).unwrap()),
+            ForeignInterface::generate_comparison_data(Some(foreign.id())).unwrap(),
+        )
+        .unwrap();
+
+        let mut expected = local.clone();
+        expected.merge(foreign.clone(), Newer::Foreign);
+
+        let expected = Action::Update {
+            id: local.id(),
+            data: to_vec(&expected).unwrap(),
+            ancestors: vec![],
+            metadata: foreign.element().metadata,
+            history: vec![
+                Sha256::digest(to_vec(&local).unwrap()).into(),
+                Sha256::digest(to_vec(&foreign).unwrap()).into(),
+            ],
+        };
+
+        assert_eq!(local_actions, vec![expected.clone()]);
+        assert_eq!(foreign_actions, vec![expected]);
+    }
+}
+
+#[cfg(test)]
+mod simulation {
+    use calimero_sdk::event::NoEvent;
+    use calimero_sdk::state::{AppState, AppStateInit};
+    use calimero_sdk::testing::{SimulatedState, Simulation};
+
+    use super::*;
+    use crate::collections::{Root, UnorderedMap};
+    use crate::guard::Guard;
+
+    const ALICE: [u8; 32] = [1; 32];
+
+    /// A tally kept for each player, of which concurrent changes are merged.
+    #[derive(BorshDeserialize, BorshSerialize, Merge)]
+    struct Tally {
+        #[merge(max)]
+        best: u64,
+        #[merge(union)]
+        badges: UnionSet<String>,
+    }
+
+    /// Application state holding a tally for each player.
+    #[derive(BorshDeserialize, BorshSerialize, Merge)]
+    struct League {
+        #[merge(nested)]
+        tallies: UnorderedMap<String, Tally>,
+    }
+
+    impl Guard for League {}
+
+    impl AppStateInit for League {
+        type Return = Self;
+    }
+
+    impl AppState for League {
+        type Event<'a> = NoEvent;
+    }
+
+    impl SimulatedState for League {
+        type Root = Root<Self>;
+
+        fn init(f: impl FnOnce() -> Self) -> Self::Root {
+            Root::new(f)
+        }
+
+        fn fetch() -> Option<Self::Root> {
+            Root::fetch()
+        }
+
+        fn migrate(f: impl FnOnce() -> Self) -> Self::Root {
+            Root::migrate(f)
+        }
+
+        fn guard(root: &Self::Root) {
+            root.guard();
+        }
+
+        fn commit(root: Self::Root) {
+            root.commit();
+        }
+
+        fn sync(artifact: &[u8]) {
+            Root::<Self>::sync(artifact).unwrap();
+        }
+    }
+
+    fn setup() -> Simulation<League> {
+        let mut sim = Simulation::new(2);
+
+        sim.init(0, ALICE, || {
+            let mut tallies = UnorderedMap::new();
+            drop(
+                tallies
+                    .insert(
+                        "alice".to_owned(),
+                        Tally {
+                            best: 1,
+                            badges: UnionSet::from_iter(["bronze".to_owned()]),
+                        },
+                    )
+                    .unwrap(),
+            );
+            League { tallies }
+        });
+        sim.deliver_all();
+
+        sim
+    }
+
+    fn record(sim: &mut Simulation<League>, replica: usize, best: u64, earned: Option<&str>) {
+        sim.call(replica, ALICE, |app| {
+            let mut tally = app.tallies.get("alice").unwrap().unwrap();
+            tally.best = best;
+            if let Some(badge) = earned {
+                let _added = tally.badges.insert(badge.to_owned());
+            }
+            drop(app.tallies.insert("alice".to_owned(), tally).unwrap());
+        });
+    }
+
+    fn tally(sim: &mut Simulation<League>, replica: usize) -> (u64, Vec<String>) {
+        sim.view(replica, |app| {
+            let tally = app.tallies.get("alice").unwrap().unwrap();
+            (tally.best, tally.badges.iter().cloned().collect())
+        })
+    }
+
+    #[test]
+    fn concurrent_changes_to_a_nested_value_merge() {
+        let mut sim = setup();
+
+        sim.partition(&[&[0], &[1]]);
+        record(&mut sim, 0, 10, Some("gold"));
+        record(&mut sim, 1, 5, Some("silver"));
+        sim.heal();
+        sim.sync(0, 1);
+
+        sim.assert_converged();
+        for replica in 0..2 {
+            assert_eq!(
+                tally(&mut sim, replica),
+                (
+                    10,
+                    vec!["bronze".to_owned(), "gold".to_owned(), "silver".to_owned()]
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn concurrent_deltas_to_a_nested_value_merge() {
+        let mut sim = setup();
+
+        record(&mut sim, 0, 10, Some("gold"));
+        record(&mut sim, 1, 5, Some("silver"));
+        sim.deliver_all();
+        sim.sync(0, 1);
+
+        sim.assert_converged();
+        assert_eq!(tally(&mut sim, 1).0, 10);
+        assert_eq!(tally(&mut sim, 0).1.len(), 3);
+    }
+
+    #[test]
+    fn removals_from_a_nested_value_survive_the_merge() {
+        let mut sim = setup();
+
+        sim.partition(&[&[0], &[1]]);
+        sim.call(0, ALICE, |app| {
+            let mut tally = app.tallies.get("alice").unwrap().unwrap();
+            assert!(tally.badges.remove(&"bronze".to_owned()));
+            drop(app.tallies.insert("alice".to_owned(), tally).unwrap());
+        });
+        record(&mut sim, 1, 5, Some("silver"));
+        sim.heal();
+        sim.sync(0, 1);
+
+        sim.assert_converged();
+        assert_eq!(tally(&mut sim, 0), (5, vec!["silver".to_owned()]));
+    }
+
+    #[test]
+    fn sequential_changes_to_a_nested_value_replace_it() {
+        let mut sim = setup();
+
+        record(&mut sim, 0, 10, None);
+        sim.deliver_all();
+        record(&mut sim, 1, 3, None);
+        sim.deliver_all();
+
+        sim.assert_converged();
+        assert_eq!(tally(&mut sim, 0).0, 3);
+    }
+}
@@ -0,0 +1,189 @@
+use std::thread::sleep;
+
+use borsh::to_vec;
+
+use super::*;
+use crate::address::Path;
+use crate::entities::{ChildInfo, Data, Element};
+use crate::interface::MainInterface;
+use crate::tests::common::{Page, Paragraph};
+
+fn add_action<D: Data>(entity: &D, ancestors: Vec<ChildInfo>) -> Action {
+    Action::Add {
+        id: entity.id(),
+        data: to_vec(entity).unwrap(),
+        ancestors,
+        metadata: entity.element().metadata,
+    }
+}
+
+fn ancestor<D: Data>(entity: &D) -> ChildInfo {
+    ChildInfo::new(entity.id(), [0; 32], entity.element().metadata)
+}
+
+#[test]
+fn apply_action__child_before_parent() {
+    let page = Page::new_from_element("Node", Element::root());
+    let para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+
+    MainInterface::apply_action(add_action(&para, vec![ancestor(&page)])).unwrap();
+
+    assert!(MainInterface::find_by_id::<Paragraph>(para.id())
+        .unwrap()
+        .is_none());
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 1);
+
+    MainInterface::apply_action(add_action(&page, vec![])).unwrap();
+
+    assert_eq!(
+        MainInterface::find_by_id::<Paragraph>(para.id())
+            .unwrap()
+            .unwrap()
+            .text,
+        "Leaf"
+    );
+    assert_eq!(
+        MainInterface::parent_of::<Page>(para.id())
+            .unwrap()
+            .unwrap()
+            .id(),
+        page.id()
+    );
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 0);
+}
+
+#[test]
+fn apply_action__cascades_to_descendants() {
+    let page = Page::new_from_element("Node", Element::root());
+    let para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+    let nested = Paragraph::new_from_element(
+        "Nested",
+        Element::new(&Path::new("::root::node::leaf::nested").unwrap(), None),
+    );
+
+    MainInterface::apply_action(add_action(&nested, vec![ancestor(&para), ancestor(&page)]))
+        .unwrap();
+    MainInterface::apply_action(add_action(&para, vec![ancestor(&page)])).unwrap();
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 2);
+
+    MainInterface::apply_action(add_action(&page, vec![])).unwrap();
+
+    assert!(MainInterface::find_by_id::<Paragraph>(para.id())
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        MainInterface::parent_of::<Paragraph>(nested.id())
+            .unwrap()
+            .unwrap()
+            .id(),
+        para.id()
+    );
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 0);
+}
+
+#[test]
+fn apply_action__delete_discards_orphan() {
+    let page = Page::new_from_element("Node", Element::root());
+    let para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+    let nested = Paragraph::new_from_element(
+        "Nested",
+        Element::new(&Path::new("::root::node::leaf::nested").unwrap(), None),
+    );
+
+    MainInterface::apply_action(add_action(&nested, vec![ancestor(&para), ancestor(&page)]))
+        .unwrap();
+    MainInterface::apply_action(add_action(&para, vec![ancestor(&page)])).unwrap();
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 2);
+
+    MainInterface::apply_action(Action::Delete {
+        id: para.id(),
+        ancestors: vec![ancestor(&page)],
+        deleted_at: time_now(),
+    })
+    .unwrap();
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 0);
+
+    MainInterface::apply_action(add_action(&page, vec![])).unwrap();
+
+    assert!(MainInterface::find_by_id::<Paragraph>(para.id())
+        .unwrap()
+        .is_none());
+    assert!(MainInterface::find_by_id::<Paragraph>(nested.id())
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn apply_action__delete_keeps_later_orphan() {
+    let page = Page::new_from_element("Node", Element::root());
+    let para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+    let deleted_at = para.element().updated_at() - 1;
+
+    MainInterface::apply_action(add_action(&para, vec![ancestor(&page)])).unwrap();
+    MainInterface::apply_action(Action::Delete {
+        id: para.id(),
+        ancestors: vec![ancestor(&page)],
+        deleted_at,
+    })
+    .unwrap();
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 1);
+
+    MainInterface::apply_action(add_action(&page, vec![])).unwrap();
+
+    assert!(MainInterface::find_by_id::<Paragraph>(para.id())
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn buffer__ignores_duplicates() {
+    let page = Page::new_from_element("Node", Element::root());
+    let para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+
+    let action = add_action(&para, vec![ancestor(&page)]);
+    MainInterface::apply_action(action.clone()).unwrap();
+    MainInterface::apply_action(action).unwrap();
+
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 1);
+}
+
+#[test]
+fn purge_expired() {
+    let page = Page::new_from_element("Node", Element::root());
+    let para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+
+    MainInterface::apply_action(add_action(&para, vec![ancestor(&page)])).unwrap();
+    assert_eq!(MainInterface::purge_expired_orphans().unwrap(), 0);
+
+    set_expiry(Duration::ZERO);
+    sleep(Duration::from_millis(1));
+
+    assert_eq!(MainInterface::purge_expired_orphans().unwrap(), 1);
+    assert_eq!(MainInterface::diagnostics().unwrap().orphans, 0);
+
+    MainInterface::apply_action(add_action(&page, vec![])).unwrap();
+
+    assert!(MainInterface::find_by_id::<Paragraph>(para.id())
+        .unwrap()
+        .is_none());
+
+    set_expiry(DEFAULT_EXPIRY);
+}
@@ -0,0 +1,86 @@
+use super::*;
+use crate::address::{Id, Path};
+use crate::entities::{Data, Element};
+use crate::index::Index;
+use crate::interface::MainInterface;
+use crate::store::MainStorage;
+use crate::tests::common::{Page, Paragraph};
+
+/// Creates a page with three paragraphs, returning the paragraphs and the
+/// resulting root hash.
+fn setup() -> (Vec<Paragraph>, [u8; 32]) {
+    let mut page = Page::new_from_element("Node", Element::root());
+    assert!(MainInterface::save(&mut page).unwrap());
+
+    let mut paragraphs = Vec::new();
+
+    for text in ["Leaf1", "Leaf2", "Leaf3"] {
+        let path = Path::new(format!("::root::node::{}", text.to_lowercase())).unwrap();
+        let mut para = Paragraph::new_from_element(text, Element::new(&path, None));
+        assert!(MainInterface::add_child_to(page.id(), &page.paragraphs, &mut para).unwrap());
+        paragraphs.push(para);
+    }
+
+    let (root_hash, _) = <Index<MainStorage>>::get_hashes_for(page.id())
+        .unwrap()
+        .unwrap();
+
+    (paragraphs, root_hash)
+}
+
+#[test]
+fn verify__valid() {
+    let (paragraphs, root_hash) = setup();
+
+    for para in &paragraphs {
+        let proof = MainInterface::generate_proof(para.id()).unwrap().unwrap();
+
+        assert_eq!(proof.data, borsh::to_vec(para).unwrap());
+        assert_eq!(proof.path.len(), 1);
+        assert!(proof.verify(&root_hash));
+        assert!(verify(&proof, &root_hash));
+    }
+}
+
+#[test]
+fn verify__root() {
+    let (_, root_hash) = setup();
+
+    let proof = MainInterface::generate_proof(Id::root()).unwrap().unwrap();
+
+    assert_eq!(proof.children.len(), 3);
+    assert!(proof.path.is_empty());
+    assert!(proof.verify(&root_hash));
+}
+
+#[test]
+fn verify__tampered_data() {
+    let (paragraphs, root_hash) = setup();
+
+    let mut proof = MainInterface::generate_proof(paragraphs[1].id())
+        .unwrap()
+        .unwrap();
+    proof.data.push(0);
+
+    assert!(!proof.verify(&root_hash));
+}
+
+#[test]
+fn verify__wrong_root() {
+    let (paragraphs, _) = setup();
+
+    let proof = MainInterface::generate_proof(paragraphs[0].id())
+        .unwrap()
+        .unwrap();
+
+    assert!(!proof.verify(&[0; 32]));
+}
+
+#[test]
+fn generate_proof__non_existent() {
+    let _ = setup();
+
+    assert!(MainInterface::generate_proof(Id::random())
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,471 @@
+use borsh::to_vec;
+
+use super::*;
+use crate::address::Path;
+use crate::entities::{ChildInfo, Data, Element};
+use crate::interface::{Interface, MainInterface};
+use crate::store::MockedStorage;
+use crate::tests::common::{Page, Paragraph};
+
+fn setup() -> (Page, Paragraph) {
+    let mut page = Page::new_from_element("Node", Element::root());
+    assert!(MainInterface::save(&mut page).unwrap());
+
+    let mut para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+    assert!(MainInterface::add_child_to(page.id(), &page.paragraphs, &mut para).unwrap());
+
+    (page, para)
+}
+
+fn ancestor<D: Data>(entity: &D) -> ChildInfo {
+    ChildInfo::new(entity.id(), [0; 32], entity.element().metadata)
+}
+
+fn change(id: Id, kind: ChangeKind, path: Vec<Id>) -> EntityChange {
+    EntityChange {
+        id,
+        kind,
+        collection: path.last().copied(),
+        path,
+    }
+}
+
+#[test]
+fn changes__local() {
+    let (page, para) = setup();
+
+    let mut para = para;
+    para.text = "Updated".to_owned();
+    para.element_mut().update();
+    assert!(MainInterface::save(&mut para).unwrap());
+
+    assert!(MainInterface::remove_child_from(page.id(), &page.paragraphs, para.id()).unwrap());
+
+    assert_eq!(
+        take_changes(),
+        vec![
+            change(page.id(), ChangeKind::Added, vec![]),
+            change(para.id(), ChangeKind::Added, vec![page.id()]),
+            change(para.id(), ChangeKind::Updated, vec![page.id()]),
+            change(para.id(), ChangeKind::Deleted, vec![page.id()]),
+        ]
+    );
+}
+
+#[test]
+fn changes__applied_actions() {
+    let page = Page::new_from_element("Node", Element::root());
+    let para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+
+    MainInterface::apply_action(Action::Add {
+        id: page.id(),
+        data: to_vec(&page).unwrap(),
+        ancestors: vec![],
+        metadata: page.element().metadata,
+    })
+    .unwrap();
+    MainInterface::apply_action(Action::Add {
+        id: para.id(),
+        data: to_vec(&para).unwrap(),
+        ancestors: vec![ancestor(&page)],
+        metadata: para.element().metadata,
+    })
+    .unwrap();
+    MainInterface::apply_action(Action::Delete {
+        id: para.id(),
+        ancestors: vec![ancestor(&page)],
+        deleted_at: para.element().updated_at(),
+    })
+    .unwrap();
+
+    assert_eq!(
+        take_changes(),
+        vec![
+            change(page.id(), ChangeKind::Added, vec![]),
+            change(para.id(), ChangeKind::Added, vec![page.id()]),
+            change(para.id(), ChangeKind::Deleted, vec![page.id()]),
+        ]
+    );
+}
+
+#[test]
+fn changes__not_for_unapplied_actions() {
+    let page = Page::new_from_element("Node", Element::root());
+    let para = Paragraph::new_from_element(
+        "Leaf",
+        Element::new(&Path::new("::root::node::leaf").unwrap(), None),
+    );
+
+    // held back until the parent arrives
+    MainInterface::apply_action(Action::Add {
+        id: para.id(),
+        data: to_vec(&para).unwrap(),
+        ancestors: vec![ancestor(&page)],
+        metadata: para.element().metadata,
+    })
+    .unwrap();
+
+    // nothing here to delete
+    MainInterface::apply_action(Action::Delete {
+        id: Id::random(),
+        ancestors: vec![ancestor(&page)],
+        deleted_at: para.element().updated_at(),
+    })
+    .unwrap();
+
+    assert!(take_changes().is_empty());
+
+    let (page, para) = setup();
+    drop(take_changes());
+
+    // older than the local version
+    let mut stale = para.element().metadata;
+    *stale.updated_at -= 1;
+
+    MainInterface::apply_action(Action::Update {
+        id: para.id(),
+        data: to_vec(&Paragraph::new_from_element(
+            "Stale",
+            para.element().clone(),
+        ))
+        .unwrap(),
+        ancestors: vec![ancestor(&page)],
+        metadata: stale,
+        history: vec![],
+    })
+    .unwrap();
+
+    assert!(take_changes().is_empty());
+}
+
+#[test]
+fn changes__compared() {
+    type ForeignInterface = Interface<MockedStorage<0>>;
+
+    let mut local = Page::new_from_element("Node", Element::root());
+    assert!(MainInterface::save(&mut local).unwrap());
+
+    let mut foreign = local.clone();
+    foreign.title = "Changed".to_owned();
+    foreign.element_mut().update();
+    assert!(ForeignInterface::save(&mut foreign).unwrap());
+
+    drop(take_changes());
+
+    MainInterface::compare_affective(
+        Some(to_vec(&foreign).unwrap()),
+        ForeignInterface::generate_comparison_data(Some(foreign.id())).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        take_changes(),
+        vec![change(local.id(), ChangeKind::Updated, vec![])]
+    );
+}
+
+#[test]
+fn changes__decoded() {
+    let (page, para) = setup();
+
+    let recorded = take_changes();
+
+    assert_eq!(changes(&to_vec(&recorded).unwrap()).unwrap(), recorded);
+    assert_eq!(recorded[1].collection, Some(page.id()));
+    assert_eq!(recorded[1].id, para.id());
+    assert!(changes(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn combine__actions_in_order() {
+    let (page, para) = setup();
+
+    let first = to_vec(&SyncArtifact::Actions(vec![Action::Update {
+        id: page.id(),
+        data: to_vec(&page).unwrap(),
+        ancestors: vec![],
+        metadata: page.element().metadata,
+        history: vec![],
+    }]))
+    .unwrap();
+
+    let second = to_vec(&SyncArtifact::Actions(vec![Action::Delete {
+        id: para.id(),
+        ancestors: vec![ancestor(&page)],
+        deleted_at: para.element().updated_at(),
+    }]))
+    .unwrap();
+
+    let combined = combine([&*first, &[], &*second]).unwrap();
+
+    assert_eq!(
+        combined,
+        to_vec(&SyncArtifact::Actions(vec![
+            Action::Update {
+                id: page.id(),
+                data: to_vec(&page).unwrap(),
+                ancestors: vec![],
+                metadata: page.element().metadata,
+                history: vec![],
+            },
+            Action::Delete {
+                id: para.id(),
+                ancestors: vec![ancestor(&page)],
+                deleted_at: para.element().updated_at(),
+            },
+        ]))
+        .unwrap()
+    );
+}
+
+#[test]
+fn combine__nothing_to_combine() {
+    let empty = to_vec(&SyncArtifact::Comparisons(vec![])).unwrap();
+
+    assert!(combine([&[][..], &*empty]).unwrap().is_empty());
+    assert!(combine([]).unwrap().is_empty());
+}
+
+#[test]
+fn combine__comparisons() {
+    let (page, _) = setup();
+
+    let artifact = to_vec(&SyncArtifact::Comparisons(vec![Comparison {
+        data: None,
+        comparison_data: MainInterface::generate_comparison_data(Some(page.id())).unwrap(),
+    }]))
+    .unwrap();
+
+    assert!(combine([&*artifact]).is_err());
+}
+
+#[cfg(test)]
+mod simulation {
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use calimero_sdk::env;
+    use calimero_sdk::event::NoEvent;
+    use calimero_sdk::private::PrivateStorage;
+    use calimero_sdk::state::{AppState, AppStateInit};
+    use calimero_sdk::testing::{SimulatedState, Simulation};
+
+    use crate::collections::{Root, UnorderedMap};
+    use crate::guard::Guard;
+    use crate::merge::Merge;
+
+    const ALICE: [u8; 32] = [1; 32];
+
+    const SECRET: &[u8] = b"correct horse battery staple";
+
+    /// Application state holding notes, some of which are kept private.
+    #[derive(BorshDeserialize, BorshSerialize)]
+    struct Notebook {
+        notes: UnorderedMap<String, String>,
+    }
+
+    impl Merge for Notebook {}
+
+    impl Guard for Notebook {}
+
+    impl AppStateInit for Notebook {
+        type Return = Self;
+    }
+
+    impl AppState for Notebook {
+        type Event<'a> = NoEvent;
+    }
+
+    impl SimulatedState for Notebook {
+        type Root = Root<Self>;
+
+        fn init(f: impl FnOnce() -> Self) -> Self::Root {
+            Root::new(f)
+        }
+
+        fn fetch() -> Option<Self::Root> {
+            Root::fetch()
+        }
+
+        fn migrate(f: impl FnOnce() -> Self) -> Self::Root {
+            Root::migrate(f)
+        }
+
+        fn guard(root: &Self::Root) {
+            root.guard();
+        }
+
+        fn commit(root: Self::Root) {
+            root.commit();
+        }
+
+        fn sync(artifact: &[u8]) {
+            Root::<Self>::sync(artifact).unwrap();
+        }
+    }
+
+    /// The state of a later revision of the application, which also records
+    /// when it was migrated to.
+    #[derive(BorshDeserialize, BorshSerialize)]
+    struct Journal {
+        notes: UnorderedMap<String, String>,
+        migrated_at: u64,
+    }
+
+    impl Merge for Journal {}
+
+    impl Guard for Journal {}
+
+    impl AppStateInit for Journal {
+        type Return = Self;
+    }
+
+    impl AppState for Journal {
+        type Event<'a> = NoEvent;
+    }
+
+    impl SimulatedState for Journal {
+        type Root = Root<Self>;
+
+        fn init(f: impl FnOnce() -> Self) -> Self::Root {
+            Root::new(f)
+        }
+
+        fn fetch() -> Option<Self::Root> {
+            Root::fetch()
+        }
+
+        fn migrate(f: impl FnOnce() -> Self) -> Self::Root {
+            Root::migrate(f)
+        }
+
+        fn guard(root: &Self::Root) {
+            root.guard();
+        }
+
+        fn commit(root: Self::Root) {
+            root.commit();
+        }
+
+        fn sync(artifact: &[u8]) {
+            Root::<Self>::sync(artifact).unwrap();
+        }
+    }
+
+    fn setup() -> Simulation<Notebook> {
+        let mut sim = Simulation::new(2);
+
+        sim.init(0, ALICE, || Notebook {
+            notes: UnorderedMap::new(),
+        });
+        sim.deliver_all();
+
+        sim
+    }
+
+    /// Writes a public note and a private one in the same call.
+    fn write(sim: &mut Simulation<Notebook>, replica: usize, title: &str) {
+        sim.call(replica, ALICE, |app| {
+            drop(
+                app.notes
+                    .insert(title.to_owned(), "public".to_owned())
+                    .unwrap(),
+            );
+
+            let _replaced = PrivateStorage::new()
+                .insert(title.as_bytes(), &SECRET)
+                .unwrap();
+        });
+    }
+
+    fn contains_secret(bytes: &[u8]) -> bool {
+        bytes.windows(SECRET.len()).any(|window| window == SECRET)
+    }
+
+    #[test]
+    fn private_values_stay_out_of_deltas() {
+        let mut sim = setup();
+
+        write(&mut sim, 0, "diary");
+
+        assert_eq!(sim.messages().count(), 1);
+        assert!(sim
+            .messages()
+            .all(|(_, message)| !contains_secret(&message.artifact)));
+
+        sim.deliver_all();
+
+        sim.assert_converged();
+        assert!(sim.replica(0).private_storage(b"diary").is_some());
+        assert_eq!(sim.replica(1).private_storage(b"diary"), None);
+    }
+
+    #[test]
+    fn private_values_stay_out_of_comparisons() {
+        let mut sim = setup();
+
+        sim.partition(&[&[0], &[1]]);
+        write(&mut sim, 0, "diary");
+        sim.heal();
+        sim.sync(1, 0);
+
+        sim.assert_converged();
+        assert_eq!(sim.replica(1).private_storage(b"diary"), None);
+        assert_eq!(
+            sim.view(1, |app| app.notes.get("diary").unwrap()),
+            Some("public".to_owned())
+        );
+    }
+
+    #[test]
+    fn private_values_leave_the_root_hash_alone() {
+        let mut sim = setup();
+
+        let before = sim.replica(0).root_hash();
+
+        sim.call(0, ALICE, |_| {
+            let _replaced = PrivateStorage::new().insert(b"diary", &SECRET).unwrap();
+        });
+
+        assert_eq!(sim.replica(0).root_hash(), before);
+        assert!(sim
+            .messages()
+            .all(|(_, message)| !contains_secret(&message.artifact)));
+    }
+
+    #[test]
+    fn migrations_are_replicated() {
+        let mut notebook = setup();
+
+        write(&mut notebook, 0, "diary");
+        notebook.deliver_all();
+
+        let mut sim = notebook.migrate(ALICE, || {
+            let previous = Root::<Notebook>::fetch().unwrap().into_inner();
+
+            Journal {
+                notes: previous.notes,
+                migrated_at: env::time_now(),
+            }
+        });
+
+        assert!(!sim.is_converged());
+        assert_eq!(sim.messages().count(), 2);
+
+        sim.deliver_all();
+
+        sim.assert_converged();
+        assert_eq!(
+            sim.view(0, |app| app.migrated_at),
+            sim.view(1, |app| app.migrated_at)
+        );
+        assert_eq!(
+            sim.view(1, |app| app.notes.get("diary").unwrap()),
+            Some("public".to_owned())
+        );
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(test)]
+#[path = "tests/blobs.rs"]
+mod tests;
+
 use core::fmt::{self, Debug, Formatter};
 use core::pin::Pin;
 use core::task::{Context, Poll};
@@ -15,7 +19,7 @@ use futures_util::io::BufReader;
 use futures_util::{pin_mut, AsyncRead, AsyncReadExt, Stream, StreamExt, TryStreamExt};
 use sha2::{Digest, Sha256};
 use thiserror::Error as ThisError;
-use tokio::fs::{create_dir_all, read as async_read, try_exists, write as async_write};
+use tokio::fs::{create_dir_all, read as async_read, read_dir, try_exists, write as async_write};
 
 pub mod config;
 
@@ -91,6 +95,11 @@ impl BlobManager {
         Ok(self.data_store.handle().has(&BlobMetaKey::new(id))?)
     }
 
+    /// Returns the number of chunks in the blob store, and their total size.
+    pub async fn usage(&self) -> EyreResult<(u64, u64)> {
+        self.blob_store.usage().await
+    }
+
     // return a concrete type that resolves to the content of the file
     pub fn get(&self, id: BlobId) -> EyreResult<Option<Blob>> {
         Blob::new(id, self.clone())
@@ -324,6 +333,23 @@ impl FileSystem {
     fn path(&self, id: BlobId) -> Utf8PathBuf {
         self.root.join(id.as_str())
     }
+
+    async fn usage(&self) -> EyreResult<(u64, u64)> {
+        let (mut count, mut size) = (0_u64, 0_u64);
+
+        let mut entries = read_dir(&self.root).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+
+            if metadata.is_file() {
+                count = count.saturating_add(1);
+                size = size.saturating_add(metadata.len());
+            }
+        }
+
+        Ok((count, size))
+    }
 }
 
 impl BlobRepository for FileSystem {
@@ -0,0 +1,31 @@
+use tempdir::TempDir;
+
+use super::*;
+
+#[tokio::test]
+async fn test_file_system_usage() {
+    let dir = TempDir::new("_calimero_blobstore").unwrap();
+
+    let path = Utf8PathBuf::try_from(dir.path().join("blobs")).unwrap();
+
+    let blob_store = FileSystem::new(&BlobStoreConfig::new(path.clone()))
+        .await
+        .unwrap();
+
+    assert_eq!(blob_store.usage().await.unwrap(), (0, 0));
+
+    blob_store.put([1; 32].into(), b"abc").await.unwrap();
+    blob_store.put([2; 32].into(), b"defgh").await.unwrap();
+
+    assert_eq!(blob_store.usage().await.unwrap(), (2, 8));
+
+    // only the chunks themselves are counted
+    create_dir_all(path.join("nested")).await.unwrap();
+
+    assert_eq!(blob_store.usage().await.unwrap(), (2, 8));
+
+    // a chunk put again is replaced rather than added
+    blob_store.put([1; 32].into(), b"a").await.unwrap();
+
+    assert_eq!(blob_store.usage().await.unwrap(), (2, 6));
+}
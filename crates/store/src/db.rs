@@ -24,6 +24,11 @@ pub enum Column {
     Blobs,
     Application,
     Generic,
+    History,
+    StateHistory,
+    PrivateState,
+    Execution,
+    Event,
 }
 
 pub trait Database<'a>: Debug + Send + Sync + 'static {
@@ -46,4 +51,26 @@ pub trait Database<'a>: Debug + Send + Sync + 'static {
     // todo! redesign this, each DB should return a transaction
     // todo! modelled similar to Iter - {put, delete, clear}
     fn apply(&self, tx: &Transaction<'a>) -> EyreResult<()>;
+
+    /// Takes a consistent view of the database, unaffected by any writes
+    /// applied after it was taken.
+    fn snapshot(&self) -> EyreResult<Box<dyn DBSnapshot + '_>>;
+
+    /// Returns the statistics the database keeps of each column, such as the
+    /// estimated number of keys, by name.
+    fn stats(&self) -> EyreResult<Vec<(Column, &'static str, u64)>> {
+        Ok(vec![])
+    }
+}
+
+pub trait DBSnapshot {
+    fn has(&self, col: Column, key: Slice<'_>) -> EyreResult<bool>;
+    fn get(&self, col: Column, key: Slice<'_>) -> EyreResult<Option<Slice<'_>>>;
+
+    // TODO: We should consider returning Iterator here.
+    #[expect(
+        clippy::iter_not_returning_iterator,
+        reason = "TODO: This should be implemented"
+    )]
+    fn iter(&self, col: Column) -> EyreResult<Iter<'_>>;
 }
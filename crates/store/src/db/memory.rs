@@ -13,10 +13,10 @@ use core::mem::transmute;
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use eyre::{eyre, Result as EyreResult};
-use raw::{CastsTo, InMemoryDBImpl, InMemoryDBInner, InMemoryIterInner};
+use raw::{CastsTo, InMemoryDBImpl, InMemoryDBInner, InMemoryIterInner, InMemorySnapshotInner};
 
 use crate::config::StoreConfig;
-use crate::db::{Column, Database};
+use crate::db::{Column, DBSnapshot, Database};
 use crate::iter::{DBIter, Iter};
 use crate::slice::Slice;
 use crate::tx::{Operation, Transaction};
@@ -136,7 +136,8 @@ impl AsRef<[u8]> for ArcSlice<'_> {
 
 impl<'a, T: InMemoryDBImpl<'a> + Debug + 'static> Database<'a> for InMemoryDB<T>
 where
-    T::Key: Ord + Clone + Borrow<[u8]>,
+    T::Key: Ord + Clone + Borrow<[u8]> + 'static,
+    T::Value: 'static,
 {
     fn open(_config: &StoreConfig) -> EyreResult<Self> {
         todo!("phase this out, please. it's not even worth writing an accomodation for")
@@ -195,6 +196,38 @@ where
 
         Ok(())
     }
+
+    fn snapshot(&self) -> EyreResult<Box<dyn DBSnapshot + '_>> {
+        let snapshot = self.db()?.snapshot();
+
+        Ok(Box::new(InMemorySnapshot { inner: snapshot }))
+    }
+}
+
+struct InMemorySnapshot<K: Ord, V> {
+    inner: InMemorySnapshotInner<K, V>,
+}
+
+impl<'a, K, V> DBSnapshot for InMemorySnapshot<K, V>
+where
+    K: Ord + Clone + Borrow<[u8]> + CastsTo<Slice<'a>> + 'a,
+    V: CastsTo<Slice<'a>> + 'a,
+{
+    fn has(&self, col: Column, key: Slice<'_>) -> EyreResult<bool> {
+        self.get(col, key).map(|v| v.is_some())
+    }
+
+    fn get(&self, col: Column, key: Slice<'_>) -> EyreResult<Option<Slice<'_>>> {
+        let Some(value) = self.inner.get(col, &key)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Slice::from_owned(ArcSlice::new(value))))
+    }
+
+    fn iter(&self, col: Column) -> EyreResult<Iter<'_>> {
+        Ok(Iter::new(InMemoryDBIter::new(self.inner.iter(col))))
+    }
 }
 
 struct InMemoryDBIter<'this> {
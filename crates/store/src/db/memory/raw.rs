@@ -143,6 +143,51 @@ impl<K: Ord + Clone + Borrow<[u8]>, V> InMemoryDBInner<K, V> {
             state: None,
         }
     }
+
+    pub fn snapshot(&self) -> InMemorySnapshotInner<K, V> {
+        InMemorySnapshotInner {
+            inner: Self {
+                arena: self.arena.clone(),
+                links: self.links.clone(),
+            },
+        }
+    }
+}
+
+/// Holds on to every value linked at the time it was taken, in the same way
+/// an iterator does for its column.
+#[derive(Debug)]
+pub struct InMemorySnapshotInner<K: Ord, V> {
+    inner: InMemoryDBInner<K, V>,
+}
+
+impl<K: Ord, V> Drop for InMemorySnapshotInner<K, V> {
+    fn drop(&mut self) {
+        while let Some((_, mut column)) = self.inner.links.pop_first() {
+            while let Some((_, idx)) = column.pop_first() {
+                if Arc::strong_count(&idx) == 1 {
+                    if let Ok(mut value) = self.inner.arena.write() {
+                        drop(value.remove(*idx));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<K: Ord + Clone + Borrow<[u8]>, V> InMemorySnapshotInner<K, V> {
+    pub fn get(&self, col: Column, key: &[u8]) -> EyreResult<Option<Arc<V>>> {
+        self.inner.get(col, key)
+    }
+
+    // TODO: We should consider returning Iterator here.
+    #[expect(
+        clippy::iter_not_returning_iterator,
+        reason = "TODO: This should be implemented"
+    )]
+    pub fn iter<'a>(&self, col: Column) -> InMemoryIterInner<'a, K, V> {
+        self.inner.iter(col)
+    }
 }
 
 #[derive(Debug)]
@@ -3,15 +3,24 @@
 mod tests;
 
 use eyre::{bail, Result as EyreResult};
-use rocksdb::{ColumnFamily, DBRawIterator, Options, WriteBatch, DB};
+use rocksdb::{ColumnFamily, DBRawIterator, Options, Snapshot, WriteBatch, DB};
 use strum::IntoEnumIterator;
 
 use crate::config::StoreConfig;
-use crate::db::{Column, Database};
+use crate::db::{Column, DBSnapshot, Database};
 use crate::iter::{DBIter, Iter};
 use crate::slice::Slice;
 use crate::tx::{Operation, Transaction};
 
+// the integer properties reported for each column family
+const PROPERTIES: [&str; 5] = [
+    "rocksdb.estimate-num-keys",
+    "rocksdb.estimate-live-data-size",
+    "rocksdb.total-sst-files-size",
+    "rocksdb.size-all-mem-tables",
+    "rocksdb.num-running-compactions",
+];
+
 #[derive(Debug)]
 pub struct RocksDB {
     db: DB,
@@ -112,6 +121,58 @@ impl Database<'_> for RocksDB {
 
         Ok(())
     }
+
+    fn snapshot(&self) -> EyreResult<Box<dyn DBSnapshot + '_>> {
+        Ok(Box::new(RocksDBSnapshot {
+            db: self,
+            snapshot: self.db.snapshot(),
+        }))
+    }
+
+    fn stats(&self) -> EyreResult<Vec<(Column, &'static str, u64)>> {
+        let mut stats = vec![];
+
+        for column in Column::iter() {
+            let cf_handle = self.try_cf_handle(column)?;
+
+            for property in PROPERTIES {
+                if let Some(value) = self.db.property_int_value_cf(cf_handle, property)? {
+                    stats.push((column, property, value));
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+struct RocksDBSnapshot<'a> {
+    db: &'a RocksDB,
+    snapshot: Snapshot<'a>,
+}
+
+impl DBSnapshot for RocksDBSnapshot<'_> {
+    fn has(&self, col: Column, key: Slice<'_>) -> EyreResult<bool> {
+        self.get(col, key).map(|value| value.is_some())
+    }
+
+    fn get(&self, col: Column, key: Slice<'_>) -> EyreResult<Option<Slice<'_>>> {
+        let cf_handle = self.db.try_cf_handle(col)?;
+
+        let value = self.snapshot.get_pinned_cf(cf_handle, key.as_ref())?;
+
+        Ok(value.map(Slice::from_owned))
+    }
+
+    fn iter(&self, col: Column) -> EyreResult<Iter<'_>> {
+        let cf_handle = self.db.try_cf_handle(col)?;
+
+        let mut iter = self.snapshot.raw_iterator_cf(cf_handle);
+
+        iter.seek_to_first();
+
+        Ok(Iter::new(DBIterator { ready: true, iter }))
+    }
 }
 
 struct DBIterator<'a> {
@@ -6,7 +6,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use calimero_primitives::context::ContextId as PrimitiveContextId;
 use calimero_primitives::identity::PublicKey as PrimitivePublicKey;
 use generic_array::sequence::Concat;
-use generic_array::typenum::U32;
+use generic_array::typenum::{U32, U8};
 use generic_array::GenericArray;
 
 use crate::db::Column;
@@ -239,3 +239,331 @@ impl Debug for ContextState {
             .finish()
     }
 }
+
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+pub struct ContextHistory(Key<ContextId>);
+
+impl ContextHistory {
+    #[must_use]
+    pub fn new(context_id: PrimitiveContextId) -> Self {
+        Self(Key((*context_id).into()))
+    }
+
+    #[must_use]
+    pub fn context_id(&self) -> PrimitiveContextId {
+        (*AsRef::<[_; 32]>::as_ref(&self.0)).into()
+    }
+}
+
+impl AsKeyParts for ContextHistory {
+    type Components = (ContextId,);
+
+    fn column() -> Column {
+        Column::History
+    }
+
+    fn as_key(&self) -> &Key<Self::Components> {
+        (&self.0).into()
+    }
+}
+
+impl FromKeyParts for ContextHistory {
+    type Error = Infallible;
+
+    fn try_from_parts(parts: Key<Self::Components>) -> Result<Self, Self::Error> {
+        Ok(Self(*<&_>::from(&parts)))
+    }
+}
+
+impl Debug for ContextHistory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextHistory")
+            .field("id", &self.context_id())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RootHash;
+
+impl KeyComponent for RootHash {
+    type LEN = U32;
+}
+
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+pub struct ContextStateHistory(Key<(ContextId, RootHash, StateKey)>);
+
+impl ContextStateHistory {
+    #[must_use]
+    pub fn new(context_id: PrimitiveContextId, root_hash: [u8; 32], state_key: [u8; 32]) -> Self {
+        Self(Key(GenericArray::from(*context_id)
+            .concat(root_hash.into())
+            .concat(state_key.into())))
+    }
+
+    #[must_use]
+    pub fn context_id(&self) -> PrimitiveContextId {
+        let mut context_id = [0; 32];
+
+        context_id.copy_from_slice(&AsRef::<[_; 96]>::as_ref(&self.0)[..32]);
+
+        context_id.into()
+    }
+
+    #[must_use]
+    pub fn root_hash(&self) -> [u8; 32] {
+        let mut root_hash = [0; 32];
+
+        root_hash.copy_from_slice(&AsRef::<[_; 96]>::as_ref(&self.0)[32..64]);
+
+        root_hash
+    }
+
+    #[must_use]
+    pub fn state_key(&self) -> [u8; 32] {
+        let mut state_key = [0; 32];
+
+        state_key.copy_from_slice(&AsRef::<[_; 96]>::as_ref(&self.0)[64..]);
+
+        state_key
+    }
+}
+
+impl AsKeyParts for ContextStateHistory {
+    type Components = (ContextId, RootHash, StateKey);
+
+    fn column() -> Column {
+        Column::StateHistory
+    }
+
+    fn as_key(&self) -> &Key<Self::Components> {
+        &self.0
+    }
+}
+
+impl FromKeyParts for ContextStateHistory {
+    type Error = Infallible;
+
+    fn try_from_parts(parts: Key<Self::Components>) -> Result<Self, Self::Error> {
+        Ok(Self(parts))
+    }
+}
+
+impl Debug for ContextStateHistory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextStateHistory")
+            .field("context_id", &self.context_id())
+            .field("root_hash", &self.root_hash())
+            .field("state_key", &self.state_key())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+pub struct ContextPrivateState(Key<(ContextId, StateKey)>);
+
+impl ContextPrivateState {
+    #[must_use]
+    pub fn new(context_id: PrimitiveContextId, state_key: [u8; 32]) -> Self {
+        Self(Key(GenericArray::from(*context_id).concat(state_key.into())))
+    }
+
+    #[must_use]
+    pub fn context_id(&self) -> PrimitiveContextId {
+        let mut context_id = [0; 32];
+
+        context_id.copy_from_slice(&AsRef::<[_; 64]>::as_ref(&self.0)[..32]);
+
+        context_id.into()
+    }
+
+    #[must_use]
+    pub fn state_key(&self) -> [u8; 32] {
+        let mut state_key = [0; 32];
+
+        state_key.copy_from_slice(&AsRef::<[_; 64]>::as_ref(&self.0)[32..]);
+
+        state_key
+    }
+}
+
+impl AsKeyParts for ContextPrivateState {
+    type Components = (ContextId, StateKey);
+
+    fn column() -> Column {
+        Column::PrivateState
+    }
+
+    fn as_key(&self) -> &Key<Self::Components> {
+        &self.0
+    }
+}
+
+impl FromKeyParts for ContextPrivateState {
+    type Error = Infallible;
+
+    fn try_from_parts(parts: Key<Self::Components>) -> Result<Self, Self::Error> {
+        Ok(Self(parts))
+    }
+}
+
+impl Debug for ContextPrivateState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextPrivateState")
+            .field("context_id", &self.context_id())
+            .field("state_key", &self.state_key())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IdempotencyKey;
+
+impl KeyComponent for IdempotencyKey {
+    type LEN = U32;
+}
+
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+pub struct ContextExecution(Key<(ContextId, PublicKey, IdempotencyKey)>);
+
+impl ContextExecution {
+    #[must_use]
+    pub fn new(
+        context_id: PrimitiveContextId,
+        executor: PrimitivePublicKey,
+        idempotency_key: [u8; 32],
+    ) -> Self {
+        Self(Key(GenericArray::from(*context_id)
+            .concat(GenericArray::from(*executor))
+            .concat(idempotency_key.into())))
+    }
+
+    #[must_use]
+    pub fn context_id(&self) -> PrimitiveContextId {
+        let mut context_id = [0; 32];
+
+        context_id.copy_from_slice(&AsRef::<[_; 96]>::as_ref(&self.0)[..32]);
+
+        context_id.into()
+    }
+
+    #[must_use]
+    pub fn executor(&self) -> PrimitivePublicKey {
+        let mut executor = [0; 32];
+
+        executor.copy_from_slice(&AsRef::<[_; 96]>::as_ref(&self.0)[32..64]);
+
+        executor.into()
+    }
+
+    #[must_use]
+    pub fn idempotency_key(&self) -> [u8; 32] {
+        let mut idempotency_key = [0; 32];
+
+        idempotency_key.copy_from_slice(&AsRef::<[_; 96]>::as_ref(&self.0)[64..]);
+
+        idempotency_key
+    }
+}
+
+impl AsKeyParts for ContextExecution {
+    type Components = (ContextId, PublicKey, IdempotencyKey);
+
+    fn column() -> Column {
+        Column::Execution
+    }
+
+    fn as_key(&self) -> &Key<Self::Components> {
+        &self.0
+    }
+}
+
+impl FromKeyParts for ContextExecution {
+    type Error = Infallible;
+
+    fn try_from_parts(parts: Key<Self::Components>) -> Result<Self, Self::Error> {
+        Ok(Self(parts))
+    }
+}
+
+impl Debug for ContextExecution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextExecution")
+            .field("context_id", &self.context_id())
+            .field("executor", &self.executor())
+            .field("idempotency_key", &self.idempotency_key())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sequence;
+
+impl KeyComponent for Sequence {
+    type LEN = U8;
+}
+
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+pub struct ContextEvent(Key<(ContextId, Sequence)>);
+
+impl ContextEvent {
+    #[must_use]
+    pub fn new(context_id: PrimitiveContextId, sequence: u64) -> Self {
+        // big-endian, so that a context's events are iterated in order
+        Self(Key(
+            GenericArray::from(*context_id).concat(sequence.to_be_bytes().into())
+        ))
+    }
+
+    #[must_use]
+    pub fn context_id(&self) -> PrimitiveContextId {
+        let mut context_id = [0; 32];
+
+        context_id.copy_from_slice(&AsRef::<[_; 40]>::as_ref(&self.0)[..32]);
+
+        context_id.into()
+    }
+
+    #[must_use]
+    pub fn sequence(&self) -> u64 {
+        let mut sequence = [0; 8];
+
+        sequence.copy_from_slice(&AsRef::<[_; 40]>::as_ref(&self.0)[32..]);
+
+        u64::from_be_bytes(sequence)
+    }
+}
+
+impl AsKeyParts for ContextEvent {
+    type Components = (ContextId, Sequence);
+
+    fn column() -> Column {
+        Column::Event
+    }
+
+    fn as_key(&self) -> &Key<Self::Components> {
+        &self.0
+    }
+}
+
+impl FromKeyParts for ContextEvent {
+    type Error = Infallible;
+
+    fn try_from_parts(parts: Key<Self::Components>) -> Result<Self, Self::Error> {
+        Ok(Self(parts))
+    }
+}
+
+impl Debug for ContextEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextEvent")
+            .field("context_id", &self.context_id())
+            .field("sequence", &self.sequence())
+            .finish()
+    }
+}
@@ -12,6 +12,7 @@ use crate::{Handle, Store};
 // mod cache;
 mod experiments;
 pub mod read_only;
+pub mod snapshot;
 pub mod temporal;
 
 pub trait Layer {
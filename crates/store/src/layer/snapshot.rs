@@ -0,0 +1,45 @@
+use core::fmt::{self, Debug, Formatter};
+
+use eyre::Result as EyreResult;
+
+use crate::db::DBSnapshot;
+use crate::iter::{Iter, Structured};
+use crate::key::{AsKeyParts, FromKeyParts};
+use crate::layer::{Layer, ReadLayer};
+use crate::slice::Slice;
+use crate::Store;
+
+pub struct Snapshot<'a> {
+    inner: Box<dyn DBSnapshot + 'a>,
+}
+
+impl Debug for Snapshot<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshot").finish_non_exhaustive()
+    }
+}
+
+impl<'a> Snapshot<'a> {
+    #[must_use]
+    pub const fn new(inner: Box<dyn DBSnapshot + 'a>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Layer for Snapshot<'_> {
+    type Base = Store;
+}
+
+impl ReadLayer for Snapshot<'_> {
+    fn has<K: AsKeyParts>(&self, key: &K) -> EyreResult<bool> {
+        self.inner.has(K::column(), key.as_key().as_slice())
+    }
+
+    fn get<K: AsKeyParts>(&self, key: &K) -> EyreResult<Option<Slice<'_>>> {
+        self.inner.get(K::column(), key.as_key().as_slice())
+    }
+
+    fn iter<K: FromKeyParts>(&self) -> EyreResult<Iter<'_, Structured<K>>> {
+        Ok(self.inner.iter(K::column())?.structured_key())
+    }
+}
@@ -13,8 +13,9 @@ pub mod slice;
 mod tx;
 
 use config::StoreConfig;
-use db::Database;
+use db::{Column, Database};
 use handle::Handle;
+use layer::snapshot::Snapshot;
 
 #[cfg(feature = "datatypes")]
 pub mod types;
@@ -34,4 +35,14 @@ impl Store {
     pub fn handle(&self) -> Handle<Self> {
         Handle::new(self.clone())
     }
+
+    /// Returns a handle that reads the store as it is now, regardless of any
+    /// writes made while it is held.
+    pub fn snapshot(&self) -> EyreResult<Handle<Snapshot<'_>>> {
+        Ok(Handle::new(Snapshot::new(self.db.snapshot()?)))
+    }
+
+    pub fn stats(&self) -> EyreResult<Vec<(Column, &'static str, u64)>> {
+        self.db.stats()
+    }
 }
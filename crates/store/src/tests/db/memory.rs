@@ -109,3 +109,50 @@ fn test_ref_memory() {
         }
     }
 }
+
+#[test]
+fn test_snapshot_memory() {
+    let db = InMemoryDB::owned();
+
+    db.put(Column::Identity, (&[1_u8]).into(), (&[1_u8]).into())
+        .unwrap();
+    db.put(Column::Identity, (&[2_u8]).into(), (&[2_u8]).into())
+        .unwrap();
+
+    let snapshot = db.snapshot().unwrap();
+
+    db.put(Column::Identity, (&[1_u8]).into(), (&[10_u8]).into())
+        .unwrap();
+    db.delete(Column::Identity, (&[2_u8]).into()).unwrap();
+    db.put(Column::Identity, (&[3_u8]).into(), (&[3_u8]).into())
+        .unwrap();
+
+    assert_eq!(
+        snapshot
+            .get(Column::Identity, (&[1_u8]).into())
+            .unwrap()
+            .unwrap(),
+        Slice::from(&[1_u8][..])
+    );
+    assert!(snapshot.has(Column::Identity, (&[2_u8]).into()).unwrap());
+    assert!(!snapshot.has(Column::Identity, (&[3_u8]).into()).unwrap());
+
+    let mut iter = snapshot.iter(Column::Identity).unwrap();
+
+    let mut keys = vec![];
+
+    while let Some(key) = iter.next().unwrap() {
+        keys.push(key.into_boxed().into_vec());
+    }
+
+    assert_eq!(keys, [[1_u8], [2_u8]]);
+
+    drop(iter);
+    drop(snapshot);
+
+    assert_eq!(
+        db.get(Column::Identity, (&[1_u8]).into()).unwrap().unwrap(),
+        Slice::from(&[10_u8][..])
+    );
+    assert!(!db.has(Column::Identity, (&[2_u8]).into()).unwrap());
+}
@@ -1,9 +1,10 @@
 use core::mem;
 
 use eyre::Ok as EyreOk;
+use strum::IntoEnumIterator;
 use tempdir::TempDir;
 
-use super::RocksDB;
+use super::{RocksDB, PROPERTIES};
 use crate::config::StoreConfig;
 use crate::db::{Column, Database};
 use crate::slice::Slice;
@@ -107,3 +108,95 @@ fn test_rocksdb_iter() {
         }
     }
 }
+
+#[test]
+fn test_rocksdb_snapshot() {
+    let dir = TempDir::new("_calimero_store_rocks").unwrap();
+
+    let config = StoreConfig {
+        path: dir.path().to_owned().try_into().unwrap(),
+    };
+
+    let db = RocksDB::open(&config).unwrap();
+
+    db.put(Column::Identity, (&[1_u8]).into(), (&[1_u8]).into())
+        .unwrap();
+    db.put(Column::Identity, (&[2_u8]).into(), (&[2_u8]).into())
+        .unwrap();
+
+    let snapshot = db.snapshot().unwrap();
+
+    db.put(Column::Identity, (&[1_u8]).into(), (&[10_u8]).into())
+        .unwrap();
+    db.delete(Column::Identity, (&[2_u8]).into()).unwrap();
+    db.put(Column::Identity, (&[3_u8]).into(), (&[3_u8]).into())
+        .unwrap();
+
+    assert_eq!(
+        snapshot
+            .get(Column::Identity, (&[1_u8]).into())
+            .unwrap()
+            .unwrap(),
+        Slice::from(&[1_u8][..])
+    );
+    assert!(snapshot.has(Column::Identity, (&[2_u8]).into()).unwrap());
+    assert!(!snapshot.has(Column::Identity, (&[3_u8]).into()).unwrap());
+
+    let mut iter = snapshot.iter(Column::Identity).unwrap();
+
+    let mut keys = vec![];
+
+    while let Some(key) = iter.next().unwrap() {
+        keys.push(key.into_boxed().into_vec());
+    }
+
+    assert_eq!(keys, [[1_u8], [2_u8]]);
+
+    drop(iter);
+    drop(snapshot);
+
+    assert_eq!(
+        db.get(Column::Identity, (&[1_u8]).into()).unwrap().unwrap(),
+        Slice::from(&[10_u8][..])
+    );
+    assert!(!db.has(Column::Identity, (&[2_u8]).into()).unwrap());
+}
+
+#[test]
+fn test_rocksdb_stats() {
+    let dir = TempDir::new("_calimero_store_rocks").unwrap();
+
+    let config = StoreConfig {
+        path: dir.path().to_owned().try_into().unwrap(),
+    };
+
+    let db = RocksDB::open(&config).unwrap();
+
+    for b in 0..10_u8 {
+        db.put(Column::Identity, (&[b]).into(), (&[b]).into())
+            .unwrap();
+    }
+
+    let stats = db.stats().unwrap();
+
+    let stat = |column: Column, property: &str| {
+        stats
+            .iter()
+            .find(|&&(c, p, _)| c == column && p == property)
+            .map(|&(_, _, value)| value)
+    };
+
+    for column in Column::iter() {
+        for property in PROPERTIES {
+            assert!(stat(column, property).is_some());
+        }
+    }
+
+    // what's in the memtables is counted before it's flushed
+    assert_eq!(
+        stat(Column::Identity, "rocksdb.estimate-num-keys"),
+        Some(10)
+    );
+    assert_eq!(stat(Column::Meta, "rocksdb.estimate-num-keys"), Some(0));
+    assert!(stat(Column::Identity, "rocksdb.size-all-mem-tables").unwrap() > 0);
+}
@@ -8,7 +8,10 @@ mod generic;
 
 pub use application::ApplicationMeta;
 pub use blobs::BlobMeta;
-pub use context::{ContextConfig, ContextIdentity, ContextMeta, ContextState};
+pub use context::{
+    ContextConfig, ContextEvent, ContextExecution, ContextHistory, ContextIdentity, ContextMeta,
+    ContextPrivateState, ContextState, ContextStateHistory,
+};
 pub use generic::GenericData;
 
 pub trait PredefinedEntry: AsKeyParts {
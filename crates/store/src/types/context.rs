@@ -3,8 +3,10 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use crate::entry::{Borsh, Identity};
 use crate::key::{
     ApplicationMeta as ApplicationMetaKey, ContextConfig as ContextConfigKey,
-    ContextIdentity as ContextIdentityKey, ContextMeta as ContextMetaKey,
-    ContextState as ContextStateKey,
+    ContextEvent as ContextEventKey, ContextExecution as ContextExecutionKey,
+    ContextHistory as ContextHistoryKey, ContextIdentity as ContextIdentityKey,
+    ContextMeta as ContextMetaKey, ContextPrivateState as ContextPrivateStateKey,
+    ContextState as ContextStateKey, ContextStateHistory as ContextStateHistoryKey,
 };
 use crate::slice::Slice;
 use crate::types::PredefinedEntry;
@@ -93,6 +95,29 @@ impl AsRef<[u8]> for ContextState<'_> {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContextPrivateState<'a> {
+    pub value: Slice<'a>,
+}
+
+impl PredefinedEntry for ContextPrivateStateKey {
+    type Codec = Identity;
+    type DataType<'a> = ContextPrivateState<'a>;
+}
+
+impl<'a> From<Slice<'a>> for ContextPrivateState<'a> {
+    fn from(value: Slice<'a>) -> Self {
+        Self { value }
+    }
+}
+
+impl AsRef<[u8]> for ContextPrivateState<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.value.as_ref()
+    }
+}
+
 /*
     if private_key is Some(_), we own this identity
     if we own the identity and sender_key is Some(_) we can encrypt all network messages using this key
@@ -114,3 +139,97 @@ impl PredefinedEntry for ContextIdentityKey {
     type Codec = Borsh;
     type DataType<'a> = ContextIdentity;
 }
+
+// roots retained for historical reads, oldest first
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContextHistory {
+    pub roots: Vec<Hash>,
+}
+
+impl ContextHistory {
+    #[must_use]
+    pub const fn new(roots: Vec<Hash>) -> Self {
+        Self { roots }
+    }
+}
+
+impl PredefinedEntry for ContextHistoryKey {
+    type Codec = Borsh;
+    type DataType<'a> = ContextHistory;
+}
+
+// value of an entry at a retained root, before it was changed (`None` if absent)
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContextStateHistory {
+    pub value: Option<Box<[u8]>>,
+}
+
+impl ContextStateHistory {
+    #[must_use]
+    pub const fn new(value: Option<Box<[u8]>>) -> Self {
+        Self { value }
+    }
+}
+
+impl PredefinedEntry for ContextStateHistoryKey {
+    type Codec = Borsh;
+    type DataType<'a> = ContextStateHistory;
+}
+
+// outcome of an execution made under an idempotency key, returned to retries
+// of it in place of executing again, until it expires
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContextExecution {
+    // hash of the method and payload, so that a key isn't reused for another call
+    pub request: Hash,
+    // nanoseconds since the unix epoch
+    pub expires_at: u64,
+    pub returns: Option<Box<[u8]>>,
+    pub logs: Vec<String>,
+}
+
+impl ContextExecution {
+    #[must_use]
+    pub const fn new(
+        request: Hash,
+        expires_at: u64,
+        returns: Option<Box<[u8]>>,
+        logs: Vec<String>,
+    ) -> Self {
+        Self {
+            request,
+            expires_at,
+            returns,
+            logs,
+        }
+    }
+}
+
+impl PredefinedEntry for ContextExecutionKey {
+    type Codec = Borsh;
+    type DataType<'a> = ContextExecution;
+}
+
+// an event emitted in a context, retained so that subscribers can catch up
+// on the events they missed
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContextEvent {
+    // the event, serialized as json
+    pub data: Box<[u8]>,
+}
+
+impl ContextEvent {
+    #[must_use]
+    pub const fn new(data: Box<[u8]>) -> Self {
+        Self { data }
+    }
+}
+
+impl PredefinedEntry for ContextEventKey {
+    type Codec = Borsh;
+    type DataType<'a> = ContextEvent;
+}